@@ -5,21 +5,72 @@
 
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+use crate::services::program::{ProgramService, ProgramServiceConfig};
+use crate::services::solana_rpc::SolanaRpcService;
+use crate::utils::account_size::{
+    account_size, rent_exempt_minimum, AccountSize, MAX_PERMITTED_DATA_LENGTH,
+};
+use crate::utils::client_codegen::client_modules;
+use crate::utils::format::{format_balance_with_currency, to_snake_case};
+use crate::utils::idl::{account_constraints, export_anchor_idl, import_idl};
+use crate::utils::schema_validation::{validate_schema, SchemaDiagnostic, Severity};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
 pub enum FieldType {
     U8,
     U16,
     U32,
     U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    #[default]
     String,
     Pubkey,
     Bool,
+    /// Length-prefixed sequence
+    Vec(Box<FieldType>),
+    Option(Box<FieldType>),
+    /// Fixed-length array
+    Array(Box<FieldType>, usize),
+    /// Struct or enum declared in `ProgramSchema::types`
+    Defined(String),
 }
 
-impl Default for FieldType {
-    fn default() -> Self {
-        FieldType::String
+impl FieldType {
+    /// Types without parameters, in the order the type picker lists them
+    pub const PRIMITIVES: [FieldType; 13] = [
+        FieldType::U8,
+        FieldType::U16,
+        FieldType::U32,
+        FieldType::U64,
+        FieldType::U128,
+        FieldType::I8,
+        FieldType::I16,
+        FieldType::I32,
+        FieldType::I64,
+        FieldType::I128,
+        FieldType::String,
+        FieldType::Pubkey,
+        FieldType::Bool,
+    ];
+
+    /// Whether values of this type can be used as a PDA seed
+    pub fn is_seed_type(&self) -> bool {
+        !matches!(
+            self,
+            FieldType::Bool
+                | FieldType::Vec(_)
+                | FieldType::Option(_)
+                | FieldType::Array(..)
+                | FieldType::Defined(_)
+        )
     }
 }
 
@@ -30,9 +81,19 @@ impl std::fmt::Display for FieldType {
             FieldType::U16 => write!(f, "u16"),
             FieldType::U32 => write!(f, "u32"),
             FieldType::U64 => write!(f, "u64"),
+            FieldType::U128 => write!(f, "u128"),
+            FieldType::I8 => write!(f, "i8"),
+            FieldType::I16 => write!(f, "i16"),
+            FieldType::I32 => write!(f, "i32"),
+            FieldType::I64 => write!(f, "i64"),
+            FieldType::I128 => write!(f, "i128"),
             FieldType::String => write!(f, "String"),
             FieldType::Pubkey => write!(f, "Pubkey"),
             FieldType::Bool => write!(f, "bool"),
+            FieldType::Vec(inner) => write!(f, "Vec<{}>", inner),
+            FieldType::Option(inner) => write!(f, "Option<{}>", inner),
+            FieldType::Array(inner, len) => write!(f, "[{}; {}]", inner, len),
+            FieldType::Defined(name) => write!(f, "{}", name),
         }
     }
 }
@@ -41,12 +102,65 @@ impl std::fmt::Display for FieldType {
 pub struct Field {
     pub name: String,
     pub field_type: FieldType,
+    /// Maximum lengths of the field's strings and vectors, outermost first,
+    /// like Anchor's `#[max_len(..)]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub max_len: Vec<usize>,
+}
+
+/// User-defined struct or enum, referenced by `FieldType::Defined`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct TypeDef {
+    pub name: String,
+    pub kind: TypeDefKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum TypeDefKind {
+    Struct {
+        fields: Vec<Field>,
+    },
+    /// Borsh enum: a `u8` variant index followed by the variant's fields
+    Enum {
+        variants: Vec<EnumVariant>,
+    },
+}
+
+impl Default for TypeDefKind {
+    fn default() -> Self {
+        TypeDefKind::Struct { fields: Vec::new() }
+    }
+}
+
+/// Enum variant; unit variants have no fields
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct EnumVariant {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct Account {
     pub name: String,
     pub fields: Vec<Field>,
+    /// Discriminator bytes when they are not derived from the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Vec<u8>>,
+    /// Seeds of the account's program derived address; empty when the
+    /// account isn't a PDA
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seeds: Vec<Seed>,
+}
+
+/// PDA seed, in the order passed to `find_program_address`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Seed {
+    /// Literal UTF-8 bytes, like `b"vault"`
+    Const(String),
+    /// Value supplied by the caller: pubkeys as their 32 bytes, strings as
+    /// UTF-8 and integers little-endian
+    Arg { name: String, arg_type: FieldType },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
@@ -58,8 +172,91 @@ pub struct InstructionArg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct Instruction {
     pub name: String,
-    pub accounts: Vec<String>,
+    pub accounts: Vec<InstructionAccount>,
     pub args: Vec<InstructionArg>,
+    /// Discriminator bytes when they are not derived from the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Vec<u8>>,
+}
+
+/// An account passed to an instruction
+///
+/// Schemas saved before the flags existed list accounts by name only; those
+/// still load, with the flags inferred from the name.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(from = "InstructionAccountEntry")]
+pub struct InstructionAccount {
+    pub name: String,
+    /// The instruction writes to the account
+    pub writable: bool,
+    /// The account must sign the transaction
+    pub signer: bool,
+}
+
+impl InstructionAccount {
+    pub fn new(name: &str, writable: bool, signer: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            writable,
+            signer,
+        }
+    }
+
+    /// A writable account that does not sign
+    pub fn writable(name: &str) -> Self {
+        Self::new(name, true, false)
+    }
+
+    /// A read-only account that does not sign
+    pub fn readonly(name: &str) -> Self {
+        Self::new(name, false, false)
+    }
+
+    /// A read-only signer
+    pub fn signer(name: &str) -> Self {
+        Self::new(name, false, true)
+    }
+
+    /// An account whose flags are inferred from its name
+    pub fn inferred(name: String) -> Self {
+        let (writable, signer) = account_constraints(&name);
+        Self {
+            name,
+            writable,
+            signer,
+        }
+    }
+}
+
+/// Instruction account as stored in a schema file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InstructionAccountEntry {
+    Name(String),
+    Account {
+        name: String,
+        #[serde(default)]
+        writable: bool,
+        #[serde(default)]
+        signer: bool,
+    },
+}
+
+impl From<InstructionAccountEntry> for InstructionAccount {
+    fn from(entry: InstructionAccountEntry) -> Self {
+        match entry {
+            InstructionAccountEntry::Name(name) => Self::inferred(name),
+            InstructionAccountEntry::Account {
+                name,
+                writable,
+                signer,
+            } => Self {
+                name,
+                writable,
+                signer,
+            },
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -67,8 +264,8 @@ pub struct InstructionTemplate {
     pub id: String,
     pub name: String,
     pub description: String,
-    pub required_accounts: Vec<String>,
-    pub optional_accounts: Vec<String>,
+    pub required_accounts: Vec<InstructionAccount>,
+    pub optional_accounts: Vec<InstructionAccount>,
     pub args: Vec<InstructionArg>,
     pub category: String,
 }
@@ -80,13 +277,13 @@ impl InstructionTemplate {
             name: "Swap".to_string(),
             description: "Swap tokens between accounts".to_string(),
             required_accounts: vec![
-                "user_account".to_string(),
-                "pool_account".to_string(),
-                "mint_a".to_string(),
-                "mint_b".to_string(),
-                "authority".to_string(),
+                InstructionAccount::writable("user_account"),
+                InstructionAccount::writable("pool_account"),
+                InstructionAccount::readonly("mint_a"),
+                InstructionAccount::readonly("mint_b"),
+                InstructionAccount::signer("authority"),
             ],
-            optional_accounts: vec!["referral_account".to_string()],
+            optional_accounts: vec![InstructionAccount::writable("referral_account")],
             args: vec![
                 InstructionArg {
                     name: "amount_in".to_string(),
@@ -107,9 +304,9 @@ impl InstructionTemplate {
             name: "Transfer".to_string(),
             description: "Transfer tokens or SOL between accounts".to_string(),
             required_accounts: vec![
-                "from_account".to_string(),
-                "to_account".to_string(),
-                "authority".to_string(),
+                InstructionAccount::new("from_account", true, true),
+                InstructionAccount::writable("to_account"),
+                InstructionAccount::signer("authority"),
             ],
             optional_accounts: vec![],
             args: vec![InstructionArg {
@@ -126,11 +323,11 @@ impl InstructionTemplate {
             name: "Add Liquidity".to_string(),
             description: "Add liquidity to a pool".to_string(),
             required_accounts: vec![
-                "user_account".to_string(),
-                "pool_account".to_string(),
-                "mint_a".to_string(),
-                "mint_b".to_string(),
-                "authority".to_string(),
+                InstructionAccount::writable("user_account"),
+                InstructionAccount::writable("pool_account"),
+                InstructionAccount::readonly("mint_a"),
+                InstructionAccount::readonly("mint_b"),
+                InstructionAccount::signer("authority"),
             ],
             optional_accounts: vec![],
             args: vec![
@@ -152,7 +349,10 @@ impl InstructionTemplate {
             id: "initialize_account".to_string(),
             name: "Initialize Account".to_string(),
             description: "Initialize a new account".to_string(),
-            required_accounts: vec!["new_account".to_string(), "authority".to_string()],
+            required_accounts: vec![
+                InstructionAccount::writable("new_account"),
+                InstructionAccount::signer("authority"),
+            ],
             optional_accounts: vec![],
             args: vec![],
             category: "Account Management".to_string(),
@@ -165,9 +365,9 @@ impl InstructionTemplate {
             name: "Close Account".to_string(),
             description: "Close an account and reclaim lamports".to_string(),
             required_accounts: vec![
-                "account_to_close".to_string(),
-                "destination_account".to_string(),
-                "authority".to_string(),
+                InstructionAccount::writable("account_to_close"),
+                InstructionAccount::writable("destination_account"),
+                InstructionAccount::signer("authority"),
             ],
             optional_accounts: vec![],
             args: vec![],
@@ -180,7 +380,10 @@ impl InstructionTemplate {
             id: "store_data".to_string(),
             name: "Store Data".to_string(),
             description: "Store data in an account".to_string(),
-            required_accounts: vec!["data_account".to_string(), "authority".to_string()],
+            required_accounts: vec![
+                InstructionAccount::writable("data_account"),
+                InstructionAccount::signer("authority"),
+            ],
             optional_accounts: vec![],
             args: vec![InstructionArg {
                 name: "data".to_string(),
@@ -195,7 +398,10 @@ impl InstructionTemplate {
             id: "update_data".to_string(),
             name: "Update Data".to_string(),
             description: "Update existing data in an account".to_string(),
-            required_accounts: vec!["data_account".to_string(), "authority".to_string()],
+            required_accounts: vec![
+                InstructionAccount::writable("data_account"),
+                InstructionAccount::signer("authority"),
+            ],
             optional_accounts: vec![],
             args: vec![InstructionArg {
                 name: "new_data".to_string(),
@@ -210,7 +416,10 @@ impl InstructionTemplate {
             id: "delete_data".to_string(),
             name: "Delete Data".to_string(),
             description: "Delete data from an account".to_string(),
-            required_accounts: vec!["data_account".to_string(), "authority".to_string()],
+            required_accounts: vec![
+                InstructionAccount::writable("data_account"),
+                InstructionAccount::signer("authority"),
+            ],
             optional_accounts: vec![],
             args: vec![],
             category: "Data Operations".to_string(),
@@ -222,7 +431,10 @@ impl InstructionTemplate {
             id: "validate_owner".to_string(),
             name: "Validate Owner".to_string(),
             description: "Validate account ownership".to_string(),
-            required_accounts: vec!["account".to_string(), "expected_owner".to_string()],
+            required_accounts: vec![
+                InstructionAccount::readonly("account"),
+                InstructionAccount::readonly("expected_owner"),
+            ],
             optional_accounts: vec![],
             args: vec![],
             category: "Validation".to_string(),
@@ -234,7 +446,7 @@ impl InstructionTemplate {
             id: "check_balance".to_string(),
             name: "Check Balance".to_string(),
             description: "Check account balance".to_string(),
-            required_accounts: vec!["account".to_string()],
+            required_accounts: vec![InstructionAccount::readonly("account")],
             optional_accounts: vec![],
             args: vec![],
             category: "Validation".to_string(),
@@ -246,7 +458,10 @@ impl InstructionTemplate {
             id: "verify_signature".to_string(),
             name: "Verify Signature".to_string(),
             description: "Verify a signature".to_string(),
-            required_accounts: vec!["message_account".to_string(), "signer_account".to_string()],
+            required_accounts: vec![
+                InstructionAccount::readonly("message_account"),
+                InstructionAccount::signer("signer_account"),
+            ],
             optional_accounts: vec![],
             args: vec![InstructionArg {
                 name: "signature".to_string(),
@@ -271,6 +486,23 @@ impl InstructionTemplate {
             Self::verify_signature(),
         ]
     }
+
+    /// Instantiate the template as a schema instruction
+    ///
+    /// Optional accounts are appended after the required ones.
+    pub fn to_instruction(&self) -> Instruction {
+        Instruction {
+            name: self.name.clone(),
+            accounts: self
+                .required_accounts
+                .iter()
+                .chain(&self.optional_accounts)
+                .cloned()
+                .collect(),
+            args: self.args.clone(),
+            discriminator: None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
@@ -279,18 +511,76 @@ pub struct ProgramSchema {
     pub version: String,
     pub accounts: Vec<Account>,
     pub instructions: Vec<Instruction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<TypeDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ErrorCode>,
+}
+
+/// Custom program error, as listed in an IDL's `errors`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
 }
 
 #[component]
 pub fn ProgramBuilder() -> Element {
     let mut schema = use_signal(ProgramSchema::default);
-    let mut json_output = use_signal(|| String::new());
+    let mut json_output = use_signal(String::new);
     let mut show_templates = use_signal(|| false);
-    let instruction_templates = use_signal(|| InstructionTemplate::get_all_templates());
+    let mut anchor_idl = use_signal(|| false);
+    let mut show_import = use_signal(|| false);
+    let mut idl_input = use_signal(String::new);
+    let mut import_result = use_signal(|| None::<Result<Vec<String>, String>>);
+    let mut client_dir = use_signal(String::new);
+    let mut client_result = use_signal(|| None::<Result<Vec<PathBuf>, String>>);
+    let diagnostics = use_memo(move || validate_schema(&schema()));
+    let clients = use_memo(move || client_modules(&schema()).map_err(|e| e.to_string()));
+    let account_sizes = use_memo(move || {
+        let schema = schema();
+        schema
+            .accounts
+            .iter()
+            .map(|account| account_size(account, &schema.types, true))
+            .collect::<Vec<_>>()
+    });
+    // Memoized separately so renames and other edits that keep every size
+    // the same don't refetch rent
+    let data_lens = use_memo(move || {
+        account_sizes()
+            .iter()
+            .map(|size| size.as_ref().map_or(0, AccountSize::total))
+            .collect::<Vec<_>>()
+    });
+    let rpc = use_context::<Signal<Arc<SolanaRpcService>>>();
+    let rent_minimums = use_resource(move || {
+        let rpc = rpc();
+        async move {
+            let data_lens = data_lens();
+            match rpc
+                .get_minimum_balances_for_rent_exemption(&data_lens)
+                .await
+            {
+                Ok(minimums) => minimums.into_iter().map(Result::ok).collect(),
+                Err(e) => {
+                    tracing::debug!("Falling back to the offline rent formula: {}", e);
+                    vec![None; data_lens.len()]
+                }
+            }
+        }
+    });
 
-    // Update JSON output whenever schema changes
+    // Update JSON output whenever the schema or output format changes
     use_effect(move || {
-        if let Ok(json) = serde_json::to_string_pretty(&schema()) {
+        let json = if anchor_idl() {
+            serde_json::to_string_pretty(&export_anchor_idl(&schema()))
+        } else {
+            serde_json::to_string_pretty(&schema())
+        };
+        if let Ok(json) = json {
             json_output.set(json);
         }
     });
@@ -300,6 +590,17 @@ pub fn ProgramBuilder() -> Element {
             s.accounts.push(Account {
                 name: format!("Account{}", s.accounts.len() + 1),
                 fields: vec![],
+                discriminator: None,
+                seeds: vec![],
+            });
+        });
+    };
+
+    let mut add_type = move |kind: TypeDefKind| {
+        schema.with_mut(|s| {
+            s.types.push(TypeDef {
+                name: format!("Type{}", s.types.len() + 1),
+                kind,
             });
         });
     };
@@ -310,6 +611,7 @@ pub fn ProgramBuilder() -> Element {
                 name: format!("instruction{}", s.instructions.len() + 1),
                 accounts: vec![],
                 args: vec![],
+                discriminator: None,
             });
         });
     };
@@ -319,11 +621,11 @@ pub fn ProgramBuilder() -> Element {
             s.instructions.push(Instruction {
                 name: "Swap".to_string(),
                 accounts: vec![
-                    "user_account".to_string(),
-                    "pool_account".to_string(),
-                    "mint_a".to_string(),
-                    "mint_b".to_string(),
-                    "authority".to_string(),
+                    InstructionAccount::writable("user_account"),
+                    InstructionAccount::writable("pool_account"),
+                    InstructionAccount::readonly("mint_a"),
+                    InstructionAccount::readonly("mint_b"),
+                    InstructionAccount::signer("authority"),
                 ],
                 args: vec![
                     InstructionArg {
@@ -335,6 +637,7 @@ pub fn ProgramBuilder() -> Element {
                         arg_type: FieldType::U64,
                     },
                 ],
+                discriminator: None,
             });
         });
         show_templates.set(false);
@@ -345,14 +648,15 @@ pub fn ProgramBuilder() -> Element {
             s.instructions.push(Instruction {
                 name: "Transfer".to_string(),
                 accounts: vec![
-                    "from_account".to_string(),
-                    "to_account".to_string(),
-                    "authority".to_string(),
+                    InstructionAccount::new("from_account", true, true),
+                    InstructionAccount::writable("to_account"),
+                    InstructionAccount::signer("authority"),
                 ],
                 args: vec![InstructionArg {
                     name: "amount".to_string(),
                     arg_type: FieldType::U64,
                 }],
+                discriminator: None,
             });
         });
         show_templates.set(false);
@@ -363,11 +667,11 @@ pub fn ProgramBuilder() -> Element {
             s.instructions.push(Instruction {
                 name: "Add Liquidity".to_string(),
                 accounts: vec![
-                    "user_account".to_string(),
-                    "pool_account".to_string(),
-                    "mint_a".to_string(),
-                    "mint_b".to_string(),
-                    "authority".to_string(),
+                    InstructionAccount::writable("user_account"),
+                    InstructionAccount::writable("pool_account"),
+                    InstructionAccount::readonly("mint_a"),
+                    InstructionAccount::readonly("mint_b"),
+                    InstructionAccount::signer("authority"),
                 ],
                 args: vec![
                     InstructionArg {
@@ -379,6 +683,7 @@ pub fn ProgramBuilder() -> Element {
                         arg_type: FieldType::U64,
                     },
                 ],
+                discriminator: None,
             });
         });
         show_templates.set(false);
@@ -388,17 +693,46 @@ pub fn ProgramBuilder() -> Element {
         schema.with_mut(|s| {
             s.instructions.push(Instruction {
                 name: "Initialize Account".to_string(),
-                accounts: vec!["new_account".to_string(), "authority".to_string()],
+                accounts: vec![
+                    InstructionAccount::writable("new_account"),
+                    InstructionAccount::signer("authority"),
+                ],
                 args: vec![],
+                discriminator: None,
             });
         });
         show_templates.set(false);
     };
 
+    let type_names: Vec<String> = schema().types.iter().map(|def| def.name.clone()).collect();
+
+    let default_client_dir = ProgramServiceConfig::default()
+        .deployment_path
+        .join(format!("{}_client", to_snake_case(&schema().name)));
+
+    let write_clients = move |_| {
+        let dir = client_dir();
+        let dir = dir.trim();
+        let result = ProgramService::new()
+            .generate_clients(&schema(), (!dir.is_empty()).then(|| Path::new(dir)))
+            .map_err(|e| e.to_string());
+        client_result.set(Some(result));
+    };
+
     let toggle_templates = move |_| {
         show_templates.set(!show_templates());
     };
 
+    let import_schema = move |_| match import_idl(&idl_input()) {
+        Ok(import) => {
+            schema.set(import.schema);
+            import_result.set(Some(Ok(import.warnings)));
+            idl_input.set(String::new());
+            show_import.set(false);
+        }
+        Err(e) => import_result.set(Some(Err(e.to_string()))),
+    };
+
     rsx! {
         div {
             style: "min-height: 100vh; background-color: #f9fafb; padding: 2rem;",
@@ -418,10 +752,71 @@ pub fn ProgramBuilder() -> Element {
                     // Program Info
                     div {
                         style: "margin-bottom: 2rem;",
-                        h2 {
-                            style: "font-size: 1.25rem; font-weight: 600; color: #1f2937; margin-bottom: 1rem;",
-                            "Program Information"
+                        div {
+                            style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 1rem;",
+                            h2 {
+                                style: "font-size: 1.25rem; font-weight: 600; color: #1f2937;",
+                                "Program Information"
+                            }
+                            button {
+                                style: "padding: 0.5rem 1rem; background-color: #6366f1; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                                onclick: move |_| {
+                                    import_result.set(None);
+                                    show_import.set(!show_import());
+                                },
+                                "📥 Import IDL"
+                            }
+                        }
+
+                        if show_import() {
+                            div {
+                                style: "margin-bottom: 1rem;",
+                                textarea {
+                                    style: "width: 100%; height: 8rem; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace; font-size: 0.75rem;",
+                                    placeholder: "Paste an Anchor (legacy or 0.30+) or Codama IDL. Importing replaces the current schema.",
+                                    value: "{idl_input}",
+                                    oninput: move |e| idl_input.set(e.value())
+                                }
+                                button {
+                                    style: "margin-top: 0.5rem; padding: 0.5rem 1rem; background-color: #10b981; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                                    onclick: import_schema,
+                                    "Import"
+                                }
+                            }
                         }
+
+                        match import_result() {
+                            Some(Ok(warnings)) if warnings.is_empty() => rsx! {
+                                p {
+                                    style: "color: #059669; font-size: 0.875rem; margin-bottom: 1rem;",
+                                    "IDL imported"
+                                }
+                            },
+                            Some(Ok(warnings)) => rsx! {
+                                div {
+                                    style: "background-color: #fffbeb; border: 1px solid #fcd34d; border-radius: 0.375rem; padding: 0.75rem; margin-bottom: 1rem;",
+                                    p {
+                                        style: "color: #92400e; font-size: 0.875rem; font-weight: 600; margin: 0 0 0.5rem 0;",
+                                        "IDL imported with {warnings.len()} warnings"
+                                    }
+                                    for (warning_idx, warning) in warnings.iter().enumerate() {
+                                        p {
+                                            key: "{warning_idx}",
+                                            style: "color: #92400e; font-size: 0.75rem; margin: 0.25rem 0;",
+                                            "⚠ {warning}"
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(error)) => rsx! {
+                                p {
+                                    style: "color: #ef4444; font-size: 0.875rem; margin-bottom: 1rem;",
+                                    "{error}"
+                                }
+                            },
+                            None => rsx! {},
+                        }
+
                         div {
                             style: "margin-bottom: 1rem;",
                             label {
@@ -434,6 +829,7 @@ pub fn ProgramBuilder() -> Element {
                                 value: "{schema().name}",
                                 oninput: move |e| schema.with_mut(|s| s.name = e.value())
                             }
+                            DiagnosticList { diagnostics: diagnostics_under(&diagnostics(), "$.name") }
                         }
                         div {
                             label {
@@ -480,15 +876,152 @@ pub fn ProgramBuilder() -> Element {
                                             }
                                         })
                                     }
-                                    p {
-                                        style: "font-size: 0.875rem; color: #6b7280;",
-                                        "Fields: {account.fields.len()}"
+                                    FieldListEditor {
+                                        fields: account.fields.clone(),
+                                        type_names: type_names.clone(),
+                                        on_change: move |fields| schema.with_mut(|s| {
+                                            if let Some(acc) = s.accounts.get_mut(account_idx) {
+                                                acc.fields = fields;
+                                            }
+                                        })
+                                    }
+                                    SeedListEditor {
+                                        seeds: account.seeds.clone(),
+                                        on_change: move |seeds| schema.with_mut(|s| {
+                                            if let Some(acc) = s.accounts.get_mut(account_idx) {
+                                                acc.seeds = seeds;
+                                            }
+                                        })
+                                    }
+                                    match account_sizes().get(account_idx).cloned() {
+                                        Some(Ok(size)) => {
+                                            let fetched = rent_minimums
+                                                .read()
+                                                .as_ref()
+                                                .and_then(|minimums| minimums.get(account_idx).copied().flatten());
+                                            let lamports = fetched.unwrap_or_else(|| rent_exempt_minimum(size.total()));
+                                            let unbounded = size.unbounded.join(", ");
+                                            rsx! {
+                                                p {
+                                                    style: "font-size: 0.875rem; color: #374151; margin: 0.5rem 0 0 0;",
+                                                    if size.is_bounded() { "Size: " } else { "Size: at least " }
+                                                    "{size.total()} bytes ({size.discriminator} + {size.data}) · Rent exempt: {format_balance_with_currency(lamports, 6)}"
+                                                    if fetched.is_none() {
+                                                        span { style: "color: #6b7280;", " (offline estimate)" }
+                                                    }
+                                                }
+                                                if !size.is_bounded() {
+                                                    p {
+                                                        style: "font-size: 0.75rem; color: #92400e; margin: 0.25rem 0 0 0;",
+                                                        "⚠ Set a max length for {unbounded}"
+                                                    }
+                                                }
+                                                if size.total() > MAX_PERMITTED_DATA_LENGTH {
+                                                    p {
+                                                        style: "font-size: 0.75rem; color: #b91c1c; margin: 0.25rem 0 0 0;",
+                                                        "✕ Larger than the 10 MiB account size limit"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        Some(Err(e)) => rsx! {
+                                            p {
+                                                style: "font-size: 0.75rem; color: #b91c1c; margin: 0.5rem 0 0 0;",
+                                                "✕ Size unknown: {e}"
+                                            }
+                                        },
+                                        None => rsx! {},
+                                    }
+                                    DiagnosticList {
+                                        diagnostics: diagnostics_under(&diagnostics(), &format!("$.accounts[{}]", account_idx))
                                     }
                                 }
                             }
                         }
                     }
 
+                    // Types Section
+                    div {
+                        style: "margin-bottom: 2rem;",
+                        div {
+                            style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 1rem;",
+                            h2 {
+                                style: "font-size: 1.25rem; font-weight: 600; color: #1f2937;",
+                                "Types"
+                            }
+                            div {
+                                style: "display: flex; gap: 0.5rem;",
+                                button {
+                                    style: "padding: 0.5rem 1rem; background-color: #3b82f6; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                                    onclick: move |_| add_type(TypeDefKind::Struct { fields: vec![] }),
+                                    "+ Struct"
+                                }
+                                button {
+                                    style: "padding: 0.5rem 1rem; background-color: #3b82f6; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                                    onclick: move |_| add_type(TypeDefKind::Enum { variants: vec![] }),
+                                    "+ Enum"
+                                }
+                            }
+                        }
+
+                        for (type_idx, def) in schema().types.iter().enumerate() {
+                            div {
+                                key: "{type_idx}",
+                                style: "border: 1px solid #e5e7eb; border-radius: 0.375rem; padding: 1rem; margin-bottom: 1rem;",
+                                div {
+                                    style: "display: flex; gap: 0.5rem; align-items: center; margin-bottom: 0.5rem;",
+                                    span {
+                                        style: "padding: 0.25rem 0.5rem; background-color: #ede9fe; color: #5b21b6; font-size: 0.75rem; border-radius: 0.25rem;",
+                                        if matches!(def.kind, TypeDefKind::Struct { .. }) { "struct" } else { "enum" }
+                                    }
+                                    input {
+                                        style: "flex: 1; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                                        value: "{def.name}",
+                                        oninput: move |e| schema.with_mut(|s| {
+                                            if let Some(def) = s.types.get_mut(type_idx) {
+                                                def.name = e.value();
+                                            }
+                                        })
+                                    }
+                                    button {
+                                        style: "padding: 0.5rem 0.75rem; background-color: #ef4444; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                                        onclick: move |_| schema.with_mut(|s| {
+                                            s.types.remove(type_idx);
+                                        }),
+                                        "✕"
+                                    }
+                                }
+                                match &def.kind {
+                                    TypeDefKind::Struct { fields } => rsx! {
+                                        FieldListEditor {
+                                            fields: fields.clone(),
+                                            type_names: type_names.clone(),
+                                            on_change: move |fields| schema.with_mut(|s| {
+                                                if let Some(def) = s.types.get_mut(type_idx) {
+                                                    def.kind = TypeDefKind::Struct { fields };
+                                                }
+                                            })
+                                        }
+                                    },
+                                    TypeDefKind::Enum { variants } => rsx! {
+                                        VariantListEditor {
+                                            variants: variants.clone(),
+                                            type_names: type_names.clone(),
+                                            on_change: move |variants| schema.with_mut(|s| {
+                                                if let Some(def) = s.types.get_mut(type_idx) {
+                                                    def.kind = TypeDefKind::Enum { variants };
+                                                }
+                                            })
+                                        }
+                                    },
+                                }
+                                DiagnosticList {
+                                    diagnostics: diagnostics_under(&diagnostics(), &format!("$.types[{}]", type_idx))
+                                }
+                            }
+                        }
+                    }
+
                     // Instructions Section
                     div {
                         div {
@@ -704,6 +1237,29 @@ pub fn ProgramBuilder() -> Element {
                                         style: "font-size: 0.875rem; color: #6b7280;",
                                         "Accounts: {instruction.accounts.len()}, Args: {instruction.args.len()}"
                                     }
+                                    FieldListEditor {
+                                        fields: instruction.args.iter().map(|arg| Field {
+                                            name: arg.name.clone(),
+                                            field_type: arg.arg_type.clone(),
+                                            max_len: vec![],
+                                        }).collect::<Vec<_>>(),
+                                        type_names: type_names.clone(),
+                                        add_label: "+ Arg",
+                                        on_change: move |fields: Vec<Field>| schema.with_mut(|s| {
+                                            if let Some(inst) = s.instructions.get_mut(instruction_idx) {
+                                                inst.args = fields
+                                                    .into_iter()
+                                                    .map(|field| InstructionArg {
+                                                        name: field.name,
+                                                        arg_type: field.field_type,
+                                                    })
+                                                    .collect();
+                                            }
+                                        })
+                                    }
+                                    DiagnosticList {
+                                        diagnostics: diagnostics_under(&diagnostics(), &format!("$.instructions[{}]", instruction_idx))
+                                    }
                                 }
                             }
                         }
@@ -714,9 +1270,44 @@ pub fn ProgramBuilder() -> Element {
                 div {
                     style: "background-color: white; border-radius: 0.5rem; padding: 1.5rem; box-shadow: 0 1px 3px 0 rgba(0, 0, 0, 0.1);",
 
-                    h2 {
-                        style: "font-size: 1.25rem; font-weight: 600; color: #1f2937; margin-bottom: 1rem;",
-                        "JSON Output"
+                    div {
+                        style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 1rem;",
+                        h2 {
+                            style: "font-size: 1.25rem; font-weight: 600; color: #1f2937;",
+                            "JSON Output"
+                        }
+                        div {
+                            style: "display: flex; gap: 0.5rem;",
+                            button {
+                                style: if anchor_idl() { "padding: 0.25rem 0.75rem; background-color: #e5e7eb; color: #374151; border: none; border-radius: 0.375rem; cursor: pointer;" } else { "padding: 0.25rem 0.75rem; background-color: #3b82f6; color: white; border: none; border-radius: 0.375rem; cursor: pointer;" },
+                                onclick: move |_| anchor_idl.set(false),
+                                "Schema"
+                            }
+                            button {
+                                style: if anchor_idl() { "padding: 0.25rem 0.75rem; background-color: #3b82f6; color: white; border: none; border-radius: 0.375rem; cursor: pointer;" } else { "padding: 0.25rem 0.75rem; background-color: #e5e7eb; color: #374151; border: none; border-radius: 0.375rem; cursor: pointer;" },
+                                onclick: move |_| anchor_idl.set(true),
+                                "Anchor IDL"
+                            }
+                        }
+                    }
+
+                    if diagnostics().is_empty() {
+                        p {
+                            style: "color: #059669; font-size: 0.875rem; margin-bottom: 1rem;",
+                            "✓ Schema is valid"
+                        }
+                    } else {
+                        div {
+                            style: "margin-bottom: 1rem;",
+                            p {
+                                style: "font-size: 0.875rem; font-weight: 600; color: #374151; margin: 0 0 0.5rem 0;",
+                                {
+                                    let errors = diagnostics().iter().filter(|d| d.is_error()).count();
+                                    format!("{} errors, {} warnings", errors, diagnostics().len() - errors)
+                                }
+                            }
+                            DiagnosticList { diagnostics: diagnostics(), show_paths: true }
+                        }
                     }
 
                     div {
@@ -733,8 +1324,505 @@ pub fn ProgramBuilder() -> Element {
                         },
                         "Copy JSON"
                     }
+
+                    // Client SDK
+                    div {
+                        style: "margin-top: 2rem; padding-top: 1.5rem; border-top: 1px solid #e5e7eb;",
+                        h2 {
+                            style: "font-size: 1.25rem; font-weight: 600; color: #1f2937; margin-bottom: 0.5rem;",
+                            "Client SDK"
+                        }
+                        p {
+                            style: "font-size: 0.875rem; color: #6b7280; margin: 0 0 1rem 0;",
+                            "Instruction builders, account decoders and PDA helpers for calling the program from Rust or TypeScript"
+                        }
+                        match clients() {
+                            Ok(clients) => rsx! {
+                                div {
+                                    style: "display: flex; gap: 0.5rem; flex-wrap: wrap; margin-bottom: 1rem;",
+                                    for client in clients.files {
+                                        span {
+                                            key: "{client.path}",
+                                            style: "padding: 0.5rem 1rem; background-color: #eef2ff; color: #4338ca; border-radius: 0.375rem; font-family: monospace; font-size: 0.875rem;",
+                                            "{client.path}"
+                                        }
+                                    }
+                                }
+                                div {
+                                    style: "display: flex; gap: 0.5rem;",
+                                    input {
+                                        style: "flex: 1; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace; font-size: 0.875rem;",
+                                        placeholder: "{default_client_dir.display()}",
+                                        value: "{client_dir}",
+                                        oninput: move |e| client_dir.set(e.value())
+                                    }
+                                    button {
+                                        style: "padding: 0.5rem 1rem; background-color: #6366f1; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                                        onclick: write_clients,
+                                        "Write to folder"
+                                    }
+                                }
+                            },
+                            Err(e) => rsx! {
+                                p {
+                                    style: "color: #b91c1c; font-size: 0.875rem; margin: 0;",
+                                    "✕ {e}"
+                                }
+                            },
+                        }
+                        match client_result() {
+                            Some(Ok(paths)) => rsx! {
+                                div {
+                                    style: "margin-top: 0.5rem;",
+                                    for path in paths {
+                                        p {
+                                            key: "{path.display()}",
+                                            style: "color: #059669; font-size: 0.75rem; font-family: monospace; margin: 0.25rem 0;",
+                                            "✓ Wrote {path.display()}"
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Err(error)) => rsx! {
+                                p {
+                                    style: "color: #ef4444; font-size: 0.875rem; margin-top: 0.5rem;",
+                                    "{error}"
+                                }
+                            },
+                            None => rsx! {},
+                        }
+                    }
                 }
             }
         }
     }
 }
+
+/// Diagnostics for the schema element at `path` and everything inside it
+fn diagnostics_under(diagnostics: &[SchemaDiagnostic], path: &str) -> Vec<SchemaDiagnostic> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_under(path))
+        .cloned()
+        .collect()
+}
+
+/// Inline list of validation diagnostics; renders nothing when empty
+#[component]
+fn DiagnosticList(
+    diagnostics: Vec<SchemaDiagnostic>,
+    #[props(default)] show_paths: bool,
+) -> Element {
+    rsx! {
+        for (diagnostic_idx, diagnostic) in diagnostics.iter().enumerate() {
+            p {
+                key: "{diagnostic_idx}",
+                style: if diagnostic.severity == Severity::Error { "color: #b91c1c; font-size: 0.75rem; margin: 0.25rem 0;" } else { "color: #92400e; font-size: 0.75rem; margin: 0.25rem 0;" },
+                title: "{diagnostic.path}",
+                if diagnostic.severity == Severity::Error { "✕ " } else { "⚠ " }
+                if show_paths {
+                    code { style: "margin-right: 0.375rem;", "{diagnostic.path}" }
+                }
+                "{diagnostic.message}"
+            }
+        }
+    }
+}
+
+/// Whether a field type contains strings or vectors that need a max length
+fn has_variable_length(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::String | FieldType::Vec(_) => true,
+        FieldType::Option(item) | FieldType::Array(item, _) => has_variable_length(item),
+        _ => false,
+    }
+}
+
+/// Parse a comma-separated max length list; `None` when any entry is invalid
+fn parse_max_len(value: &str) -> Option<Vec<usize>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| entry.parse().ok())
+        .collect()
+}
+
+/// Picker value for a field type's outermost constructor
+fn type_option_value(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Vec(_) => "Vec".to_string(),
+        FieldType::Option(_) => "Option".to_string(),
+        FieldType::Array(..) => "Array".to_string(),
+        FieldType::Defined(name) => format!("defined:{}", name),
+        primitive => primitive.to_string(),
+    }
+}
+
+/// Field type for a picked option, keeping the item type of generic types
+fn type_from_option(value: &str, previous: &FieldType) -> FieldType {
+    let item = match previous {
+        FieldType::Vec(item) | FieldType::Option(item) | FieldType::Array(item, _) => item.clone(),
+        _ => Box::new(FieldType::U8),
+    };
+    match value {
+        "Vec" => FieldType::Vec(item),
+        "Option" => FieldType::Option(item),
+        "Array" => match previous {
+            FieldType::Array(_, len) => FieldType::Array(item, *len),
+            _ => FieldType::Array(item, 32),
+        },
+        other => match other.strip_prefix("defined:") {
+            Some(name) => FieldType::Defined(name.to_string()),
+            None => FieldType::PRIMITIVES
+                .into_iter()
+                .find(|primitive| primitive.to_string() == other)
+                .unwrap_or_default(),
+        },
+    }
+}
+
+/// Type dropdown; generic types get a nested dropdown for their item type
+#[component]
+fn FieldTypeSelect(
+    field_type: FieldType,
+    type_names: Vec<String>,
+    on_change: EventHandler<FieldType>,
+) -> Element {
+    let selected = type_option_value(&field_type);
+    let missing = match &field_type {
+        FieldType::Defined(name) if !type_names.contains(name) => Some(name.clone()),
+        _ => None,
+    };
+    let previous = field_type.clone();
+
+    rsx! {
+        span {
+            style: "display: inline-flex; gap: 0.25rem; align-items: center; flex-wrap: wrap;",
+            select {
+                style: "padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace; font-size: 0.875rem;",
+                onchange: move |e| on_change.call(type_from_option(&e.value(), &previous)),
+                for primitive in FieldType::PRIMITIVES {
+                    option {
+                        value: "{primitive}",
+                        selected: selected == primitive.to_string(),
+                        "{primitive}"
+                    }
+                }
+                option { value: "Vec", selected: selected == "Vec", "Vec<…>" }
+                option { value: "Option", selected: selected == "Option", "Option<…>" }
+                option { value: "Array", selected: selected == "Array", "[…; N]" }
+                for name in type_names.iter() {
+                    option {
+                        value: "defined:{name}",
+                        selected: selected == format!("defined:{}", name),
+                        "{name}"
+                    }
+                }
+                if let Some(name) = missing {
+                    option { value: "defined:{name}", selected: true, "{name} (missing)" }
+                }
+            }
+            match field_type {
+                FieldType::Vec(item) => rsx! {
+                    FieldTypeSelect {
+                        field_type: *item,
+                        type_names: type_names.clone(),
+                        on_change: move |item| on_change.call(FieldType::Vec(Box::new(item)))
+                    }
+                },
+                FieldType::Option(item) => rsx! {
+                    FieldTypeSelect {
+                        field_type: *item,
+                        type_names: type_names.clone(),
+                        on_change: move |item| on_change.call(FieldType::Option(Box::new(item)))
+                    }
+                },
+                FieldType::Array(item, len) => {
+                    let current_item = item.clone();
+                    rsx! {
+                        FieldTypeSelect {
+                            field_type: *item,
+                            type_names: type_names.clone(),
+                            on_change: move |item| on_change.call(FieldType::Array(Box::new(item), len))
+                        }
+                        input {
+                            style: "width: 4rem; padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                            r#type: "number",
+                            min: "0",
+                            value: "{len}",
+                            oninput: move |e| {
+                                if let Ok(len) = e.value().parse() {
+                                    on_change.call(FieldType::Array(current_item.clone(), len));
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => rsx! {},
+            }
+        }
+    }
+}
+
+/// Editable list of named, typed fields
+#[component]
+fn FieldListEditor(
+    fields: Vec<Field>,
+    type_names: Vec<String>,
+    on_change: EventHandler<Vec<Field>>,
+    #[props(default = "+ Field".to_string(), into)] add_label: String,
+) -> Element {
+    let update = {
+        let fields = fields.clone();
+        move |edit: &dyn Fn(&mut Vec<Field>)| {
+            let mut fields = fields.clone();
+            edit(&mut fields);
+            on_change.call(fields);
+        }
+    };
+
+    rsx! {
+        div {
+            style: "margin-top: 0.5rem;",
+            for (field_idx, field) in fields.iter().enumerate() {
+                div {
+                    key: "{field_idx}",
+                    style: "display: flex; gap: 0.5rem; align-items: center; flex-wrap: wrap; margin-bottom: 0.5rem;",
+                    input {
+                        style: "width: 10rem; padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-size: 0.875rem;",
+                        placeholder: "field_name",
+                        value: "{field.name}",
+                        oninput: {
+                            let update = update.clone();
+                            move |e: FormEvent| update(&|fields| fields[field_idx].name = e.value())
+                        }
+                    }
+                    FieldTypeSelect {
+                        field_type: field.field_type.clone(),
+                        type_names: type_names.clone(),
+                        on_change: {
+                            let update = update.clone();
+                            move |field_type: FieldType| update(&|fields| fields[field_idx].field_type = field_type.clone())
+                        }
+                    }
+                    if has_variable_length(&field.field_type) {
+                        input {
+                            style: "width: 6rem; padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-size: 0.875rem;",
+                            placeholder: "max len",
+                            title: "Maximum length of each string or vector, outermost first (e.g. 10, 32)",
+                            value: field.max_len.iter().map(usize::to_string).collect::<Vec<_>>().join(", "),
+                            onchange: {
+                                let update = update.clone();
+                                move |e: FormEvent| {
+                                    if let Some(max_len) = parse_max_len(&e.value()) {
+                                        update(&|fields| fields[field_idx].max_len = max_len.clone());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        style: "padding: 0.25rem 0.5rem; background-color: #fee2e2; color: #b91c1c; border: none; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: {
+                            let update = update.clone();
+                            move |_| update(&|fields| {
+                                fields.remove(field_idx);
+                            })
+                        },
+                        "✕"
+                    }
+                }
+            }
+            button {
+                style: "padding: 0.25rem 0.75rem; background-color: #f3f4f6; color: #374151; border: 1px dashed #d1d5db; border-radius: 0.375rem; cursor: pointer; font-size: 0.875rem;",
+                onclick: move |_| update(&|fields| {
+                    fields.push(Field {
+                        name: format!("field{}", fields.len() + 1),
+                        field_type: FieldType::U64,
+                        max_len: vec![],
+                    })
+                }),
+                "{add_label}"
+            }
+        }
+    }
+}
+
+/// Picker value for a seed: `const` or the argument's type
+fn seed_option_value(seed: &Seed) -> String {
+    match seed {
+        Seed::Const(_) => "const".to_string(),
+        Seed::Arg { arg_type, .. } => arg_type.to_string(),
+    }
+}
+
+/// A constant seed's value or an argument seed's name
+fn seed_text(seed: &Seed) -> &str {
+    match seed {
+        Seed::Const(text) | Seed::Arg { name: text, .. } => text,
+    }
+}
+
+/// Seed for a picked option, keeping the entered text as value or name
+fn seed_from_option(value: &str, previous: &Seed) -> Seed {
+    let text = seed_text(previous).to_string();
+    match FieldType::PRIMITIVES
+        .into_iter()
+        .find(|primitive| primitive.to_string() == value)
+    {
+        Some(arg_type) => Seed::Arg {
+            name: text,
+            arg_type,
+        },
+        None => Seed::Const(text),
+    }
+}
+
+/// Editable list of an account's PDA seeds
+#[component]
+fn SeedListEditor(seeds: Vec<Seed>, on_change: EventHandler<Vec<Seed>>) -> Element {
+    let update = {
+        let seeds = seeds.clone();
+        move |edit: &dyn Fn(&mut Vec<Seed>)| {
+            let mut seeds = seeds.clone();
+            edit(&mut seeds);
+            on_change.call(seeds);
+        }
+    };
+
+    rsx! {
+        div {
+            style: "margin-top: 0.5rem;",
+            if !seeds.is_empty() {
+                p {
+                    style: "font-size: 0.75rem; font-weight: 500; color: #6b7280; margin: 0 0 0.25rem 0;",
+                    "PDA seeds"
+                }
+            }
+            for (seed_idx, seed) in seeds.iter().enumerate() {
+                div {
+                    key: "{seed_idx}",
+                    style: "display: flex; gap: 0.5rem; align-items: center; margin-bottom: 0.5rem;",
+                    select {
+                        style: "padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace; font-size: 0.875rem;",
+                        onchange: {
+                            let update = update.clone();
+                            let previous = seed.clone();
+                            move |e: FormEvent| update(&|seeds| seeds[seed_idx] = seed_from_option(&e.value(), &previous))
+                        },
+                        option { value: "const", selected: seed_option_value(seed) == "const", "const" }
+                        for primitive in FieldType::PRIMITIVES.into_iter().filter(FieldType::is_seed_type) {
+                            option {
+                                value: "{primitive}",
+                                selected: seed_option_value(seed) == primitive.to_string(),
+                                "{primitive} arg"
+                            }
+                        }
+                    }
+                    input {
+                        style: "width: 12rem; padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-size: 0.875rem;",
+                        placeholder: if matches!(seed, Seed::Const(_)) { "literal, e.g. vault" } else { "arg_name" },
+                        value: "{seed_text(seed)}",
+                        oninput: {
+                            let update = update.clone();
+                            move |e: FormEvent| update(&|seeds| match &mut seeds[seed_idx] {
+                                Seed::Const(text) | Seed::Arg { name: text, .. } => *text = e.value(),
+                            })
+                        }
+                    }
+                    button {
+                        style: "padding: 0.25rem 0.5rem; background-color: #fee2e2; color: #b91c1c; border: none; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: {
+                            let update = update.clone();
+                            move |_| update(&|seeds| {
+                                seeds.remove(seed_idx);
+                            })
+                        },
+                        "✕"
+                    }
+                }
+            }
+            button {
+                style: "padding: 0.25rem 0.75rem; background-color: #f3f4f6; color: #374151; border: 1px dashed #d1d5db; border-radius: 0.375rem; cursor: pointer; font-size: 0.875rem;",
+                onclick: move |_| update(&|seeds| seeds.push(Seed::Const(String::new()))),
+                "+ PDA Seed"
+            }
+        }
+    }
+}
+
+/// Editable list of enum variants and their fields
+#[component]
+fn VariantListEditor(
+    variants: Vec<EnumVariant>,
+    type_names: Vec<String>,
+    on_change: EventHandler<Vec<EnumVariant>>,
+) -> Element {
+    rsx! {
+        div {
+            style: "margin-top: 0.5rem;",
+            for (variant_idx, variant) in variants.iter().enumerate() {
+                div {
+                    key: "{variant_idx}",
+                    style: "border-left: 3px solid #ddd6fe; padding-left: 0.75rem; margin-bottom: 0.75rem;",
+                    div {
+                        style: "display: flex; gap: 0.5rem; align-items: center;",
+                        input {
+                            style: "width: 10rem; padding: 0.375rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-size: 0.875rem;",
+                            placeholder: "Variant",
+                            value: "{variant.name}",
+                            oninput: {
+                                let variants = variants.clone();
+                                move |e: FormEvent| {
+                                    let mut variants = variants.clone();
+                                    variants[variant_idx].name = e.value();
+                                    on_change.call(variants);
+                                }
+                            }
+                        }
+                        button {
+                            style: "padding: 0.25rem 0.5rem; background-color: #fee2e2; color: #b91c1c; border: none; border-radius: 0.375rem; cursor: pointer;",
+                            onclick: {
+                                let variants = variants.clone();
+                                move |_| {
+                                    let mut variants = variants.clone();
+                                    variants.remove(variant_idx);
+                                    on_change.call(variants);
+                                }
+                            },
+                            "✕"
+                        }
+                    }
+                    FieldListEditor {
+                        fields: variant.fields.clone(),
+                        type_names: type_names.clone(),
+                        on_change: {
+                            let variants = variants.clone();
+                            move |fields| {
+                                let mut variants = variants.clone();
+                                variants[variant_idx].fields = fields;
+                                on_change.call(variants);
+                            }
+                        }
+                    }
+                }
+            }
+            button {
+                style: "padding: 0.25rem 0.75rem; background-color: #f3f4f6; color: #374151; border: 1px dashed #d1d5db; border-radius: 0.375rem; cursor: pointer; font-size: 0.875rem;",
+                onclick: {
+                    let variants = variants.clone();
+                    move |_| {
+                        let mut variants = variants.clone();
+                        variants.push(EnumVariant {
+                            name: format!("Variant{}", variants.len() + 1),
+                            fields: vec![],
+                        });
+                        on_change.call(variants);
+                    }
+                },
+                "+ Variant"
+            }
+        }
+    }
+}
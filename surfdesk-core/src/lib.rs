@@ -20,3 +20,4 @@ pub mod models;
 pub mod pages;
 pub mod routes;
 pub mod services;
+pub mod utils;
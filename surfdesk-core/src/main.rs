@@ -1,6 +1,12 @@
+use std::sync::Arc;
+
 use dioxus::prelude::*;
-use dioxus_router::prelude::*;
+use futures_util::StreamExt;
 use surfdesk_core::routes::Route;
+use surfdesk_core::services::account::{AccountService, SharedAccountService};
+use surfdesk_core::services::solana_rpc::SolanaRpcService;
+use surfdesk_core::services::surfpool_supervisor::{SupervisorEvent, SurfpoolSupervisor};
+use surfdesk_core::services::Configurable;
 
 fn main() {
     // Initialize logging
@@ -12,6 +18,63 @@ fn main() {
 
 #[component]
 fn App() -> Element {
+    // One Surfpool process for every page, killed when the app closes
+    let supervisor = use_context_provider(SurfpoolSupervisor::from_saved_profile);
+
+    // The desktop event loop exits the process without running destructors
+    #[cfg(feature = "desktop")]
+    {
+        use dioxus::desktop::tao::event::Event;
+        use dioxus::desktop::WindowEvent;
+
+        let supervisor = supervisor.clone();
+        dioxus::desktop::use_wry_event_handler(move |event, _| {
+            if let Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } = event
+            {
+                supervisor.shutdown();
+            }
+        });
+    }
+
+    // Pages share one RPC client and account service, pointed at the Surfpool profile
+    let rpc_config = use_hook(|| supervisor.config().rpc_config());
+    let mut rpc = use_context_provider({
+        let rpc_config = rpc_config.clone();
+        move || Signal::new(Arc::new(SolanaRpcService::with_config(rpc_config)))
+    });
+    let account_service = use_context_provider(move || {
+        let mut accounts = AccountService::new();
+        if let Err(e) = accounts.configure_rpc(rpc_config) {
+            tracing::warn!("Failed to configure the account service RPC: {}", e);
+        }
+        SharedAccountService::new(tokio::sync::Mutex::new(accounts))
+    });
+
+    // Follow the profile when the Surfpool page switches it
+    use_future(move || {
+        let supervisor = supervisor.clone();
+        let account_service = account_service.clone();
+        async move {
+            let mut events = Box::pin(supervisor.subscribe());
+            while let Some(event) = events.next().await {
+                let SupervisorEvent::Configured(profile) = event else {
+                    continue;
+                };
+                let config = profile.rpc_config();
+                if *rpc.peek().get_config() == config {
+                    continue;
+                }
+                rpc.set(Arc::new(SolanaRpcService::with_config(config.clone())));
+                if let Err(e) = account_service.lock().await.configure_rpc(config) {
+                    tracing::warn!("Failed to configure the account service RPC: {}", e);
+                }
+            }
+        }
+    });
+
     rsx! {
         Router::<Route> {}
     }
@@ -4,32 +4,89 @@
 //! creating new accounts, viewing account details, managing balances, and
 //! handling account operations like transfers and stake management.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::services::account::{self, AccountService, AccountType, SharedAccountService};
+use crate::services::pubsub::{Notification, PubSubClient};
+use crate::services::solana_rpc::{CommitmentLevel, SolanaRpcService};
+use crate::services::{AsyncService, Configurable, ServiceResult};
 
 /// Account Manager page component
 #[component]
 pub fn AccountManager() -> Element {
-    let accounts = use_signal(|| {
-        vec![
-            Account {
-                address: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
-                balance: 1000000000, // 1 SOL in lamports
-                label: "Main Account".to_string(),
-                created_at: "2025-10-19".to_string(),
-            },
-            Account {
-                address: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
-                balance: 500000000, // 0.5 SOL
-                label: "Test Account".to_string(),
-                created_at: "2025-10-18".to_string(),
-            },
-        ]
+    let account_service = use_context::<SharedAccountService>();
+    let rpc = use_context::<Signal<Arc<SolanaRpcService>>>();
+
+    let accounts = use_signal(Vec::<Account>::new);
+    let locked = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let mut selected_account = use_signal(|| None::<Account>);
+    let mut show_create_modal = use_signal(|| false);
+    let mut show_import_modal = use_signal(|| false);
+
+    // Read the keystore the first time any page needs it
+    let _loaded = use_resource({
+        let account_service = account_service.clone();
+        move || {
+            let account_service = account_service.clone();
+            async move {
+                let mut service = account_service.lock().await;
+                if !service.is_loaded() {
+                    if let Err(e) = service.initialize_async().await {
+                        error.set(Some(e.to_string()));
+                    }
+                }
+                sync_accounts(&service, accounts, locked);
+            }
+        }
     });
 
-    let selected_account = use_signal(|| None::<Account>);
-    let show_create_modal = use_signal(|| false);
-    let show_import_modal = use_signal(|| false);
-    let new_account_label = use_signal(|| "".to_string());
+    // Keep balances live: one account subscription per listed account,
+    // recreated when the set of addresses or the RPC endpoint changes
+    let mut pubsub = use_signal(|| None::<PubSubClient>);
+    use_effect(move || pubsub.set(PubSubClient::from_config(rpc.read().get_config()).ok()));
+    let addresses = use_memo(move || {
+        accounts
+            .read()
+            .iter()
+            .map(|account| account.address.clone())
+            .collect::<Vec<_>>()
+    });
+    let _live_balances = use_resource(move || {
+        let pubsub = pubsub();
+        async move {
+            let addresses = addresses();
+            let Some(pubsub) = pubsub else { return };
+
+            let mut subscriptions = Vec::with_capacity(addresses.len());
+            for address in &addresses {
+                match pubsub
+                    .account_subscribe(address, CommitmentLevel::Confirmed)
+                    .await
+                {
+                    Ok(subscription) => subscriptions.push(subscription),
+                    Err(e) => tracing::warn!("Failed to subscribe to {}: {}", address, e),
+                }
+            }
+
+            let mut notifications = futures_util::stream::select_all(subscriptions);
+            while let Some(notification) = notifications.next().await {
+                if let Notification::Account { account, .. } = notification {
+                    set_balance(
+                        accounts,
+                        selected_account,
+                        &account.address,
+                        account.balance,
+                    );
+                }
+            }
+        }
+    });
 
     rsx! {
         div { class: "account-manager-page",
@@ -52,35 +109,57 @@ pub fn AccountManager() -> Element {
                 }
                 button {
                     class: "btn btn-secondary",
-                    onclick: move |_| {
-                        // TODO: Refresh account balances
-                        tracing::info!("Refreshing account balances");
+                    onclick: {
+                        let account_service = account_service.clone();
+                        move |_| {
+                            let account_service = account_service.clone();
+                            spawn(async move {
+                                let mut service = account_service.lock().await;
+                                let result = service.update_all_balances().await;
+                                if finish(&service, result, accounts, locked, error) {
+                                    for account in service.get_all_accounts() {
+                                        set_balance(accounts, selected_account, &account.address, account.balance);
+                                    }
+                                }
+                            });
+                        }
                     },
                     "🔄 Refresh"
                 }
             }
 
+            if let Some(message) = error() {
+                p { style: "color: #dc2626; font-size: 0.875rem;", "{message}" }
+            }
+
             // Accounts Grid
             div { class: "accounts-grid",
-                for (index, account) in accounts().iter().enumerate() {
+                for account in accounts() {
                     AccountCard {
+                        key: "{account.address}",
                         account: account.clone(),
                         is_selected: selected_account()
                             .as_ref()
                             .map(|a| a.address == account.address)
                             .unwrap_or(false),
-                        on_select: move |_| {
-                            selected_account.set(Some(account.clone()));
+                        on_select: {
+                            let account = account.clone();
+                            move |_| selected_account.set(Some(account.clone()))
                         },
-                        on_delete: move |_| {
-                            let mut accs = accounts.write();
-                            accs.remove(index);
-                            if selected_account()
-                                .as_ref()
-                                .map(|a| a.address == account.address)
-                                .unwrap_or(false)
-                            {
-                                selected_account.set(None);
+                        on_delete: {
+                            let account_service = account_service.clone();
+                            move |_| {
+                                let account_service = account_service.clone();
+                                let address = account.address.clone();
+                                spawn(async move {
+                                    let mut service = account_service.lock().await;
+                                    let result = service.delete_account(&address).await;
+                                    if finish(&service, result, accounts, locked, error)
+                                        && selected_account().is_some_and(|a| a.address == address)
+                                    {
+                                        selected_account.set(None);
+                                    }
+                                });
                             }
                         },
                     }
@@ -93,15 +172,20 @@ pub fn AccountManager() -> Element {
                     h2 { "Account Details" }
                     AccountDetails {
                         account: account.clone(),
-                        on_update: move |updated_account| {
-                            // Find and update the account in the list
-                            for (index, acc) in accounts().iter().enumerate() {
-                                if acc.address == updated_account.address {
-                                    accounts.write()[index] = updated_account;
-                                    break;
-                                }
+                        on_update: {
+                            let account_service = account_service.clone();
+                            move |updated_account: Account| {
+                                let account_service = account_service.clone();
+                                spawn(async move {
+                                    let mut service = account_service.lock().await;
+                                    let result = service
+                                        .update_label(&updated_account.address, updated_account.label.clone())
+                                        .await;
+                                    if finish(&service, result, accounts, locked, error) {
+                                        selected_account.set(Some(updated_account));
+                                    }
+                                });
                             }
-                            selected_account.set(Some(updated_account));
                         },
                     }
                 }
@@ -110,10 +194,20 @@ pub fn AccountManager() -> Element {
             // Create Account Modal
             if show_create_modal() {
                 CreateAccountModal {
+                    locked: locked(),
                     on_close: move |_| show_create_modal.set(false),
-                    on_create: move |account| {
-                        accounts.write().push(account);
-                        show_create_modal.set(false);
+                    on_create: {
+                        let account_service = account_service.clone();
+                        move |request: AccountRequest| {
+                            let account_service = account_service.clone();
+                            spawn(async move {
+                                let mut service = account_service.lock().await;
+                                let result = create_account(&mut service, request).await;
+                                if finish(&service, result, accounts, locked, error) {
+                                    show_create_modal.set(false);
+                                }
+                            });
+                        }
                     },
                 }
             }
@@ -121,10 +215,20 @@ pub fn AccountManager() -> Element {
             // Import Account Modal
             if show_import_modal() {
                 ImportAccountModal {
+                    locked: locked(),
                     on_close: move |_| show_import_modal.set(false),
-                    on_import: move |account| {
-                        accounts.write().push(account);
-                        show_import_modal.set(false);
+                    on_import: {
+                        let account_service = account_service.clone();
+                        move |request: AccountRequest| {
+                            let account_service = account_service.clone();
+                            spawn(async move {
+                                let mut service = account_service.lock().await;
+                                let result = import_account(&mut service, request).await;
+                                if finish(&service, result, accounts, locked, error) {
+                                    show_import_modal.set(false);
+                                }
+                            });
+                        }
                     },
                 }
             }
@@ -148,6 +252,27 @@ impl Account {
     }
 }
 
+impl From<&account::Account> for Account {
+    fn from(account: &account::Account) -> Self {
+        Self {
+            address: account.address.clone(),
+            balance: account.balance,
+            label: account.label.clone(),
+            created_at: account.created_at.clone(),
+        }
+    }
+}
+
+/// Label, secret key and keystore password entered in the create and import modals
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountRequest {
+    pub label: String,
+    /// Base58 secret key, empty when creating a new account
+    pub private_key: String,
+    /// Keystore password, only used while the keystore is locked
+    pub password: String,
+}
+
 /// Account card component
 #[component]
 fn AccountCard(
@@ -158,7 +283,7 @@ fn AccountCard(
 ) -> Element {
     rsx! {
         div {
-            class: "account-card {if is_selected { 'selected' } else { '' }}",
+            class: if is_selected { "account-card selected" } else { "account-card" },
             onclick: on_select,
 
             div { class: "account-card-header",
@@ -202,9 +327,9 @@ fn AccountCard(
 /// Account details component
 #[component]
 fn AccountDetails(account: Account, on_update: EventHandler<Account>) -> Element {
-    let new_label = use_signal(|| account.label.clone());
-    let show_receive_modal = use_signal(|| false);
-    let show_send_modal = use_signal(|| false);
+    let mut new_label = use_signal(|| account.label.clone());
+    let mut show_receive_modal = use_signal(|| false);
+    let mut show_send_modal = use_signal(|| false);
 
     rsx! {
         div { class: "account-details",
@@ -316,10 +441,12 @@ fn AccountDetails(account: Account, on_update: EventHandler<Account>) -> Element
 /// Create account modal component
 #[component]
 fn CreateAccountModal(
+    locked: bool,
     on_close: EventHandler<MouseEvent>,
-    on_create: EventHandler<Account>,
+    on_create: EventHandler<AccountRequest>,
 ) -> Element {
-    let label = use_signal(|| "New Account".to_string());
+    let mut label = use_signal(|| "New Account".to_string());
+    let password = use_signal(String::new);
 
     rsx! {
         div { class: "modal-overlay",
@@ -345,6 +472,10 @@ fn CreateAccountModal(
                         }
                     }
 
+                    if locked {
+                        KeystorePasswordInput { password }
+                    }
+
                     div { class: "info-box",
                         h4 { "🔐 Security Note" }
                         p { "A new keypair will be generated and stored securely. Make sure to backup your private key." }
@@ -360,14 +491,11 @@ fn CreateAccountModal(
                     button {
                         class: "btn btn-primary",
                         onclick: move |_| {
-                            // TODO: Generate actual keypair
-                            let new_account = Account {
-                                address: generate_mock_address(),
-                                balance: 0,
+                            on_create.call(AccountRequest {
                                 label: label(),
-                                created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
-                            };
-                            on_create.call(new_account);
+                                private_key: String::new(),
+                                password: password(),
+                            });
                         },
                         "🚀 Create Account"
                     }
@@ -380,11 +508,13 @@ fn CreateAccountModal(
 /// Import account modal component
 #[component]
 fn ImportAccountModal(
+    locked: bool,
     on_close: EventHandler<MouseEvent>,
-    on_import: EventHandler<Account>,
+    on_import: EventHandler<AccountRequest>,
 ) -> Element {
-    let private_key = use_signal(|| String::new());
-    let label = use_signal(|| "Imported Account".to_string());
+    let mut private_key = use_signal(String::new);
+    let mut label = use_signal(|| "Imported Account".to_string());
+    let password = use_signal(String::new);
 
     rsx! {
         div { class: "modal-overlay",
@@ -400,11 +530,11 @@ fn ImportAccountModal(
 
                 div { class: "modal-body",
                     div { class: "form-group",
-                        label { "Private Key or Seed Phrase" }
+                        label { "Private Key" }
                         textarea {
                             class: "form-control",
                             rows: 3,
-                            placeholder: "Enter private key or seed phrase...",
+                            placeholder: "Enter base58 private key...",
                             value: "{private_key}",
                             onchange: move |evt| private_key.set(evt.value())
                         }
@@ -420,6 +550,10 @@ fn ImportAccountModal(
                             onchange: move |evt| label.set(evt.value())
                         }
                     }
+
+                    if locked {
+                        KeystorePasswordInput { password }
+                    }
                 }
 
                 div { class: "modal-footer",
@@ -430,17 +564,13 @@ fn ImportAccountModal(
                     }
                     button {
                         class: "btn btn-primary",
+                        disabled: private_key().trim().is_empty(),
                         onclick: move |_| {
-                            // TODO: Validate and import key
-                            if !private_key().is_empty() {
-                                let imported_account = Account {
-                                    address: generate_mock_address(),
-                                    balance: 0,
-                                    label: label(),
-                                    created_at: chrono::Utc::now().format("%Y-%m-%d").to_string(),
-                                };
-                                on_import.call(imported_account);
-                            }
+                            on_import.call(AccountRequest {
+                                label: label(),
+                                private_key: private_key().trim().to_string(),
+                                password: password(),
+                            });
                         },
                         "📥 Import Account"
                     }
@@ -450,6 +580,23 @@ fn ImportAccountModal(
     }
 }
 
+/// Keystore password field shown while secret keys are locked
+#[component]
+fn KeystorePasswordInput(password: Signal<String>) -> Element {
+    rsx! {
+        div { class: "form-group",
+            label { "Keystore Password" }
+            input {
+                r#type: "password",
+                class: "form-control",
+                value: "{password}",
+                placeholder: "Unlocks the keystore, or sets its password if none exists yet",
+                onchange: move |evt| password.set(evt.value())
+            }
+        }
+    }
+}
+
 /// Receive modal component
 #[component]
 fn ReceiveModal(address: String, on_close: EventHandler<MouseEvent>) -> Element {
@@ -507,9 +654,9 @@ fn ReceiveModal(address: String, on_close: EventHandler<MouseEvent>) -> Element
 /// Send modal component
 #[component]
 fn SendModal(account: Account, on_close: EventHandler<MouseEvent>) -> Element {
-    let recipient_address = use_signal(|| String::new());
-    let amount = use_signal(|| "0.0".to_string());
-    let memo = use_signal(|| String::new());
+    let mut recipient_address = use_signal(String::new);
+    let mut amount = use_signal(|| "0.0".to_string());
+    let mut memo = use_signal(String::new);
 
     rsx! {
         div { class: "modal-overlay",
@@ -575,11 +722,11 @@ fn SendModal(account: Account, on_close: EventHandler<MouseEvent>) -> Element {
                     }
                     button {
                         class: "btn btn-primary",
-                        onclick: move |_| {
+                        onclick: move |evt| {
                             // TODO: Validate and send transaction
                             if !recipient_address().is_empty() && !amount().is_empty() {
                                 tracing::info!("Sending {} SOL to {}", amount(), recipient_address());
-                                on_close.call(MouseEvent::default());
+                                on_close.call(evt);
                             }
                         },
                         "📤 Send"
@@ -590,6 +737,25 @@ fn SendModal(account: Account, on_close: EventHandler<MouseEvent>) -> Element {
     }
 }
 
+/// Store a fetched balance on the listed account and the selected copy
+fn set_balance(
+    mut accounts: Signal<Vec<Account>>,
+    mut selected_account: Signal<Option<Account>>,
+    address: &str,
+    balance: u64,
+) {
+    if let Some(account) = accounts.write().iter_mut().find(|a| a.address == address) {
+        account.balance = balance;
+    }
+    if let Some(account) = selected_account
+        .write()
+        .as_mut()
+        .filter(|a| a.address == address)
+    {
+        account.balance = balance;
+    }
+}
+
 /// Utility function to format address for display
 fn format_address(address: &str) -> String {
     if address.len() > 20 {
@@ -599,19 +765,78 @@ fn format_address(address: &str) -> String {
     }
 }
 
-/// Generate mock address for demonstration
-fn generate_mock_address() -> String {
-    // TODO: Generate actual Solana address
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-
-    let mut hasher = DefaultHasher::new();
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_nanos()
-        .hash(&mut hasher);
-
-    let hash = hasher.finish();
-    format!("{:x}", hash)[..44].to_string()
+/// Show the keystore's accounts, oldest first, and whether it is locked
+fn sync_accounts(
+    service: &AccountService,
+    mut accounts: Signal<Vec<Account>>,
+    mut locked: Signal<bool>,
+) {
+    let mut stored: Vec<&account::Account> = service.get_all_accounts();
+    stored.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    // Keep balances already fetched for accounts that are still listed
+    let balances: HashMap<String, u64> = accounts
+        .read()
+        .iter()
+        .map(|a| (a.address.clone(), a.balance))
+        .collect();
+    accounts.set(
+        stored
+            .into_iter()
+            .map(|stored| {
+                let mut account = Account::from(stored);
+                if let Some(balance) = balances.get(&account.address) {
+                    account.balance = *balance;
+                }
+                account
+            })
+            .collect(),
+    );
+    locked.set(service.is_locked());
+}
+
+/// Report the result of an account operation and refresh the list, returning whether it succeeded
+fn finish(
+    service: &AccountService,
+    result: ServiceResult<()>,
+    accounts: Signal<Vec<Account>>,
+    locked: Signal<bool>,
+    mut error: Signal<Option<String>>,
+) -> bool {
+    let succeeded = result.is_ok();
+    error.set(result.err().map(|e| e.to_string()));
+    sync_accounts(service, accounts, locked);
+    succeeded
+}
+
+/// Unlock the keystore with the entered password if needed
+async fn unlock_if_locked(service: &mut AccountService, password: &str) -> ServiceResult<()> {
+    if service.is_locked() {
+        service.unlock(password).await?;
+    }
+    Ok(())
+}
+
+/// Create an account whose keypair is kept in the keystore
+async fn create_account(
+    service: &mut AccountService,
+    request: AccountRequest,
+) -> ServiceResult<()> {
+    unlock_if_locked(service, &request.password).await?;
+    service
+        .create_account(request.label, AccountType::User)
+        .await?;
+    Ok(())
+}
+
+/// Import an account from a base58 secret key into the keystore
+async fn import_account(
+    service: &mut AccountService,
+    request: AccountRequest,
+) -> ServiceResult<()> {
+    unlock_if_locked(service, &request.password).await?;
+    service
+        .import_account(request.private_key, request.label)
+        .await?;
+    Ok(())
 }
@@ -3,81 +3,84 @@
 //! This is the main dashboard page that provides an overview of the Surfdesk
 //! application, showing system status, quick actions, and recent activity.
 use crate::routes::Route;
-use crate::services::surfpool::{Surfpool, TraitSurfpool};
+use crate::services::surfpool::{SurfpoolStatus, TraitSurfpool};
+use crate::services::surfpool_log::{LogLevel, LogSource};
+use crate::services::surfpool_supervisor::{SupervisorEvent, SurfpoolSupervisor};
 use dioxus::prelude::*;
 use dioxus_router::components::Link;
-use dioxus_router::prelude::*;
+use futures_util::StreamExt;
 
 /// Home page component - the main dashboard
 #[component]
 pub fn Home() -> Element {
-    let navigator = use_navigator();
-    let surfpool_status = use_signal(|| "Stopped".to_string());
+    let supervisor = use_signal(consume_context::<SurfpoolSupervisor>);
+    let mut surfpool_status = use_signal(|| supervisor.peek().status().to_string());
     let network_info = use_signal(|| "Local Simulation".to_string());
-    let mut surfpool = use_signal(|| Surfpool::new());
-    let logs = use_signal(Vec::<String>::new);
+    let mut logs = use_signal(Vec::<String>::new);
 
-    let toggle_surfpool = move |_| {
-        let mut surfpool = surfpool.clone();
-        let mut surfpool_status = surfpool_status.clone();
-        let mut logs = logs.clone();
-
-        spawn(async move {
-            if surfpool_status() == "Running" {
-                // Stop the surfpool
-                match surfpool.write().stop().await {
-                    Ok(_) => {
-                        logs.write().insert(0, "🟥 Surfpool stopped".to_string());
-                        surfpool_status.set("Stopped".into());
+    // Report lifecycle changes, restarts and process warnings while the
+    // dashboard is open
+    use_future(move || async move {
+        let supervisor = supervisor();
+        let mut events = Box::pin(supervisor.subscribe());
+        let mut output = Box::pin(supervisor.logs().subscribe());
+        loop {
+            tokio::select! {
+                Some(event) = events.next() => match event {
+                    SupervisorEvent::Status(status) => {
+                        surfpool_status.set(status.to_string());
+                        let message = match status {
+                            SurfpoolStatus::Starting => continue,
+                            SurfpoolStatus::Running => "🟩 Surfpool started".to_string(),
+                            SurfpoolStatus::Stopped => "🟥 Surfpool stopped".to_string(),
+                            _ => format!("🏁 Surfpool finished: {status}"),
+                        };
+                        logs.write().insert(0, message);
                     }
-                    Err(e) => {
-                        logs.write()
-                            .insert(0, format!("Error stopping surfpool: {e}"));
+                    SupervisorEvent::Restarting(record) => {
+                        logs.write().insert(
+                            0,
+                            format!(
+                                "🔁 Restarting Surfpool in {:.1}s (attempt {})",
+                                record.delay.as_secs_f64(),
+                                record.attempt
+                            ),
+                        );
                     }
-                }
-            } else {
-                // Start the surfpool
-                match surfpool.write().start().await {
-                    Ok(_) => {
-                        logs.write().insert(0, "🟩 Surfpool started".to_string());
-                        surfpool_status.set("Running".into());
+                    SupervisorEvent::GaveUp {
+                        restarts,
+                        window_secs,
+                    } => {
+                        logs.write().insert(
+                            0,
+                            format!("🛑 Gave up on Surfpool after {restarts} restarts within {window_secs}s"),
+                        );
                     }
-                    Err(e) => {
-                        logs.write()
-                            .insert(0, format!("Error starting surfpool: {e}"));
-                        return;
+                    SupervisorEvent::Configured(_) => {}
+                },
+                Some(line) = output.next() => {
+                    if line.source != LogSource::Surfdesk && line.level >= LogLevel::Warn {
+                        logs.write().insert(0, line.message);
                     }
                 }
+                else => break,
+            }
+        }
+    });
 
-                // Monitor the surfpool with better signal management
-                let mut last_output = String::new();
-                loop {
-                    // Check if surfpool is still running
-                    let is_running = {
-                        let surfpool_guard = surfpool.read();
-                        surfpool_guard.is_running().await
-                    };
-
-                    if !is_running {
-                        logs.write().insert(0, "🏁 Surfpool finished".to_string());
-                        surfpool_status.set("Stopped".into());
-                        break;
-                    }
-
-                    // Try to read output
-                    match surfpool.write().read().await {
-                        Ok(output) => {
-                            if !output.is_empty() && output != last_output {
-                                logs.write().insert(0, output.clone());
-                                last_output = output;
-                            }
-                        }
-                        Err(_) => {
-                            // Just continue on read error, don't log every error to avoid spam
-                        }
-                    }
-
-                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    let toggle_surfpool = move |_| {
+        let mut surfpool = supervisor();
+        spawn(async move {
+            if surfpool.is_running().await {
+                if let Err(e) = surfpool.stop().await {
+                    logs.write()
+                        .insert(0, format!("Error stopping surfpool: {e}"));
+                }
+            } else if surfpool.status() != SurfpoolStatus::Starting {
+                // Start the surfpool and wait until its RPC port answers
+                if let Err(e) = surfpool.start().await {
+                    logs.write()
+                        .insert(0, format!("Error starting surfpool: {e}"));
                 }
             }
         });
@@ -99,6 +102,30 @@ pub fn Home() -> Element {
                         span { style: "font-size: 1.25rem;", "🔧" }
                         span { "Open Program Builder" }
                     }
+                    Link {
+                        to: Route::PdaPage {},
+                        style: "display: inline-flex; align-items: center; gap: 0.5rem; padding: 0.75rem 1.5rem; margin-left: 0.75rem; background-color: #4b5563; color: white; text-decoration: none; border-radius: 0.5rem; font-weight: 500;",
+                        span { style: "font-size: 1.25rem;", "🔑" }
+                        span { "Derive PDA" }
+                    }
+                    Link {
+                        to: Route::AccountManager {},
+                        style: "display: inline-flex; align-items: center; gap: 0.5rem; padding: 0.75rem 1.5rem; margin-left: 0.75rem; background-color: #4b5563; color: white; text-decoration: none; border-radius: 0.5rem; font-weight: 500;",
+                        span { style: "font-size: 1.25rem;", "👛" }
+                        span { "Manage Accounts" }
+                    }
+                    Link {
+                        to: Route::ProgramAccountsPage {},
+                        style: "display: inline-flex; align-items: center; gap: 0.5rem; padding: 0.75rem 1.5rem; margin-left: 0.75rem; background-color: #4b5563; color: white; text-decoration: none; border-radius: 0.5rem; font-weight: 500;",
+                        span { style: "font-size: 1.25rem;", "📂" }
+                        span { "Program Accounts" }
+                    }
+                    Link {
+                        to: Route::SurfpoolManager {},
+                        style: "display: inline-flex; align-items: center; gap: 0.5rem; padding: 0.75rem 1.5rem; margin-left: 0.75rem; background-color: #4b5563; color: white; text-decoration: none; border-radius: 0.5rem; font-weight: 500;",
+                        span { style: "font-size: 1.25rem;", "🌊" }
+                        span { "Surfpool" }
+                    }
                 }
             }
 
@@ -3,7 +3,9 @@
 //! This module contains all the page components that render different views
 //! in the application based on the current route.
 
-// pub mod accounts;
+pub mod accounts;
 pub mod home;
+pub mod pda;
+pub mod program_accounts;
 pub mod program_builder;
 pub mod surfpool;
@@ -0,0 +1,164 @@
+//! PDA Derivation Page
+//!
+//! This page lets users type seeds and a program id and see the program
+//! derived address and canonical bump, exactly as `find_program_address`
+//! computes them on-chain.
+
+use dioxus::prelude::*;
+
+use crate::utils::crypto::{find_program_address, SeedKind};
+
+/// A seed row as entered by the user
+#[derive(Debug, Clone, PartialEq)]
+struct SeedInput {
+    kind: SeedKind,
+    value: String,
+}
+
+/// Derive the PDA for the current inputs, returning the Base58 address and bump
+fn derive(program_id: &str, seeds: &[SeedInput]) -> Result<(String, u8), String> {
+    let program_id: [u8; 32] = bs58::decode(program_id.trim())
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "Program id must be a Base58 public key".to_string())?;
+
+    let seed_bytes = seeds
+        .iter()
+        .map(|seed| seed.kind.encode(&seed.value))
+        .collect::<Result<Vec<_>, _>>()?;
+    let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+
+    let (address, bump) =
+        find_program_address(&seed_refs, &program_id).map_err(|e| e.to_string())?;
+    Ok((bs58::encode(address).into_string(), bump))
+}
+
+#[component]
+pub fn PdaPage() -> Element {
+    let mut program_id = use_signal(|| "11111111111111111111111111111111".to_string());
+    let mut seeds = use_signal(|| {
+        vec![SeedInput {
+            kind: SeedKind::String,
+            value: "vault".to_string(),
+        }]
+    });
+
+    let result = derive(&program_id(), &seeds());
+
+    rsx! {
+        div {
+            style: "min-height: 100vh; background-color: #f9fafb; padding: 2rem;",
+
+            h1 {
+                style: "font-size: 2rem; font-weight: bold; color: #1f2937; margin-bottom: 0.5rem;",
+                "PDA Derivation"
+            }
+            p {
+                style: "color: #6b7280; margin-bottom: 2rem;",
+                "Derive program addresses and bumps from seeds, as find_program_address does on-chain"
+            }
+
+            div {
+                style: "background-color: white; border-radius: 0.5rem; padding: 1.5rem; box-shadow: 0 1px 3px 0 rgba(0, 0, 0, 0.1); margin-bottom: 2rem;",
+
+                div {
+                    style: "margin-bottom: 1.5rem;",
+                    label {
+                        style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.5rem;",
+                        "Program ID"
+                    }
+                    input {
+                        style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace;",
+                        placeholder: "Base58 program id",
+                        value: "{program_id}",
+                        oninput: move |e| program_id.set(e.value())
+                    }
+                }
+
+                div {
+                    style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 1rem;",
+                    h2 {
+                        style: "font-size: 1.25rem; font-weight: 600; color: #1f2937;",
+                        "Seeds"
+                    }
+                    button {
+                        style: "padding: 0.5rem 1rem; background-color: #3b82f6; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: move |_| seeds.write().push(SeedInput {
+                            kind: SeedKind::String,
+                            value: String::new(),
+                        }),
+                        "+ Add Seed"
+                    }
+                }
+
+                for (seed_idx, seed) in seeds().iter().enumerate() {
+                    div {
+                        key: "{seed_idx}",
+                        style: "display: flex; gap: 0.5rem; margin-bottom: 0.5rem;",
+                        select {
+                            style: "padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                            value: "{seed.kind.name()}",
+                            onchange: move |e| {
+                                if let Some(kind) = SeedKind::from_name(&e.value()) {
+                                    if let Some(seed) = seeds.write().get_mut(seed_idx) {
+                                        seed.kind = kind;
+                                    }
+                                }
+                            },
+                            for kind in SeedKind::all() {
+                                option {
+                                    value: "{kind.name()}",
+                                    selected: kind == seed.kind,
+                                    "{kind.name()}"
+                                }
+                            }
+                        }
+                        input {
+                            style: "flex: 1; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace;",
+                            value: "{seed.value}",
+                            oninput: move |e| {
+                                if let Some(seed) = seeds.write().get_mut(seed_idx) {
+                                    seed.value = e.value();
+                                }
+                            }
+                        }
+                        button {
+                            style: "padding: 0.5rem 0.75rem; background-color: #ef4444; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                            onclick: move |_| {
+                                seeds.write().remove(seed_idx);
+                            },
+                            "✕"
+                        }
+                    }
+                }
+            }
+
+            div {
+                style: "background-color: white; border-radius: 0.5rem; padding: 1.5rem; box-shadow: 0 1px 3px 0 rgba(0, 0, 0, 0.1);",
+                h2 {
+                    style: "font-size: 1.25rem; font-weight: 600; color: #1f2937; margin-bottom: 1rem;",
+                    "Derived Address"
+                }
+                match result {
+                    Ok((address, bump)) => rsx! {
+                        p {
+                            style: "font-family: monospace; font-size: 1rem; color: #111827; word-break: break-all; margin: 0 0 0.5rem 0;",
+                            "{address}"
+                        }
+                        p {
+                            style: "color: #6b7280; margin: 0;",
+                            "Bump: {bump}"
+                        }
+                    },
+                    Err(error) => rsx! {
+                        p {
+                            style: "color: #ef4444; margin: 0;",
+                            "{error}"
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Program Accounts Page
+//!
+//! This page lists the accounts owned by a program through
+//! `getProgramAccounts`, with memcmp/dataSize filters and data slices. When a
+//! program builder schema is loaded, each account is decoded into its fields.
+
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+
+use crate::components::program_builder::ProgramSchema;
+use crate::services::solana_rpc::{
+    AccountEncoding, AccountFilter, AccountInfo, DataSlice, ProgramAccountsConfig, SolanaRpcService,
+};
+use crate::utils::borsh::decode_account;
+
+/// A memcmp filter row as entered by the user
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MemcmpInput {
+    offset: String,
+    bytes: String,
+}
+
+/// Build the request options from the form inputs
+fn build_config(
+    data_size: &str,
+    memcmps: &[MemcmpInput],
+    slice_offset: &str,
+    slice_length: &str,
+    compressed: bool,
+) -> Result<ProgramAccountsConfig, String> {
+    let mut filters = Vec::new();
+
+    if !data_size.trim().is_empty() {
+        let size = data_size
+            .trim()
+            .parse()
+            .map_err(|_| "Data size must be a number of bytes".to_string())?;
+        filters.push(AccountFilter::DataSize(size));
+    }
+
+    for memcmp in memcmps.iter().filter(|m| !m.bytes.trim().is_empty()) {
+        let offset = memcmp
+            .offset
+            .trim()
+            .parse()
+            .map_err(|_| "Memcmp offset must be a number".to_string())?;
+        let bytes = bs58::decode(memcmp.bytes.trim())
+            .into_vec()
+            .map_err(|_| "Memcmp bytes must be Base58".to_string())?;
+        filters.push(AccountFilter::Memcmp { offset, bytes });
+    }
+
+    let data_slice = match (slice_offset.trim(), slice_length.trim()) {
+        ("", "") => None,
+        (offset, length) => Some(DataSlice {
+            // An empty offset starts the slice at the beginning of the data
+            offset: match offset {
+                "" => 0,
+                offset => offset
+                    .parse()
+                    .map_err(|_| "Slice offset must be a number of bytes".to_string())?,
+            },
+            length: length
+                .parse()
+                .map_err(|_| "Slice length must be a number of bytes".to_string())?,
+        }),
+    };
+
+    Ok(ProgramAccountsConfig {
+        filters,
+        data_slice,
+        encoding: if compressed {
+            AccountEncoding::Base64Zstd
+        } else {
+            AccountEncoding::Base64
+        },
+    })
+}
+
+/// Hex preview of the first bytes of account data
+fn hex_preview(data: &[u8]) -> String {
+    const PREVIEW_LEN: usize = 32;
+    let hex: String = data
+        .iter()
+        .take(PREVIEW_LEN)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if data.len() > PREVIEW_LEN {
+        format!("{}…", hex)
+    } else {
+        hex
+    }
+}
+
+#[component]
+pub fn ProgramAccountsPage() -> Element {
+    let rpc = use_context::<Signal<Arc<SolanaRpcService>>>();
+    let mut program_id = use_signal(String::new);
+    let mut data_size = use_signal(String::new);
+    let mut memcmps = use_signal(|| vec![MemcmpInput::default()]);
+    let mut slice_offset = use_signal(String::new);
+    let mut slice_length = use_signal(String::new);
+    let mut compressed = use_signal(|| false);
+    let mut schema_json = use_signal(String::new);
+    let mut accounts = use_signal(Vec::<AccountInfo>::new);
+    // Sliced data no longer starts at the discriminator, so it is not decoded
+    let mut sliced = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+    let mut loading = use_signal(|| false);
+
+    let schema: Option<Result<ProgramSchema, String>> = match schema_json().trim() {
+        "" => None,
+        json => Some(serde_json::from_str(json).map_err(|e| format!("Invalid schema: {}", e))),
+    };
+    let loaded_schema = schema
+        .as_ref()
+        .and_then(|schema| schema.as_ref().ok())
+        .cloned();
+
+    let fetch = move |_| {
+        let config = match build_config(
+            &data_size(),
+            &memcmps(),
+            &slice_offset(),
+            &slice_length(),
+            compressed(),
+        ) {
+            Ok(config) => config,
+            Err(e) => {
+                status.set(Some(Err(e)));
+                return;
+            }
+        };
+
+        loading.set(true);
+        let rpc = rpc();
+        spawn(async move {
+            match rpc.get_program_accounts(program_id().trim(), &config).await {
+                Ok(fetched) => {
+                    status.set(Some(Ok(format!("{} accounts", fetched.len()))));
+                    sliced.set(config.data_slice.is_some());
+                    accounts.set(fetched);
+                }
+                Err(e) => status.set(Some(Err(e.to_string()))),
+            }
+            loading.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            style: "min-height: 100vh; background-color: #f9fafb; padding: 2rem;",
+
+            h1 {
+                style: "font-size: 2rem; font-weight: bold; color: #1f2937; margin-bottom: 0.5rem;",
+                "Program Accounts"
+            }
+            p {
+                style: "color: #6b7280; margin-bottom: 2rem;",
+                "List the accounts owned by a program and decode them with a program builder schema"
+            }
+
+            div {
+                style: "background-color: white; border-radius: 0.5rem; padding: 1.5rem; box-shadow: 0 1px 3px 0 rgba(0, 0, 0, 0.1); margin-bottom: 2rem;",
+
+                div {
+                    style: "margin-bottom: 1rem;",
+                    label {
+                        style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.5rem;",
+                        "Program ID"
+                    }
+                    input {
+                        style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace;",
+                        placeholder: "Base58 program id",
+                        value: "{program_id}",
+                        oninput: move |e| program_id.set(e.value())
+                    }
+                }
+
+                div {
+                    style: "display: flex; gap: 1rem; margin-bottom: 1rem;",
+                    div {
+                        style: "flex: 1;",
+                        label {
+                            style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.5rem;",
+                            "Data size (bytes)"
+                        }
+                        input {
+                            style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                            placeholder: "Any",
+                            value: "{data_size}",
+                            oninput: move |e| data_size.set(e.value())
+                        }
+                    }
+                    div {
+                        style: "flex: 1;",
+                        label {
+                            style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.5rem;",
+                            "Slice offset"
+                        }
+                        input {
+                            style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                            placeholder: "0",
+                            value: "{slice_offset}",
+                            oninput: move |e| slice_offset.set(e.value())
+                        }
+                    }
+                    div {
+                        style: "flex: 1;",
+                        label {
+                            style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.5rem;",
+                            "Slice length"
+                        }
+                        input {
+                            style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                            placeholder: "Whole account",
+                            value: "{slice_length}",
+                            oninput: move |e| slice_length.set(e.value())
+                        }
+                    }
+                }
+
+                div {
+                    style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 0.5rem;",
+                    h2 {
+                        style: "font-size: 1rem; font-weight: 600; color: #1f2937;",
+                        "Memcmp filters"
+                    }
+                    button {
+                        style: "padding: 0.25rem 0.75rem; background-color: #3b82f6; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: move |_| memcmps.write().push(MemcmpInput::default()),
+                        "+ Add Filter"
+                    }
+                }
+
+                for (memcmp_idx, memcmp) in memcmps().iter().enumerate() {
+                    div {
+                        key: "{memcmp_idx}",
+                        style: "display: flex; gap: 0.5rem; margin-bottom: 0.5rem;",
+                        input {
+                            style: "width: 6rem; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                            placeholder: "Offset",
+                            value: "{memcmp.offset}",
+                            oninput: move |e| {
+                                if let Some(memcmp) = memcmps.write().get_mut(memcmp_idx) {
+                                    memcmp.offset = e.value();
+                                }
+                            }
+                        }
+                        input {
+                            style: "flex: 1; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace;",
+                            placeholder: "Base58 bytes",
+                            value: "{memcmp.bytes}",
+                            oninput: move |e| {
+                                if let Some(memcmp) = memcmps.write().get_mut(memcmp_idx) {
+                                    memcmp.bytes = e.value();
+                                }
+                            }
+                        }
+                        button {
+                            style: "padding: 0.5rem 0.75rem; background-color: #ef4444; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                            onclick: move |_| {
+                                memcmps.write().remove(memcmp_idx);
+                            },
+                            "✕"
+                        }
+                    }
+                }
+
+                label {
+                    style: "display: flex; align-items: center; gap: 0.5rem; margin: 1rem 0; color: #374151;",
+                    input {
+                        r#type: "checkbox",
+                        checked: compressed(),
+                        onchange: move |e| compressed.set(e.checked())
+                    }
+                    "Request base64+zstd compressed data"
+                }
+
+                div {
+                    style: "margin-bottom: 1rem;",
+                    label {
+                        style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.5rem;",
+                        "Program schema (optional)"
+                    }
+                    textarea {
+                        style: "width: 100%; height: 6rem; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace; font-size: 0.75rem;",
+                        placeholder: "Paste the JSON schema from the program builder to decode account data",
+                        value: "{schema_json}",
+                        oninput: move |e| schema_json.set(e.value())
+                    }
+                    match &schema {
+                        Some(Ok(schema)) => rsx! {
+                            p {
+                                style: "color: #059669; font-size: 0.875rem; margin: 0.25rem 0 0 0;",
+                                "Loaded schema {schema.name} with {schema.accounts.len()} account types"
+                            }
+                        },
+                        Some(Err(error)) => rsx! {
+                            p {
+                                style: "color: #ef4444; font-size: 0.875rem; margin: 0.25rem 0 0 0;",
+                                "{error}"
+                            }
+                        },
+                        None => rsx! {},
+                    }
+                }
+
+                button {
+                    style: "padding: 0.5rem 1.5rem; background-color: #2563eb; color: white; border: none; border-radius: 0.375rem; cursor: pointer; font-weight: 500;",
+                    disabled: loading(),
+                    onclick: fetch,
+                    if loading() { "Fetching..." } else { "Fetch Accounts" }
+                }
+
+                match status() {
+                    Some(Ok(message)) => rsx! {
+                        span { style: "margin-left: 1rem; color: #6b7280;", "{message}" }
+                    },
+                    Some(Err(error)) => rsx! {
+                        span { style: "margin-left: 1rem; color: #ef4444;", "{error}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+
+            for account in accounts() {
+                div {
+                    key: "{account.address}",
+                    style: "background-color: white; border-radius: 0.5rem; padding: 1rem 1.5rem; box-shadow: 0 1px 3px 0 rgba(0, 0, 0, 0.1); margin-bottom: 1rem;",
+                    div {
+                        style: "display: flex; justify-content: space-between; gap: 1rem; margin-bottom: 0.5rem;",
+                        span {
+                            style: "font-family: monospace; color: #111827; word-break: break-all;",
+                            "{account.address}"
+                        }
+                        span {
+                            style: "color: #6b7280; white-space: nowrap;",
+                            "{account.balance} lamports · {account.size} bytes"
+                        }
+                    }
+                    if sliced() && loaded_schema.is_some() {
+                        p {
+                            style: "color: #6b7280; font-size: 0.75rem; margin: 0 0 0.25rem 0;",
+                            "Data slice, not decoded with the schema"
+                        }
+                    }
+                    match loaded_schema
+                        .as_ref()
+                        .filter(|_| !sliced())
+                        .and_then(|schema| decode_account(schema, &account.data))
+                    {
+                        Some(decoded) => rsx! {
+                            p {
+                                style: "font-weight: 600; color: #1f2937; margin: 0 0 0.25rem 0;",
+                                "{decoded.account_name}"
+                            }
+                            for (name, value) in decoded.fields {
+                                div {
+                                    key: "{name}",
+                                    style: "display: flex; gap: 0.5rem; font-size: 0.875rem;",
+                                    span { style: "color: #6b7280; min-width: 8rem;", "{name}" }
+                                    span { style: "font-family: monospace; color: #111827; word-break: break-all;", "{value}" }
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            p {
+                                style: "font-family: monospace; font-size: 0.75rem; color: #6b7280; margin: 0; word-break: break-all;",
+                                "{hex_preview(&account.data)}"
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
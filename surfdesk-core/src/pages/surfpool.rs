@@ -2,18 +2,180 @@
 //!
 //! This page provides interface for managing Surfpool local Solana
 //! simulation network, including starting/stopping service, monitoring
-//! status, and configuring network parameters.
+//! status, and configuring network parameters. The process itself belongs to
+//! the `SurfpoolSupervisor` the app provides as context, so it keeps running
+//! and restarting while other pages are open.
+
+use std::collections::VecDeque;
 
 use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::services::surfpool::{SurfpoolConfig, SurfpoolStatus, TraitSurfpool};
+use crate::services::surfpool_log::{LogLevel, LogLine, LogSource};
+use crate::services::surfpool_supervisor::{RestartPolicy, SupervisorEvent, SurfpoolSupervisor};
+
+/// Log lines rendered at once, the newest matching ones
+const MAX_SHOWN_LOGS: usize = 1000;
+
+/// Output lines kept in the console view
+const CONSOLE_LINES: usize = 200;
 
 /// Surfpool Manager page component
 #[component]
 pub fn SurfpoolManager() -> Element {
-    let mut is_running = use_signal(|| false);
-    let mut status_message = use_signal(|| "Surfpool is stopped".to_string());
-    let port = use_signal(|| 8999u16);
-    let mut network_status = use_signal(|| "Disconnected".to_string());
-    let mut logs = use_signal(|| Vec::<String>::new());
+    let saved = use_hook(|| {
+        SurfpoolConfig::load(&SurfpoolConfig::default_path()).map_err(|e| e.to_string())
+    });
+    let mut profile = use_signal(|| saved.clone().unwrap_or_default());
+    let mut profile_message = use_signal(|| {
+        saved
+            .err()
+            .map(|e| Err(format!("Could not load the saved profile: {e}")))
+    });
+    let supervisor = use_signal(consume_context::<SurfpoolSupervisor>);
+    let mut status = use_signal(|| supervisor.peek().status());
+    let mut process = use_signal(|| supervisor.peek().process_info());
+    let mut restarts = use_signal(|| supervisor.peek().history());
+    let mut restart_notice = use_signal(|| None::<String>);
+    let mut orphan = use_signal(|| supervisor.peek().find_orphan());
+    let log_handle = use_signal(|| supervisor.peek().logs());
+    let mut log_lines = use_signal(|| VecDeque::from(log_handle.peek().snapshot()));
+    let mut min_level = use_signal(|| LogLevel::Trace);
+    let mut log_query = use_signal(String::new);
+
+    // Follow new log lines for as long as the page is open
+    use_future(move || async move {
+        let logs = log_handle();
+        let mut lines = Box::pin(logs.subscribe());
+        while let Some(line) = lines.next().await {
+            let mut buffered = log_lines.write();
+            if buffered.len() >= logs.capacity() {
+                buffered.pop_front();
+            }
+            buffered.push_back(line);
+        }
+    });
+
+    // Mirror the supervisor, which keeps running when the page closes
+    use_future(move || async move {
+        let supervisor = supervisor();
+        let mut events = Box::pin(supervisor.subscribe());
+        while let Some(event) = events.next().await {
+            match event {
+                SupervisorEvent::Status(current) => {
+                    if current == SurfpoolStatus::Starting {
+                        restart_notice.set(None);
+                    }
+                    status.set(current);
+                    process.set(supervisor.process_info());
+                }
+                SupervisorEvent::Restarting(_) => restarts.set(supervisor.history()),
+                SupervisorEvent::GaveUp {
+                    restarts: count,
+                    window_secs,
+                } => restart_notice.set(Some(format!(
+                    "Gave up after {count} restarts within {window_secs}s"
+                ))),
+                SupervisorEvent::Configured(_) => {}
+            }
+        }
+    });
+
+    let matching_logs = use_memo(move || {
+        let query = log_query();
+        log_lines
+            .read()
+            .iter()
+            .filter(|line| line.matches(min_level(), query.trim()))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+    let shown_logs =
+        &matching_logs.read()[matching_logs.read().len().saturating_sub(MAX_SHOWN_LOGS)..];
+
+    let profile_error = use_memo(move || profile().validate().err().map(|e| e.to_string()));
+    let is_running = status() == SurfpoolStatus::Running;
+    let is_busy = matches!(status(), SurfpoolStatus::Starting | SurfpoolStatus::Running);
+    let network_status = match &process().endpoint {
+        Some(endpoint) if is_running => format!("Connected - {endpoint}"),
+        _ => "Disconnected".to_string(),
+    };
+
+    let mut command = use_signal(String::new);
+    let history = use_signal(Vec::<String>::new);
+    let mut history_pos = use_signal(|| None::<usize>);
+    let console_lines = use_memo(move || {
+        let lines = log_lines.read();
+        let mut shown: Vec<_> = lines
+            .iter()
+            .rev()
+            .filter(|line| line.source != LogSource::Surfdesk || line.message.starts_with("> "))
+            .take(CONSOLE_LINES)
+            .cloned()
+            .collect();
+        shown.reverse();
+        shown
+    });
+
+    let send_command = move || {
+        let input = command.peek().trim().to_string();
+        if input.is_empty() {
+            return;
+        }
+        let (mut command, mut history, mut history_pos) = (command, history, history_pos);
+        command.set(String::new());
+        history.write().push(input.clone());
+        history_pos.set(None);
+
+        let mut surfpool = supervisor();
+        spawn(async move {
+            if let Err(e) = surfpool.write(&input).await {
+                log_handle().note(LogLevel::Error, format!("Could not send `{input}`: {e}"));
+            }
+        });
+    };
+
+    let save_profile = move |_| {
+        let config = profile();
+        let result = config
+            .save(&SurfpoolConfig::default_path())
+            .map(|_| format!("Saved to {}", SurfpoolConfig::default_path().display()));
+        profile_message.set(Some(result.map_err(|e| e.to_string())));
+    };
+
+    let start = move |_| {
+        let mut surfpool = supervisor();
+        spawn(async move {
+            if let Err(e) = surfpool.configure(profile()).await {
+                log_handle().note(LogLevel::Error, format!("Invalid profile: {e}"));
+                return;
+            }
+            // The service notes the outcome in the logs, and status changes
+            // arrive as events
+            let _ = surfpool.start().await;
+        });
+    };
+
+    let stop = move |_| {
+        let mut surfpool = supervisor();
+        spawn(async move {
+            if let Err(e) = surfpool.stop().await {
+                log_handle().note(LogLevel::Error, format!("Error stopping Surfpool: {e}"));
+            }
+        });
+    };
+
+    let kill_orphan = move |pid: u32| {
+        let mut orphan = orphan;
+        match supervisor().kill_orphan(pid) {
+            Ok(()) => orphan.set(None),
+            Err(e) => log_handle().note(
+                LogLevel::Error,
+                format!("Could not kill process {pid}: {e}"),
+            ),
+        }
+    };
 
     rsx! {
         div {
@@ -25,13 +187,156 @@ pub fn SurfpoolManager() -> Element {
                 p { style: "font-size: 1.125rem; color: #4b5563;", "Manage your local Solana simulation network" }
             }
 
+            if let Some(pid) = orphan() {
+                div {
+                    style: "margin-bottom: 2rem; display: flex; align-items: center; gap: 1rem; background-color: #fffbeb; border: 1px solid #f59e0b; border-radius: 0.5rem; padding: 1rem;",
+                    span {
+                        style: "flex: 1; color: #92400e;",
+                        "⚠️ Surfpool process {pid} from an earlier session is still running and may be holding its ports."
+                    }
+                    button {
+                        style: "padding: 0.5rem 1rem; background-color: #ef4444; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: move |_| kill_orphan(pid),
+                        "Kill it"
+                    }
+                    button {
+                        style: "padding: 0.5rem 1rem; background-color: white; color: #92400e; border: 1px solid #f59e0b; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: move |_| orphan.set(None),
+                        "Dismiss"
+                    }
+                }
+            }
+
             div {
                 style: "margin-bottom: 2rem;",
                 StatusCard {
-                    is_running: is_running(),
-                    status_message: status_message(),
-                    port: port(),
-                    network_status: network_status()
+                    is_running,
+                    status_message: format!("Surfpool: {}", status()),
+                    port: process().port.unwrap_or_default(),
+                    network_status
+                }
+            }
+
+            div {
+                style: "margin-bottom: 2rem;",
+                h2 { style: "font-size: 1.5rem; font-weight: 600; color: #111827; margin-bottom: 1rem;", "Launch Profile" }
+                div {
+                    style: "background-color: white; border-radius: 0.5rem; border: 1px solid #e5e7eb; padding: 1.5rem;",
+                    div {
+                        style: "display: grid; grid-template-columns: repeat(auto-fit, minmax(220px, 1fr)); gap: 1rem; margin-bottom: 1rem;",
+                        ProfileField {
+                            label: "Binary",
+                            value: profile().binary.display().to_string(),
+                            placeholder: "surfpool",
+                            oninput: move |value: String| profile.write().binary = value.trim().into()
+                        }
+                        ProfileField {
+                            label: "RPC port",
+                            value: profile().rpc_port.to_string(),
+                            placeholder: "8999",
+                            oninput: move |value: String| profile.write().rpc_port = value.trim().parse().unwrap_or(0)
+                        }
+                        ProfileField {
+                            label: "WebSocket port",
+                            value: profile().ws_port.to_string(),
+                            placeholder: "9000",
+                            oninput: move |value: String| profile.write().ws_port = value.trim().parse().unwrap_or(0)
+                        }
+                        ProfileField {
+                            label: "Fork from RPC",
+                            value: profile().fork_url.unwrap_or_default(),
+                            placeholder: "Surfpool default (mainnet)",
+                            oninput: move |value: String| {
+                                let value = value.trim();
+                                profile.write().fork_url = (!value.is_empty()).then(|| value.to_string());
+                            }
+                        }
+                        ProfileField {
+                            label: "Slot time (ms)",
+                            value: profile().slot_time_ms.to_string(),
+                            placeholder: "400",
+                            oninput: move |value: String| profile.write().slot_time_ms = value.trim().parse().unwrap_or(0)
+                        }
+                        ProfileField {
+                            label: "Startup timeout (s)",
+                            value: profile().startup_timeout.to_string(),
+                            placeholder: "30",
+                            oninput: move |value: String| profile.write().startup_timeout = value.trim().parse().unwrap_or(0)
+                        }
+                        div {
+                            label {
+                                style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.25rem;",
+                                "Restart policy"
+                            }
+                            select {
+                                style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; background-color: white; box-sizing: border-box;",
+                                onchange: move |e| {
+                                    if let Some(policy) = RestartPolicy::from_name(&e.value()) {
+                                        profile.write().restart.policy = policy;
+                                    }
+                                },
+                                for policy in RestartPolicy::all() {
+                                    option {
+                                        value: policy.as_str(),
+                                        selected: policy == profile().restart.policy,
+                                        "{policy}"
+                                    }
+                                }
+                            }
+                        }
+                        if profile().restart.policy != RestartPolicy::Never {
+                            ProfileField {
+                                label: "Max restarts",
+                                value: profile().restart.max_restarts.to_string(),
+                                placeholder: "5",
+                                oninput: move |value: String| profile.write().restart.max_restarts = value.trim().parse().unwrap_or(0)
+                            }
+                            ProfileField {
+                                label: "Restart window (s)",
+                                value: profile().restart.window_secs.to_string(),
+                                placeholder: "300",
+                                oninput: move |value: String| profile.write().restart.window_secs = value.trim().parse().unwrap_or(0)
+                            }
+                            ProfileField {
+                                label: "Initial backoff (ms)",
+                                value: profile().restart.initial_backoff_ms.to_string(),
+                                placeholder: "1000",
+                                oninput: move |value: String| profile.write().restart.initial_backoff_ms = value.trim().parse().unwrap_or(0)
+                            }
+                            ProfileField {
+                                label: "Max backoff (ms)",
+                                value: profile().restart.max_backoff_ms.to_string(),
+                                placeholder: "30000",
+                                oninput: move |value: String| profile.write().restart.max_backoff_ms = value.trim().parse().unwrap_or(0)
+                            }
+                        }
+                    }
+
+                    div {
+                        style: "background-color: #111827; color: #e5e7eb; border-radius: 0.375rem; padding: 0.75rem; font-family: monospace; font-size: 0.875rem; margin-bottom: 1rem; overflow-x: auto;",
+                        "$ {profile().command_line()}"
+                    }
+
+                    div {
+                        style: "display: flex; align-items: center; gap: 1rem;",
+                        button {
+                            style: "padding: 0.5rem 1rem; background-color: #2563eb; color: white; border: none; border-radius: 0.375rem; font-weight: 500; cursor: pointer;",
+                            disabled: profile_error().is_some(),
+                            onclick: save_profile,
+                            "💾 Save Profile"
+                        }
+                        if let Some(error) = profile_error() {
+                            span { style: "color: #dc2626; font-size: 0.875rem;", "{error}" }
+                        } else if let Some(message) = profile_message() {
+                            match message {
+                                Ok(message) => rsx! { span { style: "color: #059669; font-size: 0.875rem;", "{message}" } },
+                                Err(error) => rsx! { span { style: "color: #dc2626; font-size: 0.875rem;", "{error}" } },
+                            }
+                        }
+                    }
+                    if is_busy {
+                        p { style: "color: #6b7280; font-size: 0.875rem; margin: 0.75rem 0 0 0;", "Changes apply the next time Surfpool starts." }
+                    }
                 }
             }
 
@@ -40,47 +345,181 @@ pub fn SurfpoolManager() -> Element {
                 h2 { style: "font-size: 1.5rem; font-weight: 600; color: #111827; margin-bottom: 1rem;", "Control Panel" }
                 div {
                     style: "display: flex; gap: 1rem;",
-                    if !is_running() {
+                    if !is_busy {
                         button {
                             style: "padding: 0.75rem 1.5rem; background-color: #10b981; color: white; border: none; border-radius: 0.5rem; font-weight: 500; cursor: pointer;",
-                            onclick: move |_| {
-                                is_running.set(true);
-                                status_message.set("Surfpool is running".to_string());
-                                network_status.set("Connected - Local Simulation".to_string());
-                                logs.write().push("Surfpool started successfully".to_string());
-                            },
+                            disabled: profile_error().is_some(),
+                            onclick: start,
                             "🚀 Start Surfpool"
                         }
                     } else {
                         button {
                             style: "padding: 0.75rem 1.5rem; background-color: #ef4444; color: white; border: none; border-radius: 0.5rem; font-weight: 500; cursor: pointer;",
-                            onclick: move |_| {
-                                is_running.set(false);
-                                status_message.set("Surfpool is stopped".to_string());
-                                network_status.set("Disconnected".to_string());
-                                logs.write().push("Surfpool stopped successfully".to_string());
-                            },
+                            onclick: stop,
                             "🛑 Stop Surfpool"
                         }
                     }
                 }
             }
 
+            div {
+                style: "margin-bottom: 2rem;",
+                h2 { style: "font-size: 1.5rem; font-weight: 600; color: #111827; margin-bottom: 1rem;", "Restart History" }
+                div {
+                    style: "background-color: white; border-radius: 0.5rem; border: 1px solid #e5e7eb; padding: 1rem;",
+                    if let Some(notice) = restart_notice() {
+                        p { style: "color: #dc2626; font-weight: 500; margin: 0 0 0.75rem 0;", "🛑 {notice}" }
+                    }
+                    if restarts.read().is_empty() {
+                        p { style: "color: #6b7280; margin: 0;", "No restarts yet." }
+                    }
+                    for record in restarts.read().iter().rev() {
+                        div {
+                            style: "display: flex; gap: 1rem; padding: 0.25rem 0; border-bottom: 1px solid #f3f4f6; font-size: 0.875rem; color: #374151;",
+                            span {
+                                style: "color: #9ca3af; font-family: monospace;",
+                                {record.timestamp.with_timezone(&chrono::Local).format("%H:%M:%S").to_string()}
+                            }
+                            span { style: "width: 5rem; font-weight: 500;", "Attempt {record.attempt}" }
+                            span { style: "flex: 1;", "{record.reason}" }
+                            span {
+                                style: "color: #6b7280;",
+                                {format!("waited {:.1}s", record.delay.as_secs_f64())}
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                style: "margin-bottom: 2rem;",
+                h2 { style: "font-size: 1.5rem; font-weight: 600; color: #111827; margin-bottom: 1rem;", "Console" }
+                div {
+                    style: "background-color: #111827; border-radius: 0.5rem; padding: 1rem; font-family: monospace; font-size: 0.8125rem;",
+                    // Newest line last, kept in view by the reversed flow
+                    div {
+                        style: "display: flex; flex-direction: column-reverse; height: 240px; overflow-y: auto; margin-bottom: 0.75rem;",
+                        div {
+                            for line in console_lines.read().iter() {
+                                div {
+                                    style: format!("white-space: pre-wrap; word-break: break-all; color: {};", console_color(line)),
+                                    "{line.message}"
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        style: "display: flex; align-items: center; gap: 0.5rem; border-top: 1px solid #374151; padding-top: 0.75rem;",
+                        span { style: "color: #10b981;", "$" }
+                        input {
+                            style: "flex: 1; background-color: transparent; border: none; outline: none; color: #f9fafb; font-family: monospace;",
+                            placeholder: if is_running { "Type a command and press Enter" } else { "Start Surfpool to send commands" },
+                            disabled: !is_running,
+                            value: "{command}",
+                            oninput: move |e| command.set(e.value()),
+                            onkeydown: move |e| {
+                                let history = history.read();
+                                match e.key() {
+                                    Key::Enter => send_command(),
+                                    Key::ArrowUp if !history.is_empty() => {
+                                        let pos = history_pos().map_or(history.len() - 1, |pos| pos.saturating_sub(1));
+                                        history_pos.set(Some(pos));
+                                        command.set(history[pos].clone());
+                                    }
+                                    Key::ArrowDown => match history_pos() {
+                                        Some(pos) if pos + 1 < history.len() => {
+                                            history_pos.set(Some(pos + 1));
+                                            command.set(history[pos + 1].clone());
+                                        }
+                                        _ => {
+                                            history_pos.set(None);
+                                            command.set(String::new());
+                                        }
+                                    },
+                                    _ => {}
+                                }
+                            }
+                        }
+                        button {
+                            style: "padding: 0.375rem 0.75rem; background-color: #2563eb; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                            disabled: !is_running,
+                            onclick: move |_| send_command(),
+                            "Send"
+                        }
+                    }
+                }
+            }
+
             div {
                 h2 { style: "font-size: 1.5rem; font-weight: 600; color: #111827; margin-bottom: 1rem;", "Activity Logs" }
+                div {
+                    style: "display: flex; align-items: center; gap: 0.75rem; margin-bottom: 0.75rem;",
+                    select {
+                        style: "padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; background-color: white;",
+                        onchange: move |e| {
+                            if let Some(level) = LogLevel::from_word(&e.value()) {
+                                min_level.set(level);
+                            }
+                        },
+                        for level in LogLevel::all() {
+                            option {
+                                value: level.as_str(),
+                                selected: level == min_level(),
+                                if level == LogLevel::Trace { "All levels" } else { "{level} and above" }
+                            }
+                        }
+                    }
+                    input {
+                        style: "flex: 1; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem;",
+                        placeholder: "Search logs",
+                        value: "{log_query}",
+                        oninput: move |e| log_query.set(e.value())
+                    }
+                    span {
+                        style: "color: #6b7280; font-size: 0.875rem; white-space: nowrap;",
+                        "{matching_logs.read().len()} of {log_lines.read().len()} lines"
+                    }
+                    button {
+                        style: "padding: 0.5rem 1rem; background-color: #4b5563; color: white; border: none; border-radius: 0.375rem; cursor: pointer;",
+                        onclick: move |_| {
+                            log_handle().clear();
+                            log_lines.write().clear();
+                        },
+                        "Clear"
+                    }
+                }
                 div {
                     style: "background-color: white; border-radius: 0.5rem; border: 1px solid #e5e7eb; padding: 1rem; max-height: 400px; overflow-y: auto;",
-                    if logs().is_empty() {
+                    if log_lines.read().is_empty() {
                         div {
                             style: "text-align: center; color: #6b7280; padding: 2rem;",
                             p { "No activity logs yet. Start Surfpool to see logs." }
                         }
+                    } else if shown_logs.is_empty() {
+                        div {
+                            style: "text-align: center; color: #6b7280; padding: 2rem;",
+                            p { "No lines match the current filter." }
+                        }
                     } else {
-                        for log_entry in logs().iter() {
+                        if matching_logs.read().len() > shown_logs.len() {
+                            p {
+                                style: "color: #6b7280; font-size: 0.75rem; margin: 0 0 0.5rem 0;",
+                                "Showing the latest {MAX_SHOWN_LOGS} matching lines"
+                            }
+                        }
+                        for line in shown_logs.iter() {
                             div {
-                                style: "padding: 0.5rem 0; border-bottom: 1px solid #f3f4f6; font-family: monospace; font-size: 0.875rem; color: #374151;",
-                                span { style: "color: #6b7280; margin-right: 0.5rem;", "[LOG]" }
-                                span { "{log_entry}" }
+                                style: "display: flex; gap: 0.5rem; padding: 0.25rem 0; border-bottom: 1px solid #f3f4f6; font-family: monospace; font-size: 0.8125rem; color: #374151;",
+                                span {
+                                    style: "color: #9ca3af; white-space: nowrap;",
+                                    {line.timestamp.with_timezone(&chrono::Local).format("%H:%M:%S%.3f").to_string()}
+                                }
+                                span {
+                                    style: format!("width: 3.5rem; flex-shrink: 0; font-weight: 600; color: {};", level_color(line.level)),
+                                    "{line.level}"
+                                }
+                                span { style: "width: 4.5rem; flex-shrink: 0; color: #9ca3af;", "{line.source}" }
+                                span { style: "white-space: pre-wrap; word-break: break-all;", "{line.message}" }
                             }
                         }
                     }
@@ -90,6 +529,50 @@ pub fn SurfpoolManager() -> Element {
     }
 }
 
+/// Console colour for a line: commands, stderr and stdout
+fn console_color(line: &LogLine) -> &'static str {
+    match line.source {
+        LogSource::Surfdesk => "#34d399",
+        LogSource::Stderr => "#fca5a5",
+        LogSource::Stdout => "#e5e7eb",
+    }
+}
+
+/// Text colour for a log level
+fn level_color(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace => "#9ca3af",
+        LogLevel::Debug => "#6b7280",
+        LogLevel::Info => "#2563eb",
+        LogLevel::Warn => "#d97706",
+        LogLevel::Error => "#dc2626",
+    }
+}
+
+/// Labelled text input for one launch profile option
+#[component]
+fn ProfileField(
+    label: &'static str,
+    value: String,
+    placeholder: &'static str,
+    oninput: EventHandler<String>,
+) -> Element {
+    rsx! {
+        div {
+            label {
+                style: "display: block; font-size: 0.875rem; font-weight: 500; color: #374151; margin-bottom: 0.25rem;",
+                "{label}"
+            }
+            input {
+                style: "width: 100%; padding: 0.5rem; border: 1px solid #d1d5db; border-radius: 0.375rem; font-family: monospace; box-sizing: border-box;",
+                value: "{value}",
+                placeholder,
+                oninput: move |e| oninput.call(e.value())
+            }
+        }
+    }
+}
+
 /// Status card component for displaying surfpool status
 #[component]
 fn StatusCard(
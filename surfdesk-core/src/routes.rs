@@ -4,11 +4,14 @@
 //! using Dioxus Router for client-side routing.
 
 use dioxus::prelude::*;
-use dioxus_router::prelude::*;
 
 // Import pages
+use crate::pages::accounts::AccountManager;
 use crate::pages::home::Home;
+use crate::pages::pda::PdaPage;
+use crate::pages::program_accounts::ProgramAccountsPage;
 use crate::pages::program_builder::ProgramBuilderPage;
+use crate::pages::surfpool::SurfpoolManager;
 
 #[component]
 pub fn LayoutWrapper() -> Element {
@@ -31,4 +34,20 @@ pub enum Route {
     #[layout(LayoutWrapper)]
     #[route("/program-builder")]
     ProgramBuilderPage {},
+
+    #[layout(LayoutWrapper)]
+    #[route("/pda")]
+    PdaPage {},
+
+    #[layout(LayoutWrapper)]
+    #[route("/accounts")]
+    AccountManager {},
+
+    #[layout(LayoutWrapper)]
+    #[route("/program-accounts")]
+    ProgramAccountsPage {},
+
+    #[layout(LayoutWrapper)]
+    #[route("/surfpool")]
+    SurfpoolManager {},
 }
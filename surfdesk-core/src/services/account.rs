@@ -4,11 +4,20 @@
 //! with Solana accounts. It handles keypair generation, account creation,
 //! balance tracking, and transaction management through the custom RPC service.
 
+use crate::services::solana_rpc::{
+    validate_address, AccountInfo, ConfirmationOutcome, SolanaRpcConfig, SolanaRpcService,
+};
 use crate::services::{AsyncService, Configurable, Service, ServiceError, ServiceResult};
-use crate::services::solana_rpc::{SolanaRpcService, validate_address};
+use crate::utils::crypto::Keypair;
+use crate::utils::keystore::{self, KeystoreFile, KeystoreKey, KeystoreSecrets, KEYSTORE_VERSION};
+use crate::utils::mnemonic::{self, DerivationPath};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Account service shared between pages, e.g. through the Dioxus context
+pub type SharedAccountService = Arc<tokio::sync::Mutex<AccountService>>;
 
 /// Configuration for account service
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,7 +25,12 @@ pub struct AccountServiceConfig {
     /// Default derivation path for HD wallets
     pub derivation_path: String,
     /// Whether to encrypt stored keys
+    ///
+    /// Only decides how a new keystore starts out: a keystore that is
+    /// encrypted on disk stays encrypted once unlocked.
     pub encrypt_keys: bool,
+    /// PBKDF2 iterations used when a new keystore password is set
+    pub kdf_iterations: u32,
     /// Path to account storage
     pub storage_path: PathBuf,
     /// Default SOL amount for airdrops
@@ -28,10 +42,8 @@ impl Default for AccountServiceConfig {
         Self {
             derivation_path: "m/44'/501'/0'/0'".to_string(),
             encrypt_keys: true,
-            storage_path: dirs::home_dir()
-                .unwrap_or_default()
-                .join(".surfdesk")
-                .join("accounts"),
+            kdf_iterations: keystore::DEFAULT_KDF_ITERATIONS,
+            storage_path: crate::services::surfdesk_home().join("accounts"),
             default_airdrop_amount: 1_000_000_000, // 1 SOL
         }
     }
@@ -120,13 +132,31 @@ pub enum TransactionStatus {
     Pending,
     Confirmed,
     Failed,
+    /// The blockhash expired before the transaction landed
+    Expired,
+    /// Confirmation could not be checked, the transaction may still land
+    Unknown,
 }
 
 /// Service for managing Solana accounts
 pub struct AccountService {
     config: AccountServiceConfig,
     accounts: HashMap<String, Account>,
+    keypairs: HashMap<String, Keypair>,
+    transactions: Vec<Transaction>,
     rpc_service: SolanaRpcService,
+    /// Whether the keystore has been read; saving before that would clobber it
+    keystore_loaded: bool,
+    /// Encryption key for the session, present while the keystore is unlocked
+    keystore_key: Option<KeystoreKey>,
+    /// Encrypted secrets read from disk that have not been unlocked yet
+    locked_secrets: Option<KeystoreSecrets>,
+}
+
+impl Default for AccountService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AccountService {
@@ -140,14 +170,29 @@ impl AccountService {
         Self {
             rpc_service: SolanaRpcService::new(),
             accounts: HashMap::new(),
+            keypairs: HashMap::new(),
+            transactions: Vec::new(),
+            keystore_loaded: false,
+            keystore_key: None,
+            locked_secrets: None,
             config,
         }
     }
 
+    /// Point balance queries and transfers at another RPC endpoint
+    pub fn configure_rpc(&mut self, config: SolanaRpcConfig) -> ServiceResult<()> {
+        self.rpc_service.configure(config)
+    }
+
     /// Create a new account
-    pub async fn create_account(&mut self, label: String, account_type: AccountType) -> ServiceResult<Account> {
-        // Generate new keypair (mock implementation)
-        let address = self.generate_mock_address();
+    pub async fn create_account(
+        &mut self,
+        label: String,
+        account_type: AccountType,
+    ) -> ServiceResult<Account> {
+        self.ensure_unlocked()?;
+        let keypair = Keypair::generate();
+        let address = keypair.address();
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
         let account = Account {
@@ -165,16 +210,112 @@ impl AccountService {
         };
 
         self.accounts.insert(address.clone(), account.clone());
+        self.keypairs.insert(address, keypair);
         self.save_accounts().await?;
 
         Ok(account)
     }
 
-    /// Import an existing account from private key
-    pub async fn import_account(&mut self, private_key: String, label: String) -> ServiceResult<Account> {
-        // In a real implementation, this would validate and import the private key
-        // For now, create a mock account
-        let address = self.generate_mock_address();
+    /// Import an existing account from a Base58 private key
+    ///
+    /// Accepts both a bare 32-byte seed and the 64-byte secret key exported
+    /// by Phantom. For 64-byte keys the embedded public key must match.
+    pub async fn import_account(
+        &mut self,
+        private_key: String,
+        label: String,
+    ) -> ServiceResult<Account> {
+        let keypair = Keypair::from_base58_string(&private_key)
+            .map_err(|e| ServiceError::Validation(e.to_string()))?;
+        self.add_imported_keypair(keypair, label).await
+    }
+
+    /// Import an account from the contents of a solana-keygen `id.json` file
+    pub async fn import_keypair_json(
+        &mut self,
+        json: &str,
+        label: String,
+    ) -> ServiceResult<Account> {
+        let keypair =
+            Keypair::from_json(json).map_err(|e| ServiceError::Validation(e.to_string()))?;
+        self.add_imported_keypair(keypair, label).await
+    }
+
+    /// Import an account from a solana-keygen keypair file on disk
+    pub async fn import_keypair_file(
+        &mut self,
+        path: &Path,
+        label: String,
+    ) -> ServiceResult<Account> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            ServiceError::Validation(format!(
+                "Failed to read keypair file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        self.import_keypair_json(&json, label).await
+    }
+
+    /// Export an account secret key as a solana-keygen `id.json` byte array
+    pub fn export_keypair_json(&self, address: &str) -> ServiceResult<String> {
+        Ok(self.keypair_for(address)?.to_json())
+    }
+
+    /// Export an account secret key as a 64-byte Base58 string, as Phantom does
+    pub fn export_private_key(&self, address: &str) -> ServiceResult<String> {
+        Ok(self.keypair_for(address)?.to_base58_string())
+    }
+
+    /// Write an account keypair to a solana-keygen compatible file
+    ///
+    /// On Unix the file gets owner-only permissions, matching
+    /// `solana-keygen new --outfile`, even when it already existed.
+    pub fn export_keypair_file(&self, address: &str, path: &Path) -> ServiceResult<()> {
+        let json = self.export_keypair_json(address)?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let write = |mut file: std::fs::File| {
+            // `mode` only applies when the file is created
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+            }
+            std::io::Write::write_all(&mut file, json.as_bytes())
+        };
+        options.open(path).and_then(write).map_err(|e| {
+            ServiceError::Validation(format!(
+                "Failed to write keypair file {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Look up the signing keypair for an account
+    fn keypair_for(&self, address: &str) -> ServiceResult<&Keypair> {
+        self.ensure_unlocked()?;
+        self.keypairs.get(address).ok_or_else(|| {
+            ServiceError::Validation("No signing key available for account".to_string())
+        })
+    }
+
+    /// Store an imported keypair as a new user account
+    async fn add_imported_keypair(
+        &mut self,
+        keypair: Keypair,
+        label: String,
+    ) -> ServiceResult<Account> {
+        self.ensure_unlocked()?;
+        let address = keypair.address();
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
         let account = Account {
@@ -192,11 +333,80 @@ impl AccountService {
         };
 
         self.accounts.insert(address.clone(), account.clone());
+        self.keypairs.insert(address, keypair);
         self.save_accounts().await?;
 
         Ok(account)
     }
 
+    /// Import `count` accounts derived from a BIP39 seed phrase
+    ///
+    /// Accounts are derived along `config.derivation_path`, advancing the
+    /// BIP44 account level for each index, so the addresses match the ones
+    /// Phantom and solana-keygen show for the same phrase and passphrase.
+    /// Accounts that are already present are returned unchanged.
+    pub async fn import_from_mnemonic(
+        &mut self,
+        phrase: &str,
+        passphrase: &str,
+        count: u32,
+        label: String,
+    ) -> ServiceResult<Vec<Account>> {
+        self.ensure_unlocked()?;
+        if count == 0 {
+            return Err(ServiceError::Validation(
+                "At least one account must be derived".to_string(),
+            ));
+        }
+
+        let base_path: DerivationPath = self
+            .config
+            .derivation_path
+            .parse()
+            .map_err(|e: mnemonic::MnemonicError| ServiceError::Validation(e.to_string()))?;
+        let keypairs = mnemonic::keypairs_from_mnemonic(phrase, passphrase, &base_path, count)
+            .map_err(|e| ServiceError::Validation(e.to_string()))?;
+
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut accounts = Vec::with_capacity(keypairs.len());
+
+        for (index, keypair) in keypairs.into_iter().enumerate() {
+            let address = keypair.address();
+            if let Some(existing) = self.accounts.get(&address) {
+                accounts.push(existing.clone());
+                continue;
+            }
+
+            let path = base_path
+                .for_index(index as u32)
+                .map_err(|e| ServiceError::Validation(e.to_string()))?;
+            let account = Account {
+                address: address.clone(),
+                label: if count == 1 {
+                    label.clone()
+                } else {
+                    format!("{} #{}", label, index + 1)
+                },
+                balance: 0,
+                created_at: now.clone(),
+                updated_at: now.clone(),
+                metadata: AccountMetadata {
+                    is_primary: self.accounts.is_empty(),
+                    account_type: AccountType::User,
+                    notes: Some(format!("Derived from seed phrase at {}", path)),
+                    tags: vec!["imported".to_string(), "mnemonic".to_string()],
+                },
+            };
+
+            self.accounts.insert(address.clone(), account.clone());
+            self.keypairs.insert(address, keypair);
+            accounts.push(account);
+        }
+
+        self.save_accounts().await?;
+        Ok(accounts)
+    }
+
     /// Get account by address
     pub fn get_account(&self, address: &str) -> Option<&Account> {
         self.accounts.get(address)
@@ -225,21 +435,65 @@ impl AccountService {
         }
     }
 
-    /// Update all account balances
+    /// Update all account balances with a single batch request
     pub async fn update_all_balances(&mut self) -> ServiceResult<()> {
         let addresses: Vec<String> = self.accounts.keys().cloned().collect();
+        let address_refs: Vec<&str> = addresses.iter().map(String::as_str).collect();
+
+        let balances = self.rpc_service.get_balances(&address_refs).await?;
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-        for address in addresses {
-            if let Err(e) = self.update_balance(&address).await {
-                tracing::warn!("Failed to update balance for {}: {}", address, e);
+        for (address, balance) in addresses.iter().zip(balances) {
+            match balance {
+                Ok(balance) => {
+                    if let Some(account) = self.accounts.get_mut(address) {
+                        account.balance = balance;
+                        account.updated_at = now.clone();
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to update balance for {}: {}", address, e),
             }
         }
 
         Ok(())
     }
 
+    /// Fetch on-chain information for all accounts with a single batch request
+    ///
+    /// Balances are updated from the fetched data. Accounts that do not exist
+    /// on-chain yet are left out of the result.
+    pub async fn refresh_accounts(&mut self) -> ServiceResult<Vec<AccountInfo>> {
+        let addresses: Vec<String> = self.accounts.keys().cloned().collect();
+        let address_refs: Vec<&str> = addresses.iter().map(String::as_str).collect();
+
+        let infos = self.rpc_service.get_accounts(&address_refs).await?;
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let mut refreshed = Vec::with_capacity(infos.len());
+        for (address, info) in addresses.iter().zip(infos) {
+            match info {
+                Ok(info) => {
+                    if let Some(account) = self.accounts.get_mut(address) {
+                        account.balance = info.balance;
+                        account.updated_at = now.clone();
+                    }
+                    refreshed.push(info);
+                }
+                Err(e) => tracing::warn!("Failed to refresh account {}: {}", address, e),
+            }
+        }
+
+        Ok(refreshed)
+    }
+
     /// Send SOL to another account
-    pub async fn send_sol(&mut self, from: &str, to: &str, lamports: u64, memo: Option<String>) -> ServiceResult<String> {
+    pub async fn send_sol(
+        &mut self,
+        from: &str,
+        to: &str,
+        lamports: u64,
+        memo: Option<String>,
+    ) -> ServiceResult<String> {
         if !validate_address(from) || !validate_address(to) {
             return Err(ServiceError::Validation("Invalid address".to_string()));
         }
@@ -250,21 +504,28 @@ impl AccountService {
                 return Err(ServiceError::Validation("Insufficient balance".to_string()));
             }
         } else {
-            return Err(ServiceError::Validation("From account not found".to_string()));
+            return Err(ServiceError::Validation(
+                "From account not found".to_string(),
+            ));
         }
 
-        // Create and send transaction (mock implementation)
-        let transaction_data = crate::services::solana_rpc::create_transfer_transaction(from, to, lamports)?;
+        self.ensure_unlocked()?;
+        let keypair = self.keypairs.get(from).ok_or_else(|| {
+            ServiceError::Validation("No signing key available for from account".to_string())
+        })?;
+
+        // Create, sign and send transaction
+        let latest_blockhash = self.rpc_service.get_latest_blockhash_with_expiry().await?;
+        let transaction_data = crate::services::solana_rpc::create_signed_transfer_transaction(
+            keypair,
+            to,
+            lamports,
+            &latest_blockhash.blockhash,
+        )?;
         let signature = self.rpc_service.send_transaction(&transaction_data).await?;
 
-        // Update local balance (optimistic)
-        if let Some(account) = self.accounts.get_mut(from) {
-            account.balance = account.balance.saturating_sub(lamports);
-            account.updated_at = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
-        }
-
         // Record transaction
-        let transaction = Transaction {
+        self.transactions.push(Transaction {
             signature: signature.clone(),
             from: from.to_string(),
             to: to.to_string(),
@@ -274,16 +535,71 @@ impl AccountService {
             timestamp: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             fee: 5000, // Mock fee
             memo,
+        });
+
+        let confirmation = self
+            .rpc_service
+            .confirm_transaction(&signature, latest_blockhash.last_valid_block_height)
+            .await;
+        let status = match &confirmation {
+            Ok(ConfirmationOutcome::Confirmed { .. }) => TransactionStatus::Confirmed,
+            Ok(ConfirmationOutcome::Failed { .. }) => TransactionStatus::Failed,
+            Ok(ConfirmationOutcome::Expired { .. }) => TransactionStatus::Expired,
+            Err(_) => TransactionStatus::Unknown,
         };
+        if let Some(transaction) = self
+            .transactions
+            .iter_mut()
+            .rev()
+            .find(|t| t.signature == signature)
+        {
+            transaction.status = status;
+        }
 
-        // In a real implementation, this would be saved to a transaction database
-        tracing::info!("Transaction sent: {} SOL from {} to {}", lamports as f64 / 1_000_000_000.0, from, to);
+        let outcome = confirmation.map_err(|e| {
+            ServiceError::Network(format!(
+                "Could not confirm transaction {}: {}",
+                signature, e
+            ))
+        })?;
+        match outcome {
+            ConfirmationOutcome::Confirmed { slot, .. } => {
+                if let Some(account) = self.accounts.get_mut(from) {
+                    account.balance = account.balance.saturating_sub(lamports);
+                    account.updated_at = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                }
 
-        Ok(signature)
+                tracing::info!(
+                    "Transaction confirmed in slot {}: {} SOL from {} to {}",
+                    slot,
+                    lamports as f64 / 1_000_000_000.0,
+                    from,
+                    to
+                );
+                Ok(signature)
+            }
+            ConfirmationOutcome::Failed { error, .. } => Err(ServiceError::Network(format!(
+                "Transaction {} failed: {}",
+                signature, error
+            ))),
+            ConfirmationOutcome::Expired { .. } => Err(ServiceError::Network(format!(
+                "Transaction {} expired before it was confirmed",
+                signature
+            ))),
+        }
+    }
+
+    /// Transactions sent from this service, oldest first
+    pub fn get_transactions(&self) -> &[Transaction] {
+        &self.transactions
     }
 
     /// Request airdrop for an account
-    pub async fn request_airdrop(&mut self, address: &str, lamports: Option<u64>) -> ServiceResult<String> {
+    pub async fn request_airdrop(
+        &mut self,
+        address: &str,
+        lamports: Option<u64>,
+    ) -> ServiceResult<String> {
         if !validate_address(address) {
             return Err(ServiceError::Validation("Invalid address".to_string()));
         }
@@ -297,9 +613,12 @@ impl AccountService {
 
     /// Delete an account
     pub async fn delete_account(&mut self, address: &str) -> ServiceResult<()> {
+        // Deleting must also drop the secret key, which needs the keystore unlocked
+        self.ensure_unlocked()?;
         if self.accounts.remove(address).is_none() {
             return Err(ServiceError::Validation("Account not found".to_string()));
         }
+        self.keypairs.remove(address);
 
         self.save_accounts().await?;
         Ok(())
@@ -340,36 +659,240 @@ impl AccountService {
         self.accounts.values().find(|acc| acc.metadata.is_primary)
     }
 
+    /// Path of the keystore file inside `storage_path`
+    pub fn keystore_path(&self) -> PathBuf {
+        self.config.storage_path.join(keystore::KEYSTORE_FILE_NAME)
+    }
+
+    /// Whether the keystore has been read from `storage_path`
+    pub fn is_loaded(&self) -> bool {
+        self.keystore_loaded
+    }
+
+    /// Whether a keystore file exists, i.e. unlocking checks an existing password
+    pub fn keystore_exists(&self) -> bool {
+        self.keystore_path().exists()
+    }
+
+    /// Whether secret keys are unavailable until `unlock` is called
+    ///
+    /// Account metadata stays readable while locked; only operations that
+    /// need a secret key (signing, exporting, creating, deleting) are refused.
+    pub fn is_locked(&self) -> bool {
+        self.keystore_loaded
+            && self.keystore_key.is_none()
+            && (self.locked_secrets.is_some() || self.config.encrypt_keys)
+    }
+
+    /// Unlock the keystore for this session
+    ///
+    /// Decrypts the stored secrets with `password`. If no encrypted keystore
+    /// exists yet, `password` becomes the password of a new one.
+    pub async fn unlock(&mut self, password: &str) -> ServiceResult<()> {
+        if !self.keystore_loaded {
+            self.load_accounts().await?;
+        }
+
+        if let Some(key) = &self.keystore_key {
+            return if key.verify(password) {
+                Ok(())
+            } else {
+                Err(keystore_error(keystore::KeystoreError::IncorrectPassword))
+            };
+        }
+
+        match &self.locked_secrets {
+            Some(secrets) => {
+                let kdf = secrets.kdf().ok_or_else(|| {
+                    ServiceError::Storage("Locked keystore has no KDF parameters".to_string())
+                })?;
+                let key = KeystoreKey::derive(password, kdf).map_err(keystore_error)?;
+                let keys = key.decrypt(secrets).map_err(keystore_error)?;
+                // Accounts created before the keystore was read still need saving
+                let unsaved = self
+                    .keypairs
+                    .keys()
+                    .any(|address| !keys.contains_key(address));
+                self.restore_keypairs(keys)?;
+                self.locked_secrets = None;
+                self.keystore_key = Some(key);
+                if unsaved {
+                    self.save_accounts().await?;
+                }
+            }
+            None => {
+                self.keystore_key = Some(KeystoreKey::new(password, self.config.kdf_iterations));
+                // Persist right away so the new password protects any existing keys
+                self.save_accounts().await?;
+            }
+        }
+
+        tracing::info!("Keystore unlocked");
+        Ok(())
+    }
+
+    /// Lock the keystore, dropping secret keys from memory
+    pub async fn lock(&mut self) -> ServiceResult<()> {
+        if self.keystore_key.is_none() {
+            return Ok(());
+        }
+
+        let file = self.keystore_file()?;
+        keystore::write_keystore(&self.keystore_path(), &file).map_err(keystore_error)?;
+        if file.secrets.is_encrypted() {
+            self.keypairs.clear();
+            self.locked_secrets = Some(file.secrets);
+        }
+        self.keystore_key = None;
+
+        tracing::info!("Keystore locked");
+        Ok(())
+    }
+
+    /// Refuse operations that need secret keys while the keystore is locked
+    fn ensure_unlocked(&self) -> ServiceResult<()> {
+        if self.is_locked() {
+            return Err(ServiceError::Validation(
+                "Keystore is locked, unlock it with the account password first".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Base58 secret keys of all accounts with a keypair, keyed by address
+    fn secret_keys(&self) -> BTreeMap<String, String> {
+        self.keypairs
+            .iter()
+            .map(|(address, keypair)| (address.clone(), keypair.to_base58_string()))
+            .collect()
+    }
+
+    /// Rebuild keypairs from stored secret keys, checking each against its address
+    fn restore_keypairs(&mut self, keys: BTreeMap<String, String>) -> ServiceResult<()> {
+        for (address, secret) in keys {
+            let keypair = Keypair::from_base58_string(&secret).map_err(|e| {
+                ServiceError::Storage(format!("Invalid secret key for {}: {}", address, e))
+            })?;
+            if keypair.address() != address {
+                return Err(ServiceError::Storage(format!(
+                    "Secret key stored for {} belongs to {}",
+                    address,
+                    keypair.address()
+                )));
+            }
+            self.keypairs.insert(address, keypair);
+        }
+        Ok(())
+    }
+
+    /// Build the keystore contents for the current state
+    fn keystore_file(&self) -> ServiceResult<KeystoreFile<Account>> {
+        let secrets = match (&self.keystore_key, &self.locked_secrets) {
+            // Still locked: keep the encrypted secrets exactly as they were read
+            (None, Some(secrets)) => secrets.clone(),
+            // A password is set, so never write the secrets back in the clear
+            (Some(key), _) => key.encrypt(&self.secret_keys()).map_err(keystore_error)?,
+            _ => {
+                if self.config.encrypt_keys && !self.keypairs.is_empty() {
+                    tracing::warn!(
+                        "Secret keys are stored unencrypted until a keystore password is set"
+                    );
+                }
+                KeystoreSecrets::Plaintext {
+                    keys: self.secret_keys(),
+                }
+            }
+        };
+
+        let mut accounts: Vec<Account> = self.accounts.values().cloned().collect();
+        accounts.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.address.cmp(&b.address))
+        });
+
+        Ok(KeystoreFile {
+            version: KEYSTORE_VERSION,
+            accounts,
+            secrets,
+        })
+    }
+
     /// Save accounts to storage
     async fn save_accounts(&self) -> ServiceResult<()> {
-        // In a real implementation, this would encrypt and save accounts to disk
-        // For now, just log the action
-        tracing::debug!("Saving {} accounts to storage", self.accounts.len());
+        // Writing before the existing keystore was read would overwrite it
+        if !self.keystore_loaded {
+            tracing::debug!(
+                "Keystore not loaded yet, keeping {} accounts in memory",
+                self.accounts.len()
+            );
+            return Ok(());
+        }
+
+        let file = self.keystore_file()?;
+        keystore::write_keystore(&self.keystore_path(), &file).map_err(keystore_error)?;
+        tracing::debug!(
+            "Saved {} accounts to {}",
+            file.accounts.len(),
+            self.keystore_path().display()
+        );
         Ok(())
     }
 
     /// Load accounts from storage
+    ///
+    /// Metadata is always loaded. Plaintext secret keys are restored directly,
+    /// encrypted ones are kept until `unlock` is called. Accounts created or
+    /// imported before loading are kept and saved along with the stored ones.
     async fn load_accounts(&mut self) -> ServiceResult<()> {
-        // In a real implementation, this would load and decrypt accounts from disk
-        // For now, just log the action
-        tracing::debug!("Loading accounts from storage");
-        Ok(())
-    }
+        let path = self.keystore_path();
+        let unsaved = std::mem::take(&mut self.accounts);
+        if let Some(file) = keystore::read_keystore::<Account>(&path).map_err(keystore_error)? {
+            self.accounts = file
+                .accounts
+                .into_iter()
+                .map(|account| (account.address.clone(), account))
+                .collect();
+            self.keystore_key = None;
+            self.locked_secrets = None;
+
+            match file.secrets {
+                KeystoreSecrets::Plaintext { keys } => self.restore_keypairs(keys)?,
+                encrypted => self.locked_secrets = Some(encrypted),
+            }
+            tracing::info!(
+                "Loaded {} accounts from {}",
+                self.accounts.len(),
+                path.display()
+            );
+        }
 
-    /// Generate mock address for demonstration
-    fn generate_mock_address(&self) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+        let has_primary = self
+            .accounts
+            .values()
+            .any(|account| account.metadata.is_primary);
+        let has_unsaved = !unsaved.is_empty();
+        for (address, mut account) in unsaved {
+            if has_primary {
+                account.metadata.is_primary = false;
+            }
+            self.accounts.entry(address).or_insert(account);
+        }
 
-        let mut hasher = DefaultHasher::new();
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-            .hash(&mut hasher);
+        self.keystore_loaded = true;
+        // A locked keystore saves them once `unlock` restores the stored keys
+        if has_unsaved && !self.is_locked() {
+            self.save_accounts().await?;
+        }
+        Ok(())
+    }
+}
 
-        let hash = hasher.finish();
-        format!("{:x}", hash)[..44].to_string()
+/// Map keystore failures to service errors, keeping wrong passwords user-facing
+fn keystore_error(error: keystore::KeystoreError) -> ServiceError {
+    match error {
+        keystore::KeystoreError::IncorrectPassword => ServiceError::Validation(error.to_string()),
+        other => ServiceError::Storage(other.to_string()),
     }
 }
 
@@ -380,12 +903,12 @@ impl Service for AccountService {
     }
 
     fn health_check(&self) -> ServiceResult<bool> {
-        Ok(!self.accounts.is_empty() || true) // Service is healthy even with no accounts
+        // Service is healthy even with no accounts
+        Ok(true)
     }
 
     fn shutdown(&mut self) -> ServiceResult<()> {
-        // Save accounts before shutdown
-        let _ = self.save_accounts();
+        // Saving needs I/O, so it happens in `shutdown_async`
         tracing::info!("Account service shutdown");
         Ok(())
     }
@@ -415,6 +938,23 @@ impl Configurable for AccountService {
     type Config = AccountServiceConfig;
 
     fn configure(&mut self, config: Self::Config) -> ServiceResult<()> {
+        config
+            .derivation_path
+            .parse::<DerivationPath>()
+            .map_err(|e| ServiceError::Validation(e.to_string()))?;
+        if config.kdf_iterations == 0 {
+            return Err(ServiceError::Validation(
+                "KDF iterations must be positive".to_string(),
+            ));
+        }
+
+        // Moving a loaded keystore would silently start a new, empty one
+        if self.keystore_loaded && config.storage_path != self.config.storage_path {
+            return Err(ServiceError::Validation(
+                "Storage path cannot change after accounts are loaded".to_string(),
+            ));
+        }
+
         self.config = config;
         tracing::info!("Account service reconfigured");
         Ok(())
@@ -435,15 +975,569 @@ mod tests {
         assert!(service.get_all_accounts().is_empty());
     }
 
-    #[test]
-    fn test_create_account() {
+    #[tokio::test]
+    async fn test_create_account() {
         let mut service = AccountService::new();
 
-        // This would need to be run in an async context in real tests
-        // For now, just test the mock address generation
-        let address = service.generate_mock_address();
-        assert!(!address.is_empty());
-        assert!(address.len() == 44); // Base58 addresses are typically 44 chars
+        let account = service
+            .create_account("Main".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        assert_eq!(bs58::decode(&account.address).into_vec().unwrap().len(), 32);
+        assert!(account.metadata.is_primary);
+
+        // The stored keypair controls the generated address
+        let keypair = service.keypairs.get(&account.address).unwrap();
+        assert_eq!(keypair.address(), account.address);
+    }
+
+    #[tokio::test]
+    async fn test_import_account() {
+        let mut service = AccountService::new();
+        let private_key = bs58::encode([1u8; 32]).into_string();
+
+        let account = service
+            .import_account(private_key, "Imported".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9"
+        );
+        assert!(account.metadata.tags.contains(&"imported".to_string()));
+
+        assert!(service
+            .import_account("not-base58!".to_string(), "Bad".to_string())
+            .await
+            .is_err());
+        assert!(service
+            .import_account(bs58::encode([1u8; 16]).into_string(), "Short".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_from_mnemonic() {
+        let mut service = AccountService::new();
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let accounts = service
+            .import_from_mnemonic(phrase, "", 3, "Wallet".to_string())
+            .await
+            .unwrap();
+        let addresses: Vec<&str> = accounts.iter().map(|a| a.address.as_str()).collect();
+        assert_eq!(
+            addresses,
+            vec![
+                "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk",
+                "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb",
+                "7WktogJEd2wQ9eH2oWusmcoFTgeYi6rS632UviTBJ2jm",
+            ]
+        );
+        assert_eq!(accounts[1].label, "Wallet #2");
+        assert!(accounts[0].metadata.is_primary);
+        assert!(!accounts[2].metadata.is_primary);
+        assert!(service.keypairs.contains_key(addresses[2]));
+
+        // Re-importing keeps the existing accounts
+        service
+            .import_from_mnemonic(phrase, "", 2, "Again".to_string())
+            .await
+            .unwrap();
+        assert_eq!(service.get_all_accounts().len(), 3);
+
+        let bad = phrase.replace("about", "abandon");
+        assert!(service
+            .import_from_mnemonic(&bad, "", 1, "Bad".to_string())
+            .await
+            .is_err());
+        assert!(service
+            .import_from_mnemonic(phrase, "", 0, "None".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_phantom_private_key() {
+        let mut service = AccountService::new();
+        let phantom = "2AXDGYSE4f2sz7tvMMzyHvUfcoJmxudvdhBcmiUSo6iuCXagjUCKEQF21awZnUGxmwD4m9vGXuC3qieHXJQHAcT";
+
+        let account = service
+            .import_account(phantom.to_string(), "Phantom".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9"
+        );
+        assert_eq!(
+            service.export_private_key(&account.address).unwrap(),
+            phantom
+        );
+
+        // Public key half that does not belong to the seed
+        let mut bytes = bs58::decode(phantom).into_vec().unwrap();
+        bytes[40] ^= 1;
+        let tampered = bs58::encode(bytes).into_string();
+        assert!(service
+            .import_account(tampered, "Tampered".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keypair_file_round_trip() {
+        let mut service = AccountService::new();
+        let account = service
+            .create_account("Main".to_string(), AccountType::User)
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("id.json");
+        service
+            .export_keypair_file(&account.address, &path)
+            .unwrap();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        let bytes: Vec<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(bytes.len(), 64);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+        }
+
+        let mut other = AccountService::new();
+        let imported = other
+            .import_keypair_file(&path, "Copy".to_string())
+            .await
+            .unwrap();
+        assert_eq!(imported.address, account.address);
+        assert_eq!(other.export_keypair_json(&imported.address).unwrap(), json);
+
+        assert!(other
+            .import_keypair_json("[1,2,3]", "Bad".to_string())
+            .await
+            .is_err());
+        assert!(other.export_keypair_json("unknown").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_keypair_file_overwrite_tightens_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut service = AccountService::new();
+        let account = service
+            .create_account("Main".to_string(), AccountType::User)
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("id.json");
+        std::fs::write(&path, "[]").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        service
+            .export_keypair_file(&account.address, &path)
+            .unwrap();
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+    }
+
+    fn keystore_config(dir: &Path, encrypt_keys: bool) -> AccountServiceConfig {
+        AccountServiceConfig {
+            encrypt_keys,
+            // Keep password derivation fast in tests
+            kdf_iterations: 1_000,
+            storage_path: dir.to_path_buf(),
+            ..AccountServiceConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_keystore_lock_unlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut service = AccountService::with_config(keystore_config(dir.path(), true));
+        service.initialize_async().await.unwrap();
+
+        // A fresh keystore is locked until a password is chosen
+        assert!(service.is_locked());
+        assert!(service
+            .create_account("Main".to_string(), AccountType::User)
+            .await
+            .is_err());
+        service.unlock("hunter2").await.unwrap();
+        assert!(service.keystore_exists());
+
+        let account = service
+            .create_account("Main".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        service
+            .accounts
+            .get_mut(&account.address)
+            .unwrap()
+            .metadata
+            .tags
+            .push("savings".to_string());
+        service.save_accounts().await.unwrap();
+        let secret = service.export_private_key(&account.address).unwrap();
+        service.lock().await.unwrap();
+        assert!(service.is_locked());
+        assert!(service.export_private_key(&account.address).is_err());
+
+        // Secrets never hit the disk in the clear, metadata does
+        let contents = std::fs::read_to_string(service.keystore_path()).unwrap();
+        assert!(!contents.contains(&secret));
+        assert!(contents.contains("savings"));
+
+        // A new session sees the metadata but needs the password for keys
+        let mut reopened = AccountService::with_config(keystore_config(dir.path(), true));
+        reopened.initialize_async().await.unwrap();
+        let loaded = reopened.get_account(&account.address).unwrap();
+        assert_eq!(loaded.label, "Main");
+        assert!(loaded.metadata.is_primary);
+        assert_eq!(loaded.metadata.tags, vec!["savings".to_string()]);
+        assert!(reopened.is_locked());
+
+        // Metadata edits while locked keep the encrypted secrets intact
+        reopened
+            .update_label(&account.address, "Renamed".to_string())
+            .await
+            .unwrap();
+
+        assert!(reopened.unlock("wrong").await.is_err());
+        assert!(reopened.is_locked());
+        reopened.unlock("hunter2").await.unwrap();
+        assert_eq!(
+            reopened.export_private_key(&account.address).unwrap(),
+            secret
+        );
+        assert_eq!(
+            reopened.get_account(&account.address).unwrap().label,
+            "Renamed"
+        );
+
+        // Unlocking again checks the session password instead of replacing it
+        assert!(reopened.unlock("other").await.is_err());
+        reopened.unlock("hunter2").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut service = AccountService::with_config(keystore_config(dir.path(), false));
+        service.initialize_async().await.unwrap();
+        assert!(!service.is_locked());
+
+        let account = service
+            .create_account("Dev".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        let secret = service.export_private_key(&account.address).unwrap();
+        assert!(std::fs::read_to_string(service.keystore_path())
+            .unwrap()
+            .contains(&secret));
+
+        let mut reopened = AccountService::with_config(keystore_config(dir.path(), false));
+        reopened.initialize_async().await.unwrap();
+        assert!(!reopened.is_locked());
+        assert_eq!(
+            reopened.export_private_key(&account.address).unwrap(),
+            secret
+        );
+
+        // Turning encryption on and setting a password migrates the keys
+        let mut encrypted = AccountService::with_config(keystore_config(dir.path(), true));
+        encrypted.initialize_async().await.unwrap();
+        encrypted.unlock("hunter2").await.unwrap();
+        assert!(!std::fs::read_to_string(encrypted.keystore_path())
+            .unwrap()
+            .contains(&secret));
+        assert_eq!(
+            encrypted.export_private_key(&account.address).unwrap(),
+            secret
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accounts_stay_in_memory_until_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut service = AccountService::with_config(keystore_config(dir.path(), true));
+
+        service
+            .create_account("Scratch".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        assert!(!service.keystore_exists());
+    }
+
+    #[tokio::test]
+    async fn test_create_then_load_keeps_secret_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut first = AccountService::with_config(keystore_config(dir.path(), false));
+        first.initialize_async().await.unwrap();
+        let stored = first
+            .create_account("Stored".to_string(), AccountType::User)
+            .await
+            .unwrap();
+
+        let mut service = AccountService::with_config(keystore_config(dir.path(), false));
+        let early = service
+            .create_account("Early".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        let secret = service.export_private_key(&early.address).unwrap();
+        service.initialize_async().await.unwrap();
+
+        assert_eq!(service.export_private_key(&early.address).unwrap(), secret);
+        assert!(service.export_private_key(&stored.address).is_ok());
+        assert_eq!(
+            service.get_primary_account().unwrap().address,
+            stored.address
+        );
+
+        // The early account was written to the keystore while loading
+        let mut reopened = AccountService::with_config(keystore_config(dir.path(), false));
+        reopened.initialize_async().await.unwrap();
+        assert_eq!(reopened.export_private_key(&early.address).unwrap(), secret);
+    }
+
+    #[tokio::test]
+    async fn test_create_then_load_encrypted_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut first = AccountService::with_config(keystore_config(dir.path(), true));
+        first.initialize_async().await.unwrap();
+        first.unlock("hunter2").await.unwrap();
+        first
+            .create_account("Stored".to_string(), AccountType::User)
+            .await
+            .unwrap();
+
+        let mut service = AccountService::with_config(keystore_config(dir.path(), true));
+        let early = service
+            .create_account("Early".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        let secret = service.export_private_key(&early.address).unwrap();
+        service.initialize_async().await.unwrap();
+        assert!(service.is_locked());
+        service.unlock("hunter2").await.unwrap();
+        assert_eq!(service.export_private_key(&early.address).unwrap(), secret);
+
+        let mut reopened = AccountService::with_config(keystore_config(dir.path(), true));
+        reopened.initialize_async().await.unwrap();
+        reopened.unlock("hunter2").await.unwrap();
+        assert_eq!(reopened.export_private_key(&early.address).unwrap(), secret);
+    }
+
+    #[tokio::test]
+    async fn test_unlocked_keystore_is_not_downgraded() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut first = AccountService::with_config(keystore_config(dir.path(), true));
+        first.initialize_async().await.unwrap();
+        first.unlock("hunter2").await.unwrap();
+        let account = first
+            .create_account("Main".to_string(), AccountType::User)
+            .await
+            .unwrap();
+        let secret = first.export_private_key(&account.address).unwrap();
+
+        // Turning encryption off does not write an encrypted keystore back in the clear
+        let mut service = AccountService::with_config(keystore_config(dir.path(), false));
+        service.initialize_async().await.unwrap();
+        assert!(service.is_locked());
+        service.unlock("hunter2").await.unwrap();
+        service
+            .update_label(&account.address, "Renamed".to_string())
+            .await
+            .unwrap();
+        let contents = std::fs::read_to_string(service.keystore_path()).unwrap();
+        assert!(!contents.contains(&secret));
+        assert!(contents.contains("Renamed"));
+    }
+
+    #[tokio::test]
+    async fn test_send_sol_tracks_confirmation() {
+        use crate::services::solana_rpc::{mock_rpc, SolanaRpcConfig};
+        use serde_json::json;
+
+        let rpc_url = mock_rpc::spawn(vec![
+            (
+                "getLatestBlockhash",
+                json!({"result": {"context": {"slot": 1}, "value": {
+                    "blockhash": "US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx",
+                    "lastValidBlockHeight": 150
+                }}}),
+            ),
+            ("sendTransaction", json!({"result": "sig1"})),
+            ("getBlockHeight", json!({"result": 100})),
+            (
+                "getSignatureStatuses",
+                json!({"result": {"context": {"slot": 1}, "value": [null]}}),
+            ),
+            (
+                "getSignatureStatuses",
+                json!({"result": {"context": {"slot": 5}, "value": [{
+                    "slot": 5, "confirmations": 0, "err": null, "confirmationStatus": "confirmed"
+                }]}}),
+            ),
+        ]);
+
+        let mut service = AccountService::new();
+        service.rpc_service = SolanaRpcService::with_config(SolanaRpcConfig {
+            rpc_url,
+            confirm_poll_interval_ms: 1,
+            ..SolanaRpcConfig::default()
+        });
+
+        let account = service
+            .import_account(bs58::encode([1u8; 32]).into_string(), "Payer".to_string())
+            .await
+            .unwrap();
+        service.accounts.get_mut(&account.address).unwrap().balance = 2_000_000_000;
+
+        let signature = service
+            .send_sol(
+                &account.address,
+                "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu",
+                500_000_000,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(signature, "sig1");
+        assert_eq!(
+            service.get_transactions()[0].status,
+            TransactionStatus::Confirmed
+        );
+        assert_eq!(
+            service.get_account(&account.address).unwrap().balance,
+            1_500_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_sol_unknown_when_polling_fails() {
+        use crate::services::solana_rpc::{mock_rpc, SolanaRpcConfig};
+        use serde_json::json;
+
+        let rpc_url = mock_rpc::spawn(vec![
+            (
+                "getLatestBlockhash",
+                json!({"result": {"context": {"slot": 1}, "value": {
+                    "blockhash": "US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx",
+                    "lastValidBlockHeight": 150
+                }}}),
+            ),
+            ("sendTransaction", json!({"result": "sig1"})),
+            ("getBlockHeight", json!({"result": 100})),
+            (
+                "getSignatureStatuses",
+                json!({"error": {"code": -32603, "message": "Internal error"}}),
+            ),
+        ]);
+
+        let mut service = AccountService::new();
+        service.rpc_service = SolanaRpcService::with_config(SolanaRpcConfig {
+            rpc_url,
+            confirm_poll_interval_ms: 1,
+            ..SolanaRpcConfig::default()
+        });
+
+        let account = service
+            .import_account(bs58::encode([1u8; 32]).into_string(), "Payer".to_string())
+            .await
+            .unwrap();
+        service.accounts.get_mut(&account.address).unwrap().balance = 2_000_000_000;
+
+        let result = service
+            .send_sol(
+                &account.address,
+                "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu",
+                500_000_000,
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(ServiceError::Network(_))));
+        assert_eq!(
+            service.get_transactions()[0].status,
+            TransactionStatus::Unknown
+        );
+        assert_eq!(
+            service.get_account(&account.address).unwrap().balance,
+            2_000_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_configure_validates_and_pins_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut service = AccountService::with_config(keystore_config(dir.path(), false));
+
+        let bad_path = AccountServiceConfig {
+            derivation_path: "m/44'/501'/0".to_string(),
+            ..service.get_config().clone()
+        };
+        assert!(matches!(
+            service.configure(bad_path),
+            Err(ServiceError::Validation(_))
+        ));
+
+        service.initialize_async().await.unwrap();
+        let moved = AccountServiceConfig {
+            storage_path: dir.path().join("elsewhere"),
+            ..service.get_config().clone()
+        };
+        assert!(matches!(
+            service.configure(moved),
+            Err(ServiceError::Validation(_))
+        ));
+
+        let updated = AccountServiceConfig {
+            default_airdrop_amount: 2_000_000_000,
+            ..service.get_config().clone()
+        };
+        service.configure(updated).unwrap();
+        assert_eq!(service.get_config().default_airdrop_amount, 2_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_update_all_balances_batched() {
+        use crate::services::solana_rpc::{mock_rpc, SolanaRpcConfig};
+        use serde_json::json;
+
+        let mut service = AccountService::new();
+        service.rpc_service = SolanaRpcService::with_config(SolanaRpcConfig {
+            rpc_url: mock_rpc::spawn(vec![(
+                "getBalance",
+                json!({"result": {"context": {"slot": 1}, "value": 750_000_000}}),
+            )]),
+            ..SolanaRpcConfig::default()
+        });
+
+        for seed in 1..=3u8 {
+            service
+                .import_account(
+                    bs58::encode([seed; 32]).into_string(),
+                    format!("Account {}", seed),
+                )
+                .await
+                .unwrap();
+        }
+
+        service.update_all_balances().await.unwrap();
+        assert!(service
+            .get_all_accounts()
+            .iter()
+            .all(|account| account.balance == 750_000_000));
     }
 
     #[test]
@@ -1 +1,108 @@
+//! Services module for Surfdesk
+//!
+//! This module contains the backend services (Solana RPC, accounts, programs,
+//! Surfpool) and the trait layer they share: a common error type and the
+//! lifecycle contract every service implements.
+//!
+//! Lifecycle: a service is created with its configuration, `initialize`d once
+//! before use, may be `configure`d again at any time, reports its health on
+//! demand and is `shutdown` before being dropped. Async services additionally
+//! provide `*_async` variants for steps that need the network or disk.
+
+use std::path::PathBuf;
+
+pub mod account;
+pub mod program;
+pub mod pubsub;
+pub mod rpc_error;
+pub mod solana_rpc;
 pub mod surfpool;
+pub mod surfpool_log;
+pub mod surfpool_supervisor;
+
+use rpc_error::RpcError;
+
+/// Errors returned by services
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceError {
+    /// The remote endpoint could not be reached or returned unusable data
+    #[error("Network error: {0}")]
+    Network(String),
+    /// The HTTP request itself failed
+    #[error("HTTP error: {0}")]
+    Http(String),
+    /// The JSON-RPC endpoint returned an error object
+    #[error("{0}")]
+    Rpc(#[from] RpcError),
+    /// A value could not be serialized or deserialized
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    /// Invalid input from the caller
+    #[error("Validation error: {0}")]
+    Validation(String),
+    /// Reading or writing local storage failed
+    #[error("Storage error: {0}")]
+    Storage(String),
+}
+
+/// Result type returned by services
+pub type ServiceResult<T> = Result<T, ServiceError>;
+
+/// Synchronous lifecycle shared by all services
+pub trait Service {
+    /// Prepare the service for use
+    ///
+    /// Called once before the service is used. Calling it again must be harmless.
+    fn initialize(&mut self) -> ServiceResult<()>;
+
+    /// Cheap local health check that must not block on I/O
+    fn health_check(&self) -> ServiceResult<bool>;
+
+    /// Release resources held by the service
+    ///
+    /// Calling it on a service that was never initialized must be harmless.
+    fn shutdown(&mut self) -> ServiceResult<()>;
+}
+
+/// Lifecycle steps that need the network or disk
+#[async_trait::async_trait]
+pub trait AsyncService: Service {
+    /// Initialize and load or connect whatever the service depends on
+    async fn initialize_async(&mut self) -> ServiceResult<()>;
+
+    /// Health check that may contact remote endpoints
+    ///
+    /// Returns `Ok(false)` when the service is reachable but unhealthy and
+    /// reserves `Err` for failures of the check itself.
+    async fn health_check_async(&self) -> ServiceResult<bool>;
+
+    /// Persist state and shut down
+    async fn shutdown_async(&mut self) -> ServiceResult<()>;
+}
+
+/// Services whose configuration can be replaced at runtime
+pub trait Configurable {
+    /// Configuration type
+    type Config: Clone;
+
+    /// Replace the configuration, rebuilding anything that depends on it
+    ///
+    /// Implementations validate the new configuration and leave the old one
+    /// in place when it is rejected.
+    fn configure(&mut self, config: Self::Config) -> ServiceResult<()>;
+
+    /// Current configuration
+    fn get_config(&self) -> &Self::Config;
+}
+
+/// Base directory for Surfdesk data (`~/.surfdesk`)
+///
+/// Falls back to a relative `.surfdesk` directory when no home directory is
+/// known, e.g. in the browser.
+pub fn surfdesk_home() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(".surfdesk")
+}
@@ -4,12 +4,17 @@
 //! Solana programs. It handles program compilation, deployment to the network,
 //! and interaction with deployed programs through the custom RPC service.
 
+use crate::components::program_builder::{Instruction, InstructionAccount, ProgramSchema};
+use crate::services::solana_rpc::SolanaRpcService;
 use crate::services::{AsyncService, Configurable, Service, ServiceError, ServiceResult};
-use crate::services::solana_rpc::{SolanaRpcService, validate_address};
+use crate::utils::client_codegen::client_modules;
+use crate::utils::codegen::{native_program, CodegenError};
+use crate::utils::format::to_snake_case;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 /// Configuration for program service
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,12 +32,9 @@ pub struct ProgramServiceConfig {
 impl Default for ProgramServiceConfig {
     fn default() -> Self {
         Self {
-            deployment_path: dirs::home_dir()
-                .unwrap_or_default()
-                .join(".surfdesk")
-                .join("programs"),
+            deployment_path: crate::services::surfdesk_home().join("programs"),
             optimize_on_deploy: true,
-            max_program_size: 128 * 1024, // 128KB default limit
+            max_program_size: 128 * 1024,          // 128KB default limit
             default_rent_exemption: 1_000_000_000, // 1 SOL
         }
     }
@@ -116,6 +118,21 @@ pub enum ProgramCategory {
     Other,
 }
 
+impl fmt::Display for ProgramCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::DeFi => "DeFi",
+            Self::NFT => "NFT",
+            Self::Gaming => "Gaming",
+            Self::Social => "Social",
+            Self::Infrastructure => "Infrastructure",
+            Self::Utility => "Utility",
+            Self::Other => "Other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Program template for no-code creation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgramTemplate {
@@ -129,8 +146,6 @@ pub struct ProgramTemplate {
     pub category: ProgramCategory,
     /// Template components
     pub components: Vec<TemplateComponent>,
-    /// Default configuration
-    pub default_config: HashMap<String, serde_json::Value>,
 }
 
 /// Template component for visual program building
@@ -142,8 +157,6 @@ pub struct TemplateComponent {
     pub name: String,
     /// Component type
     pub component_type: ComponentType,
-    /// Component configuration
-    pub config: HashMap<String, serde_json::Value>,
     /// Position in canvas
     pub position: (i32, i32),
     /// Connections to other components
@@ -181,6 +194,12 @@ pub struct ProgramService {
     rpc_service: SolanaRpcService,
 }
 
+impl Default for ProgramService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ProgramService {
     /// Create a new program service with default configuration
     pub fn new() -> Self {
@@ -207,16 +226,16 @@ impl ProgramService {
         template_id: &str,
         name: String,
         description: String,
-        config_overrides: Option<HashMap<String, serde_json::Value>>,
     ) -> ServiceResult<Program> {
-        let template = self.templates.get(template_id)
-            .ok_or_else(|| ServiceError::Validation(format!("Template not found: {}", template_id)))?;
+        let template = self.templates.get(template_id).ok_or_else(|| {
+            ServiceError::Validation(format!("Template not found: {}", template_id))
+        })?;
 
         let program_id = self.generate_program_id(&name);
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
         // Generate program code from template
-        let source_code = self.generate_code_from_template(template, config_overrides.as_ref())?;
+        let source_code = generate_source(&name, &template.components)?;
 
         // Compile source code to binary (mock implementation)
         let binary_data = self.compile_source_code(&source_code)?;
@@ -258,7 +277,7 @@ impl ProgramService {
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
         // Generate program code from components
-        let source_code = self.generate_code_from_components(&components)?;
+        let source_code = generate_source(&name, &components)?;
 
         // Compile source code to binary
         let binary_data = self.compile_source_code(&source_code)?;
@@ -291,18 +310,23 @@ impl ProgramService {
 
     /// Deploy a program to the network
     pub async fn deploy_program(&mut self, program_id: &str) -> ServiceResult<ProgramDeployment> {
-        let program = self.programs.get_mut(program_id)
-            .ok_or_else(|| ServiceError::Validation(format!("Program not found: {}", program_id)))?;
+        let program = self.programs.get_mut(program_id).ok_or_else(|| {
+            ServiceError::Validation(format!("Program not found: {}", program_id))
+        })?;
 
         if program.binary_data.len() > self.config.max_program_size {
-            return Err(ServiceError::Validation(
-                format!("Program size exceeds maximum limit: {} > {} bytes",
-                       program.binary_data.len(), self.config.max_program_size)
-            ));
+            return Err(ServiceError::Validation(format!(
+                "Program size exceeds maximum limit: {} > {} bytes",
+                program.binary_data.len(),
+                self.config.max_program_size
+            )));
         }
 
         // Deploy program via RPC service
-        let deployment_info = self.rpc_service.deploy_program(&program.binary_data).await?;
+        let deployment_info = self
+            .rpc_service
+            .deploy_program(&program.binary_data)
+            .await?;
 
         let deployment = ProgramDeployment {
             signature: deployment_info.signature.clone(),
@@ -320,6 +344,34 @@ impl ProgramService {
         Ok(deployment)
     }
 
+    /// Generate a native program cargo project from a builder schema
+    ///
+    /// The project is written to `<deployment_path>/<crate name>`, which is
+    /// returned.
+    pub fn generate_native_project(&self, schema: &ProgramSchema) -> ServiceResult<PathBuf> {
+        let project = native_program(schema).map_err(codegen_error)?;
+        let dir = self.config.deployment_path.join(&project.name);
+        project.write_to(&dir).map_err(codegen_error)?;
+        Ok(dir)
+    }
+
+    /// Write the Rust and TypeScript clients of a builder schema
+    ///
+    /// Files go to `dir`, or `<deployment_path>/<crate name>_client` when no
+    /// directory is given. Returns the written paths.
+    pub fn generate_clients(
+        &self,
+        schema: &ProgramSchema,
+        dir: Option<&Path>,
+    ) -> ServiceResult<Vec<PathBuf>> {
+        let clients = client_modules(schema).map_err(codegen_error)?;
+        let dir = match dir {
+            Some(dir) => dir.to_path_buf(),
+            None => self.config.deployment_path.join(&clients.name),
+        };
+        clients.write_to(&dir).map_err(codegen_error)
+    }
+
     /// Get program by ID
     pub fn get_program(&self, program_id: &str) -> Option<&Program> {
         self.programs.get(program_id)
@@ -332,15 +384,22 @@ impl ProgramService {
 
     /// Get programs by category
     pub fn get_programs_by_category(&self, category: ProgramCategory) -> Vec<&Program> {
-        self.programs.values()
+        self.programs
+            .values()
             .filter(|p| p.metadata.category == category)
             .collect()
     }
 
     /// Update program
-    pub async fn update_program(&mut self, program_id: &str, name: Option<String>, description: Option<String>) -> ServiceResult<()> {
-        let program = self.programs.get_mut(program_id)
-            .ok_or_else(|| ServiceError::Validation(format!("Program not found: {}", program_id)))?;
+    pub async fn update_program(
+        &mut self,
+        program_id: &str,
+        name: Option<String>,
+        description: Option<String>,
+    ) -> ServiceResult<()> {
+        let program = self.programs.get_mut(program_id).ok_or_else(|| {
+            ServiceError::Validation(format!("Program not found: {}", program_id))
+        })?;
 
         if let Some(new_name) = name {
             program.name = new_name;
@@ -359,7 +418,10 @@ impl ProgramService {
     /// Delete a program
     pub async fn delete_program(&mut self, program_id: &str) -> ServiceResult<()> {
         if self.programs.remove(program_id).is_none() {
-            return Err(ServiceError::Validation(format!("Program not found: {}", program_id)));
+            return Err(ServiceError::Validation(format!(
+                "Program not found: {}",
+                program_id
+            )));
         }
 
         self.save_programs().await?;
@@ -377,12 +439,19 @@ impl ProgramService {
     }
 
     /// Test a deployed program
-    pub async fn test_program(&self, program_id: &str, test_data: HashMap<String, serde_json::Value>) -> ServiceResult<TestResult> {
-        let program = self.programs.get(program_id)
-            .ok_or_else(|| ServiceError::Validation(format!("Program not found: {}", program_id)))?;
+    pub async fn test_program(
+        &self,
+        program_id: &str,
+        _test_data: HashMap<String, serde_json::Value>,
+    ) -> ServiceResult<TestResult> {
+        let program = self.programs.get(program_id).ok_or_else(|| {
+            ServiceError::Validation(format!("Program not found: {}", program_id))
+        })?;
 
         if program.deployment.is_none() {
-            return Err(ServiceError::Validation("Program is not deployed".to_string()));
+            return Err(ServiceError::Validation(
+                "Program is not deployed".to_string(),
+            ));
         }
 
         // In a real implementation, this would call the deployed program with test data
@@ -409,21 +478,13 @@ impl ProgramService {
             name: "Hello World".to_string(),
             description: "Simple program that returns a greeting".to_string(),
             category: ProgramCategory::Utility,
-            components: vec![
-                TemplateComponent {
-                    id: "greeting".to_string(),
-                    name: "Greeting Message".to_string(),
-                    component_type: ComponentType::DataStorage,
-                    config: {
-                        let mut config = HashMap::new();
-                        config.insert("message".to_string(), json!("Hello, World!"));
-                        config
-                    },
-                    position: (0, 0),
-                    connections: Vec::new(),
-                }
-            ],
-            default_config: HashMap::new(),
+            components: vec![TemplateComponent {
+                id: "greeting".to_string(),
+                name: "Greeting Message".to_string(),
+                component_type: ComponentType::DataStorage,
+                position: (0, 0),
+                connections: Vec::new(),
+            }],
         };
 
         // Counter template
@@ -437,11 +498,6 @@ impl ProgramService {
                     id: "init".to_string(),
                     name: "Initialize Counter".to_string(),
                     component_type: ComponentType::Account,
-                    config: {
-                        let mut config = HashMap::new();
-                        config.insert("initial_value".to_string(), json!(0));
-                        config
-                    },
                     position: (0, 0),
                     connections: vec!["increment".to_string()],
                 },
@@ -449,143 +505,16 @@ impl ProgramService {
                     id: "increment".to_string(),
                     name: "Increment Counter".to_string(),
                     component_type: ComponentType::Math,
-                    config: {
-                        let mut config = HashMap::new();
-                        config.insert("operation".to_string(), json!("add"));
-                        config.insert("value".to_string(), json!(1));
-                        config
-                    },
                     position: (100, 0),
                     connections: Vec::new(),
-                }
+                },
             ],
-            default_config: HashMap::new(),
         };
 
         self.templates.insert(hello_world.id.clone(), hello_world);
         self.templates.insert(counter.id.clone(), counter);
     }
 
-    /// Generate code from template
-    fn generate_code_from_template(&self, template: &ProgramTemplate, config_overrides: Option<&HashMap<String, serde_json::Value>>) -> ServiceResult<String> {
-        let mut code = format!(
-            r#"// Generated program from template: {}
-// Category: {}
-// Components: {}
-
-use solana_program::{{
-    account_info::AccountInfo,
-    entrypoint,
-    entrypoint::ProgramResult,
-    pubkey::Pubkey,
-    program_error::ProgramError,
-    msg,
-}};
-
-entrypoint!(process_instruction);
-
-pub fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {{
-"#,
-            template.name,
-            template.category,
-            template.components.len()
-        );
-
-        // Add component-specific code
-        for component in &template.components {
-            match component.component_type {
-                ComponentType::DataStorage => {
-                    code.push_str(r#"
-    // Data storage component
-    let message = "Hello, World!";
-    msg!("Message: {}", message);
-"#);
-                }
-                ComponentType::Account => {
-                    code.push_str(r#"
-    // Account initialization component
-    // TODO: Initialize account data structure
-    msg!("Account initialized");
-"#);
-                }
-                ComponentType::Math => {
-                    code.push_str(r#"
-    // Math operation component
-    // TODO: Perform mathematical operation
-    msg!("Math operation performed");
-"#);
-                }
-                _ => {
-                    code.push_str(&format!(
-                        r#"
-    // {:?} component
-    msg!("{:?} component executed");
-"#,
-                        component.component_type,
-                        component.component_type
-                    ));
-                }
-            }
-        }
-
-        code.push_str(r#"
-
-    Ok(())
-}
-"#);
-
-        Ok(code)
-    }
-
-    /// Generate code from components
-    fn generate_code_from_components(&self, components: &[TemplateComponent]) -> ServiceResult<String> {
-        let mut code = String::from(
-            r#"// Generated program from custom components
-
-use solana_program::{
-    account_info::AccountInfo,
-    entrypoint,
-    entrypoint::ProgramResult,
-    pubkey::Pubkey,
-    program_error::ProgramError,
-    msg,
-};
-
-entrypoint!(process_instruction);
-
-pub fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-"#
-        );
-
-        for component in components {
-            code.push_str(&format!(
-                r#"
-    // Component: {} ({:?})
-    msg!("Executing component: {}");
-"#,
-                component.name,
-                component.component_type,
-                component.name
-            ));
-        }
-
-        code.push_str(r#"
-
-    Ok(())
-}
-"#);
-
-        Ok(code)
-    }
-
     /// Compile source code to binary (mock implementation)
     fn compile_source_code(&self, source_code: &str) -> ServiceResult<Vec<u8>> {
         // In a real implementation, this would invoke the Rust compiler or Solana toolchain
@@ -595,12 +524,17 @@ pub fn process_instruction(
 
     /// Generate a program ID from program name
     fn generate_program_id(&self, name: &str) -> String {
-        use sha2::{Sha256, Digest};
         use bs58;
+        use sha2::{Digest, Sha256};
 
         let mut hasher = Sha256::new();
         hasher.update(name.as_bytes());
-        hasher.update(chrono::Utc::now().timestamp_nanos().to_be_bytes());
+        hasher.update(
+            chrono::Utc::now()
+                .timestamp_nanos_opt()
+                .unwrap_or_default()
+                .to_be_bytes(),
+        );
         let hash = hasher.finalize();
 
         bs58::encode(&hash[..32]).into_string()
@@ -623,7 +557,10 @@ pub fn process_instruction(
 
 impl Service for ProgramService {
     fn initialize(&mut self) -> ServiceResult<()> {
-        tracing::info!("Program service initialized with {} templates", self.templates.len());
+        tracing::info!(
+            "Program service initialized with {} templates",
+            self.templates.len()
+        );
         Ok(())
     }
 
@@ -632,7 +569,7 @@ impl Service for ProgramService {
     }
 
     fn shutdown(&mut self) -> ServiceResult<()> {
-        let _ = self.save_programs();
+        // Persisting needs I/O, so it happens in `shutdown_async`
         tracing::info!("Program service shutdown");
         Ok(())
     }
@@ -661,6 +598,12 @@ impl Configurable for ProgramService {
     type Config = ProgramServiceConfig;
 
     fn configure(&mut self, config: Self::Config) -> ServiceResult<()> {
+        if config.max_program_size == 0 {
+            return Err(ServiceError::Validation(
+                "Maximum program size must be greater than zero".to_string(),
+            ));
+        }
+
         self.config = config;
         tracing::info!("Program service reconfigured");
         Ok(())
@@ -671,6 +614,45 @@ impl Configurable for ProgramService {
     }
 }
 
+/// Native program source for template components
+///
+/// Each component becomes an instruction of a generated native program, and
+/// the project's Rust files are returned as one source listing.
+fn generate_source(name: &str, components: &[TemplateComponent]) -> ServiceResult<String> {
+    let schema = ProgramSchema {
+        name: to_snake_case(name),
+        version: "1.0.0".to_string(),
+        instructions: components
+            .iter()
+            .map(|component| Instruction {
+                name: component.name.clone(),
+                accounts: vec![
+                    InstructionAccount::writable("data_account"),
+                    InstructionAccount::signer("authority"),
+                ],
+                args: Vec::new(),
+                discriminator: None,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let project = native_program(&schema).map_err(codegen_error)?;
+    Ok(project
+        .files
+        .iter()
+        .filter(|file| file.path.ends_with(".rs"))
+        .map(|file| format!("// {}\n{}", file.path, file.contents))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn codegen_error(error: CodegenError) -> ServiceError {
+    match error {
+        CodegenError::Io { .. } => ServiceError::Storage(error.to_string()),
+        _ => ServiceError::Validation(error.to_string()),
+    }
+}
+
 /// Test result for program testing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestResult {
@@ -701,6 +683,8 @@ pub enum TestStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::components::program_builder::InstructionTemplate;
+    use crate::services::solana_rpc::validate_address;
 
     #[test]
     fn test_program_service_creation() {
@@ -751,12 +735,100 @@ mod tests {
     }
 
     #[test]
-    fn test_code_generation() {
+    fn test_hello_world_template() {
         let service = ProgramService::new();
         let template = service.get_template("hello_world").unwrap();
 
-        let code = service.generate_code_from_template(template, None).unwrap();
-        assert!(code.contains("process_instruction"));
-        assert!(code.contains("Hello, World!"));
+        // Display names are spelled as Rust identifiers
+        let code = generate_source("Hello World", &template.components).unwrap();
+        assert!(code.contains("// src/lib.rs\n"));
+        assert!(code.contains("fn process_greeting_message("));
+        assert!(code.contains("HelloWorldInstruction"));
+    }
+
+    #[test]
+    fn test_code_generation() {
+        let service = ProgramService::new();
+        let template = service.get_template("counter").unwrap();
+
+        let code = generate_source("Counter", &template.components).unwrap();
+        assert!(code.contains("// src/lib.rs\n"));
+        assert!(code.contains("// src/processor.rs\n"));
+        assert!(code.contains("pub fn process_instruction("));
+        assert!(code.contains("fn process_initialize_counter("));
+        assert!(code.contains("fn process_increment_counter("));
+        assert!(code.contains("require_signer(authority)?;"));
+
+        assert!(matches!(
+            generate_source("Empty", &[]),
+            Err(ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_native_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProgramService::with_config(ProgramServiceConfig {
+            deployment_path: dir.path().to_path_buf(),
+            ..ProgramServiceConfig::default()
+        });
+
+        let mut schema = ProgramSchema {
+            name: "Store Data Program".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            service.generate_native_project(&schema),
+            Err(ServiceError::Validation(_))
+        ));
+
+        schema
+            .instructions
+            .push(InstructionTemplate::store_data().to_instruction());
+        let project_dir = service.generate_native_project(&schema).unwrap();
+        assert_eq!(project_dir, dir.path().join("store_data_program"));
+        let processor = std::fs::read_to_string(project_dir.join("src/processor.rs")).unwrap();
+        assert!(processor.contains("fn process_store_data("));
+        assert!(processor.contains("require_signer(authority)?;"));
+    }
+
+    #[test]
+    fn test_generate_clients() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = ProgramService::with_config(ProgramServiceConfig {
+            deployment_path: dir.path().to_path_buf(),
+            ..ProgramServiceConfig::default()
+        });
+        let mut schema = ProgramSchema {
+            name: "Store Data Program".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        };
+        schema
+            .instructions
+            .push(InstructionTemplate::store_data().to_instruction());
+
+        let written = service.generate_clients(&schema, None).unwrap();
+        let client_dir = dir.path().join("store_data_program_client");
+        assert_eq!(
+            written,
+            vec![
+                client_dir.join("store_data_program_client.rs"),
+                client_dir.join("store_data_program_client.ts"),
+            ]
+        );
+        let ts = std::fs::read_to_string(&written[1]).unwrap();
+        assert!(ts.contains("export function createStoreDataInstruction("));
+
+        let custom = dir.path().join("web/src/generated");
+        let written = service.generate_clients(&schema, Some(&custom)).unwrap();
+        assert!(written.iter().all(|path| path.starts_with(&custom)));
+
+        schema.name.clear();
+        assert!(matches!(
+            service.generate_clients(&schema, None),
+            Err(ServiceError::Validation(_))
+        ));
     }
 }
@@ -0,0 +1,992 @@
+//! WebSocket PubSub client for Solana subscriptions
+//!
+//! The client keeps one WebSocket connection to the node and multiplexes
+//! every subscription over it. When the connection drops it reconnects with
+//! exponential backoff and subscribes everything again, so streams handed out
+//! to pages keep delivering notifications across node restarts.
+
+use crate::services::rpc_error::{RpcError, TransactionError};
+use crate::services::solana_rpc::{
+    account_info_from_value, AccountInfo, CommitmentLevel, SolanaRpcConfig,
+};
+use crate::services::{ServiceError, ServiceResult};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Delay before the first reconnect attempt
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(250);
+
+/// Upper bound for the reconnect backoff
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// What to subscribe to
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionRequest {
+    /// Lamport or data changes of one account (`accountSubscribe`)
+    Account {
+        address: String,
+        commitment: CommitmentLevel,
+    },
+    /// A signature reaching the commitment; delivers one notification (`signatureSubscribe`)
+    Signature {
+        signature: String,
+        commitment: CommitmentLevel,
+    },
+    /// Transaction logs (`logsSubscribe`)
+    Logs {
+        filter: LogsFilter,
+        commitment: CommitmentLevel,
+    },
+    /// Every slot processed by the node (`slotSubscribe`)
+    Slot,
+    /// Changes to accounts owned by a program (`programSubscribe`)
+    ///
+    /// `filters` use the same JSON shape as `getProgramAccounts` filters.
+    Program {
+        program_id: String,
+        commitment: CommitmentLevel,
+        filters: Vec<Value>,
+    },
+}
+
+/// Which transactions `logsSubscribe` reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogsFilter {
+    /// All transactions except simple votes
+    All,
+    /// All transactions including simple votes
+    AllWithVotes,
+    /// Transactions that mention an address
+    Mentions(String),
+}
+
+impl SubscriptionRequest {
+    /// JSON-RPC subscribe method
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Account { .. } => "accountSubscribe",
+            Self::Signature { .. } => "signatureSubscribe",
+            Self::Logs { .. } => "logsSubscribe",
+            Self::Slot => "slotSubscribe",
+            Self::Program { .. } => "programSubscribe",
+        }
+    }
+
+    /// JSON-RPC unsubscribe method
+    pub fn unsubscribe_method(&self) -> &'static str {
+        match self {
+            Self::Account { .. } => "accountUnsubscribe",
+            Self::Signature { .. } => "signatureUnsubscribe",
+            Self::Logs { .. } => "logsUnsubscribe",
+            Self::Slot => "slotUnsubscribe",
+            Self::Program { .. } => "programUnsubscribe",
+        }
+    }
+
+    /// JSON-RPC subscribe parameters
+    pub fn params(&self) -> Value {
+        match self {
+            Self::Account {
+                address,
+                commitment,
+            } => json!([
+                address,
+                {"encoding": "base64", "commitment": commitment.as_str()}
+            ]),
+            Self::Signature {
+                signature,
+                commitment,
+            } => json!([
+                signature,
+                {"commitment": commitment.as_str()}
+            ]),
+            Self::Logs { filter, commitment } => {
+                let filter = match filter {
+                    LogsFilter::All => json!("all"),
+                    LogsFilter::AllWithVotes => json!("allWithVotes"),
+                    LogsFilter::Mentions(address) => json!({"mentions": [address]}),
+                };
+                json!([filter, {"commitment": commitment.as_str()}])
+            }
+            Self::Slot => json!([]),
+            Self::Program {
+                program_id,
+                commitment,
+                filters,
+            } => {
+                let mut config = json!({"encoding": "base64", "commitment": commitment.as_str()});
+                if !filters.is_empty() {
+                    config["filters"] = Value::Array(filters.clone());
+                }
+                json!([program_id, config])
+            }
+        }
+    }
+
+    /// Whether the node ends the subscription after the first notification
+    fn is_one_shot(&self) -> bool {
+        matches!(self, Self::Signature { .. })
+    }
+
+    /// Decode the `result` of a notification for this subscription
+    fn parse_notification(&self, result: &Value) -> Option<Notification> {
+        let slot = result
+            .get("context")
+            .and_then(|c| c.get("slot"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default();
+        let value = result.get("value");
+        let error = |value: &Value| {
+            value
+                .get("err")
+                .filter(|err| !err.is_null())
+                .map(TransactionError::from_json)
+        };
+
+        match self {
+            Self::Account { address, .. } => Some(Notification::Account {
+                slot,
+                account: account_info_from_value(address, value?).ok()?,
+            }),
+            Self::Signature { .. } => Some(Notification::Signature {
+                slot,
+                error: error(value?),
+            }),
+            Self::Logs { .. } => {
+                let value = value?;
+                Some(Notification::Logs {
+                    slot,
+                    signature: value.get("signature")?.as_str()?.to_string(),
+                    error: error(value),
+                    logs: value
+                        .get("logs")
+                        .and_then(|logs| logs.as_array())
+                        .map(|logs| {
+                            logs.iter()
+                                .filter_map(|line| line.as_str().map(String::from))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })
+            }
+            Self::Slot => Some(Notification::Slot {
+                slot: result.get("slot")?.as_u64()?,
+                parent: result.get("parent")?.as_u64()?,
+                root: result.get("root")?.as_u64()?,
+            }),
+            Self::Program { .. } => {
+                let value = value?;
+                let pubkey = value.get("pubkey")?.as_str()?;
+                Some(Notification::Program {
+                    slot,
+                    account: account_info_from_value(pubkey, value.get("account")?).ok()?,
+                })
+            }
+        }
+    }
+}
+
+/// Notification delivered on a subscription stream
+#[derive(Debug, Clone)]
+pub enum Notification {
+    /// The subscribed account changed
+    Account { slot: u64, account: AccountInfo },
+    /// The subscribed signature reached the requested commitment
+    Signature {
+        slot: u64,
+        error: Option<TransactionError>,
+    },
+    /// A transaction matching the logs filter was processed
+    Logs {
+        slot: u64,
+        signature: String,
+        error: Option<TransactionError>,
+        logs: Vec<String>,
+    },
+    /// The node processed a slot
+    Slot { slot: u64, parent: u64, root: u64 },
+    /// An account owned by the subscribed program changed
+    Program { slot: u64, account: AccountInfo },
+}
+
+/// State of the underlying WebSocket connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// First connection attempt in progress
+    Connecting,
+    /// Connected and subscriptions are active
+    Connected,
+    /// Connection lost, retrying; `attempt` counts failed attempts so far
+    Reconnecting { attempt: u32 },
+    /// The client was dropped
+    Closed,
+}
+
+/// Requests from client handles to the connection task
+enum Command {
+    Subscribe {
+        request: SubscriptionRequest,
+        notifications: mpsc::UnboundedSender<Notification>,
+        reply: oneshot::Sender<ServiceResult<u64>>,
+    },
+    Unsubscribe {
+        id: u64,
+    },
+}
+
+/// Client for Solana WebSocket subscriptions
+///
+/// Cheap to clone; all clones share one connection. The connection task
+/// stops once every client and subscription has been dropped.
+#[derive(Clone)]
+pub struct PubSubClient {
+    commands: mpsc::UnboundedSender<Command>,
+    state: watch::Receiver<ConnectionState>,
+}
+
+impl PubSubClient {
+    /// Start a client for a `ws://` or `wss://` endpoint
+    ///
+    /// Connecting happens in the background, so this succeeds even while the
+    /// node is down. Must be called from within a Tokio runtime.
+    pub fn connect(url: &str) -> ServiceResult<Self> {
+        let valid = url::Url::parse(url)
+            .map(|url| matches!(url.scheme(), "ws" | "wss"))
+            .unwrap_or(false);
+        if !valid {
+            return Err(ServiceError::Validation(format!(
+                "WebSocket URL must be a ws:// or wss:// URL: {}",
+                url
+            )));
+        }
+
+        let (commands, command_receiver) = mpsc::unbounded_channel();
+        let (state_sender, state) = watch::channel(ConnectionState::Connecting);
+
+        let worker = Worker {
+            url: url.to_string(),
+            commands: command_receiver,
+            state: state_sender,
+            subscriptions: HashMap::new(),
+            next_id: 1,
+        };
+        tokio::spawn(worker.run());
+
+        Ok(Self { commands, state })
+    }
+
+    /// Start a client for the WebSocket endpoint of an RPC configuration
+    pub fn from_config(config: &SolanaRpcConfig) -> ServiceResult<Self> {
+        Self::connect(&config.websocket_url()?)
+    }
+
+    /// Subscribe and return the notification stream
+    ///
+    /// While connected this waits for the node to accept the subscription and
+    /// returns its error otherwise. While disconnected the subscription is
+    /// queued and sent once the connection is back.
+    pub async fn subscribe(&self, request: SubscriptionRequest) -> ServiceResult<Subscription> {
+        let (notifications, receiver) = mpsc::unbounded_channel();
+        let (reply, reply_receiver) = oneshot::channel();
+
+        self.commands
+            .send(Command::Subscribe {
+                request,
+                notifications,
+                reply,
+            })
+            .map_err(|_| ServiceError::Network("PubSub connection closed".to_string()))?;
+
+        let id = reply_receiver
+            .await
+            .map_err(|_| ServiceError::Network("PubSub connection closed".to_string()))??;
+
+        Ok(Subscription {
+            id,
+            notifications: receiver,
+            commands: self.commands.clone(),
+        })
+    }
+
+    /// Subscribe to lamport and data changes of an account
+    pub async fn account_subscribe(
+        &self,
+        address: &str,
+        commitment: CommitmentLevel,
+    ) -> ServiceResult<Subscription> {
+        self.subscribe(SubscriptionRequest::Account {
+            address: address.to_string(),
+            commitment,
+        })
+        .await
+    }
+
+    /// Subscribe to a signature reaching the commitment
+    pub async fn signature_subscribe(
+        &self,
+        signature: &str,
+        commitment: CommitmentLevel,
+    ) -> ServiceResult<Subscription> {
+        self.subscribe(SubscriptionRequest::Signature {
+            signature: signature.to_string(),
+            commitment,
+        })
+        .await
+    }
+
+    /// Subscribe to transaction logs
+    pub async fn logs_subscribe(
+        &self,
+        filter: LogsFilter,
+        commitment: CommitmentLevel,
+    ) -> ServiceResult<Subscription> {
+        self.subscribe(SubscriptionRequest::Logs { filter, commitment })
+            .await
+    }
+
+    /// Subscribe to slot updates
+    pub async fn slot_subscribe(&self) -> ServiceResult<Subscription> {
+        self.subscribe(SubscriptionRequest::Slot).await
+    }
+
+    /// Subscribe to changes of accounts owned by a program
+    pub async fn program_subscribe(
+        &self,
+        program_id: &str,
+        commitment: CommitmentLevel,
+        filters: Vec<Value>,
+    ) -> ServiceResult<Subscription> {
+        self.subscribe(SubscriptionRequest::Program {
+            program_id: program_id.to_string(),
+            commitment,
+            filters,
+        })
+        .await
+    }
+
+    /// Current connection state
+    pub fn connection_state(&self) -> ConnectionState {
+        *self.state.borrow()
+    }
+
+    /// Receiver that observes connection state changes
+    pub fn watch_connection_state(&self) -> watch::Receiver<ConnectionState> {
+        self.state.clone()
+    }
+}
+
+/// Stream of notifications for one subscription
+///
+/// Dropping it unsubscribes. The stream ends when the node rejects a
+/// resubscription or, for signature subscriptions, after the notification.
+pub struct Subscription {
+    id: u64,
+    notifications: mpsc::UnboundedReceiver<Notification>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl Subscription {
+    /// Client-side id of this subscription, stable across reconnects
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Stream for Subscription {
+    type Item = Notification;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.notifications.poll_recv(cx)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let _ = self.commands.send(Command::Unsubscribe { id: self.id });
+    }
+}
+
+/// A subscription registered with the connection task
+struct ActiveSubscription {
+    request: SubscriptionRequest,
+    notifications: mpsc::UnboundedSender<Notification>,
+}
+
+/// Subscribe request waiting for the node's answer
+struct PendingSubscribe {
+    id: u64,
+    unsubscribe_method: &'static str,
+    reply: Option<oneshot::Sender<ServiceResult<u64>>>,
+}
+
+/// Why a connection ended
+enum Disconnect {
+    /// The socket failed or the node closed it
+    Lost,
+    /// Every client handle was dropped
+    Shutdown,
+}
+
+/// Background task that owns the WebSocket connection
+struct Worker {
+    url: String,
+    commands: mpsc::UnboundedReceiver<Command>,
+    state: watch::Sender<ConnectionState>,
+    subscriptions: HashMap<u64, ActiveSubscription>,
+    next_id: u64,
+}
+
+impl Worker {
+    async fn run(mut self) {
+        let mut attempt = 0;
+        let mut delay = INITIAL_RECONNECT_DELAY;
+
+        loop {
+            match tokio_tungstenite::connect_async(self.url.as_str()).await {
+                Ok((socket, _)) => {
+                    attempt = 0;
+                    delay = INITIAL_RECONNECT_DELAY;
+                    self.state.send_replace(ConnectionState::Connected);
+                    tracing::info!("PubSub connected to {}", self.url);
+
+                    if let Disconnect::Shutdown = self.serve(socket).await {
+                        break;
+                    }
+                    tracing::warn!("PubSub connection to {} lost", self.url);
+                }
+                Err(e) => tracing::debug!("PubSub connection to {} failed: {}", self.url, e),
+            }
+
+            attempt += 1;
+            self.state
+                .send_replace(ConnectionState::Reconnecting { attempt });
+
+            if let Disconnect::Shutdown = self.wait_offline(delay).await {
+                break;
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+
+        self.state.send_replace(ConnectionState::Closed);
+    }
+
+    /// Handle commands while disconnected until `delay` has passed
+    async fn wait_offline(&mut self, delay: Duration) -> Disconnect {
+        let sleep = tokio::time::sleep(delay);
+        tokio::pin!(sleep);
+
+        loop {
+            tokio::select! {
+                _ = &mut sleep => return Disconnect::Lost,
+                command = self.commands.recv() => match command {
+                    Some(Command::Subscribe { request, notifications, reply }) => {
+                        // Queued; it is sent on the next connection
+                        let id = self.register(request, notifications);
+                        let _ = reply.send(Ok(id));
+                    }
+                    Some(Command::Unsubscribe { id }) => {
+                        self.subscriptions.remove(&id);
+                    }
+                    None => return Disconnect::Shutdown,
+                },
+            }
+        }
+    }
+
+    fn register(
+        &mut self,
+        request: SubscriptionRequest,
+        notifications: mpsc::UnboundedSender<Notification>,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.subscriptions.insert(
+            id,
+            ActiveSubscription {
+                request,
+                notifications,
+            },
+        );
+        id
+    }
+
+    /// Run one connection until it is lost or the client shuts down
+    async fn serve<S>(&mut self, socket: S) -> Disconnect
+    where
+        S: Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
+            + Sink<Message>
+            + Unpin,
+    {
+        let (mut sink, mut stream) = socket.split();
+        let mut connection = Connection::default();
+
+        // Resubscribe everything registered before this connection
+        let mut ids: Vec<u64> = self.subscriptions.keys().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            let request = &self.subscriptions[&id].request;
+            let message = connection.subscribe_message(id, request, None);
+            if sink.send(message).await.is_err() {
+                return connection.lost();
+            }
+        }
+
+        loop {
+            tokio::select! {
+                command = self.commands.recv() => match command {
+                    Some(Command::Subscribe { request, notifications, reply }) => {
+                        let id = self.register(request, notifications);
+                        let message = connection.subscribe_message(id, &self.subscriptions[&id].request, Some(reply));
+                        if sink.send(message).await.is_err() {
+                            return connection.lost();
+                        }
+                    }
+                    Some(Command::Unsubscribe { id }) => {
+                        let Some(subscription) = self.subscriptions.remove(&id) else { continue };
+                        if let Some(message) = connection.unsubscribe_message(id, &subscription.request) {
+                            if sink.send(message).await.is_err() {
+                                return connection.lost();
+                            }
+                        }
+                    }
+                    None => {
+                        let _ = sink.close().await;
+                        return Disconnect::Shutdown;
+                    }
+                },
+                message = stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        for message in self.handle_message(&mut connection, &text) {
+                            if sink.send(message).await.is_err() {
+                                return connection.lost();
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return connection.lost(),
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+    }
+
+    /// Route one message from the node, returning any replies to send
+    fn handle_message(&mut self, connection: &mut Connection, text: &str) -> Vec<Message> {
+        let Ok(message) = serde_json::from_str::<Value>(text) else {
+            tracing::warn!("Ignoring malformed PubSub message");
+            return Vec::new();
+        };
+
+        // Answer to a subscribe request
+        if let Some(request_id) = message.get("id").and_then(|v| v.as_u64()) {
+            let Some(pending) = connection.pending.remove(&request_id) else {
+                return Vec::new();
+            };
+
+            if let Some(error) = message.get("error") {
+                let error = RpcError::from_json(error);
+                tracing::warn!("PubSub subscription {} rejected: {}", pending.id, error);
+                self.subscriptions.remove(&pending.id);
+                if let Some(reply) = pending.reply {
+                    let _ = reply.send(Err(ServiceError::Rpc(error)));
+                }
+                return Vec::new();
+            }
+
+            let Some(server_id) = message.get("result").and_then(|v| v.as_u64()) else {
+                return Vec::new();
+            };
+            connection.server_ids.insert(server_id, pending.id);
+            if let Some(reply) = pending.reply {
+                let _ = reply.send(Ok(pending.id));
+            }
+
+            // Dropped while the subscribe was in flight
+            if self.subscriptions.contains_key(&pending.id) {
+                return Vec::new();
+            }
+            connection.server_ids.remove(&server_id);
+            return vec![connection.request_message(pending.unsubscribe_method, json!([server_id]))];
+        }
+
+        // Notification for an active subscription
+        let Some(params) = message.get("params") else {
+            return Vec::new();
+        };
+        let Some(server_id) = params.get("subscription").and_then(|v| v.as_u64()) else {
+            return Vec::new();
+        };
+        let Some(&id) = connection.server_ids.get(&server_id) else {
+            return Vec::new();
+        };
+        let Some(subscription) = self.subscriptions.get(&id) else {
+            return Vec::new();
+        };
+
+        match params
+            .get("result")
+            .and_then(|result| subscription.request.parse_notification(result))
+        {
+            Some(notification) => {
+                let _ = subscription.notifications.send(notification);
+            }
+            None => tracing::warn!(
+                "Ignoring unexpected {} payload",
+                subscription.request.method()
+            ),
+        }
+
+        if subscription.request.is_one_shot() {
+            connection.server_ids.remove(&server_id);
+            self.subscriptions.remove(&id);
+        }
+
+        Vec::new()
+    }
+}
+
+/// Per-connection bookkeeping; server subscription ids change on reconnect
+#[derive(Default)]
+struct Connection {
+    next_request_id: u64,
+    /// Subscribe requests awaiting an answer, by request id
+    pending: HashMap<u64, PendingSubscribe>,
+    /// Server subscription id to client subscription id
+    server_ids: HashMap<u64, u64>,
+}
+
+impl Connection {
+    fn request_message(&mut self, method: &str, params: Value) -> Message {
+        self.next_request_id += 1;
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_request_id,
+            "method": method,
+            "params": params
+        });
+        Message::Text(request.to_string())
+    }
+
+    fn subscribe_message(
+        &mut self,
+        id: u64,
+        request: &SubscriptionRequest,
+        reply: Option<oneshot::Sender<ServiceResult<u64>>>,
+    ) -> Message {
+        let message = self.request_message(request.method(), request.params());
+        self.pending.insert(
+            self.next_request_id,
+            PendingSubscribe {
+                id,
+                unsubscribe_method: request.unsubscribe_method(),
+                reply,
+            },
+        );
+        message
+    }
+
+    fn unsubscribe_message(&mut self, id: u64, request: &SubscriptionRequest) -> Option<Message> {
+        let server_id = self
+            .server_ids
+            .iter()
+            .find_map(|(server_id, client_id)| (*client_id == id).then_some(*server_id))?;
+        self.server_ids.remove(&server_id);
+        Some(self.request_message(request.unsubscribe_method(), json!([server_id])))
+    }
+
+    /// Answer callers still waiting on a subscribe; the subscription stays
+    /// registered and is sent again after reconnecting
+    fn lost(self) -> Disconnect {
+        for pending in self.pending.into_values() {
+            if let Some(reply) = pending.reply {
+                let _ = reply.send(Ok(pending.id));
+            }
+        }
+        Disconnect::Lost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::WebSocketStream;
+
+    type ServerSocket = WebSocketStream<tokio::net::TcpStream>;
+
+    async fn listen() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    async fn accept(listener: &TcpListener) -> ServerSocket {
+        let (stream, _) = listener.accept().await.unwrap();
+        tokio_tungstenite::accept_async(stream).await.unwrap()
+    }
+
+    async fn receive(socket: &mut ServerSocket) -> Value {
+        loop {
+            if let Message::Text(text) = socket.next().await.unwrap().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    async fn send(socket: &mut ServerSocket, value: Value) {
+        socket.send(Message::Text(value.to_string())).await.unwrap();
+    }
+
+    fn account_notification(subscription: u64, lamports: u64) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "accountNotification",
+            "params": {
+                "subscription": subscription,
+                "result": {
+                    "context": {"slot": lamports},
+                    "value": {
+                        "lamports": lamports,
+                        "owner": "11111111111111111111111111111111",
+                        "data": ["", "base64"],
+                        "executable": false,
+                        "rentEpoch": 0
+                    }
+                }
+            }
+        })
+    }
+
+    fn balance(notification: Option<Notification>) -> u64 {
+        match notification {
+            Some(Notification::Account { account, .. }) => account.balance,
+            other => panic!("expected an account notification, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_subscription_params() {
+        let account = SubscriptionRequest::Account {
+            address: "addr".to_string(),
+            commitment: CommitmentLevel::Confirmed,
+        };
+        assert_eq!(account.method(), "accountSubscribe");
+        assert_eq!(
+            account.params(),
+            json!(["addr", {"encoding": "base64", "commitment": "confirmed"}])
+        );
+
+        let logs = SubscriptionRequest::Logs {
+            filter: LogsFilter::Mentions("prog".to_string()),
+            commitment: CommitmentLevel::Finalized,
+        };
+        assert_eq!(logs.unsubscribe_method(), "logsUnsubscribe");
+        assert_eq!(
+            logs.params(),
+            json!([{"mentions": ["prog"]}, {"commitment": "finalized"}])
+        );
+
+        let program = SubscriptionRequest::Program {
+            program_id: "prog".to_string(),
+            commitment: CommitmentLevel::Processed,
+            filters: vec![json!({"dataSize": 165})],
+        };
+        assert_eq!(
+            program.params(),
+            json!(["prog", {"encoding": "base64", "commitment": "processed", "filters": [{"dataSize": 165}]}])
+        );
+        assert_eq!(SubscriptionRequest::Slot.params(), json!([]));
+    }
+
+    #[test]
+    fn test_parse_notifications() {
+        let logs = SubscriptionRequest::Logs {
+            filter: LogsFilter::All,
+            commitment: CommitmentLevel::Confirmed,
+        };
+        let notification = logs.parse_notification(&json!({
+            "context": {"slot": 7},
+            "value": {
+                "signature": "sig",
+                "err": {"InstructionError": [0, {"Custom": 1}]},
+                "logs": ["Program log: hi"]
+            }
+        }));
+        match notification {
+            Some(Notification::Logs {
+                slot,
+                signature,
+                error,
+                logs,
+            }) => {
+                assert_eq!(slot, 7);
+                assert_eq!(signature, "sig");
+                assert_eq!(
+                    error.unwrap().to_string(),
+                    "instruction 0 failed: custom program error 0x1"
+                );
+                assert_eq!(logs, vec!["Program log: hi".to_string()]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let slot = SubscriptionRequest::Slot
+            .parse_notification(&json!({"parent": 9, "root": 1, "slot": 10}));
+        assert!(matches!(
+            slot,
+            Some(Notification::Slot {
+                slot: 10,
+                parent: 9,
+                root: 1
+            })
+        ));
+
+        let signature = SubscriptionRequest::Signature {
+            signature: "sig".to_string(),
+            commitment: CommitmentLevel::Confirmed,
+        };
+        let confirmed =
+            signature.parse_notification(&json!({"context": {"slot": 3}, "value": {"err": null}}));
+        assert!(matches!(
+            confirmed,
+            Some(Notification::Signature {
+                slot: 3,
+                error: None
+            })
+        ));
+    }
+
+    #[test]
+    fn test_websocket_url_from_rpc_url() {
+        let config = SolanaRpcConfig {
+            rpc_url: "http://localhost:8899".to_string(),
+            ..SolanaRpcConfig::default()
+        };
+        assert_eq!(config.websocket_url().unwrap(), "ws://localhost:8900/");
+
+        let config = SolanaRpcConfig {
+            rpc_url: "https://api.devnet.solana.com".to_string(),
+            ..SolanaRpcConfig::default()
+        };
+        assert_eq!(
+            config.websocket_url().unwrap(),
+            "wss://api.devnet.solana.com/"
+        );
+
+        assert!(PubSubClient::connect("http://localhost:8900").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resubscribes_after_reconnect() {
+        let (listener, url) = listen().await;
+        let client = PubSubClient::connect(&url).unwrap();
+
+        let server = tokio::spawn(async move {
+            // First connection: confirm, notify, then drop the socket
+            let mut socket = accept(&listener).await;
+            let request = receive(&mut socket).await;
+            assert_eq!(request["method"], "accountSubscribe");
+            send(
+                &mut socket,
+                json!({"jsonrpc": "2.0", "id": request["id"], "result": 11}),
+            )
+            .await;
+            send(&mut socket, account_notification(11, 1)).await;
+            drop(socket);
+
+            // Second connection: the client must subscribe again on its own
+            let mut socket = accept(&listener).await;
+            let request = receive(&mut socket).await;
+            assert_eq!(request["method"], "accountSubscribe");
+            assert_eq!(request["params"][0], "addr");
+            send(
+                &mut socket,
+                json!({"jsonrpc": "2.0", "id": request["id"], "result": 22}),
+            )
+            .await;
+            send(&mut socket, account_notification(22, 2)).await;
+
+            // Dropping the stream unsubscribes with the new server id
+            let request = receive(&mut socket).await;
+            assert_eq!(request["method"], "accountUnsubscribe");
+            assert_eq!(request["params"], json!([22]));
+        });
+
+        let mut subscription = client
+            .account_subscribe("addr", CommitmentLevel::Confirmed)
+            .await
+            .unwrap();
+        assert_eq!(balance(subscription.next().await), 1);
+        assert_eq!(balance(subscription.next().await), 2);
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+
+        drop(subscription);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_subscription() {
+        let (listener, url) = listen().await;
+        let client = PubSubClient::connect(&url).unwrap();
+
+        tokio::spawn(async move {
+            let mut socket = accept(&listener).await;
+            let request = receive(&mut socket).await;
+            send(
+                &mut socket,
+                json!({"jsonrpc": "2.0", "id": request["id"], "error": {"code": -32602, "message": "Invalid param: Invalid"}}),
+            )
+            .await;
+            // Keep the connection open until the client is done
+            let _ = socket.next().await;
+        });
+
+        let result = client
+            .signature_subscribe("not-a-signature", CommitmentLevel::Confirmed)
+            .await;
+        assert!(matches!(result, Err(ServiceError::Rpc(e)) if e.code == -32602));
+    }
+
+    #[tokio::test]
+    async fn test_signature_subscription_ends_after_notification() {
+        let (listener, url) = listen().await;
+        let client = PubSubClient::connect(&url).unwrap();
+
+        tokio::spawn(async move {
+            let mut socket = accept(&listener).await;
+            let request = receive(&mut socket).await;
+            send(
+                &mut socket,
+                json!({"jsonrpc": "2.0", "id": request["id"], "result": 5}),
+            )
+            .await;
+            send(
+                &mut socket,
+                json!({"jsonrpc": "2.0", "method": "signatureNotification", "params": {
+                    "subscription": 5,
+                    "result": {"context": {"slot": 42}, "value": {"err": null}}
+                }}),
+            )
+            .await;
+            let _ = socket.next().await;
+        });
+
+        let mut subscription = client
+            .signature_subscribe("sig", CommitmentLevel::Confirmed)
+            .await
+            .unwrap();
+        assert!(matches!(
+            subscription.next().await,
+            Some(Notification::Signature {
+                slot: 42,
+                error: None
+            })
+        ));
+        assert!(subscription.next().await.is_none());
+    }
+}
@@ -0,0 +1,285 @@
+//! Typed JSON-RPC errors returned by Solana nodes
+//!
+//! Nodes report failures as `{"code", "message", "data"}` objects. For failed
+//! preflight simulations `data` carries the transaction error and the program
+//! logs, which this module decodes so the UI can show what actually failed.
+
+use serde_json::Value;
+use std::fmt;
+
+/// JSON-RPC code for a failed preflight simulation
+pub const SEND_TRANSACTION_PREFLIGHT_FAILURE: i64 = -32002;
+
+/// JSON-RPC code for a transaction whose signatures did not verify
+pub const TRANSACTION_SIGNATURE_VERIFICATION_FAILURE: i64 = -32003;
+
+/// JSON-RPC code for a node that is behind or unhealthy
+pub const NODE_UNHEALTHY: i64 = -32005;
+
+/// JSON-RPC code for an unknown method
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Error object returned by a Solana JSON-RPC endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    /// JSON-RPC error code
+    pub code: i64,
+    /// Message reported by the node
+    pub message: String,
+    /// Decoded transaction error from `data.err`, if any
+    pub transaction_error: Option<TransactionError>,
+    /// Program logs from `data.logs` (preflight simulation output)
+    pub logs: Vec<String>,
+    /// Raw `data` field for anything not decoded above
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// Decode the `error` member of a JSON-RPC response
+    pub fn from_json(error: &Value) -> Self {
+        let data = error.get("data").filter(|data| !data.is_null()).cloned();
+
+        let transaction_error = data
+            .as_ref()
+            .and_then(|data| data.get("err"))
+            .filter(|err| !err.is_null())
+            .map(TransactionError::from_json);
+
+        let logs = data
+            .as_ref()
+            .and_then(|data| data.get("logs"))
+            .and_then(|logs| logs.as_array())
+            .map(|logs| {
+                logs.iter()
+                    .filter_map(|line| line.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            code: error
+                .get("code")
+                .and_then(|v| v.as_i64())
+                .unwrap_or_default(),
+            message: error
+                .get("message")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown RPC error")
+                .to_string(),
+            transaction_error,
+            logs,
+            data,
+        }
+    }
+
+    /// Whether this is a failed preflight simulation
+    pub fn is_preflight_failure(&self) -> bool {
+        self.code == SEND_TRANSACTION_PREFLIGHT_FAILURE
+    }
+
+    /// Short description, preferring the decoded transaction error
+    pub fn summary(&self) -> String {
+        match &self.transaction_error {
+            Some(error) => error.to_string(),
+            None => self.message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RPC error {}: {}", self.code, self.summary())
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// Reason a transaction failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    /// An instruction failed
+    InstructionError { index: u8, error: InstructionError },
+    /// Any other transaction-level error, e.g. `BlockhashNotFound`
+    Other(String),
+}
+
+impl TransactionError {
+    /// Decode a transaction error as serialized by the node
+    ///
+    /// Unit variants arrive as strings (`"BlockhashNotFound"`), variants with
+    /// data as single-key objects (`{"InstructionError": [2, {"Custom": 6001}]}`).
+    pub fn from_json(error: &Value) -> Self {
+        if let Some([index, instruction_error]) = error
+            .get("InstructionError")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+        {
+            if let Some(index) = index.as_u64().and_then(|index| u8::try_from(index).ok()) {
+                return Self::InstructionError {
+                    index,
+                    error: InstructionError::from_json(instruction_error),
+                };
+            }
+        }
+
+        Self::Other(describe_variant(error))
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstructionError { index, error } => {
+                write!(f, "instruction {} failed: {}", index, error)
+            }
+            Self::Other(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Reason an instruction failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionError {
+    /// Program-specific error code, e.g. an Anchor `#[error_code]`
+    Custom(u32),
+    /// Any built-in runtime error, e.g. `InvalidAccountData`
+    Other(String),
+}
+
+impl InstructionError {
+    /// Decode an instruction error as serialized by the node
+    pub fn from_json(error: &Value) -> Self {
+        match error
+            .get("Custom")
+            .and_then(|v| v.as_u64())
+            .and_then(|code| u32::try_from(code).ok())
+        {
+            Some(code) => Self::Custom(code),
+            None => Self::Other(describe_variant(error)),
+        }
+    }
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(code) => write!(f, "custom program error 0x{:x}", code),
+            Self::Other(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Render a serialized Rust enum variant as lowercase words
+///
+/// `"InvalidAccountData"` becomes `invalid account data` and
+/// `{"InsufficientFundsForRent": {"account_index": 0}}` keeps its payload.
+fn describe_variant(value: &Value) -> String {
+    match value {
+        Value::String(name) => split_camel_case(name),
+        Value::Object(map) if map.len() == 1 => {
+            let (name, payload) = map.iter().next().unwrap();
+            format!("{}: {}", split_camel_case(name), payload)
+        }
+        other => other.to_string(),
+    }
+}
+
+fn split_camel_case(name: &str) -> String {
+    let mut words = String::with_capacity(name.len() + 8);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            words.push(' ');
+        }
+        words.extend(c.to_lowercase());
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_preflight_failure_with_custom_error() {
+        let error = json!({
+            "code": -32002,
+            "message": "Transaction simulation failed: Error processing Instruction 2: custom program error: 0x1771",
+            "data": {
+                "accounts": null,
+                "err": {"InstructionError": [2, {"Custom": 6001}]},
+                "logs": [
+                    "Program 11111111111111111111111111111111 invoke [1]",
+                    "Program log: AnchorError occurred. Error Code: SlippageExceeded",
+                    "Program 11111111111111111111111111111111 failed: custom program error: 0x1771"
+                ],
+                "unitsConsumed": 4123
+            }
+        });
+
+        let rpc_error = RpcError::from_json(&error);
+        assert_eq!(rpc_error.code, SEND_TRANSACTION_PREFLIGHT_FAILURE);
+        assert!(rpc_error.is_preflight_failure());
+        assert_eq!(
+            rpc_error.transaction_error,
+            Some(TransactionError::InstructionError {
+                index: 2,
+                error: InstructionError::Custom(6001)
+            })
+        );
+        assert_eq!(rpc_error.logs.len(), 3);
+        assert!(rpc_error.logs[1].contains("SlippageExceeded"));
+        assert_eq!(
+            rpc_error.summary(),
+            "instruction 2 failed: custom program error 0x1771"
+        );
+        assert_eq!(
+            rpc_error.to_string(),
+            "RPC error -32002: instruction 2 failed: custom program error 0x1771"
+        );
+    }
+
+    #[test]
+    fn test_builtin_instruction_error() {
+        let error =
+            TransactionError::from_json(&json!({"InstructionError": [0, "InvalidAccountData"]}));
+        assert_eq!(
+            error,
+            TransactionError::InstructionError {
+                index: 0,
+                error: InstructionError::Other("invalid account data".to_string())
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "instruction 0 failed: invalid account data"
+        );
+
+        let borsh = InstructionError::from_json(&json!({"BorshIoError": "Unknown"}));
+        assert_eq!(borsh.to_string(), "borsh io error: \"Unknown\"");
+    }
+
+    #[test]
+    fn test_transaction_level_errors() {
+        assert_eq!(
+            TransactionError::from_json(&json!("BlockhashNotFound")).to_string(),
+            "blockhash not found"
+        );
+        assert_eq!(
+            TransactionError::from_json(&json!({"InsufficientFundsForRent": {"account_index": 1}}))
+                .to_string(),
+            "insufficient funds for rent: {\"account_index\":1}"
+        );
+    }
+
+    #[test]
+    fn test_plain_rpc_error() {
+        let rpc_error =
+            RpcError::from_json(&json!({"code": -32601, "message": "Method not found"}));
+        assert_eq!(rpc_error.code, METHOD_NOT_FOUND);
+        assert!(rpc_error.transaction_error.is_none());
+        assert!(rpc_error.logs.is_empty());
+        assert!(rpc_error.data.is_none());
+        assert_eq!(rpc_error.to_string(), "RPC error -32601: Method not found");
+    }
+}
@@ -5,16 +5,21 @@
 //! transaction building, account management, and program deployment through
 //! direct HTTP JSON-RPC calls.
 
+use crate::services::rpc_error::{RpcError, TransactionError};
 use crate::services::{AsyncService, Configurable, Service, ServiceError, ServiceResult};
+use crate::utils::crypto::{self, Keypair};
+use crate::utils::validation::{validate_network_url, ValidationResult};
+use base64::Engine;
+use bs58;
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
-use reqwest::Client;
-use bs58;
-use sha2::{Sha256, Digest};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Configuration for Solana RPC service
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SolanaRpcConfig {
     /// RPC endpoint URL
     pub rpc_url: String,
@@ -22,12 +27,77 @@ pub struct SolanaRpcConfig {
     pub ws_url: Option<String>,
     /// Request timeout in seconds
     pub timeout: u64,
-    /// Maximum number of retries
+    /// Maximum number of retries, also the number of consecutive failed
+    /// status polls tolerated while confirming a transaction
     pub max_retries: u32,
     /// Commitment level
     pub commitment: CommitmentLevel,
     /// Pre-flight checks
     pub preflight_checks: bool,
+    /// Delay between signature status polls while confirming, in milliseconds
+    pub confirm_poll_interval_ms: u64,
+    /// Maximum number of calls sent in one JSON-RPC batch
+    pub max_batch_size: usize,
+}
+
+impl SolanaRpcConfig {
+    /// Check that the endpoints and timeouts are usable
+    pub fn validate(&self) -> ServiceResult<()> {
+        if let ValidationResult::Invalid(reason) = validate_network_url(&self.rpc_url) {
+            return Err(ServiceError::Validation(format!("RPC URL: {}", reason)));
+        }
+
+        if let Some(ws_url) = &self.ws_url {
+            let valid = url::Url::parse(ws_url)
+                .map(|url| matches!(url.scheme(), "ws" | "wss") && url.host().is_some())
+                .unwrap_or(false);
+            if !valid {
+                return Err(ServiceError::Validation(format!(
+                    "WebSocket URL must be a ws:// or wss:// URL: {}",
+                    ws_url
+                )));
+            }
+        }
+
+        if self.timeout == 0 {
+            return Err(ServiceError::Validation(
+                "Timeout must be at least 1 second".to_string(),
+            ));
+        }
+
+        if self.max_batch_size == 0 {
+            return Err(ServiceError::Validation(
+                "Batch size must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// WebSocket endpoint for subscriptions
+    ///
+    /// Uses `ws_url` when set, otherwise follows the validator convention of
+    /// serving PubSub one port above the RPC port on the same host.
+    pub fn websocket_url(&self) -> ServiceResult<String> {
+        if let Some(ws_url) = &self.ws_url {
+            return Ok(ws_url.clone());
+        }
+
+        let mut url = url::Url::parse(&self.rpc_url)
+            .map_err(|e| ServiceError::Validation(format!("Invalid RPC URL: {}", e)))?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        let port = url.port().map(|port| port.saturating_add(1));
+
+        url.set_scheme(scheme)
+            .and_then(|_| url.set_port(port))
+            .map_err(|_| {
+                ServiceError::Validation(format!(
+                    "Cannot derive a WebSocket URL from {}",
+                    self.rpc_url
+                ))
+            })?;
+        Ok(url.to_string())
+    }
 }
 
 impl Default for SolanaRpcConfig {
@@ -39,21 +109,39 @@ impl Default for SolanaRpcConfig {
             max_retries: 3,
             commitment: CommitmentLevel::Confirmed,
             preflight_checks: true,
+            confirm_poll_interval_ms: 500,
+            max_batch_size: 100,
         }
     }
 }
 
-/// Commitment levels for Solana transactions
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Commitment levels for Solana transactions, ordered from weakest to strongest
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum CommitmentLevel {
     Processed,
+    #[default]
     Confirmed,
     Finalized,
 }
 
-impl Default for CommitmentLevel {
-    fn default() -> Self {
-        Self::Confirmed
+impl CommitmentLevel {
+    /// Name used by the JSON-RPC API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Processed => "processed",
+            Self::Confirmed => "confirmed",
+            Self::Finalized => "finalized",
+        }
+    }
+
+    /// Parse a JSON-RPC commitment name
+    pub fn from_rpc_str(value: &str) -> Option<Self> {
+        match value {
+            "processed" => Some(Self::Processed),
+            "confirmed" => Some(Self::Confirmed),
+            "finalized" => Some(Self::Finalized),
+            _ => None,
+        }
     }
 }
 
@@ -76,6 +164,64 @@ pub struct AccountInfo {
     pub size: u64,
 }
 
+/// Server-side filter for `getProgramAccounts`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountFilter {
+    /// Account data equals `bytes` starting at `offset`
+    Memcmp { offset: usize, bytes: Vec<u8> },
+    /// Account data is exactly this many bytes long
+    DataSize(u64),
+}
+
+impl AccountFilter {
+    /// JSON form expected by the RPC API
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Memcmp { offset, bytes } => json!({
+                "memcmp": {"offset": offset, "bytes": bs58::encode(bytes).into_string()}
+            }),
+            Self::DataSize(size) => json!({"dataSize": size}),
+        }
+    }
+}
+
+/// Encoding requested for account data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountEncoding {
+    #[default]
+    Base64,
+    /// Zstandard-compressed, then base64; smaller for large or sparse accounts
+    Base64Zstd,
+}
+
+impl AccountEncoding {
+    /// Name used by the JSON-RPC API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base64 => "base64",
+            Self::Base64Zstd => "base64+zstd",
+        }
+    }
+}
+
+/// Byte range of account data to return
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataSlice {
+    pub offset: usize,
+    pub length: usize,
+}
+
+/// Options for `get_program_accounts`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramAccountsConfig {
+    /// Filters an account must match all of
+    pub filters: Vec<AccountFilter>,
+    /// Only return this range of each account's data
+    pub data_slice: Option<DataSlice>,
+    /// Encoding for account data on the wire
+    pub encoding: AccountEncoding,
+}
+
 /// Transaction information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionInfo {
@@ -101,6 +247,69 @@ pub enum TransactionStatus {
     Pending,
 }
 
+/// Recent blockhash together with the last block height it can land in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatestBlockhash {
+    /// Base58 blockhash
+    pub blockhash: String,
+    /// Transactions using this blockhash expire after this block height
+    pub last_valid_block_height: u64,
+}
+
+/// Signature status as reported by `getSignatureStatuses`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignatureStatus {
+    /// Slot the transaction was processed in
+    pub slot: u64,
+    /// Number of confirmations, `None` once the block is rooted
+    pub confirmations: Option<u64>,
+    /// Transaction error, if it failed
+    pub err: Option<Value>,
+    /// Commitment level reached so far
+    pub confirmation_status: Option<CommitmentLevel>,
+}
+
+impl SignatureStatus {
+    /// Parse one entry of a `getSignatureStatuses` result
+    fn from_json(value: &Value) -> Option<Self> {
+        if value.is_null() {
+            return None;
+        }
+        Some(Self {
+            slot: value.get("slot").and_then(|v| v.as_u64()).unwrap_or(0),
+            confirmations: value.get("confirmations").and_then(|v| v.as_u64()),
+            err: value.get("err").filter(|err| !err.is_null()).cloned(),
+            confirmation_status: value
+                .get("confirmationStatus")
+                .and_then(|v| v.as_str())
+                .and_then(CommitmentLevel::from_rpc_str),
+        })
+    }
+
+    /// Whether the transaction reached at least `commitment`
+    pub fn satisfies(&self, commitment: CommitmentLevel) -> bool {
+        match self.confirmation_status {
+            Some(level) => level >= commitment,
+            // Older nodes omit the status; no confirmation count means rooted
+            None => self.confirmations.is_none(),
+        }
+    }
+}
+
+/// Final result of waiting for a transaction to be confirmed
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmationOutcome {
+    /// The transaction reached the configured commitment
+    Confirmed {
+        slot: u64,
+        commitment: CommitmentLevel,
+    },
+    /// The transaction landed but failed
+    Failed { slot: u64, error: TransactionError },
+    /// The blockhash expired before the transaction landed
+    Expired { last_valid_block_height: u64 },
+}
+
 /// Program deployment information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgramDeployment {
@@ -114,11 +323,483 @@ pub struct ProgramDeployment {
     pub size: u64,
 }
 
+/// System program address
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+/// Length of a public key in bytes
+pub const PUBKEY_LENGTH: usize = 32;
+
+/// Length of an ed25519 signature in bytes
+pub const SIGNATURE_LENGTH: usize = 64;
+
+/// Raw public key bytes
+pub type Pubkey = [u8; PUBKEY_LENGTH];
+
+/// Raw signature bytes
+pub type Signature = [u8; SIGNATURE_LENGTH];
+
+/// Account referenced by an instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountMeta {
+    /// Account public key
+    pub pubkey: Pubkey,
+    /// Whether the account must sign the transaction
+    pub is_signer: bool,
+    /// Whether the instruction may modify the account
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    /// Create a writable account reference
+    pub fn new(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    /// Create a read-only account reference
+    pub fn new_readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
+/// Instruction with full account references, before compilation into a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// Program that executes the instruction
+    pub program_id: Pubkey,
+    /// Accounts passed to the program
+    pub accounts: Vec<AccountMeta>,
+    /// Opaque instruction data
+    pub data: Vec<u8>,
+}
+
+/// Legacy message header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageHeader {
+    /// Number of signatures required, also the number of leading signer keys
+    pub num_required_signatures: u8,
+    /// Number of read-only keys among the signer keys
+    pub num_readonly_signed_accounts: u8,
+    /// Number of read-only keys among the non-signer keys
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+/// Instruction referencing accounts by index into the message account keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    /// Index of the program account
+    pub program_id_index: u8,
+    /// Indexes of the instruction accounts
+    pub accounts: Vec<u8>,
+    /// Opaque instruction data
+    pub data: Vec<u8>,
+}
+
+/// Legacy transaction message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// Message header
+    pub header: MessageHeader,
+    /// All account keys used by the instructions, ordered by signer/writable group
+    pub account_keys: Vec<Pubkey>,
+    /// Recent blockhash
+    pub recent_blockhash: [u8; 32],
+    /// Compiled instructions
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+impl Message {
+    /// Compile instructions into a message paid for by `payer`
+    ///
+    /// Account keys follow the runtime's ordering: the fee payer first, then
+    /// writable signers, read-only signers, writable non-signers and read-only
+    /// non-signers, with each group sorted by key bytes.
+    pub fn new(
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        recent_blockhash: [u8; 32],
+    ) -> ServiceResult<Self> {
+        // (is_signer, is_writable) per key
+        let mut key_metas: BTreeMap<Pubkey, (bool, bool)> = BTreeMap::new();
+        for instruction in instructions {
+            key_metas.entry(instruction.program_id).or_default();
+            for meta in &instruction.accounts {
+                let entry = key_metas.entry(meta.pubkey).or_default();
+                entry.0 |= meta.is_signer;
+                entry.1 |= meta.is_writable;
+            }
+        }
+        key_metas.remove(payer);
+
+        let mut writable_signers = Vec::new();
+        let mut readonly_signers = Vec::new();
+        let mut writable_non_signers = Vec::new();
+        let mut readonly_non_signers = Vec::new();
+        for (key, (is_signer, is_writable)) in key_metas {
+            match (is_signer, is_writable) {
+                (true, true) => writable_signers.push(key),
+                (true, false) => readonly_signers.push(key),
+                (false, true) => writable_non_signers.push(key),
+                (false, false) => readonly_non_signers.push(key),
+            }
+        }
+
+        let header = MessageHeader {
+            num_required_signatures: u8::try_from(
+                1 + writable_signers.len() + readonly_signers.len(),
+            )
+            .map_err(|_| ServiceError::Validation("Too many signers".to_string()))?,
+            num_readonly_signed_accounts: readonly_signers.len() as u8,
+            num_readonly_unsigned_accounts: u8::try_from(readonly_non_signers.len())
+                .map_err(|_| ServiceError::Validation("Too many read-only accounts".to_string()))?,
+        };
+
+        let account_keys: Vec<Pubkey> = std::iter::once(*payer)
+            .chain(writable_signers)
+            .chain(readonly_signers)
+            .chain(writable_non_signers)
+            .chain(readonly_non_signers)
+            .collect();
+
+        if account_keys.len() > u8::MAX as usize + 1 {
+            return Err(ServiceError::Validation(format!(
+                "Message references {} accounts, maximum is 256",
+                account_keys.len()
+            )));
+        }
+
+        let index_of = |key: &Pubkey| -> u8 {
+            // Every key was inserted above, so the lookup cannot fail
+            account_keys
+                .iter()
+                .position(|k| k == key)
+                .unwrap_or_default() as u8
+        };
+
+        let instructions = instructions
+            .iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: index_of(&instruction.program_id),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| index_of(&meta.pubkey))
+                    .collect(),
+                data: instruction.data.clone(),
+            })
+            .collect();
+
+        Ok(Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        })
+    }
+
+    /// Keys that must sign the message, in signature order
+    pub fn signer_keys(&self) -> &[Pubkey] {
+        let count = (self.header.num_required_signatures as usize).min(self.account_keys.len());
+        &self.account_keys[..count]
+    }
+
+    /// Whether the key at `index` may be modified by the transaction
+    pub fn is_writable(&self, index: usize) -> bool {
+        let num_signers = self.header.num_required_signatures as usize;
+        if index < num_signers {
+            index < num_signers - self.header.num_readonly_signed_accounts as usize
+        } else {
+            let num_unsigned = self.account_keys.len().saturating_sub(num_signers);
+            index - num_signers
+                < num_unsigned.saturating_sub(self.header.num_readonly_unsigned_accounts as usize)
+        }
+    }
+
+    /// Serialize the message into the bytes that signers sign
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.push(self.header.num_required_signatures);
+        out.push(self.header.num_readonly_signed_accounts);
+        out.push(self.header.num_readonly_unsigned_accounts);
+
+        encode_compact_u16(self.account_keys.len() as u16, &mut out);
+        for key in &self.account_keys {
+            out.extend_from_slice(key);
+        }
+
+        out.extend_from_slice(&self.recent_blockhash);
+
+        encode_compact_u16(self.instructions.len() as u16, &mut out);
+        for instruction in &self.instructions {
+            out.push(instruction.program_id_index);
+            encode_compact_u16(instruction.accounts.len() as u16, &mut out);
+            out.extend_from_slice(&instruction.accounts);
+            encode_compact_u16(instruction.data.len() as u16, &mut out);
+            out.extend_from_slice(&instruction.data);
+        }
+
+        out
+    }
+
+    /// Deserialize a legacy message
+    pub fn deserialize(bytes: &[u8]) -> ServiceResult<Self> {
+        let mut reader = WireReader::new(bytes);
+        let message = Self::read(&mut reader)?;
+        reader.finish()?;
+        Ok(message)
+    }
+
+    fn read(reader: &mut WireReader<'_>) -> ServiceResult<Self> {
+        let header = MessageHeader {
+            num_required_signatures: reader.read_u8()?,
+            num_readonly_signed_accounts: reader.read_u8()?,
+            num_readonly_unsigned_accounts: reader.read_u8()?,
+        };
+        if header.num_required_signatures & 0x80 != 0 {
+            return Err(ServiceError::Validation(
+                "Versioned messages are not supported".to_string(),
+            ));
+        }
+
+        let num_keys = reader.read_compact_u16()? as usize;
+        let mut account_keys = Vec::with_capacity(num_keys);
+        for _ in 0..num_keys {
+            account_keys.push(reader.read_array::<PUBKEY_LENGTH>()?);
+        }
+
+        let recent_blockhash = reader.read_array::<32>()?;
+
+        let num_instructions = reader.read_compact_u16()? as usize;
+        let mut instructions = Vec::with_capacity(num_instructions);
+        for _ in 0..num_instructions {
+            let program_id_index = reader.read_u8()?;
+            let num_accounts = reader.read_compact_u16()? as usize;
+            let accounts = reader.read_bytes(num_accounts)?.to_vec();
+            let data_len = reader.read_compact_u16()? as usize;
+            let data = reader.read_bytes(data_len)?.to_vec();
+            instructions.push(CompiledInstruction {
+                program_id_index,
+                accounts,
+                data,
+            });
+        }
+
+        Ok(Self {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        })
+    }
+}
+
+/// Legacy transaction: signatures followed by the message they sign
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// One signature per required signer, in signer key order
+    pub signatures: Vec<Signature>,
+    /// Signed message
+    pub message: Message,
+}
+
+impl Transaction {
+    /// Create a transaction with zeroed signature slots for every required signer
+    pub fn new_unsigned(message: Message) -> Self {
+        Self {
+            signatures: vec![
+                [0u8; SIGNATURE_LENGTH];
+                message.header.num_required_signatures as usize
+            ],
+            message,
+        }
+    }
+
+    /// Sign the message with `keypair`, filling in that signer's signature slot
+    pub fn sign(&mut self, keypair: &Keypair) -> ServiceResult<()> {
+        let pubkey = keypair.pubkey();
+        let index = self
+            .message
+            .signer_keys()
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or_else(|| {
+                ServiceError::Validation(format!(
+                    "{} is not a required signer of this transaction",
+                    keypair.address()
+                ))
+            })?;
+
+        let num_signers = self.message.header.num_required_signatures as usize;
+        self.signatures.resize(num_signers, [0u8; SIGNATURE_LENGTH]);
+        self.signatures[index] = keypair.sign(&self.message.serialize());
+        Ok(())
+    }
+
+    /// Check every signature against its signer key
+    pub fn verify_signatures(&self) -> bool {
+        let message_data = self.message.serialize();
+        let signer_keys = self.message.signer_keys();
+
+        self.signatures.len() == signer_keys.len()
+            && signer_keys
+                .iter()
+                .zip(&self.signatures)
+                .all(|(key, signature)| crypto::verify(key, &message_data, signature))
+    }
+
+    /// Serialize the transaction into wire format
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode_compact_u16(self.signatures.len() as u16, &mut out);
+        for signature in &self.signatures {
+            out.extend_from_slice(signature);
+        }
+        out.extend_from_slice(&self.message.serialize());
+        out
+    }
+
+    /// Deserialize a transaction from wire format
+    pub fn deserialize(bytes: &[u8]) -> ServiceResult<Self> {
+        let mut reader = WireReader::new(bytes);
+
+        let num_signatures = reader.read_compact_u16()? as usize;
+        let mut signatures = Vec::with_capacity(num_signatures);
+        for _ in 0..num_signatures {
+            signatures.push(reader.read_array::<SIGNATURE_LENGTH>()?);
+        }
+
+        let message = Message::read(&mut reader)?;
+        reader.finish()?;
+
+        if signatures.len() != message.header.num_required_signatures as usize {
+            return Err(ServiceError::Validation(format!(
+                "Transaction has {} signatures but requires {}",
+                signatures.len(),
+                message.header.num_required_signatures
+            )));
+        }
+
+        Ok(Self {
+            signatures,
+            message,
+        })
+    }
+}
+
+/// Append `value` in Solana's compact-u16 ("short vec") encoding
+pub fn encode_compact_u16(value: u16, out: &mut Vec<u8>) {
+    let mut remaining = value;
+    loop {
+        let mut byte = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+        if remaining == 0 {
+            out.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
+/// Decode a compact-u16 value, returning it with the number of bytes consumed
+pub fn decode_compact_u16(bytes: &[u8]) -> ServiceResult<(u16, usize)> {
+    let mut value: u32 = 0;
+    for (index, byte) in bytes.iter().take(3).enumerate() {
+        // A zero continuation byte would be an alias of a shorter encoding
+        if index > 0 && *byte == 0 {
+            return Err(ServiceError::Validation(
+                "Non-canonical compact-u16 encoding".to_string(),
+            ));
+        }
+        if index == 2 && *byte > 0x03 {
+            return Err(ServiceError::Validation("compact-u16 overflow".to_string()));
+        }
+
+        value |= ((*byte & 0x7f) as u32) << (index * 7);
+        if byte & 0x80 == 0 {
+            return Ok((value as u16, index + 1));
+        }
+    }
+
+    Err(ServiceError::Validation(
+        "Truncated compact-u16 value".to_string(),
+    ))
+}
+
+/// Cursor over serialized transaction bytes
+struct WireReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> WireReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> ServiceResult<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len());
+        match end {
+            Some(end) => {
+                let slice = &self.bytes[self.offset..end];
+                self.offset = end;
+                Ok(slice)
+            }
+            None => Err(ServiceError::Validation(
+                "Truncated transaction data".to_string(),
+            )),
+        }
+    }
+
+    fn read_u8(&mut self) -> ServiceResult<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_array<const N: usize>(&mut self) -> ServiceResult<[u8; N]> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    fn read_compact_u16(&mut self) -> ServiceResult<u16> {
+        let (value, consumed) = decode_compact_u16(&self.bytes[self.offset..])?;
+        self.offset += consumed;
+        Ok(value)
+    }
+
+    fn finish(&self) -> ServiceResult<()> {
+        if self.offset == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(ServiceError::Validation(format!(
+                "{} trailing bytes after transaction data",
+                self.bytes.len() - self.offset
+            )))
+        }
+    }
+}
+
 /// Service for interacting with Solana network via RPC
 pub struct SolanaRpcService {
     config: SolanaRpcConfig,
     client: Client,
-    request_id: u64,
+    request_id: AtomicU64,
+}
+
+impl Default for SolanaRpcService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SolanaRpcService {
@@ -137,95 +818,156 @@ impl SolanaRpcService {
         Self {
             config,
             client,
-            request_id: 1,
+            request_id: AtomicU64::new(1),
         }
     }
 
     /// Get account information
-    pub async fn get_account(&mut self, address: &str) -> ServiceResult<AccountInfo> {
-        let params = json!([
-            address,
-            {
-                "encoding": "base64"
-            }
-        ]);
-
-        let response = self.make_request("getAccountInfo", params).await?;
+    pub async fn get_account(&self, address: &str) -> ServiceResult<AccountInfo> {
+        let response = self
+            .make_request("getAccountInfo", account_info_params(address))
+            .await?;
+        parse_account_info(address, &response)
+    }
 
-        if let Some(account_data) = response.get("result").and_then(|r| r.get("value")) {
-            let balance = account_data.get("lamports")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
+    /// Get information for several accounts in one batch request
+    ///
+    /// Results are in the same order as `addresses`; a missing account or a
+    /// failed call only fails its own entry.
+    pub async fn get_accounts(
+        &self,
+        addresses: &[&str],
+    ) -> ServiceResult<Vec<ServiceResult<AccountInfo>>> {
+        let calls = addresses
+            .iter()
+            .map(|address| ("getAccountInfo", account_info_params(address)))
+            .collect();
+
+        let responses = self.batch_request(calls).await?;
+        Ok(addresses
+            .iter()
+            .zip(responses)
+            .map(|(address, response)| {
+                response.and_then(|response| parse_account_info(address, &response))
+            })
+            .collect())
+    }
 
-            let owner = account_data.get("owner")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
+    /// Get account balance
+    pub async fn get_balance(&self, address: &str) -> ServiceResult<u64> {
+        let response = self.make_request("getBalance", json!([address])).await?;
+        parse_balance(&response)
+    }
 
-            let data_base64 = account_data.get("data")
-                .and_then(|v| v.as_array())
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
+    /// List the accounts owned by a program
+    ///
+    /// With a data slice, `data` and `size` cover only the requested range.
+    pub async fn get_program_accounts(
+        &self,
+        program_id: &str,
+        config: &ProgramAccountsConfig,
+    ) -> ServiceResult<Vec<AccountInfo>> {
+        if !validate_address(program_id) {
+            return Err(ServiceError::Validation(format!(
+                "Invalid program id: {}",
+                program_id
+            )));
+        }
 
-            let data = base64::decode(data_base64).unwrap_or_default();
+        let mut options = json!({
+            "encoding": config.encoding.as_str(),
+            "commitment": self.config.commitment.as_str()
+        });
+        if !config.filters.is_empty() {
+            options["filters"] = config.filters.iter().map(AccountFilter::to_json).collect();
+        }
+        if let Some(slice) = config.data_slice {
+            options["dataSlice"] = json!({"offset": slice.offset, "length": slice.length});
+        }
 
-            let executable = account_data.get("executable")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
+        let response = self
+            .make_request("getProgramAccounts", json!([program_id, options]))
+            .await?;
+        parse_program_accounts(&response)
+    }
 
-            let rent_epoch = account_data.get("rentEpoch")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
-
-            Ok(AccountInfo {
-                address: address.to_string(),
-                balance,
-                owner,
-                data: data.clone(),
-                executable,
-                rent_epoch,
-                size: data.len() as u64,
-            })
-        } else {
-            Err(ServiceError::Network(format!("Account not found: {}", address)))
-        }
+    /// Get the balances of several accounts in one batch request
+    ///
+    /// Results are in the same order as `addresses`.
+    pub async fn get_balances(&self, addresses: &[&str]) -> ServiceResult<Vec<ServiceResult<u64>>> {
+        let calls = addresses
+            .iter()
+            .map(|address| ("getBalance", json!([address])))
+            .collect();
+
+        let responses = self.batch_request(calls).await?;
+        Ok(responses
+            .into_iter()
+            .map(|response| response.and_then(|response| parse_balance(&response)))
+            .collect())
     }
 
-    /// Get account balance
-    pub async fn get_balance(&mut self, address: &str) -> ServiceResult<u64> {
-        let params = json!([address]);
+    /// Get the lamports an account with `data_len` bytes needs to be rent exempt
+    pub async fn get_minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> ServiceResult<u64> {
+        let params = json!([data_len, { "commitment": self.config.commitment.as_str() }]);
+        let response = self
+            .make_request("getMinimumBalanceForRentExemption", params)
+            .await?;
+        parse_rent_exemption(&response)
+    }
 
-        let response = self.make_request("getBalance", params).await?;
+    /// Get the rent-exempt minimums for several data sizes in one batch request
+    ///
+    /// Results are in the same order as `data_lens`.
+    pub async fn get_minimum_balances_for_rent_exemption(
+        &self,
+        data_lens: &[usize],
+    ) -> ServiceResult<Vec<ServiceResult<u64>>> {
+        let calls = data_lens
+            .iter()
+            .map(|data_len| {
+                (
+                    "getMinimumBalanceForRentExemption",
+                    json!([data_len, { "commitment": self.config.commitment.as_str() }]),
+                )
+            })
+            .collect();
 
-        if let Some(balance) = response.get("result")
-            .and_then(|r| r.get("value"))
-            .and_then(|v| v.as_u64())
-        {
-            Ok(balance)
-        } else {
-            Err(ServiceError::Network("Failed to get balance".to_string()))
-        }
+        let responses = self.batch_request(calls).await?;
+        Ok(responses
+            .into_iter()
+            .map(|response| response.and_then(|response| parse_rent_exemption(&response)))
+            .collect())
     }
 
-    /// Send transaction
-    pub async fn send_transaction(&mut self, transaction: &[u8]) -> ServiceResult<String> {
-        let transaction_base64 = base64::encode(transaction);
-        let params = json!([transaction_base64]);
+    /// Send a serialized transaction
+    pub async fn send_transaction(&self, transaction: &[u8]) -> ServiceResult<String> {
+        let transaction_base64 = base64::engine::general_purpose::STANDARD.encode(transaction);
+        let params = json!([
+            transaction_base64,
+            {
+                "encoding": "base64",
+                "skipPreflight": !self.config.preflight_checks,
+                "preflightCommitment": self.config.commitment.as_str()
+            }
+        ]);
 
         let response = self.make_request("sendTransaction", params).await?;
 
-        if let Some(signature) = response.get("result")
-            .and_then(|v| v.as_str())
-        {
+        if let Some(signature) = response.get("result").and_then(|v| v.as_str()) {
             Ok(signature.to_string())
         } else {
-            Err(ServiceError::Network("Failed to send transaction".to_string()))
+            Err(ServiceError::Network(
+                "Failed to send transaction".to_string(),
+            ))
         }
     }
 
     /// Get transaction information
-    pub async fn get_transaction(&mut self, signature: &str) -> ServiceResult<TransactionInfo> {
+    pub async fn get_transaction(&self, signature: &str) -> ServiceResult<TransactionInfo> {
         let params = json!([
             signature,
             {
@@ -236,18 +978,13 @@ impl SolanaRpcService {
         let response = self.make_request("getTransaction", params).await?;
 
         if let Some(tx_data) = response.get("result") {
-            let slot = tx_data.get("slot")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
+            let slot = tx_data.get("slot").and_then(|v| v.as_u64()).unwrap_or(0);
 
-            let block_time = tx_data.get("blockTime")
-                .and_then(|v| v.as_i64());
+            let block_time = tx_data.get("blockTime").and_then(|v| v.as_i64());
 
             let default_meta = json!({});
             let meta = tx_data.get("meta").unwrap_or(&default_meta);
-            let fee = meta.get("fee")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
+            let fee = meta.get("fee").and_then(|v| v.as_u64()).unwrap_or(0);
 
             let err = meta.get("err");
             let status = if err.is_some() && err.unwrap().is_null() {
@@ -258,10 +995,9 @@ impl SolanaRpcService {
                 TransactionStatus::Pending
             };
 
-            let error = if status == TransactionStatus::Failed {
-                Some(format!("{:?}", err))
-            } else {
-                None
+            let error = match err {
+                Some(err) if !err.is_null() => Some(TransactionError::from_json(err).to_string()),
+                _ => None,
             };
 
             Ok(TransactionInfo {
@@ -273,7 +1009,10 @@ impl SolanaRpcService {
                 fee,
             })
         } else {
-            Err(ServiceError::Network(format!("Transaction not found: {}", signature)))
+            Err(ServiceError::Network(format!(
+                "Transaction not found: {}",
+                signature
+            )))
         }
     }
 
@@ -287,7 +1026,10 @@ impl SolanaRpcService {
 
         // For now, return a mock deployment
         let program_id = generate_program_id(program_data);
-        let signature = format!("mock_signature_{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0));
+        let signature = format!(
+            "mock_signature_{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+        );
 
         Ok(ProgramDeployment {
             program_id,
@@ -298,38 +1040,232 @@ impl SolanaRpcService {
     }
 
     /// Get latest blockhash
-    pub async fn get_latest_blockhash(&mut self) -> ServiceResult<String> {
+    pub async fn get_latest_blockhash(&self) -> ServiceResult<String> {
+        Ok(self.get_latest_blockhash_with_expiry().await?.blockhash)
+    }
+
+    /// Get latest blockhash and the last block height it is valid for
+    pub async fn get_latest_blockhash_with_expiry(&self) -> ServiceResult<LatestBlockhash> {
         let params = json!([{
-            "commitment": format!("{:?}", self.config.commitment).to_lowercase()
+            "commitment": self.config.commitment.as_str()
         }]);
 
         let response = self.make_request("getLatestBlockhash", params).await?;
+        let value = response.get("result").and_then(|r| r.get("value"));
 
-        if let Some(blockhash) = response.get("result")
-            .and_then(|r| r.get("value"))
+        let blockhash = value
             .and_then(|v| v.get("blockhash"))
+            .and_then(|v| v.as_str());
+        let last_valid_block_height = value
+            .and_then(|v| v.get("lastValidBlockHeight"))
+            .and_then(|v| v.as_u64());
+
+        match (blockhash, last_valid_block_height) {
+            (Some(blockhash), Some(last_valid_block_height)) => Ok(LatestBlockhash {
+                blockhash: blockhash.to_string(),
+                last_valid_block_height,
+            }),
+            _ => Err(ServiceError::Network(
+                "Failed to get latest blockhash".to_string(),
+            )),
+        }
+    }
+
+    /// Get the current block height
+    pub async fn get_block_height(&self) -> ServiceResult<u64> {
+        let params = json!([{
+            "commitment": self.config.commitment.as_str()
+        }]);
+
+        let response = self.make_request("getBlockHeight", params).await?;
+
+        response
+            .get("result")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| ServiceError::Network("Failed to get block height".to_string()))
+    }
+
+    /// Check that the node reports itself healthy
+    ///
+    /// Nodes that are behind answer with an RPC error instead of `"ok"`.
+    pub async fn get_health(&self) -> ServiceResult<()> {
+        let response = self.make_request("getHealth", json!([])).await?;
+
+        match response.get("result").and_then(|v| v.as_str()) {
+            Some("ok") => Ok(()),
+            _ => Err(ServiceError::Network("Node is not healthy".to_string())),
+        }
+    }
+
+    /// Get the `solana-core` version the node reports
+    pub async fn get_version(&self) -> ServiceResult<String> {
+        let response = self.make_request("getVersion", json!([])).await?;
+
+        response
+            .get("result")
+            .and_then(|r| r.get("solana-core"))
             .and_then(|v| v.as_str())
-        {
-            Ok(blockhash.to_string())
-        } else {
-            Err(ServiceError::Network("Failed to get latest blockhash".to_string()))
+            .map(str::to_string)
+            .ok_or_else(|| ServiceError::Network("Failed to get version".to_string()))
+    }
+
+    /// Get the statuses of recently submitted transactions
+    ///
+    /// Entries are `None` for signatures the node has not seen.
+    pub async fn get_signature_statuses(
+        &self,
+        signatures: &[&str],
+    ) -> ServiceResult<Vec<Option<SignatureStatus>>> {
+        let params = json!([signatures, { "searchTransactionHistory": false }]);
+
+        let response = self.make_request("getSignatureStatuses", params).await?;
+
+        let statuses = response
+            .get("result")
+            .and_then(|r| r.get("value"))
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| ServiceError::Network("Failed to get signature statuses".to_string()))?;
+
+        Ok(statuses.iter().map(SignatureStatus::from_json).collect())
+    }
+
+    /// Wait until a transaction reaches the configured commitment
+    ///
+    /// Polls `getSignatureStatuses` until the transaction reaches
+    /// `config.commitment`, fails, or can no longer land because the block
+    /// height passed `last_valid_block_height` of its blockhash.
+    ///
+    /// Up to `config.max_retries` consecutive failed polls are retried; after
+    /// that the poll error is returned and the outcome is unknown.
+    pub async fn confirm_transaction(
+        &self,
+        signature: &str,
+        last_valid_block_height: u64,
+    ) -> ServiceResult<ConfirmationOutcome> {
+        let poll_interval = std::time::Duration::from_millis(self.config.confirm_poll_interval_ms);
+        let mut failed_polls = 0;
+
+        loop {
+            match self
+                .poll_confirmation(signature, last_valid_block_height)
+                .await
+            {
+                Ok(Some(outcome)) => return Ok(outcome),
+                Ok(None) => failed_polls = 0,
+                Err(e) if failed_polls < self.config.max_retries => {
+                    failed_polls += 1;
+                    tracing::warn!("Polling status of {} failed ({}), retrying", signature, e);
+                }
+                Err(e) => return Err(e),
+            }
+
+            tokio::time::sleep(poll_interval).await;
         }
     }
 
+    /// Check a transaction once, returning `None` while it is still in flight
+    async fn poll_confirmation(
+        &self,
+        signature: &str,
+        last_valid_block_height: u64,
+    ) -> ServiceResult<Option<ConfirmationOutcome>> {
+        // Read the block height before the status so a transaction that
+        // lands in between is never reported as expired
+        let expired = self.get_block_height().await? > last_valid_block_height;
+        let status = self
+            .get_signature_statuses(&[signature])
+            .await?
+            .into_iter()
+            .next()
+            .flatten();
+
+        Ok(match status {
+            Some(status) if status.err.is_some() => Some(ConfirmationOutcome::Failed {
+                slot: status.slot,
+                error: TransactionError::from_json(status.err.as_ref().unwrap_or(&Value::Null)),
+            }),
+            Some(status) if status.satisfies(self.config.commitment) => {
+                Some(ConfirmationOutcome::Confirmed {
+                    slot: status.slot,
+                    commitment: status
+                        .confirmation_status
+                        .unwrap_or(CommitmentLevel::Finalized),
+                })
+            }
+            // Landed but not yet at the requested commitment, it can no longer expire
+            Some(_) => None,
+            None if expired => Some(ConfirmationOutcome::Expired {
+                last_valid_block_height,
+            }),
+            None => None,
+        })
+    }
+
+    /// Allocate the id for the next JSON-RPC request
+    fn next_request_id(&self) -> u64 {
+        self.request_id.fetch_add(1, Ordering::Relaxed)
+    }
+
     /// Make a JSON-RPC request
-    async fn make_request(&mut self, method: &str, params: Value) -> ServiceResult<Value> {
+    async fn make_request(&self, method: &str, params: Value) -> ServiceResult<Value> {
         let request_body = json!({
             "jsonrpc": "2.0",
-            "id": self.request_id,
+            "id": self.next_request_id(),
             "method": method,
             "params": params
         });
 
-        self.request_id += 1;
+        let response_json = self.post(&request_body).await?;
+
+        // Check for RPC error
+        if let Some(error) = response_json.get("error") {
+            return Err(ServiceError::Rpc(RpcError::from_json(error)));
+        }
 
-        let response = self.client
+        Ok(response_json)
+    }
+
+    /// Send several JSON-RPC calls as batches of at most `max_batch_size`
+    ///
+    /// Returns one response per call, in call order, matched back by request
+    /// id since nodes may answer a batch in any order. An RPC error only fails
+    /// its own call; the outer error is reserved for transport failures.
+    pub async fn batch_request(
+        &self,
+        calls: Vec<(&str, Value)>,
+    ) -> ServiceResult<Vec<ServiceResult<Value>>> {
+        let mut results = Vec::with_capacity(calls.len());
+
+        for chunk in calls.chunks(self.config.max_batch_size.max(1)) {
+            let ids: Vec<u64> = chunk.iter().map(|_| self.next_request_id()).collect();
+            let request_body = Value::Array(
+                chunk
+                    .iter()
+                    .zip(&ids)
+                    .map(|((method, params), id)| {
+                        json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "method": method,
+                            "params": params
+                        })
+                    })
+                    .collect(),
+            );
+
+            let response_json = self.post(&request_body).await?;
+            results.extend(match_batch_responses(&ids, response_json)?);
+        }
+
+        Ok(results)
+    }
+
+    /// POST a JSON-RPC body and parse the JSON reply
+    async fn post(&self, body: &Value) -> ServiceResult<Value> {
+        let response = self
+            .client
             .post(&self.config.rpc_url)
-            .json(&request_body)
+            .json(body)
             .send()
             .await
             .map_err(|e| ServiceError::Http(e.to_string()))?;
@@ -339,40 +1275,31 @@ impl SolanaRpcService {
             .await
             .map_err(|e| ServiceError::Http(e.to_string()))?;
 
-        let response_json: Value = serde_json::from_str(&response_text)
-            .map_err(|e| ServiceError::Serialization(e))?;
-
-        // Check for RPC error
-        if let Some(error) = response_json.get("error") {
-            let message = error.get("message")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown RPC error");
-
-            return Err(ServiceError::Network(format!("RPC error: {}", message)));
-        }
-
-        Ok(response_json)
+        serde_json::from_str(&response_text).map_err(ServiceError::Serialization)
     }
 
     /// Airdrop SOL to an account (devnet/testnet only)
-    pub async fn request_airdrop(&mut self, address: &str, lamports: u64) -> ServiceResult<String> {
+    pub async fn request_airdrop(&self, address: &str, lamports: u64) -> ServiceResult<String> {
         let params = json!([address, lamports]);
 
         let response = self.make_request("requestAirdrop", params).await?;
 
-        if let Some(signature) = response.get("result")
-            .and_then(|v| v.as_str())
-        {
+        if let Some(signature) = response.get("result").and_then(|v| v.as_str()) {
             Ok(signature.to_string())
         } else {
-            Err(ServiceError::Network("Failed to request airdrop".to_string()))
+            Err(ServiceError::Network(
+                "Failed to request airdrop".to_string(),
+            ))
         }
     }
 }
 
 impl Service for SolanaRpcService {
     fn initialize(&mut self) -> ServiceResult<()> {
-        tracing::info!("Solana RPC service initialized with endpoint: {}", self.config.rpc_url);
+        tracing::info!(
+            "Solana RPC service initialized with endpoint: {}",
+            self.config.rpc_url
+        );
         Ok(())
     }
 
@@ -423,6 +1350,7 @@ impl Configurable for SolanaRpcService {
     type Config = SolanaRpcConfig;
 
     fn configure(&mut self, config: Self::Config) -> ServiceResult<()> {
+        config.validate()?;
         self.config = config.clone();
 
         // Recreate client with new timeout
@@ -431,7 +1359,10 @@ impl Configurable for SolanaRpcService {
             .build()
             .unwrap_or_default();
 
-        tracing::info!("Solana RPC service reconfigured with endpoint: {}", config.rpc_url);
+        tracing::info!(
+            "Solana RPC service reconfigured with endpoint: {}",
+            config.rpc_url
+        );
         Ok(())
     }
 
@@ -440,6 +1371,193 @@ impl Configurable for SolanaRpcService {
     }
 }
 
+/// Parameters for a base64 `getAccountInfo` call
+fn account_info_params(address: &str) -> Value {
+    json!([
+        address,
+        {
+            "encoding": "base64"
+        }
+    ])
+}
+
+/// Parse a `getAccountInfo` response
+fn parse_account_info(address: &str, response: &Value) -> ServiceResult<AccountInfo> {
+    let account_data = response
+        .get("result")
+        .and_then(|r| r.get("value"))
+        .filter(|v| !v.is_null())
+        .ok_or_else(|| ServiceError::Network(format!("Account not found: {}", address)))?;
+
+    account_info_from_value(address, account_data)
+}
+
+/// Build account information from an encoded account object
+pub(crate) fn account_info_from_value(
+    address: &str,
+    account_data: &Value,
+) -> ServiceResult<AccountInfo> {
+    let balance = account_data
+        .get("lamports")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    let owner = account_data
+        .get("owner")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let data = match account_data.get("data") {
+        Some(data) => decode_account_data(data)?,
+        None => Vec::new(),
+    };
+
+    let executable = account_data
+        .get("executable")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let rent_epoch = account_data
+        .get("rentEpoch")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    Ok(AccountInfo {
+        address: address.to_string(),
+        balance,
+        owner,
+        size: data.len() as u64,
+        data,
+        executable,
+        rent_epoch,
+    })
+}
+
+/// Decode the `data` field of an account object
+///
+/// Handles `[payload, encoding]` pairs for `base64`, `base64+zstd` and
+/// `base58`, and the legacy bare Base58 string.
+fn decode_account_data(data: &Value) -> ServiceResult<Vec<u8>> {
+    let (payload, encoding) = match data {
+        Value::String(payload) => (payload.as_str(), "base58"),
+        Value::Array(pair) => (
+            pair.first().and_then(|v| v.as_str()).unwrap_or_default(),
+            pair.get(1).and_then(|v| v.as_str()).unwrap_or("base64"),
+        ),
+        _ => {
+            return Err(ServiceError::Network(
+                "Unsupported account data format".to_string(),
+            ))
+        }
+    };
+
+    let invalid = |e: &dyn std::fmt::Display| {
+        ServiceError::Network(format!("Invalid {} account data: {}", encoding, e))
+    };
+    match encoding {
+        "base64" => base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| invalid(&e)),
+        "base64+zstd" => {
+            let compressed = base64::engine::general_purpose::STANDARD
+                .decode(payload)
+                .map_err(|e| invalid(&e))?;
+            let mut decoder =
+                ruzstd::StreamingDecoder::new(compressed.as_slice()).map_err(|e| invalid(&e))?;
+            let mut data = Vec::new();
+            std::io::Read::read_to_end(&mut decoder, &mut data).map_err(|e| invalid(&e))?;
+            Ok(data)
+        }
+        "base58" => bs58::decode(payload).into_vec().map_err(|e| invalid(&e)),
+        other => Err(ServiceError::Network(format!(
+            "Unsupported account data encoding: {}",
+            other
+        ))),
+    }
+}
+
+/// Parse a `getProgramAccounts` response, with or without `withContext`
+fn parse_program_accounts(response: &Value) -> ServiceResult<Vec<AccountInfo>> {
+    let result = response
+        .get("result")
+        .ok_or_else(|| ServiceError::Network("Failed to get program accounts".to_string()))?;
+    let entries = result
+        .get("value")
+        .unwrap_or(result)
+        .as_array()
+        .ok_or_else(|| {
+            ServiceError::Network("Program accounts response is not a list".to_string())
+        })?;
+
+    entries
+        .iter()
+        .map(|entry| {
+            let pubkey = entry
+                .get("pubkey")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    ServiceError::Network("Program account without pubkey".to_string())
+                })?;
+            let account = entry.get("account").ok_or_else(|| {
+                ServiceError::Network(format!("Program account {} without data", pubkey))
+            })?;
+            account_info_from_value(pubkey, account)
+        })
+        .collect()
+}
+
+/// Parse a `getBalance` response
+fn parse_balance(response: &Value) -> ServiceResult<u64> {
+    response
+        .get("result")
+        .and_then(|r| r.get("value"))
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| ServiceError::Network("Failed to get balance".to_string()))
+}
+
+fn parse_rent_exemption(response: &Value) -> ServiceResult<u64> {
+    response
+        .get("result")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| {
+            ServiceError::Network("Failed to get minimum balance for rent exemption".to_string())
+        })
+}
+
+/// Order batch responses by request id and split out per-call RPC errors
+fn match_batch_responses(ids: &[u64], response: Value) -> ServiceResult<Vec<ServiceResult<Value>>> {
+    let responses = match response {
+        Value::Array(responses) => responses,
+        // Nodes that reject the whole batch answer with a single error object
+        other => {
+            return Err(match other.get("error") {
+                Some(error) => ServiceError::Rpc(RpcError::from_json(error)),
+                None => ServiceError::Network("Batch response is not an array".to_string()),
+            })
+        }
+    };
+
+    let mut by_id: HashMap<u64, Value> = responses
+        .into_iter()
+        .filter_map(|response| Some((response.get("id")?.as_u64()?, response)))
+        .collect();
+
+    Ok(ids
+        .iter()
+        .map(|id| match by_id.remove(id) {
+            Some(response) => match response.get("error") {
+                Some(error) => Err(ServiceError::Rpc(RpcError::from_json(error))),
+                None => Ok(response),
+            },
+            None => Err(ServiceError::Network(format!(
+                "No response for batch request {}",
+                id
+            ))),
+        })
+        .collect())
+}
+
 /// Generate a mock program ID from program data
 fn generate_program_id(program_data: &[u8]) -> String {
     let mut hasher = Sha256::new();
@@ -451,12 +1569,78 @@ fn generate_program_id(program_data: &[u8]) -> String {
     bs58::encode(hash_bytes).into_string()
 }
 
+/// Decode a base58 address into public key bytes
+pub fn decode_pubkey(address: &str) -> ServiceResult<Pubkey> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|_| ServiceError::Validation(format!("Invalid base58 address: {}", address)))?;
+
+    Pubkey::try_from(bytes.as_slice()).map_err(|_| {
+        ServiceError::Validation(format!(
+            "Address must decode to {} bytes, got {}: {}",
+            PUBKEY_LENGTH,
+            bytes.len(),
+            address
+        ))
+    })
+}
+
+/// Decode a base58 blockhash into raw bytes
+pub fn decode_blockhash(blockhash: &str) -> ServiceResult<[u8; 32]> {
+    decode_pubkey(blockhash)
+        .map_err(|_| ServiceError::Validation(format!("Invalid blockhash: {}", blockhash)))
+}
+
+/// Build a system program transfer instruction
+pub fn system_transfer_instruction(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    // SystemInstruction::Transfer is variant 2, encoded as a little-endian u32
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: [0u8; PUBKEY_LENGTH],
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+        data,
+    }
+}
+
 /// Create a basic transfer transaction
-pub fn create_transfer_transaction(from: &str, to: &str, lamports: u64) -> ServiceResult<Vec<u8>> {
-    // In a real implementation, this would create a proper Solana transaction
-    // For now, return a mock transaction
-    let transaction_data = format!("transfer:{}:{}:{}", from, to, lamports);
-    Ok(transaction_data.into_bytes())
+///
+/// Returns the serialized transaction with a zeroed signature slot for `from`,
+/// which must be filled in before the transaction is sent.
+pub fn create_transfer_transaction(
+    from: &str,
+    to: &str,
+    lamports: u64,
+    recent_blockhash: &str,
+) -> ServiceResult<Vec<u8>> {
+    let from = decode_pubkey(from)?;
+    let to = decode_pubkey(to)?;
+    let recent_blockhash = decode_blockhash(recent_blockhash)?;
+
+    let instruction = system_transfer_instruction(&from, &to, lamports);
+    let message = Message::new(&[instruction], &from, recent_blockhash)?;
+
+    Ok(Transaction::new_unsigned(message).serialize())
+}
+
+/// Create a transfer transaction signed by `from`
+pub fn create_signed_transfer_transaction(
+    from: &Keypair,
+    to: &str,
+    lamports: u64,
+    recent_blockhash: &str,
+) -> ServiceResult<Vec<u8>> {
+    let to = decode_pubkey(to)?;
+    let recent_blockhash = decode_blockhash(recent_blockhash)?;
+
+    let instruction = system_transfer_instruction(&from.pubkey(), &to, lamports);
+    let message = Message::new(&[instruction], &from.pubkey(), recent_blockhash)?;
+
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.sign(from)?;
+    Ok(transaction.serialize())
 }
 
 /// Create a program deployment transaction
@@ -483,6 +1667,110 @@ pub fn sol_to_lamports(sol: f64) -> u64 {
     (sol * 1_000_000_000.0) as u64
 }
 
+/// Minimal JSON-RPC HTTP server for tests
+#[cfg(test)]
+pub(crate) mod mock_rpc {
+    use serde_json::{json, Value};
+    use std::collections::{HashMap, VecDeque};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Port of a server URL returned by `spawn`
+    pub fn port_of(url: &str) -> u16 {
+        url.rsplit(':').next().unwrap().parse().unwrap()
+    }
+
+    /// A port nothing listens on
+    pub fn closed_port() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    /// Serve canned replies per method and return the server URL
+    ///
+    /// Each reply is merged into the JSON-RPC envelope, so pass
+    /// `json!({"result": ...})` or `json!({"error": ...})`. Replies for a
+    /// method are used in order and the last one repeats.
+    pub fn spawn(replies: Vec<(&str, Value)>) -> String {
+        let mut queues: HashMap<String, VecDeque<Value>> = HashMap::new();
+        for (method, reply) in replies {
+            queues
+                .entry(method.to_string())
+                .or_default()
+                .push_back(reply);
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let Some(request) = read_request(&mut stream) else {
+                    continue;
+                };
+
+                let mut reply = |request: &Value| {
+                    let method = request["method"].as_str().unwrap_or_default();
+                    let queue = queues.get_mut(method);
+                    let reply = match queue {
+                        Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+                        Some(queue) => queue.front().cloned().unwrap_or(Value::Null),
+                        None => json!({"error": {"code": -32601, "message": "Method not found"}}),
+                    };
+                    let mut envelope = json!({"jsonrpc": "2.0", "id": request["id"].clone()});
+                    if let (Some(envelope), Some(reply)) =
+                        (envelope.as_object_mut(), reply.as_object())
+                    {
+                        envelope.extend(reply.clone());
+                    }
+                    envelope
+                };
+
+                // Batches get one reply per request, in order
+                let body = match &request {
+                    Value::Array(requests) => {
+                        Value::Array(requests.iter().map(&mut reply).collect())
+                    }
+                    single => reply(single),
+                };
+
+                let body = body.to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        url
+    }
+
+    fn read_request(stream: &mut std::net::TcpStream) -> Option<Value> {
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).ok()?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().ok()?;
+                }
+            }
+        }
+
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).ok()?;
+        serde_json::from_slice(&body).ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -508,14 +1796,180 @@ mod tests {
         assert_eq!(back_to_lamports, lamports);
     }
 
+    // Reference bytes below were produced by solana-sdk for keypairs derived
+    // from the seeds [1; 32] (payer) and [2; 32] (recipient)
+    const PAYER: &str = "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9";
+    const RECIPIENT: &str = "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu";
+    const BLOCKHASH: &str = "US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx";
+
+    const SIGNED_TRANSFER_HEX: &str = "0181d62b0855504875b5632a1d5a4f68404eb44eaf3a456e7ba993884510124688a6153c5292ffa4df06c6a8c7c9cc138dca37b90717f72eb8b5afb54b6057b109010001038a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c8139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b3940000000000000000000000000000000000000000000000000000000000000000070707070707070707070707070707070707070707070707070707070707070701020200010c0200000000ca9a3b00000000";
+
+    fn hex(input: &str) -> Vec<u8> {
+        (0..input.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&input[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
     #[test]
     fn test_create_transfer_transaction() {
-        let from = "11111111111111111111111111111111";
-        let to = "22222222222222222222222222222222";
-        let lamports = 1_000_000_000;
+        let transaction =
+            create_transfer_transaction(PAYER, RECIPIENT, 1_000_000_000, BLOCKHASH).unwrap();
+
+        // Identical to the reference apart from the zeroed signature
+        let mut expected = hex(SIGNED_TRANSFER_HEX);
+        expected[1..65].fill(0);
+        assert_eq!(transaction, expected);
+    }
+
+    #[test]
+    fn test_create_signed_transfer_transaction() {
+        let payer = Keypair::from_seed(&[1u8; 32]);
+        let transaction =
+            create_signed_transfer_transaction(&payer, RECIPIENT, 1_000_000_000, BLOCKHASH)
+                .unwrap();
+
+        // Ed25519 signatures are deterministic, so the bytes match exactly
+        assert_eq!(transaction, hex(SIGNED_TRANSFER_HEX));
+        assert!(Transaction::deserialize(&transaction)
+            .unwrap()
+            .verify_signatures());
+    }
 
-        let transaction = create_transfer_transaction(from, to, lamports).unwrap();
-        assert!(!transaction.is_empty());
+    #[test]
+    fn test_sign_rejects_non_signer() {
+        let bytes = create_transfer_transaction(PAYER, RECIPIENT, 1, BLOCKHASH).unwrap();
+        let mut transaction = Transaction::deserialize(&bytes).unwrap();
+        assert!(!transaction.verify_signatures());
+
+        assert!(transaction.sign(&Keypair::from_seed(&[2u8; 32])).is_err());
+        transaction.sign(&Keypair::from_seed(&[1u8; 32])).unwrap();
+        assert!(transaction.verify_signatures());
+    }
+
+    #[test]
+    fn test_create_transfer_transaction_invalid_address() {
+        assert!(create_transfer_transaction("invalid_address", RECIPIENT, 1, BLOCKHASH).is_err());
+        // Valid base58 but not 32 bytes long
+        assert!(create_transfer_transaction(
+            PAYER,
+            "22222222222222222222222222222222",
+            1,
+            BLOCKHASH
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_transaction_round_trip() {
+        let bytes = hex(SIGNED_TRANSFER_HEX);
+        let transaction = Transaction::deserialize(&bytes).unwrap();
+
+        assert_eq!(transaction.signatures.len(), 1);
+        assert_eq!(transaction.message.header.num_required_signatures, 1);
+        assert_eq!(transaction.message.header.num_readonly_signed_accounts, 0);
+        assert_eq!(transaction.message.header.num_readonly_unsigned_accounts, 1);
+        assert_eq!(
+            transaction.message.account_keys,
+            vec![
+                decode_pubkey(PAYER).unwrap(),
+                decode_pubkey(RECIPIENT).unwrap(),
+                decode_pubkey(SYSTEM_PROGRAM_ID).unwrap(),
+            ]
+        );
+        assert_eq!(
+            transaction.message.recent_blockhash,
+            decode_blockhash(BLOCKHASH).unwrap()
+        );
+        assert_eq!(transaction.serialize(), bytes);
+
+        // Trailing or missing bytes are rejected
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(Transaction::deserialize(&extended).is_err());
+        assert!(Transaction::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_message_account_ordering() {
+        let payer = decode_pubkey(PAYER).unwrap();
+        let program = [9u8; 32];
+        let first = Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta::new_readonly([3u8; 32], false),
+                AccountMeta::new([4u8; 32], true),
+                AccountMeta::new([5u8; 32], false),
+            ],
+            data: vec![1, 2, 3],
+        };
+        let second = Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta::new_readonly(payer, true),
+                AccountMeta::new_readonly([3u8; 32], true),
+            ],
+            data: vec![],
+        };
+
+        let message = Message::new(&[first, second], &payer, [7u8; 32]).unwrap();
+
+        // Reference message serialized by solana-sdk
+        let expected = hex("030101058a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c04040404040404040404040404040404040404040404040404040404040404040303030303030303030303030303030303030303030303030303030303030303050505050505050505050505050505050505050505050505050505050505050509090909090909090909090909090909090909090909090909090909090909090707070707070707070707070707070707070707070707070707070707070707020403020103030102030402000200");
+        assert_eq!(message.serialize(), expected);
+        assert_eq!(Message::deserialize(&expected).unwrap(), message);
+
+        assert_eq!(message.signer_keys(), &[payer, [4u8; 32], [3u8; 32]]);
+        assert!(message.is_writable(0));
+        assert!(message.is_writable(1));
+        assert!(!message.is_writable(2));
+        assert!(message.is_writable(3));
+        assert!(!message.is_writable(4));
+    }
+
+    #[test]
+    fn test_message_keys_sorted_within_group() {
+        let payer = decode_pubkey(PAYER).unwrap();
+        let instruction = Instruction {
+            program_id: [5u8; 32],
+            accounts: vec![
+                AccountMeta::new([8u8; 32], false),
+                AccountMeta::new([6u8; 32], false),
+                AccountMeta::new_readonly([7u8; 32], false),
+                AccountMeta::new_readonly([9u8; 32], false),
+            ],
+            data: vec![0xff],
+        };
+
+        let message = Message::new(&[instruction], &payer, [7u8; 32]).unwrap();
+
+        let expected = hex("010003068a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c0606060606060606060606060606060606060606060606060606060606060606080808080808080808080808080808080808080808080808080808080808080805050505050505050505050505050505050505050505050505050505050505050707070707070707070707070707070707070707070707070707070707070707090909090909090909090909090909090909090909090909090909090909090907070707070707070707070707070707070707070707070707070707070707070103040201040501ff");
+        assert_eq!(message.serialize(), expected);
+    }
+
+    #[test]
+    fn test_compact_u16() {
+        let cases: [(u16, &[u8]); 7] = [
+            (0, &[0x00]),
+            (0x7f, &[0x7f]),
+            (0x80, &[0x80, 0x01]),
+            (0xff, &[0xff, 0x01]),
+            (0x3fff, &[0xff, 0x7f]),
+            (0x4000, &[0x80, 0x80, 0x01]),
+            (0xffff, &[0xff, 0xff, 0x03]),
+        ];
+
+        for (value, encoded) in cases {
+            let mut out = Vec::new();
+            encode_compact_u16(value, &mut out);
+            assert_eq!(out, encoded);
+            assert_eq!(decode_compact_u16(encoded).unwrap(), (value, encoded.len()));
+        }
+
+        assert!(decode_compact_u16(&[0x80, 0x00]).is_err());
+        assert!(decode_compact_u16(&[0xff, 0xff, 0x04]).is_err());
+        assert!(decode_compact_u16(&[0x80]).is_err());
+        assert!(decode_compact_u16(&[]).is_err());
     }
 
     #[test]
@@ -525,4 +1979,452 @@ mod tests {
         assert!(!program_id.is_empty());
         assert!(validate_address(&program_id));
     }
+
+    fn mock_service(replies: Vec<(&str, Value)>) -> SolanaRpcService {
+        SolanaRpcService::with_config(SolanaRpcConfig {
+            rpc_url: mock_rpc::spawn(replies),
+            confirm_poll_interval_ms: 1,
+            ..SolanaRpcConfig::default()
+        })
+    }
+
+    fn status(slot: u64, commitment: &str, err: Value) -> Value {
+        json!({"result": {"context": {"slot": slot}, "value": [{
+            "slot": slot,
+            "confirmations": if commitment == "finalized" { Value::Null } else { json!(1) },
+            "err": err,
+            "confirmationStatus": commitment,
+        }]}})
+    }
+
+    fn block_height(height: u64) -> (&'static str, Value) {
+        ("getBlockHeight", json!({"result": height}))
+    }
+
+    #[test]
+    fn test_commitment_ordering() {
+        assert!(CommitmentLevel::Processed < CommitmentLevel::Confirmed);
+        assert!(CommitmentLevel::Confirmed < CommitmentLevel::Finalized);
+        assert_eq!(
+            CommitmentLevel::from_rpc_str("finalized"),
+            Some(CommitmentLevel::Finalized)
+        );
+        assert_eq!(CommitmentLevel::Confirmed.as_str(), "confirmed");
+    }
+
+    #[test]
+    fn test_configure_rejects_invalid_config() {
+        let mut service = SolanaRpcService::new();
+
+        let bad_url = SolanaRpcConfig {
+            rpc_url: "localhost:8899".to_string(),
+            ..SolanaRpcConfig::default()
+        };
+        assert!(matches!(
+            service.configure(bad_url),
+            Err(ServiceError::Validation(_))
+        ));
+
+        let bad_ws = SolanaRpcConfig {
+            ws_url: Some("http://localhost:8900".to_string()),
+            ..SolanaRpcConfig::default()
+        };
+        assert!(matches!(
+            service.configure(bad_ws),
+            Err(ServiceError::Validation(_))
+        ));
+        assert_eq!(
+            service.get_config().rpc_url,
+            SolanaRpcConfig::default().rpc_url
+        );
+
+        let devnet = SolanaRpcConfig {
+            rpc_url: "https://api.devnet.solana.com".to_string(),
+            ws_url: Some("wss://api.devnet.solana.com".to_string()),
+            ..SolanaRpcConfig::default()
+        };
+        service.configure(devnet).unwrap();
+        assert_eq!(
+            service.get_config().rpc_url,
+            "https://api.devnet.solana.com"
+        );
+    }
+
+    #[test]
+    fn test_match_batch_responses_by_id() {
+        let responses = json!([
+            {"jsonrpc": "2.0", "id": 9, "error": {"code": -32602, "message": "Invalid param"}},
+            {"jsonrpc": "2.0", "id": 7, "result": {"value": 42}}
+        ]);
+
+        let results = match_batch_responses(&[7, 8, 9], responses).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(parse_balance(results[0].as_ref().unwrap()).unwrap(), 42);
+        assert!(matches!(results[1], Err(ServiceError::Network(_))));
+        assert!(matches!(&results[2], Err(ServiceError::Rpc(e)) if e.code == -32602));
+
+        let rejected = json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32600, "message": "Batch requests are disabled"}});
+        assert!(matches!(
+            match_batch_responses(&[1], rejected),
+            Err(ServiceError::Rpc(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_balances_in_chunks() {
+        let service = SolanaRpcService::with_config(SolanaRpcConfig {
+            rpc_url: mock_rpc::spawn(vec![
+                (
+                    "getBalance",
+                    json!({"result": {"context": {"slot": 1}, "value": 10}}),
+                ),
+                (
+                    "getBalance",
+                    json!({"result": {"context": {"slot": 1}, "value": 20}}),
+                ),
+                (
+                    "getBalance",
+                    json!({"result": {"context": {"slot": 1}, "value": 30}}),
+                ),
+            ]),
+            max_batch_size: 2,
+            ..SolanaRpcConfig::default()
+        });
+
+        let balances = service.get_balances(&["a", "b", "c"]).await.unwrap();
+        let balances: Vec<u64> = balances.into_iter().map(Result::unwrap).collect();
+        assert_eq!(balances, vec![10, 20, 30]);
+
+        assert!(service.get_balances(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_minimum_balance_for_rent_exemption() {
+        let service = mock_service(vec![
+            (
+                "getMinimumBalanceForRentExemption",
+                json!({"result": 890880}),
+            ),
+            (
+                "getMinimumBalanceForRentExemption",
+                json!({"result": 2039280}),
+            ),
+            (
+                "getMinimumBalanceForRentExemption",
+                json!({"error": {"code": -32602, "message": "Invalid params"}}),
+            ),
+        ]);
+
+        assert_eq!(
+            service
+                .get_minimum_balance_for_rent_exemption(0)
+                .await
+                .unwrap(),
+            890880
+        );
+
+        let minimums = service
+            .get_minimum_balances_for_rent_exemption(&[165, 1 << 40])
+            .await
+            .unwrap();
+        assert_eq!(*minimums[0].as_ref().unwrap(), 2039280);
+        assert!(matches!(&minimums[1], Err(ServiceError::Rpc(e)) if e.code == -32602));
+    }
+
+    #[tokio::test]
+    async fn test_get_accounts_batch() {
+        let service = mock_service(vec![
+            (
+                "getAccountInfo",
+                json!({"result": {"context": {"slot": 1}, "value": {
+                    "lamports": 5000,
+                    "owner": "11111111111111111111111111111111",
+                    "data": ["AQID", "base64"],
+                    "executable": false,
+                    "rentEpoch": 0
+                }}}),
+            ),
+            (
+                "getAccountInfo",
+                json!({"result": {"context": {"slot": 1}, "value": null}}),
+            ),
+        ]);
+
+        let accounts = service.get_accounts(&["funded", "missing"]).await.unwrap();
+        let funded = accounts[0].as_ref().unwrap();
+        assert_eq!(funded.address, "funded");
+        assert_eq!(funded.balance, 5000);
+        assert_eq!(funded.data, vec![1, 2, 3]);
+        assert_eq!(funded.size, 3);
+        assert!(matches!(accounts[1], Err(ServiceError::Network(_))));
+    }
+
+    #[test]
+    fn test_decode_account_data_encodings() {
+        let plain = base64::engine::general_purpose::STANDARD
+            .decode("BwcHBwcHBwcqAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==")
+            .unwrap();
+
+        let zstd =
+            decode_account_data(&json!(["KLUv/SBAZQAAIAcHKgACAAMHYwAL", "base64+zstd"])).unwrap();
+        assert_eq!(zstd, plain);
+        assert_eq!(zstd.len(), 64);
+        assert_eq!(&zstd[8..16], &42u64.to_le_bytes());
+
+        assert_eq!(
+            decode_account_data(&json!(["AQID", "base64"])).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(decode_account_data(&json!("Ldp")).unwrap(), vec![1, 2, 3]);
+        assert!(decode_account_data(&json!(["AQID", "jsonParsed"])).is_err());
+        assert!(decode_account_data(&json!(["not zstd", "base64+zstd"])).is_err());
+    }
+
+    #[test]
+    fn test_account_filter_json() {
+        let memcmp = AccountFilter::Memcmp {
+            offset: 8,
+            bytes: vec![1, 2, 3],
+        };
+        assert_eq!(
+            memcmp.to_json(),
+            json!({"memcmp": {"offset": 8, "bytes": "Ldp"}})
+        );
+        assert_eq!(
+            AccountFilter::DataSize(165).to_json(),
+            json!({"dataSize": 165})
+        );
+        assert_eq!(AccountEncoding::Base64Zstd.as_str(), "base64+zstd");
+    }
+
+    #[tokio::test]
+    async fn test_get_program_accounts() {
+        let service = mock_service(vec![(
+            "getProgramAccounts",
+            json!({"result": [
+                {
+                    "pubkey": "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu",
+                    "account": {
+                        "lamports": 1_000_000,
+                        "owner": "11111111111111111111111111111111",
+                        "data": ["KLUv/SBAZQAAIAcHKgACAAMHYwAL", "base64+zstd"],
+                        "executable": false,
+                        "rentEpoch": 18446744073709551615u64,
+                        "space": 64
+                    }
+                }
+            ]}),
+        )]);
+
+        let config = ProgramAccountsConfig {
+            filters: vec![AccountFilter::DataSize(64)],
+            data_slice: None,
+            encoding: AccountEncoding::Base64Zstd,
+        };
+        let accounts = service
+            .get_program_accounts("11111111111111111111111111111111", &config)
+            .await
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(
+            accounts[0].address,
+            "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu"
+        );
+        assert_eq!(accounts[0].balance, 1_000_000);
+        assert_eq!(accounts[0].size, 64);
+
+        assert!(matches!(
+            service.get_program_accounts("not a program", &config).await,
+            Err(ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_program_accounts_with_context() {
+        let response = json!({"result": {"context": {"slot": 3}, "value": [
+            {"pubkey": "a", "account": {"lamports": 5, "owner": "o", "data": ["", "base64"], "executable": false, "rentEpoch": 0}}
+        ]}});
+        let accounts = parse_program_accounts(&response).unwrap();
+        assert_eq!(accounts[0].address, "a");
+        assert!(accounts[0].data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_blockhash_with_expiry() {
+        let service = mock_service(vec![(
+            "getLatestBlockhash",
+            json!({"result": {"context": {"slot": 1}, "value": {
+                "blockhash": BLOCKHASH,
+                "lastValidBlockHeight": 150
+            }}}),
+        )]);
+
+        let latest = service.get_latest_blockhash_with_expiry().await.unwrap();
+        assert_eq!(latest.blockhash, BLOCKHASH);
+        assert_eq!(latest.last_valid_block_height, 150);
+    }
+
+    #[tokio::test]
+    async fn test_get_health_and_version() {
+        let service = mock_service(vec![
+            ("getHealth", json!({"result": "ok"})),
+            (
+                "getVersion",
+                json!({"result": {"solana-core": "2.1.0", "feature-set": 1}}),
+            ),
+        ]);
+        service.get_health().await.unwrap();
+        assert_eq!(service.get_version().await.unwrap(), "2.1.0");
+
+        let behind = mock_service(vec![(
+            "getHealth",
+            json!({"error": {"code": -32005, "message": "Node is behind by 42 slots"}}),
+        )]);
+        assert!(behind.get_health().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_transaction_waits_for_commitment() {
+        let service = mock_service(vec![
+            block_height(100),
+            (
+                "getSignatureStatuses",
+                json!({"result": {"context": {"slot": 1}, "value": [null]}}),
+            ),
+            ("getSignatureStatuses", status(12, "processed", Value::Null)),
+            ("getSignatureStatuses", status(12, "confirmed", Value::Null)),
+        ]);
+
+        let outcome = service.confirm_transaction("sig", 150).await.unwrap();
+        assert_eq!(
+            outcome,
+            ConfirmationOutcome::Confirmed {
+                slot: 12,
+                commitment: CommitmentLevel::Confirmed
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_transaction_finalized_commitment() {
+        let service = SolanaRpcService::with_config(SolanaRpcConfig {
+            rpc_url: mock_rpc::spawn(vec![
+                block_height(100),
+                ("getSignatureStatuses", status(12, "confirmed", Value::Null)),
+                ("getSignatureStatuses", status(12, "finalized", Value::Null)),
+            ]),
+            commitment: CommitmentLevel::Finalized,
+            confirm_poll_interval_ms: 1,
+            ..SolanaRpcConfig::default()
+        });
+
+        let outcome = service.confirm_transaction("sig", 150).await.unwrap();
+        assert_eq!(
+            outcome,
+            ConfirmationOutcome::Confirmed {
+                slot: 12,
+                commitment: CommitmentLevel::Finalized
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_transaction_failed() {
+        let service = mock_service(vec![
+            block_height(100),
+            (
+                "getSignatureStatuses",
+                status(
+                    13,
+                    "confirmed",
+                    json!({"InstructionError": [0, {"Custom": 1}]}),
+                ),
+            ),
+        ]);
+
+        let outcome = service.confirm_transaction("sig", 150).await.unwrap();
+        assert_eq!(
+            outcome,
+            ConfirmationOutcome::Failed {
+                slot: 13,
+                error: TransactionError::InstructionError {
+                    index: 0,
+                    error: crate::services::rpc_error::InstructionError::Custom(1)
+                }
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_transaction_expired() {
+        let service = mock_service(vec![
+            block_height(149),
+            block_height(151),
+            (
+                "getSignatureStatuses",
+                json!({"result": {"context": {"slot": 1}, "value": [null]}}),
+            ),
+        ]);
+
+        let outcome = service.confirm_transaction("sig", 150).await.unwrap();
+        assert_eq!(
+            outcome,
+            ConfirmationOutcome::Expired {
+                last_valid_block_height: 150
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_transaction_retries_failed_polls() {
+        let unavailable = (
+            "getSignatureStatuses",
+            json!({"error": {"code": -32603, "message": "Internal error"}}),
+        );
+        let service = mock_service(vec![
+            block_height(100),
+            unavailable.clone(),
+            unavailable.clone(),
+            ("getSignatureStatuses", status(12, "confirmed", Value::Null)),
+        ]);
+        let outcome = service.confirm_transaction("sig", 150).await.unwrap();
+        assert!(matches!(
+            outcome,
+            ConfirmationOutcome::Confirmed { slot: 12, .. }
+        ));
+
+        // Polls that keep failing give up instead of waiting forever
+        let service = mock_service(vec![block_height(100), unavailable]);
+        assert!(service.confirm_transaction("sig", 150).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_preflight_error() {
+        let service = mock_service(vec![(
+            "sendTransaction",
+            json!({"error": {
+                "code": -32002,
+                "message": "Transaction simulation failed: Error processing Instruction 0: custom program error: 0x1771",
+                "data": {
+                    "err": {"InstructionError": [0, {"Custom": 6001}]},
+                    "logs": ["Program log: Error: slippage exceeded"]
+                }
+            }}),
+        )]);
+
+        match service.send_transaction(&hex(SIGNED_TRANSFER_HEX)).await {
+            Err(ServiceError::Rpc(error)) => {
+                assert!(error.is_preflight_failure());
+                assert_eq!(
+                    error.summary(),
+                    "instruction 0 failed: custom program error 0x1771"
+                );
+                assert_eq!(
+                    error.logs,
+                    vec!["Program log: Error: slippage exceeded".to_string()]
+                );
+            }
+            other => panic!("expected an RPC error, got {:?}", other),
+        }
+    }
 }
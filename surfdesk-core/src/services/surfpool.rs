@@ -1,40 +1,259 @@
+//! Surfpool process management
+//!
+//! Runs the `surfpool` binary as a child process. `start` returns once the
+//! RPC server answers `getHealth` or `getVersion`, and `poll_exit` notices
+//! when the process dies on its own. The command line comes from a
+//! `SurfpoolConfig` profile persisted in `~/.surfdesk/surfpool.json`.
+//! Background tasks copy stdout and stderr into `logs` as they arrive, and
+//! `write` sends commands to the process on stdin.
+//!
+//! The pid of a running process is kept in `~/.surfdesk/surfpool.pid`, so a
+//! process left behind by a session that did not shut down cleanly can be
+//! found with `find_orphan`; `start` refuses to run while one is alive.
+//! Dropping a `Surfpool` kills its process.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use tokio::{
-    io::{AsyncBufReadExt, BufReader},
+    io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader},
     process::{Child, Command},
+    time::{sleep, Instant},
 };
 
+use crate::models::{ProcessStatus, SurfpoolProcess};
+use crate::services::solana_rpc::{SolanaRpcConfig, SolanaRpcService};
+use crate::services::surfpool_log::{LogLevel, LogSource, SurfpoolLogs};
+use crate::services::surfpool_supervisor::RestartConfig;
+use crate::services::{Configurable, ServiceError, ServiceResult};
+use crate::utils::validation::{validate_network_url, validate_port, ValidationResult};
+
+/// Port the Surfpool RPC server listens on
+pub const DEFAULT_RPC_PORT: u16 = 8999;
+
+/// Port the Surfpool WebSocket server listens on
+pub const DEFAULT_WS_PORT: u16 = 9000;
+
+/// Delay between readiness probes while starting
+const PROBE_INTERVAL: Duration = Duration::from_millis(250);
+
 #[async_trait]
 pub trait TraitSurfpool {
     async fn start(&mut self) -> Result<()>;
     async fn stop(&mut self) -> Result<()>;
     async fn is_running(&self) -> bool;
     async fn write(&mut self, input: &str) -> Result<()>;
-    async fn read(&mut self) -> Result<String>;
+    /// Handle to the captured output and lifecycle notes
+    fn logs(&self) -> SurfpoolLogs;
 }
 
 #[derive(Debug)]
 pub struct Surfpool {
     pub process: Option<Child>,
     pub status: SurfpoolStatus,
+    /// Launch profile used by the next `start`
+    pub config: SurfpoolConfig,
+    /// Output of every run, kept across restarts
+    pub logs: SurfpoolLogs,
+    /// File holding the pid while the process runs, if any
+    pub pidfile: Option<PathBuf>,
+    /// Pid this instance wrote to `pidfile`
+    pidfile_pid: Option<u32>,
+}
+
+/// Launch profile for Surfpool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurfpoolConfig {
+    /// Executable to launch, looked up in `PATH` unless it is a path
+    pub binary: PathBuf,
+    /// Port of the RPC server
+    pub rpc_port: u16,
+    /// Port of the WebSocket server
+    pub ws_port: u16,
+    /// RPC endpoint to fork state from, Surfpool's own default when `None`
+    pub fork_url: Option<String>,
+    /// Slot time in milliseconds
+    pub slot_time_ms: u64,
+    /// How long `start` waits for the RPC server to answer, in seconds
+    pub startup_timeout: u64,
+    /// What the supervisor does when the process dies
+    pub restart: RestartConfig,
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+impl Default for SurfpoolConfig {
+    fn default() -> Self {
+        Self {
+            binary: PathBuf::from("surfpool"),
+            rpc_port: DEFAULT_RPC_PORT,
+            ws_port: DEFAULT_WS_PORT,
+            fork_url: None,
+            slot_time_ms: 400,
+            startup_timeout: 30,
+            restart: RestartConfig::default(),
+        }
+    }
+}
+
+impl SurfpoolConfig {
+    /// Where the profile is persisted (`~/.surfdesk/surfpool.json`)
+    pub fn default_path() -> PathBuf {
+        crate::services::surfdesk_home().join("surfpool.json")
+    }
+
+    /// Read a profile, falling back to the defaults if the file does not exist
+    pub fn load(path: &Path) -> ServiceResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ServiceError::Storage(format!(
+                "Failed to read {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Validate the profile and write it to `path`
+    pub fn save(&self, path: &Path) -> ServiceResult<()> {
+        self.validate()?;
+        let json = serde_json::to_string_pretty(self)?;
+        let write = || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, json + "\n")
+        };
+        write()
+            .map_err(|e| ServiceError::Storage(format!("Failed to write {}: {e}", path.display())))
+    }
+
+    /// Check that the profile can be launched
+    pub fn validate(&self) -> ServiceResult<()> {
+        if self.binary.as_os_str().is_empty() {
+            return Err(ServiceError::Validation(
+                "Binary path cannot be empty".to_string(),
+            ));
+        }
+
+        for (name, port) in [
+            ("RPC port", self.rpc_port),
+            ("WebSocket port", self.ws_port),
+        ] {
+            if let ValidationResult::Invalid(reason) = validate_port(port) {
+                return Err(ServiceError::Validation(format!("{name}: {reason}")));
+            }
+        }
+        if self.rpc_port == self.ws_port {
+            return Err(ServiceError::Validation(
+                "RPC and WebSocket ports must differ".to_string(),
+            ));
+        }
+
+        if let Some(url) = &self.fork_url {
+            if let ValidationResult::Invalid(reason) = validate_network_url(url) {
+                return Err(ServiceError::Validation(format!("Fork URL: {reason}")));
+            }
+        }
+
+        if self.slot_time_ms == 0 {
+            return Err(ServiceError::Validation(
+                "Slot time must be at least 1 ms".to_string(),
+            ));
+        }
+        if self.startup_timeout == 0 {
+            return Err(ServiceError::Validation(
+                "Startup timeout must be at least 1 second".to_string(),
+            ));
+        }
+
+        self.restart.validate()
+    }
+
+    /// Arguments passed to the binary
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "start".to_string(),
+            "--no-tui".to_string(),
+            "--debug".to_string(),
+            "--port".to_string(),
+            self.rpc_port.to_string(),
+            "--ws-port".to_string(),
+            self.ws_port.to_string(),
+            "--slot-time".to_string(),
+            self.slot_time_ms.to_string(),
+        ];
+        if let Some(url) = &self.fork_url {
+            args.push("--rpc-url".to_string());
+            args.push(url.clone());
+        }
+        args
+    }
+
+    /// The full command line, for display
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.binary.display().to_string())
+            .chain(self.args())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// URL of the RPC server
+    pub fn rpc_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.rpc_port)
+    }
+
+    /// URL of the WebSocket server
+    pub fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.ws_port)
+    }
+
+    /// RPC client configuration for talking to this Surfpool
+    pub fn rpc_config(&self) -> SolanaRpcConfig {
+        SolanaRpcConfig {
+            rpc_url: self.rpc_url(),
+            ws_url: Some(self.ws_url()),
+            ..SolanaRpcConfig::default()
+        }
+    }
+}
+
+/// Lifecycle state of the Surfpool process
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum SurfpoolStatus {
+    /// Never started, or stopped on request
     #[default]
-    Stop,
+    Stopped,
+    /// Spawned and waiting for the RPC server to answer
+    Starting,
+    /// Answering RPC requests
     Running,
+    /// Exited on its own, with its exit code unless it was killed by a signal
+    Exited(Option<i32>),
+    /// Could not be spawned or never became ready
+    Failed(String),
+}
+
+impl fmt::Display for SurfpoolStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SurfpoolStatus::Stopped => write!(f, "Stopped"),
+            SurfpoolStatus::Starting => write!(f, "Starting"),
+            SurfpoolStatus::Running => write!(f, "Running"),
+            SurfpoolStatus::Exited(Some(code)) => write!(f, "Exited with code {code}"),
+            SurfpoolStatus::Exited(None) => write!(f, "Killed by a signal"),
+            SurfpoolStatus::Failed(reason) => write!(f, "Failed: {reason}"),
+        }
+    }
 }
 
 impl Default for Surfpool {
     fn default() -> Self {
-        Self {
-            process: None,
-            status: SurfpoolStatus::Stop,
-        }
+        Self::with_config(SurfpoolConfig::default())
     }
 }
 
@@ -42,29 +261,264 @@ impl Surfpool {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-#[async_trait]
-impl TraitSurfpool for Surfpool {
-    async fn start(&mut self) -> Result<()> {
-        let mut cmd = Command::new("surfpool");
-        cmd.arg("start")
-            .arg("--no-tui")
-            .arg("--debug")
+    /// Create a stopped instance that launches with `config`
+    pub fn with_config(config: SurfpoolConfig) -> Self {
+        Self {
+            process: None,
+            status: SurfpoolStatus::Stopped,
+            config,
+            logs: SurfpoolLogs::default(),
+            pidfile: Some(default_pidfile()),
+            pidfile_pid: None,
+        }
+    }
+
+    /// Pid of a Surfpool process left running by an earlier session
+    pub fn find_orphan(&self) -> Option<u32> {
+        if self.process.is_some() {
+            return None;
+        }
+        find_orphan(self.pidfile.as_deref()?, &self.config.binary)
+    }
+
+    /// Snapshot of the process for the `models` layer
+    pub fn process_info(&self) -> SurfpoolProcess {
+        let status = match &self.status {
+            SurfpoolStatus::Running => ProcessStatus::Running,
+            SurfpoolStatus::Stopped | SurfpoolStatus::Exited(Some(0)) => ProcessStatus::Stopped,
+            SurfpoolStatus::Starting => ProcessStatus::Unknown,
+            SurfpoolStatus::Exited(_) => ProcessStatus::Error(self.status.to_string()),
+            SurfpoolStatus::Failed(reason) => ProcessStatus::Error(reason.clone()),
+        };
+
+        SurfpoolProcess {
+            id: "surfpool".to_string(),
+            name: "Surfpool".to_string(),
+            status,
+            pid: self.process.as_ref().and_then(Child::id),
+            port: Some(self.config.rpc_port),
+            endpoint: Some(self.config.rpc_url()),
+        }
+    }
+
+    /// Check whether the process has exited and update `status` if so
+    pub fn poll_exit(&mut self) -> &SurfpoolStatus {
+        if let Some(child) = self.process.as_mut() {
+            match child.try_wait() {
+                Ok(None) => {}
+                Ok(Some(exit)) => {
+                    self.process = None;
+                    self.remove_pidfile();
+                    self.status = SurfpoolStatus::Exited(exit.code());
+                    let level = if exit.success() {
+                        LogLevel::Info
+                    } else {
+                        LogLevel::Error
+                    };
+                    self.logs.note(
+                        level,
+                        format!("Surfpool {}", self.status.to_string().to_lowercase()),
+                    );
+                }
+                Err(e) => {
+                    self.process = None;
+                    self.remove_pidfile();
+                    let _ = self.fail(e.to_string());
+                }
+            }
+        }
+        &self.status
+    }
+
+    /// Mark the run as failed and note why
+    fn fail(&mut self, reason: String) -> anyhow::Error {
+        self.logs.note(LogLevel::Error, reason.clone());
+        self.status = SurfpoolStatus::Failed(reason.clone());
+        anyhow!(reason)
+    }
+
+    /// Record the pid of the new process, which is not worth failing over
+    ///
+    /// A pidfile naming another live Surfpool process is left alone.
+    fn write_pidfile(&mut self, pid: u32) {
+        let Some(path) = &self.pidfile else {
+            return;
+        };
+        if let Some(other) = find_orphan(path, &self.config.binary).filter(|other| *other != pid) {
+            self.logs.note(
+                LogLevel::Warn,
+                format!(
+                    "Not writing {}, it belongs to running process {other}",
+                    path.display()
+                ),
+            );
+            return;
+        }
+        let write = || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, format!("{pid}\n"))
+        };
+        match write() {
+            Ok(()) => self.pidfile_pid = Some(pid),
+            Err(e) => self.logs.note(
+                LogLevel::Warn,
+                format!("Could not write {}: {e}", path.display()),
+            ),
+        }
+    }
+
+    /// Remove the pidfile if it still holds the pid this instance wrote
+    fn remove_pidfile(&mut self) {
+        let (Some(path), Some(pid)) = (&self.pidfile, self.pidfile_pid.take()) else {
+            return;
+        };
+        let ours = std::fs::read_to_string(path).is_ok_and(|text| text.trim() == pid.to_string());
+        if ours {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Kill the process without waiting for it to exit
+    ///
+    /// For paths that cannot await, such as `Drop`.
+    pub fn kill(&mut self) {
+        if let Some(mut child) = self.process.take() {
+            let _ = child.start_kill();
+            self.remove_pidfile();
+            self.status = SurfpoolStatus::Stopped;
+        }
+    }
+
+    /// Launch the process without waiting for it to be ready
+    ///
+    /// `start` is `spawn` followed by `wait_until_ready`; the pid is known
+    /// as soon as this returns.
+    pub fn spawn(&mut self) -> Result<()> {
+        self.poll_exit();
+        if self.process.is_some() {
+            bail!(
+                "Surfpool is already {}",
+                self.status.to_string().to_lowercase()
+            );
+        }
+        if let Some(pid) = self.find_orphan() {
+            let reason = format!(
+                "Surfpool process {pid} from an earlier session is still running; kill it before starting another"
+            );
+            self.logs.note(LogLevel::Error, reason.clone());
+            bail!(reason);
+        }
+
+        self.config.validate()?;
+        self.logs.note(
+            LogLevel::Info,
+            format!("Starting {}", self.config.command_line()),
+        );
+
+        let mut cmd = Command::new(&self.config.binary);
+        cmd.args(self.config.args())
+            .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        let child = cmd.spawn()?;
-        self.status = SurfpoolStatus::Running;
-        self.process = Some(child);
+        match cmd.spawn() {
+            Ok(mut child) => {
+                if let Some(stdout) = child.stdout.take() {
+                    tokio::spawn(capture(stdout, LogSource::Stdout, self.logs.clone()));
+                }
+                if let Some(stderr) = child.stderr.take() {
+                    tokio::spawn(capture(stderr, LogSource::Stderr, self.logs.clone()));
+                }
+                if let Some(pid) = child.id() {
+                    self.write_pidfile(pid);
+                }
+                self.process = Some(child);
+                self.status = SurfpoolStatus::Starting;
+            }
+            Err(e) => {
+                let reason = format!("Could not run {}: {e}", self.config.binary.display());
+                return Err(self.fail(reason));
+            }
+        }
         Ok(())
     }
 
+    /// Poll the RPC server until it answers, the process exits or
+    /// `startup_timeout` passes
+    pub async fn wait_until_ready(&mut self) -> Result<()> {
+        let rpc = SolanaRpcService::with_config(SolanaRpcConfig {
+            timeout: 1,
+            ..self.config.rpc_config()
+        });
+        let timeout = Duration::from_secs(self.config.startup_timeout);
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            self.poll_exit();
+            if self.process.is_none() {
+                bail!("Surfpool stopped before it was ready: {}", self.status);
+            }
+
+            // Older builds may not implement getHealth
+            if rpc.get_health().await.is_ok() || rpc.get_version().await.is_ok() {
+                self.status = SurfpoolStatus::Running;
+                self.logs.note(
+                    LogLevel::Info,
+                    format!("Surfpool is ready on {}", self.config.rpc_url()),
+                );
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                let reason = format!(
+                    "RPC port {} did not answer within {}s",
+                    self.config.rpc_port, self.config.startup_timeout
+                );
+                if let Some(mut child) = self.process.take() {
+                    let _ = child.kill().await;
+                    self.remove_pidfile();
+                }
+                return Err(self.fail(reason));
+            }
+
+            sleep(PROBE_INTERVAL).await;
+        }
+    }
+}
+
+impl Configurable for Surfpool {
+    type Config = SurfpoolConfig;
+
+    /// Takes effect on the next `start`
+    fn configure(&mut self, config: Self::Config) -> ServiceResult<()> {
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    fn get_config(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+#[async_trait]
+impl TraitSurfpool for Surfpool {
+    async fn start(&mut self) -> Result<()> {
+        self.spawn()?;
+        self.wait_until_ready().await
+    }
+
     async fn stop(&mut self) -> Result<()> {
         if let Some(mut child) = self.process.take() {
-            let _ = child.kill().await; // ป้องกัน panic กรณีโปรเซสปิดไปแล้ว
+            // The process may have exited already
+            let _ = child.kill().await;
+            self.remove_pidfile();
+            self.logs.note(LogLevel::Info, "Surfpool stopped");
         }
-        self.status = SurfpoolStatus::Stop;
+        self.status = SurfpoolStatus::Stopped;
         Ok(())
     }
 
@@ -72,22 +526,441 @@ impl TraitSurfpool for Surfpool {
         self.status == SurfpoolStatus::Running
     }
 
-    async fn write(&mut self, _input: &str) -> Result<()> {
-        // จะ implement ต่อได้ถ้ามี stdin
+    /// Send one line to the process, adding the newline if it is missing
+    async fn write(&mut self, input: &str) -> Result<()> {
+        self.poll_exit();
+        let Some(stdin) = self.process.as_mut().and_then(|child| child.stdin.as_mut()) else {
+            bail!("Surfpool is not running");
+        };
+
+        let line = if input.ends_with('\n') {
+            input.to_string()
+        } else {
+            format!("{input}\n")
+        };
+        stdin.write_all(line.as_bytes()).await?;
+        stdin.flush().await?;
+
+        self.logs
+            .note(LogLevel::Info, format!("> {}", line.trim_end()));
         Ok(())
     }
 
-    async fn read(&mut self) -> Result<String> {
-        if let Some(child) = &mut self.process {
-            if let Some(stdout) = child.stdout.as_mut() {
-                let mut reader = BufReader::new(stdout);
-                let mut line = String::new();
-                let bytes = reader.read_line(&mut line).await?;
-                if bytes > 0 {
-                    return Ok(line.trim().to_string());
-                }
+    fn logs(&self) -> SurfpoolLogs {
+        self.logs.clone()
+    }
+}
+
+impl Drop for Surfpool {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Where the pid of the running process is recorded (`~/.surfdesk/surfpool.pid`)
+pub fn default_pidfile() -> PathBuf {
+    crate::services::surfdesk_home().join("surfpool.pid")
+}
+
+/// Pid from `pidfile` if that process is still alive and runs `binary`
+///
+/// The command line check guards against the pid having been reused since
+/// the pidfile was written.
+#[cfg(target_os = "linux")]
+pub fn find_orphan(pidfile: &Path, binary: &Path) -> Option<u32> {
+    let pid: u32 = std::fs::read_to_string(pidfile).ok()?.trim().parse().ok()?;
+    if pid == std::process::id() {
+        return None;
+    }
+
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    // Zombies keep their /proc entry but have an empty command line
+    let cmdline = std::fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    let name = binary.file_name()?;
+    cmdline
+        .split(|&b| b == 0)
+        .any(|arg| Path::new(OsStr::from_bytes(arg)).file_name() == Some(name))
+        .then_some(pid)
+}
+
+/// Orphans are only detected on Linux
+#[cfg(not(target_os = "linux"))]
+pub fn find_orphan(_pidfile: &Path, _binary: &Path) -> Option<u32> {
+    None
+}
+
+/// Ask an orphaned process to terminate and remove its pidfile
+pub fn kill_orphan(pid: u32, pidfile: &Path) -> Result<()> {
+    let status = std::process::Command::new("kill")
+        .arg(pid.to_string())
+        .status()?;
+    if !status.success() {
+        bail!("Could not kill process {pid}");
+    }
+    let _ = std::fs::remove_file(pidfile);
+    Ok(())
+}
+
+/// Copy lines from one output stream into `logs` until it closes
+async fn capture(stream: impl AsyncRead + Unpin, source: LogSource, logs: SurfpoolLogs) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => logs.push(source, &String::from_utf8_lossy(&line)),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::services::solana_rpc::mock_rpc::{self, closed_port, port_of};
+    use crate::services::surfpool_log::LogLine;
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    /// Fake `surfpool` binary from `tests/fixtures`
+    fn fake_surfpool(script: &str, rpc_port: u16) -> Surfpool {
+        let mut surfpool = Surfpool::with_config(SurfpoolConfig {
+            binary: fixture(script),
+            rpc_port,
+            startup_timeout: 5,
+            ..SurfpoolConfig::default()
+        });
+        surfpool.pidfile = None;
+        surfpool
+    }
+
+    fn fixture(script: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(script)
+    }
+
+    #[test]
+    fn test_config_args() {
+        let mut config = SurfpoolConfig::default();
+        assert_eq!(
+            config.command_line(),
+            "surfpool start --no-tui --debug --port 8999 --ws-port 9000 --slot-time 400"
+        );
+
+        config.fork_url = Some("https://api.devnet.solana.com".to_string());
+        assert_eq!(
+            &config.args()[9..],
+            ["--rpc-url", "https://api.devnet.solana.com"]
+        );
+        assert_eq!(config.ws_url(), "ws://127.0.0.1:9000");
+
+        let rpc = config.rpc_config();
+        assert_eq!(rpc.rpc_url, "http://127.0.0.1:8999");
+        assert_eq!(rpc.websocket_url().unwrap(), "ws://127.0.0.1:9000");
+    }
+
+    #[test]
+    fn test_config_validate() {
+        assert!(SurfpoolConfig::default().validate().is_ok());
+
+        let invalid = [
+            SurfpoolConfig {
+                rpc_port: 80,
+                ..SurfpoolConfig::default()
+            },
+            SurfpoolConfig {
+                ws_port: DEFAULT_RPC_PORT,
+                ..SurfpoolConfig::default()
+            },
+            SurfpoolConfig {
+                fork_url: Some("mainnet".to_string()),
+                ..SurfpoolConfig::default()
+            },
+            SurfpoolConfig {
+                slot_time_ms: 0,
+                ..SurfpoolConfig::default()
+            },
+            SurfpoolConfig {
+                binary: PathBuf::new(),
+                ..SurfpoolConfig::default()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+
+        let mut surfpool = Surfpool::new();
+        assert!(surfpool
+            .configure(SurfpoolConfig {
+                slot_time_ms: 0,
+                ..SurfpoolConfig::default()
+            })
+            .is_err());
+        assert_eq!(surfpool.get_config(), &SurfpoolConfig::default());
+    }
+
+    #[test]
+    fn test_config_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("surfpool.json");
+        assert_eq!(
+            SurfpoolConfig::load(&path).unwrap(),
+            SurfpoolConfig::default()
+        );
+
+        let config = SurfpoolConfig {
+            rpc_port: 8899,
+            ws_port: 8900,
+            fork_url: Some("https://api.mainnet-beta.solana.com".to_string()),
+            ..SurfpoolConfig::default()
+        };
+        config.save(&path).unwrap();
+        assert_eq!(SurfpoolConfig::load(&path).unwrap(), config);
+
+        // Missing fields take their defaults
+        std::fs::write(&path, r#"{"rpc_port": 9100}"#).unwrap();
+        let partial = SurfpoolConfig::load(&path).unwrap();
+        assert_eq!(partial.rpc_port, 9100);
+        assert_eq!(partial.ws_port, DEFAULT_WS_PORT);
+    }
+
+    #[test]
+    fn test_process_info() {
+        let mut surfpool = Surfpool::new();
+        let info = surfpool.process_info();
+        assert!(matches!(info.status, ProcessStatus::Stopped));
+        assert_eq!(info.port, Some(DEFAULT_RPC_PORT));
+        assert_eq!(info.endpoint.as_deref(), Some("http://127.0.0.1:8999"));
+        assert_eq!(info.pid, None);
+
+        surfpool.status = SurfpoolStatus::Exited(Some(3));
+        assert!(matches!(
+            surfpool.process_info().status,
+            ProcessStatus::Error(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_start_waits_for_health() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", port_of(&url));
+
+        surfpool.start().await.unwrap();
+        assert_eq!(surfpool.status, SurfpoolStatus::Running);
+        assert!(surfpool.is_running().await);
+        assert!(surfpool.start().await.is_err());
+        assert!(surfpool.process_info().pid.is_some());
+
+        surfpool.stop().await.unwrap();
+        assert_eq!(surfpool.status, SurfpoolStatus::Stopped);
+        assert!(surfpool.process.is_none());
+    }
+
+    /// Wait until the buffered logs contain `message`
+    async fn wait_for_log(surfpool: &Surfpool, message: &str) -> LogLine {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let found = surfpool
+                .logs
+                .snapshot()
+                .into_iter()
+                .find(|line| line.message.contains(message));
+            if let Some(line) = found {
+                return line;
             }
+            assert!(
+                Instant::now() < deadline,
+                "no log line containing {message:?}"
+            );
+            sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_captures_stdout_and_stderr() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", port_of(&url));
+        let mut subscriber = Box::pin(surfpool.logs().subscribe());
+        surfpool.start().await.unwrap();
+
+        let stdout = wait_for_log(&surfpool, "fake surfpool start").await;
+        assert_eq!(stdout.source, LogSource::Stdout);
+        assert!(stdout
+            .message
+            .contains(&format!("--port {}", port_of(&url))));
+        let stderr = wait_for_log(&surfpool, "upstream RPC is slow").await;
+        assert_eq!(
+            (stderr.source, stderr.level),
+            (LogSource::Stderr, LogLevel::Warn)
+        );
+
+        let first = subscriber.next().await.unwrap();
+        assert_eq!(first.source, LogSource::Surfdesk);
+        assert!(first.message.starts_with("Starting "), "{}", first.message);
+
+        surfpool.stop().await.unwrap();
+        wait_for_log(&surfpool, "Surfpool stopped").await;
+    }
+
+    #[tokio::test]
+    async fn test_write_to_stdin() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", port_of(&url));
+        assert!(surfpool.write("status").await.is_err());
+
+        surfpool.start().await.unwrap();
+        surfpool.write("airdrop 5").await.unwrap();
+        surfpool.write("status\n").await.unwrap();
+
+        let echo = wait_for_log(&surfpool, "> airdrop 5").await;
+        assert_eq!(echo.source, LogSource::Surfdesk);
+        assert_eq!(
+            wait_for_log(&surfpool, "received airdrop 5").await.source,
+            LogSource::Stdout
+        );
+        wait_for_log(&surfpool, "received status").await;
+
+        surfpool.stop().await.unwrap();
+        assert!(surfpool.write("status").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_start_falls_back_to_version() {
+        let url = mock_rpc::spawn(vec![(
+            "getVersion",
+            json!({"result": {"solana-core": "2.1.0"}}),
+        )]);
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", port_of(&url));
+
+        surfpool.start().await.unwrap();
+        assert_eq!(surfpool.status, SurfpoolStatus::Running);
+        surfpool.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exit_during_startup() {
+        let mut surfpool = fake_surfpool("fake_surfpool_crash.sh", closed_port());
+
+        let err = surfpool.start().await.unwrap_err();
+        assert_eq!(surfpool.status, SurfpoolStatus::Exited(Some(3)));
+        assert!(err.to_string().contains("Exited with code 3"), "{err}");
+
+        let crash = wait_for_log(&surfpool, "address already in use").await;
+        assert_eq!(
+            (crash.source, crash.level),
+            (LogSource::Stderr, LogLevel::Error)
+        );
+        let exit = wait_for_log(&surfpool, "Surfpool exited with code 3").await;
+        assert_eq!(exit.level, LogLevel::Error);
+    }
+
+    #[tokio::test]
+    async fn test_startup_timeout() {
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", closed_port());
+        surfpool.config.startup_timeout = 1;
+
+        assert!(surfpool.start().await.is_err());
+        assert!(matches!(surfpool.status, SurfpoolStatus::Failed(_)));
+        assert!(surfpool.process.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_binary() {
+        let mut surfpool = fake_surfpool("no_such_surfpool", closed_port());
+
+        assert!(surfpool.start().await.is_err());
+        assert!(matches!(surfpool.status, SurfpoolStatus::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_poll_exit_detects_kill() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", port_of(&url));
+        surfpool.start().await.unwrap();
+
+        let pid = surfpool.process.as_ref().unwrap().id().unwrap();
+        std::process::Command::new("kill")
+            .arg(pid.to_string())
+            .status()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while *surfpool.poll_exit() == SurfpoolStatus::Running && Instant::now() < deadline {
+            sleep(Duration::from_millis(20)).await;
         }
-        Ok(String::from("No output"))
+        assert_eq!(surfpool.status, SurfpoolStatus::Exited(None));
+        assert!(!surfpool.is_running().await);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_pidfile_and_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let pidfile = dir.path().join("surfpool.pid");
+        let binary = fixture("fake_surfpool.sh");
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut surfpool = fake_surfpool("fake_surfpool.sh", port_of(&url));
+        surfpool.pidfile = Some(pidfile.clone());
+
+        surfpool.start().await.unwrap();
+        let pid = surfpool.process_info().pid.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&pidfile).unwrap().trim(),
+            pid.to_string()
+        );
+        // Our own process is not an orphan, but it is to a new session
+        assert_eq!(surfpool.find_orphan(), None);
+        assert_eq!(find_orphan(&pidfile, &binary), Some(pid));
+        surfpool.stop().await.unwrap();
+        assert!(!pidfile.exists());
+
+        // A process left behind by a session that did not stop it
+        let mut orphan = std::process::Command::new(&binary)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        std::fs::write(&pidfile, format!("{}\n", orphan.id())).unwrap();
+        // The command line is empty until the shell has started
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while surfpool.find_orphan().is_none() && Instant::now() < deadline {
+            sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(surfpool.find_orphan(), Some(orphan.id()));
+
+        // A second process is not started next to it, nor is its pidfile taken over
+        let error = surfpool.start().await.unwrap_err();
+        assert!(
+            error.to_string().contains("from an earlier session"),
+            "{error}"
+        );
+        assert_eq!(surfpool.status, SurfpoolStatus::Stopped);
+        surfpool.write_pidfile(orphan.id() + 1);
+        assert_eq!(
+            std::fs::read_to_string(&pidfile).unwrap().trim(),
+            orphan.id().to_string()
+        );
+        surfpool.remove_pidfile();
+        assert!(pidfile.exists());
+        assert_eq!(
+            find_orphan(&pidfile, Path::new("solana-test-validator")),
+            None
+        );
+
+        kill_orphan(orphan.id(), &pidfile).unwrap();
+        orphan.wait().unwrap();
+        assert!(!pidfile.exists());
+
+        // Stale or unrelated pids are ignored
+        std::fs::write(&pidfile, format!("{}\n", orphan.id())).unwrap();
+        assert_eq!(find_orphan(&pidfile, &binary), None);
+        std::fs::write(&pidfile, format!("{}\n", std::process::id())).unwrap();
+        assert_eq!(find_orphan(&pidfile, &binary), None);
+        std::fs::write(&pidfile, "not a pid").unwrap();
+        assert_eq!(find_orphan(&pidfile, &binary), None);
     }
 }
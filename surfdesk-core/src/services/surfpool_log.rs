@@ -0,0 +1,366 @@
+//! Surfpool log capture
+//!
+//! Lines from the Surfpool process, plus Surfdesk's own notes about its
+//! lifecycle, go into a bounded buffer of timestamped entries with a parsed
+//! level. Subscribers receive new entries as a stream.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use tokio::sync::broadcast;
+
+/// Entries kept by default before the oldest are dropped
+pub const DEFAULT_LOG_CAPACITY: usize = 5000;
+
+/// Entries a slow subscriber may fall behind before it skips ahead
+const SUBSCRIBER_BACKLOG: usize = 1024;
+
+/// Severity of a log entry, from least to most severe
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn all() -> [LogLevel; 5] {
+        [
+            LogLevel::Trace,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    /// Parse a level word such as `INFO`, `warning` or `ERR`
+    pub fn from_word(word: &str) -> Option<Self> {
+        match word.to_ascii_uppercase().as_str() {
+            "TRACE" | "TRC" => Some(LogLevel::Trace),
+            "DEBUG" | "DBG" => Some(LogLevel::Debug),
+            "INFO" | "INF" => Some(LogLevel::Info),
+            "WARN" | "WARNING" | "WRN" => Some(LogLevel::Warn),
+            "ERROR" | "ERR" | "FATAL" | "PANIC" | "PANICKED" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a log entry came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    Stdout,
+    Stderr,
+    /// Lifecycle notes written by Surfdesk itself
+    Surfdesk,
+}
+
+impl fmt::Display for LogSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LogSource::Stdout => "stdout",
+            LogSource::Stderr => "stderr",
+            LogSource::Surfdesk => "surfdesk",
+        })
+    }
+}
+
+/// One captured log line
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    pub timestamp: DateTime<Utc>,
+    pub source: LogSource,
+    pub level: LogLevel,
+    /// The line without ANSI colour codes or trailing whitespace
+    pub message: String,
+}
+
+impl LogLine {
+    /// Whether the entry is at least `min_level` and contains `query`,
+    /// ignoring case
+    pub fn matches(&self, min_level: LogLevel, query: &str) -> bool {
+        self.level >= min_level
+            && (query.is_empty() || self.message.to_lowercase().contains(&query.to_lowercase()))
+    }
+}
+
+/// Find the level of a process output line
+///
+/// Looks at the first few words for a level, bare (`INFO`), bracketed
+/// (`[WARN]`) or followed by a colon (`error:`), which covers the `tracing`
+/// and `log` formats as well as clap errors and panics. Lines without one
+/// are `Info`.
+pub fn parse_level(message: &str) -> LogLevel {
+    message
+        .split_whitespace()
+        .take(4)
+        .find_map(|word| {
+            let word =
+                word.trim_matches(|c: char| matches!(c, '[' | ']' | '(' | ')' | ':' | '<' | '>'));
+            LogLevel::from_word(word)
+        })
+        .unwrap_or_default()
+}
+
+/// Remove ANSI escape sequences such as colour codes
+pub fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        // CSI sequences end with a byte in `@`..=`~`, others are two characters
+        if chars.next_if_eq(&'[').is_some() {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            chars.next();
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+struct Buffer {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+}
+
+/// Shared handle to a bounded log buffer
+///
+/// Clones share the same buffer, so the capture tasks, the service and the
+/// UI can each hold one.
+#[derive(Debug, Clone)]
+pub struct SurfpoolLogs {
+    buffer: Arc<Mutex<Buffer>>,
+    sender: broadcast::Sender<LogLine>,
+}
+
+impl Default for SurfpoolLogs {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+impl SurfpoolLogs {
+    /// Create a buffer keeping at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_BACKLOG);
+        Self {
+            buffer: Arc::new(Mutex::new(Buffer {
+                lines: VecDeque::new(),
+                capacity: capacity.max(1),
+            })),
+            sender,
+        }
+    }
+
+    /// Record a line of process output, parsing its level
+    pub fn push(&self, source: LogSource, raw: &str) {
+        let message = strip_ansi(raw.trim_end());
+        let level = parse_level(&message);
+        self.append(source, level, message);
+    }
+
+    /// Record a Surfdesk lifecycle note
+    pub fn note(&self, level: LogLevel, message: impl Into<String>) {
+        self.append(LogSource::Surfdesk, level, message.into());
+    }
+
+    fn append(&self, source: LogSource, level: LogLevel, message: String) {
+        let line = LogLine {
+            timestamp: Utc::now(),
+            source,
+            level,
+            message,
+        };
+
+        {
+            let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+            if buffer.lines.len() == buffer.capacity {
+                buffer.lines.pop_front();
+            }
+            buffer.lines.push_back(line.clone());
+        }
+
+        // No subscribers is not an error
+        let _ = self.sender.send(line);
+    }
+
+    /// Copy of the buffered entries, oldest first
+    pub fn snapshot(&self) -> Vec<LogLine> {
+        let buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        buffer.lines.iter().cloned().collect()
+    }
+
+    /// Maximum number of buffered entries
+    pub fn capacity(&self) -> usize {
+        self.buffer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .capacity
+    }
+
+    /// Drop all buffered entries
+    pub fn clear(&self) {
+        self.buffer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .lines
+            .clear();
+    }
+
+    /// Stream of entries recorded from now on
+    ///
+    /// A subscriber that falls more than 1024 entries behind skips the ones
+    /// it missed; `snapshot` still has them.
+    pub fn subscribe(&self) -> impl Stream<Item = LogLine> {
+        broadcast_stream(self.sender.subscribe())
+    }
+}
+
+/// Stream of the values sent on a broadcast channel, skipping any the
+/// receiver lagged behind on
+pub(crate) fn broadcast_stream<T: Clone + Send + 'static>(
+    receiver: broadcast::Receiver<T>,
+) -> impl Stream<Item = T> {
+    futures_util::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(value) => return Some((value, receiver)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[test]
+    fn test_parse_level() {
+        let cases = [
+            (
+                "2025-01-01T00:00:00.000Z  INFO surfpool: Slot 42",
+                LogLevel::Info,
+            ),
+            (
+                "2025-01-01T00:00:00Z WARN  surfpool::rpc: slow request",
+                LogLevel::Warn,
+            ),
+            (
+                "[2025-01-01 00:00:00 DEBUG surfpool] fetched account",
+                LogLevel::Debug,
+            ),
+            ("[ERROR] cannot reach upstream", LogLevel::Error),
+            ("error: address already in use", LogLevel::Error),
+            ("thread 'main' panicked at src/main.rs:1:1", LogLevel::Error),
+            ("trace: entering slot loop", LogLevel::Trace),
+            ("Surfpool listening on 127.0.0.1:8999", LogLevel::Info),
+            ("", LogLevel::Info),
+        ];
+        for (line, level) in cases {
+            assert_eq!(parse_level(line), level, "{line}");
+        }
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\u{1b}[2m2025\u{1b}[0m \u{1b}[33m WARN\u{1b}[0m slow"),
+            "2025  WARN slow"
+        );
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_push_parses_colored_lines() {
+        let logs = SurfpoolLogs::default();
+        logs.push(LogSource::Stderr, "\u{1b}[31mERROR\u{1b}[0m boom\n");
+
+        let lines = logs.snapshot();
+        assert_eq!(lines[0].message, "ERROR boom");
+        assert_eq!(lines[0].level, LogLevel::Error);
+        assert_eq!(lines[0].source, LogSource::Stderr);
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let logs = SurfpoolLogs::new(3);
+        for i in 0..5 {
+            logs.push(LogSource::Stdout, &format!("line {i}"));
+        }
+
+        let messages: Vec<_> = logs
+            .snapshot()
+            .into_iter()
+            .map(|line| line.message)
+            .collect();
+        assert_eq!(messages, ["line 2", "line 3", "line 4"]);
+
+        logs.clear();
+        assert!(logs.snapshot().is_empty());
+        assert_eq!(logs.capacity(), 3);
+    }
+
+    #[test]
+    fn test_matches() {
+        let logs = SurfpoolLogs::default();
+        logs.push(LogSource::Stdout, "WARN Upstream RPC is slow");
+        let line = &logs.snapshot()[0];
+
+        assert!(line.matches(LogLevel::Trace, ""));
+        assert!(line.matches(LogLevel::Warn, "upstream"));
+        assert!(!line.matches(LogLevel::Error, ""));
+        assert!(!line.matches(LogLevel::Info, "airdrop"));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe() {
+        let logs = SurfpoolLogs::default();
+        logs.push(LogSource::Stdout, "before");
+
+        let mut stream = Box::pin(logs.subscribe());
+        logs.push(LogSource::Stdout, "after");
+        logs.note(LogLevel::Error, "stopped");
+
+        let first = stream.next().await.unwrap();
+        assert_eq!(first.message, "after");
+        let second = stream.next().await.unwrap();
+        assert_eq!(
+            (second.source, second.level),
+            (LogSource::Surfdesk, LogLevel::Error)
+        );
+    }
+}
@@ -0,0 +1,896 @@
+//! Surfpool crash supervision
+//!
+//! `SurfpoolSupervisor` owns a `Surfpool` and watches it after `start`. When
+//! the process dies, the profile's `RestartPolicy` decides whether to start it
+//! again. Each restart within a window waits twice as long as the one before,
+//! and the supervisor gives up once too many restarts happen in that window.
+//! Restarts are kept in a history, and status changes, restarts and giving up
+//! are published as `SupervisorEvent`s.
+//!
+//! Clones share the same process. Once the last clone is dropped the watch
+//! task stops and the process is killed on the spot, without needing the
+//! async runtime. `shutdown` does the same for exits that skip destructors.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Mutex as AsyncMutex};
+use tokio::task::AbortHandle;
+use tokio::time::{sleep, Instant};
+
+use crate::models::SurfpoolProcess;
+use crate::services::surfpool::{self, Surfpool, SurfpoolConfig, SurfpoolStatus, TraitSurfpool};
+use crate::services::surfpool_log::{broadcast_stream, LogLevel, SurfpoolLogs};
+use crate::services::{Configurable, ServiceError, ServiceResult};
+
+/// How often the watch task checks whether the process is still alive
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Restart records kept before the oldest are dropped
+const HISTORY_CAPACITY: usize = 100;
+
+/// Events a slow subscriber may fall behind before it skips ahead
+const SUBSCRIBER_BACKLOG: usize = 64;
+
+/// When the supervisor starts a process that died
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Leave it stopped
+    #[default]
+    Never,
+    /// Restart unless it exited with code 0
+    OnFailure,
+    /// Restart however it ended
+    Always,
+}
+
+impl RestartPolicy {
+    pub fn all() -> [RestartPolicy; 3] {
+        [
+            RestartPolicy::Never,
+            RestartPolicy::OnFailure,
+            RestartPolicy::Always,
+        ]
+    }
+
+    /// Name used in the saved profile
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestartPolicy::Never => "never",
+            RestartPolicy::OnFailure => "on-failure",
+            RestartPolicy::Always => "always",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|policy| policy.as_str() == name)
+    }
+
+    /// Whether a run that ended with `status` is restarted
+    pub fn restarts(&self, status: &SurfpoolStatus) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => *status != SurfpoolStatus::Exited(Some(0)),
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+impl fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RestartPolicy::Never => "Never",
+            RestartPolicy::OnFailure => "On failure",
+            RestartPolicy::Always => "Always",
+        })
+    }
+}
+
+/// Restart settings of a launch profile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RestartConfig {
+    pub policy: RestartPolicy,
+    /// Delay before the first restart in a window, in milliseconds
+    pub initial_backoff_ms: u64,
+    /// Longest delay before a restart, in milliseconds
+    pub max_backoff_ms: u64,
+    /// Restarts allowed within `window_secs` before giving up
+    pub max_restarts: u32,
+    /// Length of the window restarts are counted in, in seconds
+    pub window_secs: u64,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            policy: RestartPolicy::Never,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+            max_restarts: 5,
+            window_secs: 300,
+        }
+    }
+}
+
+impl RestartConfig {
+    pub fn validate(&self) -> ServiceResult<()> {
+        if self.initial_backoff_ms == 0 {
+            return Err(ServiceError::Validation(
+                "Initial backoff must be at least 1 ms".to_string(),
+            ));
+        }
+        if self.max_backoff_ms < self.initial_backoff_ms {
+            return Err(ServiceError::Validation(
+                "Maximum backoff cannot be shorter than the initial backoff".to_string(),
+            ));
+        }
+        if self.window_secs == 0 {
+            return Err(ServiceError::Validation(
+                "Restart window must be at least 1 second".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Delay before restart number `attempt` of a window, counting from 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+/// One restart made by the supervisor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Restart number within the current window, starting at 1
+    pub attempt: u32,
+    /// How the previous run ended
+    pub reason: SurfpoolStatus,
+    /// Wait before starting again
+    pub delay: Duration,
+}
+
+/// What to do about a process that died
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartDecision {
+    /// The policy leaves it stopped
+    Stay,
+    /// Start it again after the recorded delay
+    Restart(RestartRecord),
+    /// Too many restarts within the window
+    GiveUp,
+}
+
+/// Applies a `RestartConfig` to successive exits
+#[derive(Debug, Clone)]
+pub struct RestartTracker {
+    config: RestartConfig,
+    /// When each restart of the current window was decided
+    recent: VecDeque<Instant>,
+    history: VecDeque<RestartRecord>,
+}
+
+impl RestartTracker {
+    pub fn new(config: RestartConfig) -> Self {
+        Self {
+            config,
+            recent: VecDeque::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &RestartConfig {
+        &self.config
+    }
+
+    /// Switch to `config` and count restarts afresh, keeping the history
+    pub fn reset(&mut self, config: RestartConfig) {
+        self.config = config;
+        self.recent.clear();
+    }
+
+    /// Decide what follows a run that ended with `status` at `now`
+    pub fn decide(&mut self, status: &SurfpoolStatus, now: Instant) -> RestartDecision {
+        if !self.config.policy.restarts(status) {
+            return RestartDecision::Stay;
+        }
+
+        let window = Duration::from_secs(self.config.window_secs);
+        while self
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= window)
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= self.config.max_restarts as usize {
+            return RestartDecision::GiveUp;
+        }
+
+        let attempt = self.recent.len() as u32 + 1;
+        let record = RestartRecord {
+            timestamp: Utc::now(),
+            attempt,
+            reason: status.clone(),
+            delay: self.config.backoff(attempt),
+        };
+        self.recent.push_back(now);
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back(record.clone());
+        RestartDecision::Restart(record)
+    }
+
+    /// Restarts made so far, oldest first
+    pub fn history(&self) -> Vec<RestartRecord> {
+        self.history.iter().cloned().collect()
+    }
+}
+
+/// Something the UI may want to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupervisorEvent {
+    /// The process changed state
+    Status(SurfpoolStatus),
+    /// A restart was scheduled
+    Restarting(RestartRecord),
+    /// The restart limit was reached and the process is left stopped
+    GaveUp { restarts: u32, window_secs: u64 },
+    /// The launch profile was replaced
+    Configured(SurfpoolConfig),
+}
+
+/// Shared handle to a supervised Surfpool process
+#[derive(Debug, Clone)]
+pub struct SurfpoolSupervisor {
+    inner: Arc<Inner>,
+    watch: Arc<WatchTask>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    surfpool: AsyncMutex<Surfpool>,
+    /// Mirror of the process that can be read without waiting on `surfpool`
+    state: Mutex<State>,
+    events: broadcast::Sender<SupervisorEvent>,
+    logs: SurfpoolLogs,
+    pidfile: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+struct State {
+    status: SurfpoolStatus,
+    process: SurfpoolProcess,
+    config: SurfpoolConfig,
+    tracker: RestartTracker,
+}
+
+/// The running watch task, aborted when replaced
+///
+/// Dropped with the last supervisor handle, which also kills the process.
+#[derive(Debug)]
+struct WatchTask {
+    task: Mutex<Option<AbortHandle>>,
+    inner: Arc<Inner>,
+}
+
+impl WatchTask {
+    fn replace(&self, task: Option<AbortHandle>) {
+        let mut current = self.task.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = std::mem::replace(&mut *current, task) {
+            old.abort();
+        }
+    }
+}
+
+impl Drop for WatchTask {
+    fn drop(&mut self) {
+        self.replace(None);
+        self.inner.kill();
+    }
+}
+
+impl Inner {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Update the mirror and announce a status change
+    fn publish(&self, status: SurfpoolStatus, process: SurfpoolProcess) {
+        let changed = {
+            let mut state = self.state();
+            state.process = process;
+            std::mem::replace(&mut state.status, status.clone()) != status
+        };
+        if changed {
+            let _ = self.events.send(SupervisorEvent::Status(status));
+        }
+    }
+
+    fn sync(&self, surfpool: &Surfpool) {
+        self.publish(surfpool.status.clone(), surfpool.process_info());
+    }
+
+    /// Kill the process without waiting on the async runtime
+    fn kill(&self) {
+        match self.surfpool.try_lock() {
+            Ok(mut surfpool) => {
+                surfpool.kill();
+                self.sync(&surfpool);
+            }
+            // A start or stop holds the lock, so go by the last known pid
+            Err(_) => {
+                if let Some(pid) = self.state().process.pid {
+                    let _ = std::process::Command::new("kill")
+                        .arg(pid.to_string())
+                        .status();
+                }
+            }
+        }
+    }
+
+    async fn start(&self, surfpool: &mut Surfpool) -> Result<()> {
+        self.publish(SurfpoolStatus::Starting, surfpool.process_info());
+        let result = match surfpool.spawn() {
+            Ok(()) => {
+                // Publish the pid before waiting, so `kill` can reach the
+                // process while `surfpool` is locked
+                self.sync(surfpool);
+                surfpool.wait_until_ready().await
+            }
+            Err(e) => Err(e),
+        };
+        self.sync(surfpool);
+        result
+    }
+
+    /// Restart the process whenever it dies, until the policy says otherwise
+    async fn watch(self: Arc<Self>) {
+        loop {
+            sleep(WATCH_INTERVAL).await;
+            let status = {
+                let mut surfpool = self.surfpool.lock().await;
+                let status = surfpool.poll_exit().clone();
+                self.sync(&surfpool);
+                status
+            };
+            match status {
+                SurfpoolStatus::Starting | SurfpoolStatus::Running => continue,
+                SurfpoolStatus::Stopped => return,
+                SurfpoolStatus::Exited(_) | SurfpoolStatus::Failed(_) => {}
+            }
+
+            let decision = self.state().tracker.decide(&status, Instant::now());
+            match decision {
+                RestartDecision::Stay => return,
+                RestartDecision::GiveUp => {
+                    let config = self.state().tracker.config().clone();
+                    self.logs.note(
+                        LogLevel::Error,
+                        format!(
+                            "Giving up after {} restarts within {}s",
+                            config.max_restarts, config.window_secs
+                        ),
+                    );
+                    let _ = self.events.send(SupervisorEvent::GaveUp {
+                        restarts: config.max_restarts,
+                        window_secs: config.window_secs,
+                    });
+                    return;
+                }
+                RestartDecision::Restart(record) => {
+                    self.logs.note(
+                        LogLevel::Warn,
+                        format!(
+                            "Restarting Surfpool in {:.1}s (attempt {})",
+                            record.delay.as_secs_f64(),
+                            record.attempt
+                        ),
+                    );
+                    let delay = record.delay;
+                    let _ = self.events.send(SupervisorEvent::Restarting(record));
+                    sleep(delay).await;
+
+                    // A failed start is noted in the logs and decided on next time round
+                    let mut surfpool = self.surfpool.lock().await;
+                    let _ = self.start(&mut surfpool).await;
+                }
+            }
+        }
+    }
+}
+
+impl SurfpoolSupervisor {
+    pub fn new(surfpool: Surfpool) -> Self {
+        let (events, _) = broadcast::channel(SUBSCRIBER_BACKLOG);
+        let state = State {
+            status: surfpool.status.clone(),
+            process: surfpool.process_info(),
+            config: surfpool.config.clone(),
+            tracker: RestartTracker::new(surfpool.config.restart.clone()),
+        };
+        let inner = Arc::new(Inner {
+            logs: surfpool.logs.clone(),
+            pidfile: surfpool.pidfile.clone(),
+            surfpool: AsyncMutex::new(surfpool),
+            state: Mutex::new(state),
+            events,
+        });
+        Self {
+            watch: Arc::new(WatchTask {
+                task: Mutex::new(None),
+                inner: inner.clone(),
+            }),
+            inner,
+        }
+    }
+
+    /// Supervise the saved launch profile, or the defaults if it cannot be read
+    pub fn from_saved_profile() -> Self {
+        let config = SurfpoolConfig::load(&SurfpoolConfig::default_path()).unwrap_or_default();
+        Self::new(Surfpool::with_config(config))
+    }
+
+    pub fn status(&self) -> SurfpoolStatus {
+        self.inner.state().status.clone()
+    }
+
+    /// Snapshot of the process for the `models` layer
+    pub fn process_info(&self) -> SurfpoolProcess {
+        self.inner.state().process.clone()
+    }
+
+    /// Launch profile used by the next `start`
+    pub fn config(&self) -> SurfpoolConfig {
+        self.inner.state().config.clone()
+    }
+
+    /// Replace the launch profile; takes effect on the next `start`
+    pub async fn configure(&self, config: SurfpoolConfig) -> ServiceResult<()> {
+        let mut surfpool = self.inner.surfpool.lock().await;
+        surfpool.configure(config.clone())?;
+        self.inner.state().config = config.clone();
+        let _ = self.inner.events.send(SupervisorEvent::Configured(config));
+        Ok(())
+    }
+
+    /// Restarts made so far, oldest first
+    pub fn history(&self) -> Vec<RestartRecord> {
+        self.inner.state().tracker.history()
+    }
+
+    /// Stream of events from now on
+    pub fn subscribe(&self) -> impl Stream<Item = SupervisorEvent> {
+        broadcast_stream(self.inner.events.subscribe())
+    }
+
+    /// Pid of a Surfpool process left running by an earlier session
+    pub fn find_orphan(&self) -> Option<u32> {
+        let state = self.inner.state();
+        let pid = surfpool::find_orphan(self.inner.pidfile.as_deref()?, &state.config.binary)?;
+        (state.process.pid != Some(pid)).then_some(pid)
+    }
+
+    /// Stop watching and kill the process right away
+    ///
+    /// For shutdown paths that cannot await, such as the window closing.
+    pub fn shutdown(&self) {
+        self.watch.replace(None);
+        self.inner.kill();
+    }
+
+    /// Terminate a process found by `find_orphan`
+    pub fn kill_orphan(&self, pid: u32) -> Result<()> {
+        let Some(pidfile) = &self.inner.pidfile else {
+            bail!("No pidfile is kept for Surfpool");
+        };
+        surfpool::kill_orphan(pid, pidfile)?;
+        self.inner.logs.note(
+            LogLevel::Info,
+            format!("Killed Surfpool process {pid} from an earlier session"),
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TraitSurfpool for SurfpoolSupervisor {
+    /// Start the process and keep it running according to the restart policy
+    async fn start(&mut self) -> Result<()> {
+        let mut surfpool = self.inner.surfpool.lock().await;
+        surfpool.poll_exit();
+        if surfpool.process.is_some() {
+            // Reports that it is already running, and leaves its watch alone
+            return surfpool.start().await;
+        }
+
+        // An earlier watch may still be waiting to restart the old run
+        self.watch.replace(None);
+        let restart = surfpool.config.restart.clone();
+        self.inner.state().tracker.reset(restart);
+        let result = self.inner.start(&mut surfpool).await;
+        drop(surfpool);
+
+        // A process that fails its first start is restarted like any other
+        let task = tokio::spawn(self.inner.clone().watch());
+        self.watch.replace(Some(task.abort_handle()));
+        result
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.watch.replace(None);
+        let mut surfpool = self.inner.surfpool.lock().await;
+        let result = surfpool.stop().await;
+        self.inner.sync(&surfpool);
+        result
+    }
+
+    async fn is_running(&self) -> bool {
+        self.status() == SurfpoolStatus::Running
+    }
+
+    async fn write(&mut self, input: &str) -> Result<()> {
+        let mut surfpool = self.inner.surfpool.lock().await;
+        let result = surfpool.write(input).await;
+        self.inner.sync(&surfpool);
+        result
+    }
+
+    fn logs(&self) -> SurfpoolLogs {
+        self.inner.logs.clone()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::services::solana_rpc::mock_rpc::{self, closed_port, port_of};
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    fn restart_config(policy: RestartPolicy) -> RestartConfig {
+        RestartConfig {
+            policy,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 40,
+            max_restarts: 2,
+            window_secs: 60,
+        }
+    }
+
+    /// Supervised fake `surfpool` binary from `tests/fixtures`
+    fn fake_supervisor(script: &str, rpc_port: u16, policy: RestartPolicy) -> SurfpoolSupervisor {
+        let mut surfpool = Surfpool::with_config(SurfpoolConfig {
+            binary: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(script),
+            rpc_port,
+            startup_timeout: 5,
+            restart: restart_config(policy),
+            ..SurfpoolConfig::default()
+        });
+        surfpool.pidfile = None;
+        SurfpoolSupervisor::new(surfpool)
+    }
+
+    /// Next event matching `wanted`, failing after 10 seconds
+    async fn next_event(
+        events: &mut (impl Stream<Item = SupervisorEvent> + Unpin),
+        wanted: impl Fn(&SupervisorEvent) -> bool,
+    ) -> SupervisorEvent {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let event = tokio::time::timeout_at(deadline, events.next())
+                .await
+                .expect("timed out waiting for an event")
+                .unwrap();
+            if wanted(&event) {
+                return event;
+            }
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = RestartConfig {
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 5000,
+            ..RestartConfig::default()
+        };
+        let delays: Vec<_> = (1..=4).map(|attempt| config.backoff(attempt)).collect();
+        assert_eq!(delays, [1000, 2000, 4000, 5000].map(Duration::from_millis));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_policies() {
+        let clean = SurfpoolStatus::Exited(Some(0));
+        let crashed = SurfpoolStatus::Exited(Some(3));
+        let failed = SurfpoolStatus::Failed("timed out".to_string());
+
+        assert!(!RestartPolicy::Never.restarts(&crashed));
+        assert!(!RestartPolicy::OnFailure.restarts(&clean));
+        assert!(RestartPolicy::OnFailure.restarts(&crashed));
+        assert!(RestartPolicy::OnFailure.restarts(&failed));
+        assert!(RestartPolicy::Always.restarts(&clean));
+
+        for policy in RestartPolicy::all() {
+            assert_eq!(RestartPolicy::from_name(policy.as_str()), Some(policy));
+        }
+        assert_eq!(
+            serde_json::to_value(RestartPolicy::OnFailure).unwrap(),
+            json!("on-failure")
+        );
+    }
+
+    #[test]
+    fn test_restart_window() {
+        let mut tracker = RestartTracker::new(restart_config(RestartPolicy::Always));
+        let crashed = SurfpoolStatus::Exited(Some(3));
+        let start = Instant::now();
+
+        let RestartDecision::Restart(first) = tracker.decide(&crashed, start) else {
+            panic!("expected a restart");
+        };
+        assert_eq!((first.attempt, first.delay), (1, Duration::from_millis(10)));
+        let RestartDecision::Restart(second) = tracker.decide(&crashed, start) else {
+            panic!("expected a restart");
+        };
+        assert_eq!(
+            (second.attempt, second.delay),
+            (2, Duration::from_millis(20))
+        );
+        assert_eq!(tracker.decide(&crashed, start), RestartDecision::GiveUp);
+
+        // Restarts older than the window no longer count
+        let later = start + Duration::from_secs(61);
+        assert!(matches!(
+            tracker.decide(&crashed, later),
+            RestartDecision::Restart(RestartRecord { attempt: 1, .. })
+        ));
+        assert_eq!(tracker.history().len(), 3);
+
+        tracker.reset(restart_config(RestartPolicy::Never));
+        assert_eq!(tracker.decide(&crashed, later), RestartDecision::Stay);
+        assert_eq!(tracker.history().len(), 3);
+    }
+
+    #[test]
+    fn test_restart_config_validate() {
+        assert!(RestartConfig::default().validate().is_ok());
+
+        let invalid = [
+            RestartConfig {
+                initial_backoff_ms: 0,
+                ..RestartConfig::default()
+            },
+            RestartConfig {
+                max_backoff_ms: 10,
+                ..RestartConfig::default()
+            },
+            RestartConfig {
+                window_secs: 0,
+                ..RestartConfig::default()
+            },
+        ];
+        for config in invalid {
+            let profile = SurfpoolConfig {
+                restart: config,
+                ..SurfpoolConfig::default()
+            };
+            assert!(profile.validate().is_err(), "{profile:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_on_crash_loop() {
+        let mut supervisor = fake_supervisor(
+            "fake_surfpool_crash.sh",
+            closed_port(),
+            RestartPolicy::OnFailure,
+        );
+        let mut events = Box::pin(supervisor.subscribe());
+
+        assert!(supervisor.start().await.is_err());
+        for attempt in 1..=2 {
+            let event = next_event(&mut events, |event| {
+                matches!(event, SupervisorEvent::Restarting(_))
+            })
+            .await;
+            let SupervisorEvent::Restarting(record) = event else {
+                unreachable!();
+            };
+            assert_eq!(record.attempt, attempt);
+            assert_eq!(record.reason, SurfpoolStatus::Exited(Some(3)));
+        }
+        let gave_up = next_event(&mut events, |event| {
+            matches!(event, SupervisorEvent::GaveUp { .. })
+        })
+        .await;
+        assert_eq!(
+            gave_up,
+            SupervisorEvent::GaveUp {
+                restarts: 2,
+                window_secs: 60
+            }
+        );
+
+        assert_eq!(supervisor.history().len(), 2);
+        assert_eq!(supervisor.status(), SurfpoolStatus::Exited(Some(3)));
+        assert!(supervisor
+            .logs()
+            .snapshot()
+            .iter()
+            .any(|line| line.message == "Giving up after 2 restarts within 60s"));
+    }
+
+    #[tokio::test]
+    async fn test_never_leaves_it_stopped() {
+        let mut supervisor = fake_supervisor(
+            "fake_surfpool_crash.sh",
+            closed_port(),
+            RestartPolicy::Never,
+        );
+
+        assert!(supervisor.start().await.is_err());
+        sleep(WATCH_INTERVAL * 3).await;
+        assert!(supervisor.history().is_empty());
+        assert_eq!(supervisor.status(), SurfpoolStatus::Exited(Some(3)));
+    }
+
+    #[tokio::test]
+    async fn test_always_restarts_after_kill() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut supervisor =
+            fake_supervisor("fake_surfpool.sh", port_of(&url), RestartPolicy::Always);
+        let mut events = Box::pin(supervisor.subscribe());
+
+        supervisor.start().await.unwrap();
+        assert!(supervisor.is_running().await);
+        // Starting again fails without unhooking the watch
+        assert!(supervisor.start().await.is_err());
+        let pid = supervisor.process_info().pid.unwrap();
+        std::process::Command::new("kill")
+            .arg(pid.to_string())
+            .status()
+            .unwrap();
+
+        next_event(&mut events, |event| {
+            matches!(event, SupervisorEvent::Restarting(_))
+        })
+        .await;
+        next_event(&mut events, |event| {
+            *event == SupervisorEvent::Status(SurfpoolStatus::Running)
+        })
+        .await;
+        let restarted = supervisor.process_info().pid.unwrap();
+        assert_ne!(restarted, pid);
+        assert_eq!(supervisor.history()[0].reason, SurfpoolStatus::Exited(None));
+
+        // Stopping on request is not restarted, even with `Always`
+        supervisor.stop().await.unwrap();
+        sleep(WATCH_INTERVAL * 3).await;
+        assert_eq!(supervisor.status(), SurfpoolStatus::Stopped);
+        assert_eq!(supervisor.history().len(), 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_drop_kills_process() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut supervisor =
+            fake_supervisor("fake_surfpool.sh", port_of(&url), RestartPolicy::Always);
+        supervisor.start().await.unwrap();
+        let pid = supervisor.process_info().pid.unwrap();
+
+        let clone = supervisor.clone();
+        drop(supervisor);
+        assert!(clone.is_running().await);
+        drop(clone);
+        assert_killed(pid);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_drop_kills_process_without_runtime() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut supervisor =
+            fake_supervisor("fake_surfpool.sh", port_of(&url), RestartPolicy::Always);
+        runtime.block_on(supervisor.start()).unwrap();
+        let pid = supervisor.process_info().pid.unwrap();
+
+        // As when the app exits, nothing async runs any more
+        drop(runtime);
+        drop(supervisor);
+        assert_killed(pid);
+    }
+
+    #[tokio::test]
+    async fn test_configure_announces_profile() {
+        let supervisor = fake_supervisor("fake_surfpool.sh", closed_port(), RestartPolicy::Never);
+        let mut events = Box::pin(supervisor.subscribe());
+        let config = SurfpoolConfig {
+            rpc_port: closed_port(),
+            ..supervisor.config()
+        };
+
+        supervisor.configure(config.clone()).await.unwrap();
+        let event = next_event(&mut events, |event| {
+            matches!(event, SupervisorEvent::Configured(_))
+        })
+        .await;
+        assert_eq!(event, SupervisorEvent::Configured(config.clone()));
+        assert_eq!(supervisor.config(), config);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_shutdown_kills_process() {
+        let url = mock_rpc::spawn(vec![("getHealth", json!({"result": "ok"}))]);
+        let mut supervisor =
+            fake_supervisor("fake_surfpool.sh", port_of(&url), RestartPolicy::Always);
+        supervisor.start().await.unwrap();
+        let pid = supervisor.process_info().pid.unwrap();
+
+        supervisor.shutdown();
+        assert_eq!(supervisor.status(), SurfpoolStatus::Stopped);
+        assert_killed(pid);
+
+        // The watch is gone too, so nothing starts it again
+        sleep(WATCH_INTERVAL * 3).await;
+        assert!(supervisor.history().is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_shutdown_while_starting() {
+        // Nothing answers on the RPC port, so the start never completes
+        let supervisor = fake_supervisor("fake_surfpool.sh", closed_port(), RestartPolicy::Always);
+        let mut starting = supervisor.clone();
+        let start = tokio::spawn(async move { starting.start().await });
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let pid = loop {
+            if let Some(pid) = supervisor.process_info().pid {
+                break pid;
+            }
+            assert!(Instant::now() < deadline, "the process was not spawned");
+            sleep(Duration::from_millis(20)).await;
+        };
+        assert_eq!(supervisor.status(), SurfpoolStatus::Starting);
+
+        supervisor.shutdown();
+        assert_killed(pid);
+        assert!(start.await.unwrap().is_err());
+    }
+
+    /// Fail unless process `pid` dies within 5 seconds
+    #[cfg(target_os = "linux")]
+    fn assert_killed(pid: u32) {
+        // A killed process that is not reaped yet has an empty command line
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while std::fs::read(format!("/proc/{pid}/cmdline")).is_ok_and(|cmd| !cmd.is_empty()) {
+            assert!(Instant::now() < deadline, "process {pid} is still running");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
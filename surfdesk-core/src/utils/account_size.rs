@@ -0,0 +1,369 @@
+//! Account size and rent-exemption estimates for schema accounts
+//!
+//! Sizes follow the Borsh layout used by `utils::borsh`, the same way
+//! Anchor's `InitSpace` does: strings and vectors take their `u32` length
+//! prefix plus room for `max_len` elements, options and enums one tag byte
+//! plus their largest payload. Fields without a max length only count their
+//! length prefix and are reported as unbounded.
+
+use crate::components::program_builder::{Account, Field, FieldType, TypeDef, TypeDefKind};
+use crate::utils::borsh::DISCRIMINATOR_LEN;
+
+/// Bytes the runtime charges rent for on top of the account data
+pub const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+
+/// Default rent rate of the Solana clusters
+pub const DEFAULT_LAMPORTS_PER_BYTE_YEAR: u64 = 3_480;
+
+/// Years of rent an account must hold to be exempt
+pub const DEFAULT_EXEMPTION_THRESHOLD: f64 = 2.0;
+
+/// Largest data size an account can be allocated with
+pub const MAX_PERMITTED_DATA_LENGTH: usize = 10 * 1024 * 1024;
+
+/// Errors produced while sizing a schema layout
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SizeError {
+    #[error("`{field}` refers to unknown type `{name}`")]
+    UnknownType { field: String, name: String },
+    #[error("`{field}` contains `{name}` inside itself, so it has no fixed size")]
+    RecursiveType { field: String, name: String },
+}
+
+/// Serialized size of an account
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AccountSize {
+    pub discriminator: usize,
+    pub data: usize,
+    /// Strings and vectors without a max length, sized as empty
+    pub unbounded: Vec<String>,
+}
+
+impl AccountSize {
+    /// Bytes to allocate for the account
+    pub fn total(&self) -> usize {
+        self.discriminator.saturating_add(self.data)
+    }
+
+    /// Whether every string and vector has a max length
+    pub fn is_bounded(&self) -> bool {
+        self.unbounded.is_empty()
+    }
+}
+
+/// Size of an account, with or without its discriminator prefix
+pub fn account_size(
+    account: &Account,
+    types: &[TypeDef],
+    with_discriminator: bool,
+) -> Result<AccountSize, SizeError> {
+    let mut size = fields_size(&account.fields, types)?;
+    if with_discriminator {
+        size.discriminator = account
+            .discriminator
+            .as_ref()
+            .map_or(DISCRIMINATOR_LEN, Vec::len);
+    }
+    Ok(size)
+}
+
+/// Size of a sequence of fields, without any discriminator
+pub fn fields_size(fields: &[Field], types: &[TypeDef]) -> Result<AccountSize, SizeError> {
+    let mut sizer = Sizer {
+        types,
+        stack: Vec::new(),
+        unbounded: Vec::new(),
+    };
+    let data = sizer.fields("", fields)?;
+    Ok(AccountSize {
+        discriminator: 0,
+        data,
+        unbounded: sizer.unbounded,
+    })
+}
+
+/// Lamports an account with `data_len` bytes needs to be rent exempt
+///
+/// Uses the default cluster rent parameters; prefer
+/// `SolanaRpcService::get_minimum_balance_for_rent_exemption` when a node is
+/// reachable.
+pub fn rent_exempt_minimum(data_len: usize) -> u64 {
+    let bytes = ACCOUNT_STORAGE_OVERHEAD.saturating_add(data_len as u64);
+    (bytes.saturating_mul(DEFAULT_LAMPORTS_PER_BYTE_YEAR) as f64 * DEFAULT_EXEMPTION_THRESHOLD)
+        as u64
+}
+
+struct Sizer<'a> {
+    types: &'a [TypeDef],
+    /// Defined types currently being sized, to detect recursion
+    stack: Vec<&'a str>,
+    unbounded: Vec<String>,
+}
+
+impl<'a> Sizer<'a> {
+    fn fields(&mut self, path: &str, fields: &'a [Field]) -> Result<usize, SizeError> {
+        let mut total = 0usize;
+        for field in fields {
+            let path = if path.is_empty() {
+                field.name.clone()
+            } else {
+                format!("{}.{}", path, field.name)
+            };
+            total = total.saturating_add(self.value(&path, &field.field_type, &field.max_len)?);
+        }
+        Ok(total)
+    }
+
+    fn value(
+        &mut self,
+        path: &str,
+        field_type: &'a FieldType,
+        max_len: &[usize],
+    ) -> Result<usize, SizeError> {
+        Ok(match field_type {
+            FieldType::U8 | FieldType::I8 | FieldType::Bool => 1,
+            FieldType::U16 | FieldType::I16 => 2,
+            FieldType::U32 | FieldType::I32 => 4,
+            FieldType::U64 | FieldType::I64 => 8,
+            FieldType::U128 | FieldType::I128 => 16,
+            FieldType::Pubkey => 32,
+            FieldType::String => match max_len.first() {
+                Some(len) => 4usize.saturating_add(*len),
+                None => self.unbounded(path),
+            },
+            FieldType::Vec(item) => match max_len.split_first() {
+                Some((len, rest)) => {
+                    let item = self.value(&format!("{}[]", path), item, rest)?;
+                    4usize.saturating_add(len.saturating_mul(item))
+                }
+                None => self.unbounded(path),
+            },
+            FieldType::Option(item) => 1usize.saturating_add(self.value(path, item, max_len)?),
+            FieldType::Array(item, len) => len.saturating_mul(self.value(path, item, max_len)?),
+            FieldType::Defined(name) => self.defined(path, name)?,
+        })
+    }
+
+    fn unbounded(&mut self, path: &str) -> usize {
+        self.unbounded.push(path.to_string());
+        4
+    }
+
+    fn defined(&mut self, path: &str, name: &str) -> Result<usize, SizeError> {
+        let def = self
+            .types
+            .iter()
+            .find(|def| def.name == name)
+            .ok_or_else(|| SizeError::UnknownType {
+                field: path.to_string(),
+                name: name.to_string(),
+            })?;
+        if self.stack.contains(&def.name.as_str()) {
+            return Err(SizeError::RecursiveType {
+                field: path.to_string(),
+                name: name.to_string(),
+            });
+        }
+
+        self.stack.push(&def.name);
+        let size = match &def.kind {
+            TypeDefKind::Struct { fields } => self.fields(path, fields),
+            TypeDefKind::Enum { variants } => {
+                let mut largest = 0usize;
+                for variant in variants {
+                    let path = format!("{}.{}", path, variant.name);
+                    largest = largest.max(self.fields(&path, &variant.fields)?);
+                }
+                Ok(1usize.saturating_add(largest))
+            }
+        };
+        self.stack.pop();
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::program_builder::EnumVariant;
+    use crate::utils::borsh::encode_fields;
+    use crate::utils::fixtures::field;
+    use serde_json::json;
+
+    fn account(fields: Vec<Field>) -> Account {
+        Account {
+            name: "Profile".to_string(),
+            fields,
+            discriminator: None,
+            seeds: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fixed_size_account() {
+        let counter = account(vec![
+            field("authority", FieldType::Pubkey),
+            field("count", FieldType::U64),
+            field("bump", FieldType::U8),
+        ]);
+
+        let size = account_size(&counter, &[], true).unwrap();
+        assert_eq!(size.discriminator, 8);
+        assert_eq!(size.data, 41);
+        assert_eq!(size.total(), 49);
+        assert!(size.is_bounded());
+
+        assert_eq!(account_size(&counter, &[], false).unwrap().total(), 41);
+    }
+
+    #[test]
+    fn test_max_lengths() {
+        let profile = account(vec![
+            Field {
+                max_len: vec![32],
+                ..field("name", FieldType::String)
+            },
+            Field {
+                max_len: vec![5, 10],
+                ..field("tags", FieldType::Vec(Box::new(FieldType::String)))
+            },
+            Field {
+                max_len: vec![3],
+                ..field(
+                    "scores",
+                    FieldType::Option(Box::new(FieldType::Vec(Box::new(FieldType::U16)))),
+                )
+            },
+            field("seed", FieldType::Array(Box::new(FieldType::U8), 16)),
+        ]);
+
+        let size = account_size(&profile, &[], false).unwrap();
+        assert_eq!(
+            size.data,
+            (4 + 32) + (4 + 5 * (4 + 10)) + (1 + 4 + 3 * 2) + 16
+        );
+
+        // A value filling every max length encodes to exactly that size
+        let value = json!({
+            "name": "x".repeat(32),
+            "tags": vec!["y".repeat(10); 5],
+            "scores": [1, 2, 3],
+            "seed": vec![0; 16],
+        });
+        let encoded = encode_fields(&profile.fields, &[], &value).unwrap();
+        assert_eq!(encoded.len(), size.data);
+    }
+
+    #[test]
+    fn test_unbounded_fields() {
+        let profile = account(vec![
+            field("bio", FieldType::String),
+            Field {
+                max_len: vec![2],
+                ..field("links", FieldType::Vec(Box::new(FieldType::String)))
+            },
+        ]);
+
+        let size = account_size(&profile, &[], false).unwrap();
+        assert_eq!(size.data, 4 + 4 + 2 * 4);
+        assert_eq!(
+            size.unbounded,
+            vec!["bio".to_string(), "links[]".to_string()]
+        );
+        assert!(!size.is_bounded());
+    }
+
+    #[test]
+    fn test_declared_discriminator() {
+        let mut short = account(vec![field("value", FieldType::U32)]);
+        short.discriminator = Some(vec![7]);
+        assert_eq!(account_size(&short, &[], true).unwrap().total(), 5);
+    }
+
+    #[test]
+    fn test_defined_types() {
+        let types = vec![
+            TypeDef {
+                name: "Point".to_string(),
+                kind: TypeDefKind::Struct {
+                    fields: vec![
+                        field("x", FieldType::I32),
+                        field("y", FieldType::I32),
+                        field("label", FieldType::String),
+                    ],
+                },
+            },
+            TypeDef {
+                name: "Shape".to_string(),
+                kind: TypeDefKind::Enum {
+                    variants: vec![
+                        EnumVariant {
+                            name: "Empty".to_string(),
+                            fields: vec![],
+                        },
+                        EnumVariant {
+                            name: "Line".to_string(),
+                            fields: vec![
+                                field("from", FieldType::Defined("Point".to_string())),
+                                field("to", FieldType::Defined("Point".to_string())),
+                            ],
+                        },
+                    ],
+                },
+            },
+        ];
+
+        let size = fields_size(
+            &[field("shape", FieldType::Defined("Shape".to_string()))],
+            &types,
+        )
+        .unwrap();
+        assert_eq!(size.data, 1 + 2 * 12);
+        assert_eq!(
+            size.unbounded,
+            vec![
+                "shape.Line.from.label".to_string(),
+                "shape.Line.to.label".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_size_errors() {
+        let unknown = [field("config", FieldType::Defined("Config".to_string()))];
+        assert_eq!(
+            fields_size(&unknown, &[]),
+            Err(SizeError::UnknownType {
+                field: "config".to_string(),
+                name: "Config".to_string()
+            })
+        );
+
+        let types = vec![TypeDef {
+            name: "Node".to_string(),
+            kind: TypeDefKind::Struct {
+                fields: vec![field(
+                    "next",
+                    FieldType::Option(Box::new(FieldType::Defined("Node".to_string()))),
+                )],
+            },
+        }];
+        let recursive = [field("head", FieldType::Defined("Node".to_string()))];
+        assert_eq!(
+            fields_size(&recursive, &types),
+            Err(SizeError::RecursiveType {
+                field: "head.next".to_string(),
+                name: "Node".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_rent_exempt_minimum() {
+        assert_eq!(rent_exempt_minimum(0), 890_880);
+        // SPL token account
+        assert_eq!(rent_exempt_minimum(165), 2_039_280);
+        // SPL mint
+        assert_eq!(rent_exempt_minimum(82), 1_461_600);
+    }
+}
@@ -0,0 +1,1173 @@
+//! Borsh encoding and decoding driven by a `ProgramSchema`
+//!
+//! Accounts and instruction data designed in the program builder are plain
+//! Borsh structs, optionally preceded by Anchor's 8-byte discriminator. This
+//! module converts between those bytes and JSON objects keyed by field name:
+//! integers are little-endian, strings and vectors carry a `u32` length
+//! prefix, options and enums a `u8` tag, and Pubkeys are 32 raw bytes shown
+//! as Base58.
+
+use crate::components::program_builder::{
+    Account, Field, FieldType, Instruction, InstructionArg, ProgramSchema, TypeDef, TypeDefKind,
+};
+use crate::utils::format::{to_pascal_case, to_snake_case};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Length of an Anchor discriminator
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Errors produced while encoding or decoding Borsh data
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BorshError {
+    #[error("Unexpected end of data at byte {offset}: `{field}` needs {needed} more bytes")]
+    UnexpectedEnd {
+        field: String,
+        offset: usize,
+        needed: usize,
+    },
+    #[error("Invalid bool value {value} for `{field}`")]
+    InvalidBool { field: String, value: u8 },
+    #[error("`{field}` is not valid UTF-8")]
+    InvalidUtf8 { field: String },
+    #[error("Data does not start with the `{name}` discriminator")]
+    DiscriminatorMismatch { name: String },
+    #[error("{count} unexpected bytes after the last field")]
+    TrailingBytes { count: usize },
+    #[error("Missing value for `{field}`")]
+    MissingField { field: String },
+    #[error("Unknown field `{field}`")]
+    UnknownField { field: String },
+    #[error("`{field}` must be {expected}")]
+    TypeMismatch { field: String, expected: String },
+    #[error("`{field}` is out of range for {field_type}")]
+    OutOfRange { field: String, field_type: String },
+    #[error("`{field}` is not a valid Base58 Pubkey")]
+    InvalidPubkey { field: String },
+    #[error("Invalid option or enum tag {tag} for `{field}`")]
+    InvalidTag { field: String, tag: u8 },
+    #[error("`{field}` refers to unknown type `{name}`")]
+    UnknownType { field: String, name: String },
+    #[error("Unknown variant `{variant}` for `{field}`")]
+    UnknownVariant { field: String, variant: String },
+    #[error("`{field}` nests defined types too deeply")]
+    TooDeep { field: String },
+}
+
+/// Anchor discriminator for an account type: `sha256("account:<Name>")[..8]`
+pub fn account_discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    discriminator("account", name)
+}
+
+/// Anchor discriminator for an instruction: `sha256("global:<snake_name>")[..8]`
+pub fn instruction_discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    discriminator("global", &to_snake_case(name))
+}
+
+/// Discriminator of a schema account: its declared bytes or Anchor's default
+///
+/// Anchor hashes the Rust struct name, so the default uses the PascalCase
+/// form of the schema name.
+pub fn schema_account_discriminator(account: &Account) -> Vec<u8> {
+    account
+        .discriminator
+        .clone()
+        .unwrap_or_else(|| account_discriminator(&to_pascal_case(&account.name)).to_vec())
+}
+
+/// Discriminator of a schema instruction: its declared bytes or Anchor's default
+pub fn schema_instruction_discriminator(instruction: &Instruction) -> Vec<u8> {
+    instruction
+        .discriminator
+        .clone()
+        .unwrap_or_else(|| instruction_discriminator(&instruction.name).to_vec())
+}
+
+/// First 8 bytes of `sha256("<namespace>:<name>")`
+pub(crate) fn discriminator(namespace: &str, name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    let hash = Sha256::digest(format!("{}:{}", namespace, name).as_bytes());
+    let mut discriminator = [0u8; DISCRIMINATOR_LEN];
+    discriminator.copy_from_slice(&hash[..DISCRIMINATOR_LEN]);
+    discriminator
+}
+
+/// Nesting limit for defined types, guarding against self-referential schemas
+const MAX_DEPTH: usize = 32;
+
+/// Path of a struct member, e.g. `config.authority`
+fn child(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn find_type<'t>(types: &'t [TypeDef], field: &str, name: &str) -> Result<&'t TypeDef, BorshError> {
+    types
+        .iter()
+        .find(|def| def.name == name)
+        .ok_or_else(|| BorshError::UnknownType {
+            field: field.to_string(),
+            name: name.to_string(),
+        })
+}
+
+/// Cursor over Borsh-encoded bytes
+pub struct BorshReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    types: &'a [TypeDef],
+    depth: usize,
+}
+
+impl<'a> BorshReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            offset: 0,
+            types: &[],
+            depth: 0,
+        }
+    }
+
+    /// Resolve `FieldType::Defined` against these type definitions
+    pub fn with_types(mut self, types: &'a [TypeDef]) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// Bytes consumed so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Bytes not yet consumed
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.offset..]
+    }
+
+    fn take(&mut self, field: &str, len: usize) -> Result<&'a [u8], BorshError> {
+        let available = self.data.len() - self.offset;
+        if available < len {
+            return Err(BorshError::UnexpectedEnd {
+                field: field.to_string(),
+                offset: self.offset,
+                needed: len - available,
+            });
+        }
+        let bytes = &self.data[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self, field: &str) -> Result<[u8; N], BorshError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(field, N)?);
+        Ok(array)
+    }
+
+    /// Read one value of the given type as JSON
+    ///
+    /// Integers up to 64 bits become JSON numbers and 128-bit integers decimal
+    /// strings. Pubkeys are Base58 strings, options `null` when absent, unit
+    /// enum variants their name and other variants `{"Name": {fields}}`.
+    pub fn read_value(&mut self, field: &str, field_type: &FieldType) -> Result<Value, BorshError> {
+        Ok(match field_type {
+            FieldType::U8 => Value::from(self.take_array::<1>(field)?[0]),
+            FieldType::U16 => Value::from(u16::from_le_bytes(self.take_array(field)?)),
+            FieldType::U32 => Value::from(u32::from_le_bytes(self.take_array(field)?)),
+            FieldType::U64 => Value::from(u64::from_le_bytes(self.take_array(field)?)),
+            FieldType::U128 => {
+                Value::String(u128::from_le_bytes(self.take_array(field)?).to_string())
+            }
+            FieldType::I8 => Value::from(i8::from_le_bytes(self.take_array(field)?)),
+            FieldType::I16 => Value::from(i16::from_le_bytes(self.take_array(field)?)),
+            FieldType::I32 => Value::from(i32::from_le_bytes(self.take_array(field)?)),
+            FieldType::I64 => Value::from(i64::from_le_bytes(self.take_array(field)?)),
+            FieldType::I128 => {
+                Value::String(i128::from_le_bytes(self.take_array(field)?).to_string())
+            }
+            FieldType::Bool => match self.take_array::<1>(field)?[0] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                value => {
+                    return Err(BorshError::InvalidBool {
+                        field: field.to_string(),
+                        value,
+                    })
+                }
+            },
+            FieldType::String => {
+                let len = u32::from_le_bytes(self.take_array(field)?) as usize;
+                let bytes = self.take(field, len)?;
+                let text = std::str::from_utf8(bytes).map_err(|_| BorshError::InvalidUtf8 {
+                    field: field.to_string(),
+                })?;
+                Value::String(text.to_string())
+            }
+            FieldType::Pubkey => Value::String(bs58::encode(self.take(field, 32)?).into_string()),
+            FieldType::Vec(inner) => {
+                let len = u32::from_le_bytes(self.take_array(field)?) as usize;
+                // Elements take at least a byte each, so a larger length is corrupt
+                let available = self.remaining().len();
+                if len > available {
+                    return Err(BorshError::UnexpectedEnd {
+                        field: field.to_string(),
+                        offset: self.offset,
+                        needed: len - available,
+                    });
+                }
+                self.read_items(field, inner, len)?
+            }
+            FieldType::Option(inner) => match self.take_array::<1>(field)?[0] {
+                0 => Value::Null,
+                1 => self.read_value(field, inner)?,
+                tag => {
+                    return Err(BorshError::InvalidTag {
+                        field: field.to_string(),
+                        tag,
+                    })
+                }
+            },
+            FieldType::Array(inner, len) => self.read_items(field, inner, *len)?,
+            FieldType::Defined(name) => {
+                let def = find_type(self.types, field, name)?;
+                if self.depth >= MAX_DEPTH {
+                    return Err(BorshError::TooDeep {
+                        field: field.to_string(),
+                    });
+                }
+                self.depth += 1;
+                let value = self.read_defined(field, def);
+                self.depth -= 1;
+                value?
+            }
+        })
+    }
+
+    fn read_items(
+        &mut self,
+        field: &str,
+        item: &FieldType,
+        len: usize,
+    ) -> Result<Value, BorshError> {
+        (0..len)
+            .map(|i| self.read_value(&format!("{}[{}]", field, i), item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    }
+
+    fn read_defined(&mut self, field: &str, def: &TypeDef) -> Result<Value, BorshError> {
+        match &def.kind {
+            TypeDefKind::Struct { fields } => {
+                read_layout(self, field, field_layout(fields)).map(Value::Object)
+            }
+            TypeDefKind::Enum { variants } => {
+                let tag = self.take_array::<1>(field)?[0];
+                let variant = variants
+                    .get(tag as usize)
+                    .ok_or_else(|| BorshError::InvalidTag {
+                        field: field.to_string(),
+                        tag,
+                    })?;
+                if variant.fields.is_empty() {
+                    return Ok(Value::String(variant.name.clone()));
+                }
+                let path = child(field, &variant.name);
+                let fields = read_layout(self, &path, field_layout(&variant.fields))?;
+                let mut value = Map::new();
+                value.insert(variant.name.clone(), Value::Object(fields));
+                Ok(Value::Object(value))
+            }
+        }
+    }
+}
+
+/// Buffer that Borsh-encodes JSON values
+#[derive(Debug, Default)]
+pub struct BorshWriter<'a> {
+    data: Vec<u8>,
+    types: &'a [TypeDef],
+    depth: usize,
+}
+
+impl<'a> BorshWriter<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `FieldType::Defined` against these type definitions
+    pub fn with_types(mut self, types: &'a [TypeDef]) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// The bytes written so far
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Write one JSON value as the given type
+    ///
+    /// Integers may be JSON numbers or decimal strings, so `u64` and 128-bit
+    /// values beyond JavaScript's safe range survive a round trip through a
+    /// text field. Other values use the shapes produced by `read_value`.
+    pub fn write_value(
+        &mut self,
+        field: &str,
+        field_type: &FieldType,
+        value: &Value,
+    ) -> Result<(), BorshError> {
+        match field_type {
+            FieldType::U8 => self.data.push(unsigned(field, field_type, value)?),
+            FieldType::U16 => {
+                self.write_bytes(unsigned::<u16>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::U32 => {
+                self.write_bytes(unsigned::<u32>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::U64 => {
+                self.write_bytes(unsigned::<u64>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::U128 => {
+                self.write_bytes(unsigned::<u128>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::I8 => {
+                self.write_bytes(signed::<i8>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::I16 => {
+                self.write_bytes(signed::<i16>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::I32 => {
+                self.write_bytes(signed::<i32>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::I64 => {
+                self.write_bytes(signed::<i64>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::I128 => {
+                self.write_bytes(signed::<i128>(field, field_type, value)?.to_le_bytes())
+            }
+            FieldType::Bool => {
+                let flag = value
+                    .as_bool()
+                    .ok_or_else(|| type_mismatch(field, "a boolean"))?;
+                self.data.push(flag as u8);
+            }
+            FieldType::String => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| type_mismatch(field, "a string"))?;
+                self.write_len(field, field_type, text.len())?;
+                self.data.extend_from_slice(text.as_bytes());
+            }
+            FieldType::Pubkey => {
+                let address = value
+                    .as_str()
+                    .ok_or_else(|| type_mismatch(field, "a Base58 string"))?;
+                let bytes = bs58::decode(address)
+                    .into_vec()
+                    .ok()
+                    .filter(|bytes| bytes.len() == 32)
+                    .ok_or_else(|| BorshError::InvalidPubkey {
+                        field: field.to_string(),
+                    })?;
+                self.data.extend_from_slice(&bytes);
+            }
+            FieldType::Vec(inner) => {
+                let items = value
+                    .as_array()
+                    .ok_or_else(|| type_mismatch(field, "an array"))?;
+                self.write_len(field, field_type, items.len())?;
+                self.write_items(field, inner, items)?;
+            }
+            FieldType::Option(inner) => {
+                if value.is_null() {
+                    self.data.push(0);
+                } else {
+                    self.data.push(1);
+                    self.write_value(field, inner, value)?;
+                }
+            }
+            FieldType::Array(inner, len) => {
+                let items = value
+                    .as_array()
+                    .filter(|items| items.len() == *len)
+                    .ok_or_else(|| type_mismatch(field, &format!("an array of {} items", len)))?;
+                self.write_items(field, inner, items)?;
+            }
+            FieldType::Defined(name) => {
+                let def = find_type(self.types, field, name)?;
+                if self.depth >= MAX_DEPTH {
+                    return Err(BorshError::TooDeep {
+                        field: field.to_string(),
+                    });
+                }
+                self.depth += 1;
+                let result = self.write_defined(field, def, value);
+                self.depth -= 1;
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_bytes<const N: usize>(&mut self, bytes: [u8; N]) {
+        self.data.extend_from_slice(&bytes);
+    }
+
+    /// Write a `u32` length prefix
+    fn write_len(
+        &mut self,
+        field: &str,
+        field_type: &FieldType,
+        len: usize,
+    ) -> Result<(), BorshError> {
+        let len = u32::try_from(len).map_err(|_| BorshError::OutOfRange {
+            field: field.to_string(),
+            field_type: field_type.to_string(),
+        })?;
+        self.write_bytes(len.to_le_bytes());
+        Ok(())
+    }
+
+    fn write_items(
+        &mut self,
+        field: &str,
+        item: &FieldType,
+        items: &[Value],
+    ) -> Result<(), BorshError> {
+        for (i, value) in items.iter().enumerate() {
+            self.write_value(&format!("{}[{}]", field, i), item, value)?;
+        }
+        Ok(())
+    }
+
+    fn write_defined(
+        &mut self,
+        field: &str,
+        def: &TypeDef,
+        value: &Value,
+    ) -> Result<(), BorshError> {
+        let variants = match &def.kind {
+            TypeDefKind::Struct { fields } => {
+                return write_layout(self, field, field_layout(fields), value);
+            }
+            TypeDefKind::Enum { variants } => variants,
+        };
+
+        let (name, fields) = match value {
+            Value::String(name) => (name, None),
+            Value::Object(object) if object.len() == 1 => {
+                let (name, fields) = object.iter().next().unwrap_or_else(|| unreachable!());
+                (name, Some(fields))
+            }
+            _ => {
+                return Err(type_mismatch(
+                    field,
+                    "a variant name or an object with a single variant",
+                ))
+            }
+        };
+        let (index, variant) = variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| &variant.name == name)
+            .ok_or_else(|| BorshError::UnknownVariant {
+                field: field.to_string(),
+                variant: name.clone(),
+            })?;
+        self.data
+            .push(u8::try_from(index).map_err(|_| BorshError::OutOfRange {
+                field: field.to_string(),
+                field_type: def.name.clone(),
+            })?);
+
+        match fields {
+            Some(fields) => write_layout(
+                self,
+                &child(field, &variant.name),
+                field_layout(&variant.fields),
+                fields,
+            ),
+            None if variant.fields.is_empty() => Ok(()),
+            None => Err(type_mismatch(
+                field,
+                &format!("an object with the fields of `{}`", variant.name),
+            )),
+        }
+    }
+}
+
+fn type_mismatch(field: &str, expected: &str) -> BorshError {
+    BorshError::TypeMismatch {
+        field: field.to_string(),
+        expected: expected.to_string(),
+    }
+}
+
+fn unsigned<T: TryFrom<u128>>(
+    field: &str,
+    field_type: &FieldType,
+    value: &Value,
+) -> Result<T, BorshError> {
+    let number = match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(text) => text.trim().parse::<u128>().ok(),
+        _ => return Err(type_mismatch(field, &format!("a {} number", field_type))),
+    };
+    number
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| BorshError::OutOfRange {
+            field: field.to_string(),
+            field_type: field_type.to_string(),
+        })
+}
+
+fn signed<T: TryFrom<i128>>(
+    field: &str,
+    field_type: &FieldType,
+    value: &Value,
+) -> Result<T, BorshError> {
+    let number = match value {
+        Value::Number(number) => number
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| number.as_u64().map(i128::from)),
+        Value::String(text) => text.trim().parse::<i128>().ok(),
+        _ => return Err(type_mismatch(field, &format!("a {} number", field_type))),
+    };
+    number
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| BorshError::OutOfRange {
+            field: field.to_string(),
+            field_type: field_type.to_string(),
+        })
+}
+
+/// Read named values in order into a JSON object
+fn read_layout<'a>(
+    reader: &mut BorshReader,
+    path: &str,
+    layout: impl IntoIterator<Item = (&'a str, &'a FieldType)>,
+) -> Result<Map<String, Value>, BorshError> {
+    let mut values = Map::new();
+    for (name, field_type) in layout {
+        values.insert(
+            name.to_string(),
+            reader.read_value(&child(path, name), field_type)?,
+        );
+    }
+    Ok(values)
+}
+
+/// Write the members of a JSON object in layout order
+///
+/// Every field must be present and no unknown keys are allowed, so typos in
+/// hand-written input are caught instead of silently dropped.
+fn write_layout<'a>(
+    writer: &mut BorshWriter,
+    path: &str,
+    layout: impl IntoIterator<Item = (&'a str, &'a FieldType)> + Clone,
+    value: &Value,
+) -> Result<(), BorshError> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| type_mismatch(if path.is_empty() { "value" } else { path }, "an object"))?;
+    if let Some(unknown) = object.keys().find(|key| {
+        !layout
+            .clone()
+            .into_iter()
+            .any(|(name, _)| name == key.as_str())
+    }) {
+        return Err(BorshError::UnknownField {
+            field: child(path, unknown),
+        });
+    }
+    for (name, field_type) in layout {
+        let member = object.get(name).ok_or_else(|| BorshError::MissingField {
+            field: child(path, name),
+        })?;
+        writer.write_value(&child(path, name), field_type, member)?;
+    }
+    Ok(())
+}
+
+fn field_layout(fields: &[Field]) -> impl Iterator<Item = (&str, &FieldType)> + Clone {
+    fields
+        .iter()
+        .map(|field| (field.name.as_str(), &field.field_type))
+}
+
+fn arg_layout(args: &[InstructionArg]) -> impl Iterator<Item = (&str, &FieldType)> + Clone {
+    args.iter().map(|arg| (arg.name.as_str(), &arg.arg_type))
+}
+
+/// Check and skip a leading discriminator
+fn expect_discriminator(
+    reader: &mut BorshReader,
+    expected: &[u8],
+    name: &str,
+) -> Result<(), BorshError> {
+    if reader.take("discriminator", expected.len())? != expected {
+        return Err(BorshError::DiscriminatorMismatch {
+            name: name.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Decode a sequence of fields, returning the values and the bytes consumed
+pub fn decode_fields(
+    fields: &[Field],
+    types: &[TypeDef],
+    data: &[u8],
+) -> Result<(Map<String, Value>, usize), BorshError> {
+    let mut reader = BorshReader::new(data).with_types(types);
+    let values = read_layout(&mut reader, "", field_layout(fields))?;
+    Ok((values, reader.offset()))
+}
+
+/// Encode a JSON object as the given fields
+pub fn encode_fields(
+    fields: &[Field],
+    types: &[TypeDef],
+    value: &Value,
+) -> Result<Vec<u8>, BorshError> {
+    let mut writer = BorshWriter::new().with_types(types);
+    write_layout(&mut writer, "", field_layout(fields), value)?;
+    Ok(writer.into_inner())
+}
+
+/// Encode account data, optionally prefixed with its discriminator
+///
+/// `types` resolves `FieldType::Defined`, usually `ProgramSchema::types`.
+pub fn encode_account(
+    account: &Account,
+    types: &[TypeDef],
+    value: &Value,
+    with_discriminator: bool,
+) -> Result<Vec<u8>, BorshError> {
+    let mut writer = BorshWriter::new().with_types(types);
+    if with_discriminator {
+        writer
+            .data
+            .extend_from_slice(&schema_account_discriminator(account));
+    }
+    write_layout(&mut writer, "", field_layout(&account.fields), value)?;
+    Ok(writer.into_inner())
+}
+
+/// Decode account data as a specific schema account
+///
+/// Bytes after the last field are ignored, as accounts are often allocated
+/// with room to grow.
+pub fn decode_account_as(
+    account: &Account,
+    types: &[TypeDef],
+    data: &[u8],
+    with_discriminator: bool,
+) -> Result<Map<String, Value>, BorshError> {
+    let mut reader = BorshReader::new(data).with_types(types);
+    if with_discriminator {
+        expect_discriminator(
+            &mut reader,
+            &schema_account_discriminator(account),
+            &account.name,
+        )?;
+    }
+    read_layout(&mut reader, "", field_layout(&account.fields))
+}
+
+/// Encode instruction data from a JSON object of arguments
+pub fn encode_instruction(
+    instruction: &Instruction,
+    types: &[TypeDef],
+    args: &Value,
+    with_discriminator: bool,
+) -> Result<Vec<u8>, BorshError> {
+    let mut writer = BorshWriter::new().with_types(types);
+    if with_discriminator {
+        writer
+            .data
+            .extend_from_slice(&schema_instruction_discriminator(instruction));
+    }
+    write_layout(&mut writer, "", arg_layout(&instruction.args), args)?;
+    Ok(writer.into_inner())
+}
+
+/// Decode instruction data into a JSON object of arguments
+///
+/// Unlike account data, instruction data must be consumed exactly.
+pub fn decode_instruction(
+    instruction: &Instruction,
+    types: &[TypeDef],
+    data: &[u8],
+    with_discriminator: bool,
+) -> Result<Map<String, Value>, BorshError> {
+    let mut reader = BorshReader::new(data).with_types(types);
+    if with_discriminator {
+        expect_discriminator(
+            &mut reader,
+            &schema_instruction_discriminator(instruction),
+            &instruction.name,
+        )?;
+    }
+    let values = read_layout(&mut reader, "", arg_layout(&instruction.args))?;
+    match reader.remaining().len() {
+        0 => Ok(values),
+        count => Err(BorshError::TrailingBytes { count }),
+    }
+}
+
+/// Account data decoded against a schema account
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedAccount {
+    /// Name of the matching schema account
+    pub account_name: String,
+    /// Whether the data started with the account's discriminator
+    pub has_discriminator: bool,
+    /// Field values by name
+    pub fields: Map<String, Value>,
+    /// Bytes left after the last field, e.g. space reserved for growth
+    pub trailing_bytes: usize,
+}
+
+/// Find the schema account that describes `data` and decode it
+///
+/// A discriminator identifies the account directly. Without one, the first
+/// account whose fields decode with only zero padding left over wins.
+pub fn decode_account(schema: &ProgramSchema, data: &[u8]) -> Option<DecodedAccount> {
+    let tagged = schema.accounts.iter().find_map(|account| {
+        let discriminator = schema_account_discriminator(account);
+        (!discriminator.is_empty() && data.starts_with(&discriminator))
+            .then_some((account, discriminator.len()))
+    });
+    if let Some((account, len)) = tagged {
+        return try_layout(account, &schema.types, &data[len..], true);
+    }
+
+    schema
+        .accounts
+        .iter()
+        .filter_map(|account| try_layout(account, &schema.types, data, false))
+        .find(|decoded| {
+            data[data.len() - decoded.trailing_bytes..]
+                .iter()
+                .all(|&b| b == 0)
+        })
+}
+
+fn try_layout(
+    account: &Account,
+    types: &[TypeDef],
+    data: &[u8],
+    has_discriminator: bool,
+) -> Option<DecodedAccount> {
+    let (fields, consumed) = decode_fields(&account.fields, types, data).ok()?;
+    Some(DecodedAccount {
+        account_name: account.name.clone(),
+        has_discriminator,
+        fields,
+        trailing_bytes: data.len() - consumed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::program_builder::{EnumVariant, InstructionAccount};
+    use crate::utils::fixtures::{counter_schema, field};
+    use serde_json::json;
+
+    /// The counter schema plus a `Profile` account with a different layout
+    fn accounts_schema() -> ProgramSchema {
+        let mut schema = counter_schema();
+        schema.accounts.push(Account {
+            name: "Profile".to_string(),
+            fields: vec![
+                field("name", FieldType::String),
+                field("active", FieldType::Bool),
+            ],
+            discriminator: None,
+            seeds: vec![],
+        });
+        schema
+    }
+
+    #[test]
+    fn test_account_discriminator() {
+        // sha256("account:Counter")[..8], as computed by Anchor
+        assert_eq!(
+            account_discriminator("Counter"),
+            [255, 176, 4, 245, 188, 253, 124, 25]
+        );
+    }
+
+    #[test]
+    fn test_decode_fields() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&5u32.to_le_bytes());
+        data.extend_from_slice(b"alice");
+        data.push(1);
+        data.extend_from_slice(&0x0102u16.to_le_bytes());
+
+        let fields = [
+            field("name", FieldType::String),
+            field("active", FieldType::Bool),
+            field("level", FieldType::U16),
+        ];
+        let (values, consumed) = decode_fields(&fields, &[], &data).unwrap();
+        assert_eq!(consumed, data.len());
+        assert_eq!(
+            Value::Object(values),
+            json!({"name": "alice", "active": true, "level": 258})
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let short = decode_fields(&[field("count", FieldType::U64)], &[], &[1, 2, 3]);
+        assert_eq!(
+            short,
+            Err(BorshError::UnexpectedEnd {
+                field: "count".to_string(),
+                offset: 0,
+                needed: 5
+            })
+        );
+
+        let bad_bool = decode_fields(&[field("active", FieldType::Bool)], &[], &[2]);
+        assert!(matches!(
+            bad_bool,
+            Err(BorshError::InvalidBool { value: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_account_with_discriminator() {
+        let mut data = account_discriminator("Counter").to_vec();
+        data.extend_from_slice(&[9u8; 32]);
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(b"hi");
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&(-5i32).to_le_bytes());
+        data.push(0);
+
+        let decoded = decode_account(&accounts_schema(), &data).unwrap();
+        assert_eq!(decoded.account_name, "Counter");
+        assert!(decoded.has_discriminator);
+        assert_eq!(decoded.trailing_bytes, 0);
+        assert_eq!(decoded.fields["count"], json!(42));
+        assert_eq!(decoded.fields["label"], json!("hi"));
+        assert_eq!(decoded.fields["history"], json!([-5]));
+        assert_eq!(decoded.fields["mode"], json!("Open"));
+        assert_eq!(
+            decoded.fields["authority"],
+            json!(bs58::encode([9u8; 32]).into_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_account_by_layout() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(b"bob");
+        data.push(0);
+        data.extend_from_slice(&[0u8; 16]);
+
+        let decoded = decode_account(&accounts_schema(), &data).unwrap();
+        assert_eq!(decoded.account_name, "Profile");
+        assert!(!decoded.has_discriminator);
+        assert_eq!(decoded.trailing_bytes, 16);
+
+        assert!(decode_account(&accounts_schema(), &[1, 2, 3]).is_none());
+    }
+
+    fn add_liquidity_instruction() -> Instruction {
+        Instruction {
+            name: "Add Liquidity".to_string(),
+            accounts: vec![InstructionAccount::writable("pool")],
+            args: vec![
+                InstructionArg {
+                    name: "amount".to_string(),
+                    arg_type: FieldType::U64,
+                },
+                InstructionArg {
+                    name: "memo".to_string(),
+                    arg_type: FieldType::String,
+                },
+            ],
+            discriminator: None,
+        }
+    }
+
+    #[test]
+    fn test_instruction_discriminator() {
+        // sha256("global:add_liquidity")[..8]
+        assert_eq!(
+            instruction_discriminator("Add Liquidity"),
+            discriminator("global", "add_liquidity")
+        );
+        assert_ne!(
+            instruction_discriminator("Add Liquidity"),
+            account_discriminator("add_liquidity")
+        );
+    }
+
+    #[test]
+    fn test_account_round_trip() {
+        let schema = accounts_schema();
+        let authority = bs58::encode([9u8; 32]).into_string();
+        let mode = json!({"Locked": {"until_slot": -7, "unlockers": [authority, authority]}});
+        let value = json!({
+            "authority": authority,
+            "count": "18446744073709551615",
+            "label": "hi",
+            "history": [null, 3],
+            "mode": mode,
+        });
+
+        let data = encode_account(&schema.accounts[0], &schema.types, &value, true).unwrap();
+        assert_eq!(
+            data.len(),
+            DISCRIMINATOR_LEN + 32 + 8 + (4 + 2) + (4 + 1 + 5) + (1 + 8 + 64)
+        );
+        assert_eq!(data[..DISCRIMINATOR_LEN], account_discriminator("Counter"));
+
+        let decoded = decode_account_as(&schema.accounts[0], &schema.types, &data, true).unwrap();
+        assert_eq!(decoded["count"], json!(u64::MAX));
+        assert_eq!(decoded["authority"], json!(authority));
+        assert_eq!(decoded["history"], json!([null, 3]));
+        assert_eq!(decoded["mode"], mode);
+
+        assert_eq!(
+            decode_account_as(&schema.accounts[1], &[], &data, true),
+            Err(BorshError::DiscriminatorMismatch {
+                name: "Profile".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_account_discriminator_uses_struct_name() {
+        // Anchor hashes the Rust struct name, whatever the schema calls the account
+        let mut schema = accounts_schema();
+        schema.accounts[1].name = "user profile".to_string();
+        let value = json!({"name": "alice", "active": true});
+
+        let data = encode_account(&schema.accounts[1], &[], &value, true).unwrap();
+        assert_eq!(
+            data[..DISCRIMINATOR_LEN],
+            account_discriminator("UserProfile")
+        );
+        assert!(decode_account_as(&schema.accounts[1], &[], &data, true).is_ok());
+
+        let decoded = decode_account(&schema, &data).unwrap();
+        assert_eq!(decoded.account_name, "user profile");
+        assert!(decoded.has_discriminator);
+    }
+
+    #[test]
+    fn test_instruction_round_trip() {
+        let instruction = add_liquidity_instruction();
+        let data = encode_instruction(
+            &instruction,
+            &[],
+            &json!({"amount": 1000, "memo": "hi"}),
+            false,
+        )
+        .unwrap();
+        assert_eq!(data, [232, 3, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, b'h', b'i']);
+
+        let decoded = decode_instruction(&instruction, &[], &data, false).unwrap();
+        assert_eq!(
+            Value::Object(decoded),
+            json!({"amount": 1000, "memo": "hi"})
+        );
+
+        let mut padded = data.clone();
+        padded.push(0);
+        assert_eq!(
+            decode_instruction(&instruction, &[], &padded, false),
+            Err(BorshError::TrailingBytes { count: 1 })
+        );
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        let instruction = add_liquidity_instruction();
+        let encode = |value: Value| encode_instruction(&instruction, &[], &value, false);
+
+        assert_eq!(
+            encode(json!({"amount": 1})),
+            Err(BorshError::MissingField {
+                field: "memo".to_string()
+            })
+        );
+        assert_eq!(
+            encode(json!({"amount": 1, "memo": "", "extra": true})),
+            Err(BorshError::UnknownField {
+                field: "extra".to_string()
+            })
+        );
+        assert!(matches!(
+            encode(json!({"amount": -1, "memo": ""})),
+            Err(BorshError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            encode(json!({"amount": 1, "memo": 5})),
+            Err(BorshError::TypeMismatch { .. })
+        ));
+
+        let bump = [field("bump", FieldType::U8)];
+        assert!(matches!(
+            encode_fields(&bump, &[], &json!({"bump": 256})),
+            Err(BorshError::OutOfRange { .. })
+        ));
+        let key = [field("key", FieldType::Pubkey)];
+        assert!(matches!(
+            encode_fields(&key, &[], &json!({"key": "abc"})),
+            Err(BorshError::InvalidPubkey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_declared_discriminator() {
+        let mut instruction = add_liquidity_instruction();
+        instruction.discriminator = Some(vec![7]);
+        let data =
+            encode_instruction(&instruction, &[], &json!({"amount": 1, "memo": ""}), true).unwrap();
+        assert_eq!(data[0], 7);
+        assert_eq!(data.len(), 1 + 8 + 4);
+        assert!(decode_instruction(&instruction, &[], &data, true).is_ok());
+
+        let mut schema = accounts_schema();
+        schema.accounts[1].discriminator = Some(vec![1, 2]);
+        let mut data = vec![1, 2];
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.push(1);
+        let decoded = decode_account(&schema, &data).unwrap();
+        assert_eq!(decoded.account_name, "Profile");
+        assert!(decoded.has_discriminator);
+    }
+
+    fn stake_types() -> Vec<TypeDef> {
+        vec![
+            TypeDef {
+                name: "Staker".to_string(),
+                kind: TypeDefKind::Struct {
+                    fields: vec![
+                        field("owner", FieldType::Pubkey),
+                        field("delta", FieldType::I64),
+                    ],
+                },
+            },
+            TypeDef {
+                name: "Mode".to_string(),
+                kind: TypeDefKind::Enum {
+                    variants: vec![
+                        EnumVariant {
+                            name: "Paused".to_string(),
+                            fields: Vec::new(),
+                        },
+                        EnumVariant {
+                            name: "Locked".to_string(),
+                            fields: vec![field("until", FieldType::U128)],
+                        },
+                    ],
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn test_composite_round_trip() {
+        let types = stake_types();
+        let fields = [
+            field(
+                "stakers",
+                FieldType::Vec(Box::new(FieldType::Defined("Staker".to_string()))),
+            ),
+            field("authority", FieldType::Option(Box::new(FieldType::Pubkey))),
+            field("seed", FieldType::Array(Box::new(FieldType::U8), 3)),
+            field("mode", FieldType::Defined("Mode".to_string())),
+            field("fallback", FieldType::Defined("Mode".to_string())),
+        ];
+        let owner = bs58::encode([3u8; 32]).into_string();
+        let value = json!({
+            "stakers": [{"owner": owner, "delta": -5}],
+            "authority": null,
+            "seed": [1, 2, 3],
+            "mode": {"Locked": {"until": "340282366920938463463374607431768211455"}},
+            "fallback": "Paused",
+        });
+
+        let data = encode_fields(&fields, &types, &value).unwrap();
+        let mut expected = 1u32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&[3u8; 32]);
+        expected.extend_from_slice(&(-5i64).to_le_bytes());
+        expected.extend_from_slice(&[0, 1, 2, 3, 1]);
+        expected.extend_from_slice(&u128::MAX.to_le_bytes());
+        expected.push(0);
+        assert_eq!(data, expected);
+
+        let (decoded, consumed) = decode_fields(&fields, &types, &data).unwrap();
+        assert_eq!(consumed, data.len());
+        assert_eq!(Value::Object(decoded), value);
+    }
+
+    #[test]
+    fn test_composite_errors() {
+        let types = stake_types();
+        let staker = [field("staker", FieldType::Defined("Staker".to_string()))];
+        assert_eq!(
+            encode_fields(
+                &staker,
+                &types,
+                &json!({"staker": {"owner": bs58::encode([0u8; 32]).into_string()}})
+            ),
+            Err(BorshError::MissingField {
+                field: "staker.delta".to_string()
+            })
+        );
+
+        let mode = [field("mode", FieldType::Defined("Mode".to_string()))];
+        assert!(matches!(
+            encode_fields(&mode, &types, &json!({"mode": "Running"})),
+            Err(BorshError::UnknownVariant { .. })
+        ));
+        assert_eq!(
+            decode_fields(&mode, &types, &[2]),
+            Err(BorshError::InvalidTag {
+                field: "mode".to_string(),
+                tag: 2
+            })
+        );
+        assert!(matches!(
+            decode_fields(&mode, &[], &[0]),
+            Err(BorshError::UnknownType { .. })
+        ));
+
+        let list = [field("list", FieldType::Vec(Box::new(FieldType::U8)))];
+        assert!(matches!(
+            decode_fields(&list, &[], &[255, 255, 255, 255, 1]),
+            Err(BorshError::UnexpectedEnd { .. })
+        ));
+
+        // A struct that contains itself can never be decoded
+        let recursive = vec![TypeDef {
+            name: "Node".to_string(),
+            kind: TypeDefKind::Struct {
+                fields: vec![field("next", FieldType::Defined("Node".to_string()))],
+            },
+        }];
+        let node = [field("node", FieldType::Defined("Node".to_string()))];
+        assert!(matches!(
+            decode_fields(&node, &recursive, &[]),
+            Err(BorshError::TooDeep { .. })
+        ));
+    }
+}
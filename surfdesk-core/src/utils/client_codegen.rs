@@ -0,0 +1,1159 @@
+//! Client SDK generation
+//!
+//! Turns a program builder `ProgramSchema` into a Rust module and a
+//! TypeScript module for calling the program: instruction builders that take
+//! the instruction's accounts and encode its arguments, account decoders, and
+//! PDA helpers for accounts that declare seeds. Both modules use the
+//! discriminators and Borsh layouts of `utils::borsh`, so they agree with the
+//! IDL export and the generated native program.
+//!
+//! The Rust module depends on `borsh` and `solana-program`, the TypeScript
+//! module on `@solana/web3.js` 1.x and carries its own small Borsh reader and
+//! writer.
+
+use crate::components::program_builder::{
+    Account, FieldType, Instruction, ProgramSchema, Seed, TypeDef, TypeDefKind,
+};
+use crate::utils::borsh::{schema_account_discriminator, schema_instruction_discriminator};
+use crate::utils::codegen::{
+    byte_array, check_schema, file, fits, rust_type, signature, struct_fields, typedef,
+    typedef_fields, use_list, uses_pubkey, CodegenError, GeneratedFile, GeneratedProject,
+    BORSH_VERSION, DERIVE, SOLANA_PROGRAM_VERSION,
+};
+use crate::utils::format::{to_camel_case, to_pascal_case, to_snake_case};
+use std::collections::BTreeSet;
+
+/// Language of a generated client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientLanguage {
+    Rust,
+    TypeScript,
+}
+
+impl ClientLanguage {
+    pub fn all() -> [ClientLanguage; 2] {
+        [ClientLanguage::Rust, ClientLanguage::TypeScript]
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ClientLanguage::Rust => "rs",
+            ClientLanguage::TypeScript => "ts",
+        }
+    }
+}
+
+/// Generate the client module for one language
+///
+/// The schema must pass `validate_schema` without errors.
+pub fn client_module(
+    schema: &ProgramSchema,
+    language: ClientLanguage,
+) -> Result<GeneratedFile, CodegenError> {
+    check_schema(schema)?;
+    let path = format!(
+        "{}_client.{}",
+        to_snake_case(&schema.name),
+        language.extension()
+    );
+    let contents = match language {
+        ClientLanguage::Rust => RustClient { schema }.module(),
+        ClientLanguage::TypeScript => TsClient { schema }.module(),
+    };
+    Ok(file(&path, contents))
+}
+
+/// Generate the Rust and TypeScript client modules
+pub fn client_modules(schema: &ProgramSchema) -> Result<GeneratedProject, CodegenError> {
+    Ok(GeneratedProject {
+        name: format!("{}_client", to_snake_case(&schema.name)),
+        files: ClientLanguage::all()
+            .into_iter()
+            .map(|language| client_module(schema, language))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Fields of the schema's accounts and types
+fn field_types(schema: &ProgramSchema) -> impl Iterator<Item = &FieldType> {
+    schema
+        .accounts
+        .iter()
+        .flat_map(|account| account.fields.iter())
+        .chain(schema.types.iter().flat_map(typedef_fields))
+        .map(|field| &field.field_type)
+}
+
+fn seed_args(account: &Account) -> impl Iterator<Item = (&String, &FieldType)> {
+    account.seeds.iter().filter_map(|seed| match seed {
+        Seed::Arg { name, arg_type } => Some((name, arg_type)),
+        Seed::Const(_) => None,
+    })
+}
+
+/// Call with its arguments on one line, or one per line when too long
+fn call(indent: &str, head: &str, args: &[String], tail: &str, trailing_comma: bool) -> String {
+    let single = format!("{}{}({}){}", indent, head, args.join(", "), tail);
+    if fits(&single) {
+        return single + "\n";
+    }
+    let mut out = format!("{}{}(\n", indent, head);
+    for (i, arg) in args.iter().enumerate() {
+        let comma = if trailing_comma || i + 1 < args.len() {
+            ","
+        } else {
+            ""
+        };
+        out.push_str(&format!("{}    {}{}\n", indent, arg, comma));
+    }
+    out.push_str(&format!("{}){}\n", indent, tail));
+    out
+}
+
+struct RustClient<'a> {
+    schema: &'a ProgramSchema,
+}
+
+impl RustClient<'_> {
+    fn module(&self) -> String {
+        let schema = self.schema;
+        let has_borsh = !schema.accounts.is_empty()
+            || !schema.types.is_empty()
+            || schema.instructions.iter().any(|ix| !ix.args.is_empty());
+        let has_accounts = schema.instructions.iter().any(|ix| !ix.accounts.is_empty());
+        let needs_pubkey = !schema.instructions.is_empty()
+            || schema
+                .accounts
+                .iter()
+                .any(|account| !account.seeds.is_empty())
+            || field_types(schema).any(uses_pubkey)
+            || schema
+                .instructions
+                .iter()
+                .flat_map(|ix| ix.args.iter())
+                .any(|arg| uses_pubkey(&arg.arg_type));
+
+        let mut out = format!(
+            "//! Client for the `{}` program, generated by Surfdesk from its program schema\n",
+            schema.name
+        );
+        out.push_str(&format!(
+            "//!\n//! Needs `borsh` {} with the `derive` feature and `solana-program` {}.\n",
+            BORSH_VERSION, SOLANA_PROGRAM_VERSION
+        ));
+        let mut imports = String::new();
+        if has_borsh {
+            imports.push_str("use borsh::{BorshDeserialize, BorshSerialize};\n");
+        }
+        let mut instruction_items = BTreeSet::new();
+        if !schema.instructions.is_empty() {
+            instruction_items.insert("Instruction".to_string());
+        }
+        if has_accounts {
+            instruction_items.insert("AccountMeta".to_string());
+        }
+        imports.push_str(&use_list("solana_program::instruction", &instruction_items));
+        if !schema.accounts.is_empty() {
+            imports.push_str("use solana_program::program_error::ProgramError;\n");
+        }
+        if needs_pubkey {
+            imports.push_str("use solana_program::pubkey::Pubkey;\n");
+        }
+        if !imports.is_empty() {
+            out.push('\n');
+            out.push_str(&imports);
+        }
+
+        for def in &schema.types {
+            out.push('\n');
+            out.push_str(&typedef(def));
+        }
+        for account in &schema.accounts {
+            out.push('\n');
+            out.push_str(&self.account(account));
+        }
+        for instruction in &schema.instructions {
+            out.push('\n');
+            out.push_str(&self.instruction(instruction));
+        }
+        out
+    }
+
+    fn account(&self, account: &Account) -> String {
+        let name = to_pascal_case(&account.name);
+        let discriminator = schema_account_discriminator(account);
+
+        let mut out = format!(
+            "/// `{}` account data, stored after its discriminator\n",
+            name
+        );
+        out.push_str(DERIVE);
+        out.push_str(&format!("pub struct {} {{\n", name));
+        out.push_str(&struct_fields(&account.fields, "    ", true));
+        out.push_str("}\n\n");
+
+        out.push_str(&format!("impl {} {{\n", name));
+        out.push_str(&format!(
+            "    pub const DISCRIMINATOR: [u8; {}] = {};\n\n",
+            discriminator.len(),
+            byte_array(&discriminator)
+        ));
+        out.push_str(
+            r#"    /// Decode account data, checking its discriminator
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut rest = data
+            .strip_prefix(&Self::DISCRIMINATOR[..])
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Self::deserialize(&mut rest).map_err(|_| ProgramError::InvalidAccountData)
+    }
+"#,
+        );
+
+        if !account.seeds.is_empty() {
+            let mut params: Vec<String> = seed_args(account)
+                .map(|(name, arg_type)| {
+                    let param_type = match arg_type {
+                        FieldType::Pubkey => "&Pubkey".to_string(),
+                        FieldType::String => "&str".to_string(),
+                        integer => integer.to_string(),
+                    };
+                    format!("{}: {}", to_snake_case(name), param_type)
+                })
+                .collect();
+            params.push("program_id: &Pubkey".to_string());
+            let seeds: Vec<String> = account.seeds.iter().map(rust_seed).collect();
+
+            out.push_str("\n    /// Find the account's program derived address and bump\n");
+            out.push_str(&signature(
+                "    ",
+                "pub fn find_address",
+                &params,
+                "(Pubkey, u8)",
+            ));
+            let single_array = format!("&[{}]", seeds.join(", "));
+            let array = if fits(&format!("            {},", single_array)) {
+                single_array
+            } else {
+                format!(
+                    "&[\n                {},\n            ]",
+                    seeds.join(",\n                ")
+                )
+            };
+            out.push_str(&call(
+                "        ",
+                "Pubkey::find_program_address",
+                &[array, "program_id".to_string()],
+                "",
+                true,
+            ));
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn instruction(&self, instruction: &Instruction) -> String {
+        let variant = to_pascal_case(&instruction.name);
+        let constant = format!(
+            "{}_DISCRIMINATOR",
+            to_snake_case(&instruction.name).to_uppercase()
+        );
+        let discriminator = schema_instruction_discriminator(instruction);
+        let mut out = String::new();
+
+        if !instruction.accounts.is_empty() {
+            out.push_str(&format!("/// Accounts of `{}`\n", variant));
+            out.push_str("#[derive(Clone, Copy, Debug, PartialEq)]\n");
+            out.push_str(&format!("pub struct {}Accounts {{\n", variant));
+            for account in &instruction.accounts {
+                let flags = match (account.writable, account.signer) {
+                    (true, true) => Some("Writable, signer"),
+                    (true, false) => Some("Writable"),
+                    (false, true) => Some("Signer"),
+                    (false, false) => None,
+                };
+                if let Some(flags) = flags {
+                    out.push_str(&format!("    /// {}\n", flags));
+                }
+                out.push_str(&format!(
+                    "    pub {}: Pubkey,\n",
+                    to_snake_case(&account.name)
+                ));
+            }
+            out.push_str("}\n\n");
+        }
+        if !instruction.args.is_empty() {
+            out.push_str(&format!("/// Arguments of `{}`\n", variant));
+            out.push_str(DERIVE);
+            out.push_str(&format!("pub struct {}Args {{\n", variant));
+            for arg in &instruction.args {
+                out.push_str(&format!(
+                    "    pub {}: {},\n",
+                    to_snake_case(&arg.name),
+                    rust_type(&arg.arg_type)
+                ));
+            }
+            out.push_str("}\n\n");
+        }
+
+        out.push_str(&format!(
+            "pub const {}: [u8; {}] = {};\n\n",
+            constant,
+            discriminator.len(),
+            byte_array(&discriminator)
+        ));
+
+        let mut params = vec!["program_id: &Pubkey".to_string()];
+        if !instruction.accounts.is_empty() {
+            params.push(format!("accounts: &{}Accounts", variant));
+        }
+        if !instruction.args.is_empty() {
+            params.push(format!("args: {}Args", variant));
+        }
+        out.push_str(&format!("/// Build the `{}` instruction\n", variant));
+        out.push_str(&signature(
+            "",
+            &format!("pub fn {}", to_snake_case(&instruction.name)),
+            &params,
+            "Instruction",
+        ));
+        if !instruction.args.is_empty() {
+            out.push_str(&format!(
+                "    let mut data = {}.to_vec();\n    args.serialize(&mut data)\n        .expect(\"writing to a Vec cannot fail\");\n",
+                constant
+            ));
+        }
+        out.push_str("    Instruction {\n        program_id: *program_id,\n");
+        if instruction.accounts.is_empty() {
+            out.push_str("        accounts: vec![],\n");
+        } else {
+            out.push_str("        accounts: vec![\n");
+            for account in &instruction.accounts {
+                out.push_str(&format!(
+                    "            AccountMeta::{}(accounts.{}, {}),\n",
+                    if account.writable {
+                        "new"
+                    } else {
+                        "new_readonly"
+                    },
+                    to_snake_case(&account.name),
+                    account.signer
+                ));
+            }
+            out.push_str("        ],\n");
+        }
+        if instruction.args.is_empty() {
+            out.push_str(&format!("        data: {}.to_vec(),\n", constant));
+        } else {
+            out.push_str("        data,\n");
+        }
+        out.push_str("    }\n}\n");
+        out
+    }
+}
+
+/// Rust expression for a seed's bytes
+fn rust_seed(seed: &Seed) -> String {
+    match seed {
+        Seed::Const(value) if value.chars().all(|c| c == ' ' || c.is_ascii_graphic()) => {
+            format!("b{:?}", value)
+        }
+        Seed::Const(value) => format!("{:?}.as_bytes()", value),
+        Seed::Arg { name, arg_type } => {
+            let name = to_snake_case(name);
+            match arg_type {
+                FieldType::Pubkey => format!("{}.as_ref()", name),
+                FieldType::String => format!("{}.as_bytes()", name),
+                _ => format!("&{}.to_le_bytes()", name),
+            }
+        }
+    }
+}
+
+/// Borsh reader and writer shared by the generated TypeScript functions
+const TS_RUNTIME: &str = r#"/** Borsh encoder for instruction data and seeds */
+export class BorshWriter {
+  private readonly bytes: number[] = [];
+
+  raw(bytes: ArrayLike<number>): void {
+    for (let i = 0; i < bytes.length; i++) {
+      this.bytes.push(bytes[i]);
+    }
+  }
+
+  u8(value: number): void {
+    this.int(value, 1, false);
+  }
+
+  u16(value: number): void {
+    this.int(value, 2, false);
+  }
+
+  u32(value: number): void {
+    this.int(value, 4, false);
+  }
+
+  u64(value: bigint): void {
+    this.int(value, 8, false);
+  }
+
+  u128(value: bigint): void {
+    this.int(value, 16, false);
+  }
+
+  i8(value: number): void {
+    this.int(value, 1, true);
+  }
+
+  i16(value: number): void {
+    this.int(value, 2, true);
+  }
+
+  i32(value: number): void {
+    this.int(value, 4, true);
+  }
+
+  i64(value: bigint): void {
+    this.int(value, 8, true);
+  }
+
+  i128(value: bigint): void {
+    this.int(value, 16, true);
+  }
+
+  bool(value: boolean): void {
+    this.u8(value ? 1 : 0);
+  }
+
+  string(value: string): void {
+    const bytes = new TextEncoder().encode(value);
+    this.u32(bytes.length);
+    this.raw(bytes);
+  }
+
+  pubkey(value: PublicKey): void {
+    this.raw(value.toBytes());
+  }
+
+  option<T>(value: T | null, write: (item: T) => void): void {
+    if (value === null) {
+      this.u8(0);
+    } else {
+      this.u8(1);
+      write(value);
+    }
+  }
+
+  vec<T>(items: T[], write: (item: T) => void): void {
+    this.u32(items.length);
+    this.array(items, items.length, write);
+  }
+
+  array<T>(items: T[], length: number, write: (item: T) => void): void {
+    if (items.length !== length) {
+      throw new RangeError(`Expected ${length} items, got ${items.length}`);
+    }
+    for (const item of items) {
+      write(item);
+    }
+  }
+
+  toBuffer(): Buffer {
+    return Buffer.from(this.bytes);
+  }
+
+  private int(value: number | bigint, size: number, signed: boolean): void {
+    const bits = BigInt(size * 8);
+    let n = BigInt(value);
+    const min = signed ? -(1n << (bits - 1n)) : 0n;
+    const max = (signed ? 1n << (bits - 1n) : 1n << bits) - 1n;
+    if (n < min || n > max) {
+      throw new RangeError(`${value} does not fit in ${signed ? "i" : "u"}${bits}`);
+    }
+    n = BigInt.asUintN(size * 8, n);
+    for (let i = 0; i < size; i++) {
+      this.bytes.push(Number(n & 0xffn));
+      n >>= 8n;
+    }
+  }
+}
+
+/** Borsh decoder for account data */
+export class BorshReader {
+  private offset = 0;
+
+  constructor(private readonly data: Uint8Array) {}
+
+  discriminator(expected: readonly number[]): void {
+    const actual = this.take(expected.length);
+    if (!expected.every((byte, i) => actual[i] === byte)) {
+      throw new Error("Account discriminator mismatch");
+    }
+  }
+
+  u8(): number {
+    return Number(this.int(1, false));
+  }
+
+  u16(): number {
+    return Number(this.int(2, false));
+  }
+
+  u32(): number {
+    return Number(this.int(4, false));
+  }
+
+  u64(): bigint {
+    return this.int(8, false);
+  }
+
+  u128(): bigint {
+    return this.int(16, false);
+  }
+
+  i8(): number {
+    return Number(this.int(1, true));
+  }
+
+  i16(): number {
+    return Number(this.int(2, true));
+  }
+
+  i32(): number {
+    return Number(this.int(4, true));
+  }
+
+  i64(): bigint {
+    return this.int(8, true);
+  }
+
+  i128(): bigint {
+    return this.int(16, true);
+  }
+
+  bool(): boolean {
+    const value = this.u8();
+    if (value > 1) {
+      throw new Error(`Invalid bool ${value}`);
+    }
+    return value === 1;
+  }
+
+  string(): string {
+    const bytes = this.take(this.u32());
+    return new TextDecoder("utf-8", { fatal: true }).decode(bytes);
+  }
+
+  pubkey(): PublicKey {
+    return new PublicKey(this.take(32));
+  }
+
+  option<T>(read: () => T): T | null {
+    const tag = this.u8();
+    if (tag > 1) {
+      throw new Error(`Invalid option tag ${tag}`);
+    }
+    return tag === 1 ? read() : null;
+  }
+
+  vec<T>(read: () => T): T[] {
+    return this.array(this.u32(), read);
+  }
+
+  array<T>(length: number, read: () => T): T[] {
+    const items: T[] = [];
+    for (let i = 0; i < length; i++) {
+      items.push(read());
+    }
+    return items;
+  }
+
+  private take(length: number): Uint8Array {
+    if (this.offset + length > this.data.length) {
+      throw new Error("Unexpected end of data");
+    }
+    const bytes = this.data.subarray(this.offset, this.offset + length);
+    this.offset += length;
+    return bytes;
+  }
+
+  private int(size: number, signed: boolean): bigint {
+    const bytes = this.take(size);
+    let n = 0n;
+    for (let i = size - 1; i >= 0; i--) {
+      n = (n << 8n) | BigInt(bytes[i]);
+    }
+    return signed ? BigInt.asIntN(size * 8, n) : n;
+  }
+}
+"#;
+
+/// Encode integer seeds with a throwaway writer
+const TS_SEED_BYTES: &str = r#"
+function seedBytes(write: (w: BorshWriter) => void): Buffer {
+  const w = new BorshWriter();
+  write(w);
+  return w.toBuffer();
+}
+"#;
+
+/// Whether a TypeScript line fits Prettier's default width
+fn ts_fits(line: &str) -> bool {
+    line.chars().count() <= 80
+}
+
+/// TypeScript spelling of a schema field type
+pub fn ts_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::U8
+        | FieldType::U16
+        | FieldType::U32
+        | FieldType::I8
+        | FieldType::I16
+        | FieldType::I32 => "number".to_string(),
+        FieldType::U64 | FieldType::U128 | FieldType::I64 | FieldType::I128 => "bigint".to_string(),
+        FieldType::String => "string".to_string(),
+        FieldType::Pubkey => "PublicKey".to_string(),
+        FieldType::Bool => "boolean".to_string(),
+        FieldType::Vec(item) | FieldType::Array(item, _) => match item.as_ref() {
+            FieldType::Option(_) => format!("Array<{}>", ts_type(item)),
+            _ => format!("{}[]", ts_type(item)),
+        },
+        FieldType::Option(item) => format!("{} | null", ts_type(item)),
+        FieldType::Defined(name) => to_pascal_case(name),
+    }
+}
+
+/// Expression writing `value` with the writer `w`
+fn ts_encode(field_type: &FieldType, value: &str) -> String {
+    match field_type {
+        FieldType::Vec(item) => format!("w.vec({}, (item) => {})", value, ts_encode(item, "item")),
+        FieldType::Option(item) => {
+            format!("w.option({}, (item) => {})", value, ts_encode(item, "item"))
+        }
+        FieldType::Array(item, len) => format!(
+            "w.array({}, {}, (item) => {})",
+            value,
+            len,
+            ts_encode(item, "item")
+        ),
+        FieldType::Defined(name) => format!("encode{}(w, {})", to_pascal_case(name), value),
+        primitive => format!("w.{}({})", primitive.to_string().to_lowercase(), value),
+    }
+}
+
+/// Expression reading a value with the reader `r`
+fn ts_decode(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Vec(item) => format!("r.vec(() => {})", ts_decode(item)),
+        FieldType::Option(item) => format!("r.option(() => {})", ts_decode(item)),
+        FieldType::Array(item, len) => format!("r.array({}, () => {})", len, ts_decode(item)),
+        FieldType::Defined(name) => format!("decode{}(r)", to_pascal_case(name)),
+        primitive => format!("r.{}()", primitive.to_string().to_lowercase()),
+    }
+}
+
+/// TypeScript expression for a seed's bytes, reading args from `seeds`
+fn ts_seed(seed: &Seed) -> String {
+    match seed {
+        Seed::Const(value) => format!("Buffer.from({})", serde_json::Value::from(value.as_str())),
+        Seed::Arg { name, arg_type } => {
+            let value = format!("seeds.{}", to_camel_case(name));
+            match arg_type {
+                FieldType::Pubkey => format!("{}.toBuffer()", value),
+                FieldType::String => format!("Buffer.from({})", value),
+                integer => format!("seedBytes((w) => {})", ts_encode(integer, &value)),
+            }
+        }
+    }
+}
+
+/// Function signature, wrapped one parameter per line when too long
+fn ts_signature(head: &str, params: &[String], ret: &str) -> String {
+    let single = format!("{}({}): {} {{\n", head, params.join(", "), ret);
+    if ts_fits(single.trim_end()) {
+        return single;
+    }
+    let mut out = format!("{}(\n", head);
+    for param in params {
+        out.push_str(&format!("  {},\n", param));
+    }
+    out.push_str(&format!("): {} {{\n", ret));
+    out
+}
+
+/// `{ a: x, b: y }` on one line when it fits after `prefix`, else one
+/// property per line at `indent`
+fn ts_object(prefix: &str, indent: &str, properties: &[String], suffix: &str) -> String {
+    if properties.is_empty() {
+        return format!("{}{{}}{}\n", prefix, suffix);
+    }
+    let single = format!("{}{{ {} }}{}", prefix, properties.join(", "), suffix);
+    if ts_fits(&single) {
+        return single + "\n";
+    }
+    let mut out = format!("{}{{\n", prefix);
+    for property in properties {
+        out.push_str(&format!("{}  {},\n", indent, property));
+    }
+    out.push_str(&format!("{}}}{}\n", indent, suffix));
+    out
+}
+
+struct TsClient<'a> {
+    schema: &'a ProgramSchema,
+}
+
+impl TsClient<'_> {
+    fn module(&self) -> String {
+        let schema = self.schema;
+        let mut out = format!(
+            "// Client for the `{}` program, generated by Surfdesk from its program schema\n",
+            schema.name
+        );
+        out.push_str("//\n// Needs `@solana/web3.js` 1.x and an ES2020 or later target.\n\n");
+        if schema.instructions.is_empty() {
+            out.push_str("import { Buffer } from \"buffer\";\nimport { PublicKey } from \"@solana/web3.js\";\n\n");
+        } else {
+            out.push_str("import { Buffer } from \"buffer\";\nimport { PublicKey, TransactionInstruction } from \"@solana/web3.js\";\n\n");
+        }
+        out.push_str(TS_RUNTIME);
+        let integer_seeds = schema
+            .accounts
+            .iter()
+            .flat_map(seed_args)
+            .any(|(_, arg_type)| !matches!(arg_type, FieldType::Pubkey | FieldType::String));
+        if integer_seeds {
+            out.push_str(TS_SEED_BYTES);
+        }
+
+        for def in &schema.types {
+            out.push('\n');
+            out.push_str(&self.typedef(def));
+        }
+        for account in &schema.accounts {
+            out.push('\n');
+            out.push_str(&self.account(account));
+        }
+        for instruction in &schema.instructions {
+            out.push('\n');
+            out.push_str(&self.instruction(instruction));
+        }
+        out
+    }
+
+    fn interface(&self, name: &str, fields: &[(String, String)]) -> String {
+        if fields.is_empty() {
+            return format!("export interface {} {{}}\n", name);
+        }
+        let mut out = format!("export interface {} {{\n", name);
+        for (field, field_type) in fields {
+            out.push_str(&format!("  {}: {};\n", field, field_type));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn typedef(&self, def: &TypeDef) -> String {
+        let name = to_pascal_case(&def.name);
+        let mut out = String::new();
+        match &def.kind {
+            TypeDefKind::Struct { fields } => {
+                let members: Vec<(String, String)> = fields
+                    .iter()
+                    .map(|field| (to_camel_case(&field.name), ts_type(&field.field_type)))
+                    .collect();
+                out.push_str(&self.interface(&name, &members));
+
+                out.push_str(&format!(
+                    "\nexport function encode{}(w: BorshWriter, value: {}): void {{\n",
+                    name, name
+                ));
+                for field in fields {
+                    let value = format!("value.{}", to_camel_case(&field.name));
+                    out.push_str(&format!("  {};\n", ts_encode(&field.field_type, &value)));
+                }
+                out.push_str("}\n");
+
+                out.push_str(&format!(
+                    "\nexport function decode{}(r: BorshReader): {} {{\n",
+                    name, name
+                ));
+                let properties: Vec<String> = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            to_camel_case(&field.name),
+                            ts_decode(&field.field_type)
+                        )
+                    })
+                    .collect();
+                out.push_str(&ts_object("  return ", "  ", &properties, ";"));
+                out.push_str("}\n");
+            }
+            TypeDefKind::Enum { variants } if variants.is_empty() => {
+                out.push_str(&format!("export type {} = never;\n", name));
+                out.push_str(&format!(
+                    "\nexport function encode{}(_w: BorshWriter, _value: {}): void {{\n  throw new Error(\"{} has no variants\");\n}}\n",
+                    name, name, name
+                ));
+                out.push_str(&format!(
+                    "\nexport function decode{}(_r: BorshReader): {} {{\n  throw new Error(\"{} has no variants\");\n}}\n",
+                    name, name, name
+                ));
+            }
+            TypeDefKind::Enum { variants } => {
+                let alternatives: Vec<String> = variants
+                    .iter()
+                    .map(|variant| {
+                        let mut properties =
+                            vec![format!("__kind: \"{}\"", to_pascal_case(&variant.name))];
+                        properties.extend(variant.fields.iter().map(|field| {
+                            format!(
+                                "{}: {}",
+                                to_camel_case(&field.name),
+                                ts_type(&field.field_type)
+                            )
+                        }));
+                        format!("{{ {} }}", properties.join("; "))
+                    })
+                    .collect();
+                let single = format!("export type {} = {};", name, alternatives.join(" | "));
+                if ts_fits(&single) {
+                    out.push_str(&single);
+                    out.push('\n');
+                } else {
+                    out.push_str(&format!("export type {} =\n", name));
+                    for (i, alternative) in alternatives.iter().enumerate() {
+                        out.push_str(&format!(
+                            "  | {}{}\n",
+                            alternative,
+                            if i + 1 == alternatives.len() { ";" } else { "" }
+                        ));
+                    }
+                }
+
+                out.push_str(&format!(
+                    "\nexport function encode{}(w: BorshWriter, value: {}): void {{\n  switch (value.__kind) {{\n",
+                    name, name
+                ));
+                for (index, variant) in variants.iter().enumerate() {
+                    out.push_str(&format!(
+                        "    case \"{}\":\n      w.u8({});\n",
+                        to_pascal_case(&variant.name),
+                        index
+                    ));
+                    for field in &variant.fields {
+                        let value = format!("value.{}", to_camel_case(&field.name));
+                        out.push_str(&format!(
+                            "      {};\n",
+                            ts_encode(&field.field_type, &value)
+                        ));
+                    }
+                    out.push_str("      break;\n");
+                }
+                out.push_str("  }\n}\n");
+
+                out.push_str(&format!(
+                    "\nexport function decode{}(r: BorshReader): {} {{\n  const variant = r.u8();\n  switch (variant) {{\n",
+                    name, name
+                ));
+                for (index, variant) in variants.iter().enumerate() {
+                    let mut properties =
+                        vec![format!("__kind: \"{}\"", to_pascal_case(&variant.name))];
+                    properties.extend(variant.fields.iter().map(|field| {
+                        format!(
+                            "{}: {}",
+                            to_camel_case(&field.name),
+                            ts_decode(&field.field_type)
+                        )
+                    }));
+                    out.push_str(&format!("    case {}:\n", index));
+                    out.push_str(&ts_object("      return ", "      ", &properties, ";"));
+                }
+                out.push_str(&format!(
+                    "    default:\n      throw new Error(`Invalid {} variant ${{variant}}`);\n  }}\n}}\n",
+                    name
+                ));
+            }
+        }
+        out
+    }
+
+    fn account(&self, account: &Account) -> String {
+        let name = to_pascal_case(&account.name);
+        let constant = format!(
+            "{}_DISCRIMINATOR",
+            to_snake_case(&account.name).to_uppercase()
+        );
+        let discriminator = schema_account_discriminator(account);
+
+        let members: Vec<(String, String)> = account
+            .fields
+            .iter()
+            .map(|field| (to_camel_case(&field.name), ts_type(&field.field_type)))
+            .collect();
+        let mut out = format!(
+            "/** `{}` account data, stored after its discriminator */\n",
+            name
+        );
+        out.push_str(&self.interface(&name, &members));
+        out.push_str(&format!(
+            "\nexport const {}: readonly number[] = {};\n",
+            constant,
+            byte_array(&discriminator)
+        ));
+
+        out.push_str(&format!(
+            "\n/** Decode `{}` account data, checking its discriminator */\nexport function decode{}(data: Uint8Array): {} {{\n",
+            name, name, name
+        ));
+        out.push_str(&format!(
+            "  const r = new BorshReader(data);\n  r.discriminator({});\n",
+            constant
+        ));
+        let properties: Vec<String> = account
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}: {}",
+                    to_camel_case(&field.name),
+                    ts_decode(&field.field_type)
+                )
+            })
+            .collect();
+        out.push_str(&ts_object("  return ", "  ", &properties, ";"));
+        out.push_str("}\n");
+
+        if !account.seeds.is_empty() {
+            let seed_members: Vec<(String, String)> = seed_args(account)
+                .map(|(name, arg_type)| (to_camel_case(name), ts_type(arg_type)))
+                .collect();
+            let mut params = Vec::new();
+            if !seed_members.is_empty() {
+                out.push_str(&format!("\n/** Seeds of the `{}` address */\n", name));
+                out.push_str(&self.interface(&format!("{}Seeds", name), &seed_members));
+                params.push(format!("seeds: {}Seeds", name));
+            }
+            params.push("programId: PublicKey".to_string());
+
+            out.push_str(&format!(
+                "\n/** Find the `{}` program derived address and bump */\n",
+                name
+            ));
+            out.push_str(&ts_signature(
+                &format!("export function find{}Address", name),
+                &params,
+                "[PublicKey, number]",
+            ));
+            let seeds: Vec<String> = account.seeds.iter().map(ts_seed).collect();
+            let single = format!(
+                "  return PublicKey.findProgramAddressSync([{}], programId);",
+                seeds.join(", ")
+            );
+            if ts_fits(&single) {
+                out.push_str(&single);
+                out.push('\n');
+            } else {
+                out.push_str("  return PublicKey.findProgramAddressSync(\n    [\n");
+                for seed in &seeds {
+                    out.push_str(&format!("      {},\n", seed));
+                }
+                out.push_str("    ],\n    programId,\n  );\n");
+            }
+            out.push_str("}\n");
+        }
+        out
+    }
+
+    fn instruction(&self, instruction: &Instruction) -> String {
+        let variant = to_pascal_case(&instruction.name);
+        let constant = format!(
+            "{}_DISCRIMINATOR",
+            to_snake_case(&instruction.name).to_uppercase()
+        );
+        let discriminator = schema_instruction_discriminator(instruction);
+        let mut out = String::new();
+
+        let mut params = vec!["programId: PublicKey".to_string()];
+        if !instruction.accounts.is_empty() {
+            let members: Vec<(String, String)> = instruction
+                .accounts
+                .iter()
+                .map(|account| (to_camel_case(&account.name), "PublicKey".to_string()))
+                .collect();
+            out.push_str(&format!("/** Accounts of `{}` */\n", variant));
+            out.push_str(&self.interface(&format!("{}Accounts", variant), &members));
+            out.push('\n');
+            params.push(format!("accounts: {}Accounts", variant));
+        }
+        if !instruction.args.is_empty() {
+            let members: Vec<(String, String)> = instruction
+                .args
+                .iter()
+                .map(|arg| (to_camel_case(&arg.name), ts_type(&arg.arg_type)))
+                .collect();
+            out.push_str(&format!("/** Arguments of `{}` */\n", variant));
+            out.push_str(&self.interface(&format!("{}Args", variant), &members));
+            out.push('\n');
+            params.push(format!("args: {}Args", variant));
+        }
+        out.push_str(&format!(
+            "export const {}: readonly number[] = {};\n\n",
+            constant,
+            byte_array(&discriminator)
+        ));
+
+        out.push_str(&format!("/** Build the `{}` instruction */\n", variant));
+        out.push_str(&ts_signature(
+            &format!("export function create{}Instruction", variant),
+            &params,
+            "TransactionInstruction",
+        ));
+        out.push_str(&format!(
+            "  const w = new BorshWriter();\n  w.raw({});\n",
+            constant
+        ));
+        for arg in &instruction.args {
+            let value = format!("args.{}", to_camel_case(&arg.name));
+            out.push_str(&format!("  {};\n", ts_encode(&arg.arg_type, &value)));
+        }
+        out.push_str("  return new TransactionInstruction({\n    programId,\n");
+        if instruction.accounts.is_empty() {
+            out.push_str("    keys: [],\n");
+        } else {
+            out.push_str("    keys: [\n");
+            for account in &instruction.accounts {
+                out.push_str(&ts_object(
+                    "      ",
+                    "      ",
+                    &[
+                        format!("pubkey: accounts.{}", to_camel_case(&account.name)),
+                        format!("isSigner: {}", account.signer),
+                        format!("isWritable: {}", account.writable),
+                    ],
+                    ",",
+                ));
+            }
+            out.push_str("    ],\n");
+        }
+        out.push_str("    data: w.toBuffer(),\n  });\n}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures::counter_schema;
+    use crate::utils::golden::assert_golden;
+
+    #[test]
+    fn test_counter_client_snapshots() {
+        let clients = client_modules(&counter_schema()).unwrap();
+        assert_eq!(clients.name, "counter_client");
+        assert_eq!(
+            clients
+                .files
+                .iter()
+                .map(|file| file.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["counter_client.rs", "counter_client.ts"]
+        );
+        for file in &clients.files {
+            assert_golden(&format!("client_counter/{}", file.path), &file.contents);
+        }
+    }
+
+    #[test]
+    fn test_discriminators_match_native_program() {
+        let schema = counter_schema();
+        let rust = client_module(&schema, ClientLanguage::Rust).unwrap();
+        let ts = client_module(&schema, ClientLanguage::TypeScript).unwrap();
+        let increment = byte_array(&schema_instruction_discriminator(&schema.instructions[1]));
+        assert!(rust.contents.contains(&format!(
+            "pub const INCREMENT_DISCRIMINATOR: [u8; 8] = {};",
+            increment
+        )));
+        assert!(ts.contents.contains(&format!(
+            "export const INCREMENT_DISCRIMINATOR: readonly number[] = {};",
+            increment
+        )));
+    }
+
+    #[test]
+    fn test_minimal_schema() {
+        let schema = ProgramSchema {
+            name: "Memo Log".to_string(),
+            version: "0.1.0".to_string(),
+            instructions: vec![Instruction {
+                name: "Ping".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let rust = client_module(&schema, ClientLanguage::Rust).unwrap();
+        assert_eq!(rust.path, "memo_log_client.rs");
+        assert!(!rust.contents.contains("use borsh"));
+        assert!(!rust.contents.contains("AccountMeta"));
+        assert!(rust
+            .contents
+            .contains("pub fn ping(program_id: &Pubkey) -> Instruction {"));
+
+        let ts = client_module(&schema, ClientLanguage::TypeScript).unwrap();
+        assert_eq!(ts.path, "memo_log_client.ts");
+        assert!(ts.contents.contains("keys: [],"));
+        assert!(!ts.contents.contains("function seedBytes"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_schemas() {
+        let mut schema = counter_schema();
+        schema.accounts[0].seeds.push(Seed::Arg {
+            name: "flag".to_string(),
+            arg_type: FieldType::Bool,
+        });
+        assert!(matches!(
+            client_modules(&schema),
+            Err(CodegenError::InvalidSchema(errors)) if errors[0].path == "$.accounts[0].seeds[3].Arg.arg_type"
+        ));
+    }
+
+    #[test]
+    fn test_seed_expressions() {
+        assert_eq!(rust_seed(&Seed::Const("vault".to_string())), "b\"vault\"");
+        assert_eq!(
+            rust_seed(&Seed::Const("caf\u{e9}".to_string())),
+            "\"caf\u{e9}\".as_bytes()"
+        );
+        let index = Seed::Arg {
+            name: "Pool Index".to_string(),
+            arg_type: FieldType::U64,
+        };
+        assert_eq!(rust_seed(&index), "&pool_index.to_le_bytes()");
+        assert_eq!(ts_seed(&index), "seedBytes((w) => w.u64(seeds.poolIndex))");
+        assert_eq!(
+            ts_seed(&Seed::Const("a\"b".to_string())),
+            "Buffer.from(\"a\\\"b\")"
+        );
+    }
+
+    #[test]
+    fn test_ts_type() {
+        assert_eq!(ts_type(&FieldType::U32), "number");
+        assert_eq!(ts_type(&FieldType::I128), "bigint");
+        assert_eq!(
+            ts_type(&FieldType::Vec(Box::new(FieldType::Option(Box::new(
+                FieldType::Defined("stake mode".to_string())
+            ))))),
+            "Array<StakeMode | null>"
+        );
+        assert_eq!(
+            ts_type(&FieldType::Array(Box::new(FieldType::Pubkey), 3)),
+            "PublicKey[]"
+        );
+    }
+}
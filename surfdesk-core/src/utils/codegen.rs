@@ -0,0 +1,1067 @@
+//! Native Solana program generation
+//!
+//! Turns a program builder `ProgramSchema` into a cargo project for a native
+//! (non-Anchor) program built on `solana-program` and `borsh`. Accounts and
+//! instructions keep the Anchor discriminators used by `utils::borsh` and the
+//! IDL export, so clients generated from the IDL can talk to the program.
+//!
+//! The generated processor decodes instructions, checks signer and writable
+//! flags declared on the schema's instruction accounts, and loads and
+//! saves the schema accounts it receives. The instruction logic itself is
+//! left to the developer.
+
+use crate::components::program_builder::{
+    Account, Field, FieldType, Instruction, ProgramSchema, TypeDef, TypeDefKind,
+};
+use crate::utils::account_size::{account_size, SizeError};
+use crate::utils::borsh::{schema_account_discriminator, schema_instruction_discriminator};
+use crate::utils::format::{to_pascal_case, to_snake_case};
+use crate::utils::schema_validation::{validate_schema, SchemaDiagnostic};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// `solana-program` version the generated project depends on
+pub const SOLANA_PROGRAM_VERSION: &str = "2.2";
+
+/// `borsh` version the generated project depends on
+pub const BORSH_VERSION: &str = "1.5";
+
+/// Errors produced while generating code
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    #[error("Schema has {} errors, first at {}: {}", .0.len(), .0[0].path, .0[0].message)]
+    InvalidSchema(Vec<SchemaDiagnostic>),
+    #[error("Schema has no instructions")]
+    NoInstructions,
+    #[error("Error `{name}` uses code {code}, which is reserved for generated errors")]
+    ReservedError { name: String, code: u32 },
+    #[error(transparent)]
+    Size(#[from] SizeError),
+    #[error("Failed to write {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// A generated source file, relative to the project root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// A generated project: a name and its files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedProject {
+    pub name: String,
+    pub files: Vec<GeneratedFile>,
+}
+
+impl GeneratedProject {
+    /// Contents of the file at `path`, if generated
+    pub fn file(&self, path: &str) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|file| file.path == path)
+            .map(|file| file.contents.as_str())
+    }
+
+    /// Write every file under `dir`, creating directories as needed
+    ///
+    /// Existing files with the same paths are overwritten; other files in
+    /// `dir` are left alone.
+    pub fn write_to(&self, dir: &Path) -> Result<Vec<PathBuf>, CodegenError> {
+        let mut written = Vec::with_capacity(self.files.len());
+        for file in &self.files {
+            let path = dir.join(&file.path);
+            let io_error = |source| CodegenError::Io {
+                path: path.clone(),
+                source,
+            };
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(io_error)?;
+            }
+            std::fs::write(&path, &file.contents).map_err(io_error)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+/// Error codes of the errors every generated program defines, as in Anchor
+const FRAMEWORK_ERRORS: [(&str, u32, &str); 3] = [
+    (
+        "InstructionFallbackNotFound",
+        101,
+        "No instruction matches the discriminator",
+    ),
+    (
+        "ConstraintMut",
+        2000,
+        "An account that must be writable is read-only",
+    ),
+    (
+        "AccountDiscriminatorMismatch",
+        3002,
+        "Account data does not start with the expected discriminator",
+    ),
+];
+
+/// Generate a native program cargo project from a schema
+///
+/// The schema must pass `validate_schema` without errors and define at
+/// least one instruction.
+pub fn native_program(schema: &ProgramSchema) -> Result<GeneratedProject, CodegenError> {
+    check_schema(schema)?;
+    if schema.instructions.is_empty() {
+        return Err(CodegenError::NoInstructions);
+    }
+    for error in &schema.errors {
+        let reserved = FRAMEWORK_ERRORS
+            .iter()
+            .any(|(name, code, _)| *code == error.code || *name == to_pascal_case(&error.name));
+        if reserved {
+            return Err(CodegenError::ReservedError {
+                name: error.name.clone(),
+                code: error.code,
+            });
+        }
+    }
+
+    let generator = NativeGenerator::new(schema);
+    Ok(GeneratedProject {
+        name: generator.crate_name.clone(),
+        files: vec![
+            file(".gitignore", "/target\n".to_string()),
+            file("Cargo.toml", generator.cargo_toml()),
+            file("src/lib.rs", generator.lib_rs()),
+            file("src/error.rs", generator.error_rs()),
+            file("src/instruction.rs", generator.instruction_rs()),
+            file("src/processor.rs", generator.processor_rs()),
+            file("src/state.rs", generator.state_rs()?),
+        ],
+    })
+}
+
+/// Fail with every error `validate_schema` reports for the schema
+pub(crate) fn check_schema(schema: &ProgramSchema) -> Result<(), CodegenError> {
+    let errors: Vec<SchemaDiagnostic> = validate_schema(schema)
+        .into_iter()
+        .filter(SchemaDiagnostic::is_error)
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CodegenError::InvalidSchema(errors))
+    }
+}
+
+pub(crate) fn file(path: &str, contents: String) -> GeneratedFile {
+    GeneratedFile {
+        path: path.to_string(),
+        contents,
+    }
+}
+
+/// Rust spelling of a schema field type
+pub fn rust_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Vec(item) => format!("Vec<{}>", rust_type(item)),
+        FieldType::Option(item) => format!("Option<{}>", rust_type(item)),
+        FieldType::Array(item, len) => format!("[{}; {}]", rust_type(item), len),
+        FieldType::Defined(name) => to_pascal_case(name),
+        primitive => primitive.to_string(),
+    }
+}
+
+/// Rust byte array literal, e.g. `[1, 2, 3]`
+pub(crate) fn byte_array(bytes: &[u8]) -> String {
+    let items: Vec<String> = bytes.iter().map(u8::to_string).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Whether a line fits rustfmt's default width
+pub(crate) fn fits(line: &str) -> bool {
+    line.chars().count() <= 100
+}
+
+/// Function signature, wrapped one parameter per line when too long
+pub(crate) fn signature(indent: &str, head: &str, params: &[String], ret: &str) -> String {
+    let single = format!("{}{}({}) -> {} {{\n", indent, head, params.join(", "), ret);
+    if fits(single.trim_end()) {
+        return single;
+    }
+    let mut out = format!("{}{}(\n", indent, head);
+    for param in params {
+        out.push_str(&format!("{}    {},\n", indent, param));
+    }
+    out.push_str(&format!("{}) -> {} {{\n", indent, ret));
+    out
+}
+
+/// Collect the defined type names a field type refers to
+fn defined_names(field_type: &FieldType, names: &mut BTreeSet<String>) {
+    match field_type {
+        FieldType::Vec(item) | FieldType::Option(item) | FieldType::Array(item, _) => {
+            defined_names(item, names)
+        }
+        FieldType::Defined(name) => {
+            names.insert(to_pascal_case(name));
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn uses_pubkey(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Pubkey => true,
+        FieldType::Vec(item) | FieldType::Option(item) | FieldType::Array(item, _) => {
+            uses_pubkey(item)
+        }
+        _ => false,
+    }
+}
+
+/// An instruction account with its derived flags
+struct AccountRef<'a> {
+    name: String,
+    writable: bool,
+    signer: bool,
+    /// Schema account stored in this account, if any
+    state: Option<&'a Account>,
+    /// Whether the instruction creates the account instead of loading it
+    created: bool,
+}
+
+impl AccountRef<'_> {
+    /// Whether the handler loads the account's state
+    fn loads(&self) -> bool {
+        self.state.is_some() && !self.created
+    }
+}
+
+struct NativeGenerator<'a> {
+    schema: &'a ProgramSchema,
+    crate_name: String,
+    error_enum: String,
+    instruction_enum: String,
+}
+
+impl<'a> NativeGenerator<'a> {
+    fn new(schema: &'a ProgramSchema) -> Self {
+        let program = to_pascal_case(&schema.name);
+        Self {
+            schema,
+            crate_name: to_snake_case(&schema.name),
+            error_enum: format!("{}Error", program),
+            instruction_enum: format!("{}Instruction", program),
+        }
+    }
+
+    fn accounts(&self, instruction: &'a Instruction) -> Vec<AccountRef<'a>> {
+        // Accounts written by `initialize`/`create_*` instructions have no state yet
+        let snake = to_snake_case(&instruction.name);
+        let creates = snake.starts_with("init") || snake.starts_with("create");
+        instruction
+            .accounts
+            .iter()
+            .map(|account| {
+                let (writable, signer) = (account.writable, account.signer);
+                let name = to_snake_case(&account.name);
+                AccountRef {
+                    state: self
+                        .schema
+                        .accounts
+                        .iter()
+                        .find(|account| to_snake_case(&account.name) == name),
+                    name,
+                    writable,
+                    signer,
+                    created: creates && writable,
+                }
+            })
+            .collect()
+    }
+
+    fn version(&self) -> &str {
+        let version = self.schema.version.trim();
+        let parts: Vec<&str> = version.split('.').collect();
+        let is_semver = parts.len() == 3
+            && parts
+                .iter()
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        if is_semver {
+            version
+        } else {
+            "0.1.0"
+        }
+    }
+
+    fn cargo_toml(&self) -> String {
+        format!(
+            r#"[package]
+name = "{name}"
+version = "{version}"
+edition = "2021"
+
+[lib]
+crate-type = ["cdylib", "lib"]
+
+[features]
+no-entrypoint = []
+custom-heap = []
+custom-panic = []
+
+[dependencies]
+borsh = {{ version = "{borsh}", features = ["derive"] }}
+solana-program = "{solana}"
+
+[lints.rust]
+unexpected_cfgs = {{ level = "warn", check-cfg = ['cfg(target_os, values("solana"))'] }}
+"#,
+            name = self.crate_name,
+            version = self.version(),
+            borsh = BORSH_VERSION,
+            solana = SOLANA_PROGRAM_VERSION,
+        )
+    }
+
+    fn lib_rs(&self) -> String {
+        format!(
+            r#"//! `{name}` program, generated by Surfdesk from its program schema
+
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint {{
+    use crate::processor::process_instruction;
+
+    solana_program::entrypoint!(process_instruction);
+}}
+"#,
+            name = self.schema.name,
+        )
+    }
+
+    fn error_rs(&self) -> String {
+        let mut out = String::from(
+            "//! Program errors\n\nuse solana_program::program_error::ProgramError;\n\n",
+        );
+        out.push_str("/// Errors returned as `ProgramError::Custom`, numbered like Anchor's\n");
+        out.push_str("#[derive(Clone, Copy, Debug, PartialEq, Eq)]\n#[repr(u32)]\n");
+        out.push_str(&format!("pub enum {} {{\n", self.error_enum));
+        for (name, code, msg) in FRAMEWORK_ERRORS {
+            out.push_str(&format!("    /// {}\n    {} = {},\n", msg, name, code));
+        }
+        for error in &self.schema.errors {
+            if let Some(msg) = &error.msg {
+                out.push_str(&format!("    /// {}\n", msg));
+            }
+            out.push_str(&format!(
+                "    {} = {},\n",
+                to_pascal_case(&error.name),
+                error.code
+            ));
+        }
+        out.push_str("}\n\n");
+        out.push_str(&format!(
+            "impl From<{error}> for ProgramError {{\n    fn from(error: {error}) -> Self {{\n        ProgramError::Custom(error as u32)\n    }}\n}}\n",
+            error = self.error_enum
+        ));
+        out
+    }
+
+    fn instruction_rs(&self) -> String {
+        let instructions = &self.schema.instructions;
+        let has_args = instructions.iter().any(|ix| !ix.args.is_empty());
+        let arg_types: Vec<&FieldType> = instructions
+            .iter()
+            .flat_map(|ix| ix.args.iter().map(|arg| &arg.arg_type))
+            .collect();
+
+        let mut out = String::from("//! Instruction data\n\n");
+        if has_args {
+            out.push_str("use borsh::{BorshDeserialize, BorshSerialize};\n");
+        }
+        out.push_str("use solana_program::program_error::ProgramError;\n");
+        if arg_types.iter().any(|ty| uses_pubkey(ty)) {
+            out.push_str("use solana_program::pubkey::Pubkey;\n");
+        }
+        out.push_str(&format!("\nuse crate::error::{};\n", self.error_enum));
+        let mut defined = BTreeSet::new();
+        for ty in &arg_types {
+            defined_names(ty, &mut defined);
+        }
+        out.push_str(&use_list("crate::state", &defined));
+        out.push('\n');
+
+        for instruction in instructions.iter().filter(|ix| !ix.args.is_empty()) {
+            out.push_str(&format!(
+                "/// Arguments of `{}`\n",
+                to_pascal_case(&instruction.name)
+            ));
+            out.push_str(DERIVE);
+            out.push_str(&format!(
+                "pub struct {}Args {{\n",
+                to_pascal_case(&instruction.name)
+            ));
+            for arg in &instruction.args {
+                out.push_str(&format!(
+                    "    pub {}: {},\n",
+                    to_snake_case(&arg.name),
+                    rust_type(&arg.arg_type)
+                ));
+            }
+            out.push_str("}\n\n");
+        }
+
+        out.push_str("/// Instructions, each prefixed by its discriminator\n");
+        out.push_str("#[derive(Clone, Debug, PartialEq)]\n");
+        out.push_str(&format!("pub enum {} {{\n", self.instruction_enum));
+        for instruction in instructions {
+            let accounts = self.accounts(instruction);
+            if !accounts.is_empty() {
+                out.push_str("    /// Accounts:\n");
+                for (i, account) in accounts.iter().enumerate() {
+                    out.push_str(&format!(
+                        "    /// {}. `[{}]` {}\n",
+                        i,
+                        flags(account),
+                        account.name
+                    ));
+                }
+            }
+            let variant = to_pascal_case(&instruction.name);
+            if instruction.args.is_empty() {
+                out.push_str(&format!("    {},\n", variant));
+            } else {
+                out.push_str(&format!("    {}({}Args),\n", variant, variant));
+            }
+        }
+        out.push_str("}\n\n");
+
+        out.push_str(&format!("impl {} {{\n", self.instruction_enum));
+        for instruction in instructions {
+            let discriminator = schema_instruction_discriminator(instruction);
+            out.push_str(&format!(
+                "    pub const {}: [u8; {}] = {};\n",
+                to_snake_case(&instruction.name).to_uppercase(),
+                discriminator.len(),
+                byte_array(&discriminator)
+            ));
+        }
+
+        out.push_str("\n    /// Decode instruction data by its discriminator\n");
+        out.push_str("    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {\n");
+        for instruction in instructions {
+            let variant = to_pascal_case(&instruction.name);
+            let constant = to_snake_case(&instruction.name).to_uppercase();
+            out.push_str(&format!(
+                "        if let Some(args) = data.strip_prefix(&Self::{}[..]) {{\n",
+                constant
+            ));
+            if instruction.args.is_empty() {
+                out.push_str(&format!(
+                    "            if !args.is_empty() {{\n                return Err(ProgramError::InvalidInstructionData);\n            }}\n            return Ok(Self::{});\n",
+                    variant
+                ));
+            } else {
+                out.push_str(&format!(
+                    "            let args = {}Args::try_from_slice(args)\n                .map_err(|_| ProgramError::InvalidInstructionData)?;\n            return Ok(Self::{}(args));\n",
+                    variant, variant
+                ));
+            }
+            out.push_str("        }\n");
+        }
+        out.push_str(&format!(
+            "        Err({}::InstructionFallbackNotFound.into())\n    }}\n",
+            self.error_enum
+        ));
+
+        out.push_str("\n    /// Encode the instruction with its discriminator\n");
+        out.push_str("    pub fn pack(&self) -> Vec<u8> {\n");
+        out.push_str("        let mut data = Vec::new();\n        match self {\n");
+        for instruction in instructions {
+            let variant = to_pascal_case(&instruction.name);
+            let constant = to_snake_case(&instruction.name).to_uppercase();
+            if instruction.args.is_empty() {
+                out.push_str(&format!(
+                    "            Self::{} => data.extend_from_slice(&Self::{}),\n",
+                    variant, constant
+                ));
+            } else {
+                out.push_str(&format!(
+                    "            Self::{}(args) => {{\n                data.extend_from_slice(&Self::{});\n                args.serialize(&mut data)\n                    .expect(\"writing to a Vec cannot fail\");\n            }}\n",
+                    variant, constant
+                ));
+            }
+        }
+        out.push_str("        }\n        data\n    }\n}\n");
+        out
+    }
+
+    fn processor_rs(&self) -> String {
+        let instructions = &self.schema.instructions;
+        let all_accounts: Vec<AccountRef> = instructions
+            .iter()
+            .flat_map(|ix| self.accounts(ix))
+            .collect();
+        let needs_signer = all_accounts.iter().any(|account| account.signer);
+        let needs_writable = all_accounts.iter().any(|account| account.writable);
+        let states: BTreeSet<String> = all_accounts
+            .iter()
+            .filter(|account| account.loads())
+            .filter_map(|account| account.state)
+            .map(|account| to_pascal_case(&account.name))
+            .collect();
+
+        let mut out = String::from("//! Instruction processing\n\n");
+        if all_accounts.is_empty() {
+            out.push_str("use solana_program::account_info::AccountInfo;\n");
+        } else {
+            out.push_str("use solana_program::account_info::{next_account_info, AccountInfo};\n");
+        }
+        out.push_str("use solana_program::entrypoint::ProgramResult;\nuse solana_program::msg;\n");
+        if needs_signer {
+            out.push_str("use solana_program::program_error::ProgramError;\n");
+        }
+        out.push_str("use solana_program::pubkey::Pubkey;\n\n");
+        if needs_writable {
+            out.push_str(&format!("use crate::error::{};\n", self.error_enum));
+        }
+        let mut instruction_items = BTreeSet::new();
+        instruction_items.insert(self.instruction_enum.clone());
+        for instruction in instructions.iter().filter(|ix| !ix.args.is_empty()) {
+            instruction_items.insert(format!("{}Args", to_pascal_case(&instruction.name)));
+        }
+        out.push_str(&use_list("crate::instruction", &instruction_items));
+        out.push_str(&use_list("crate::state", &states));
+
+        out.push_str("\n/// Program entrypoint: dispatch on the instruction discriminator\n");
+        out.push_str(
+            "pub fn process_instruction(\n    program_id: &Pubkey,\n    accounts: &[AccountInfo],\n    instruction_data: &[u8],\n) -> ProgramResult {\n",
+        );
+        out.push_str(&format!(
+            "    match {}::unpack(instruction_data)? {{\n",
+            self.instruction_enum
+        ));
+        for instruction in instructions {
+            let variant = to_pascal_case(&instruction.name);
+            let handler = format!("process_{}", to_snake_case(&instruction.name));
+            let (pattern, call) = if instruction.args.is_empty() {
+                (
+                    format!("{}::{}", self.instruction_enum, variant),
+                    format!("{}(program_id, accounts)", handler),
+                )
+            } else {
+                (
+                    format!("{}::{}(args)", self.instruction_enum, variant),
+                    format!("{}(program_id, accounts, args)", handler),
+                )
+            };
+            let arm = format!("        {} => {},", pattern, call);
+            if fits(&arm) {
+                out.push_str(&arm);
+                out.push('\n');
+            } else {
+                out.push_str(&format!(
+                    "        {} => {{\n            {}\n        }}\n",
+                    pattern, call
+                ));
+            }
+        }
+        out.push_str("    }\n}\n");
+
+        for instruction in instructions {
+            out.push('\n');
+            out.push_str(&self.handler(instruction));
+        }
+
+        if needs_signer {
+            out.push_str(
+                "\nfn require_signer(account: &AccountInfo) -> ProgramResult {\n    if !account.is_signer {\n        msg!(\"{} must sign\", account.key);\n        return Err(ProgramError::MissingRequiredSignature);\n    }\n    Ok(())\n}\n",
+            );
+        }
+        if needs_writable {
+            out.push_str(&format!(
+                "\nfn require_writable(account: &AccountInfo) -> ProgramResult {{\n    if !account.is_writable {{\n        msg!(\"{{}} must be writable\", account.key);\n        return Err({}::ConstraintMut.into());\n    }}\n    Ok(())\n}}\n",
+                self.error_enum
+            ));
+        }
+        out
+    }
+
+    fn handler(&self, instruction: &'a Instruction) -> String {
+        let accounts = self.accounts(instruction);
+        let variant = to_pascal_case(&instruction.name);
+        let loads_state = accounts.iter().any(AccountRef::loads);
+
+        let mut params = vec![
+            format!(
+                "{}: &Pubkey",
+                if loads_state {
+                    "program_id"
+                } else {
+                    "_program_id"
+                }
+            ),
+            format!(
+                "{}: &[AccountInfo]",
+                if accounts.is_empty() {
+                    "_accounts"
+                } else {
+                    "accounts"
+                }
+            ),
+        ];
+        if !instruction.args.is_empty() {
+            params.push(format!("args: {}Args", variant));
+        }
+        let mut out = signature(
+            "",
+            &format!("fn process_{}", to_snake_case(&instruction.name)),
+            &params,
+            "ProgramResult",
+        );
+
+        if !accounts.is_empty() {
+            out.push_str("    let accounts_iter = &mut accounts.iter();\n");
+            for account in &accounts {
+                let used = account.signer || account.writable || account.state.is_some();
+                out.push_str(&format!(
+                    "    let {}{} = next_account_info(accounts_iter)?;\n",
+                    if used { "" } else { "_" },
+                    account.name
+                ));
+            }
+
+            let checks: Vec<String> = accounts
+                .iter()
+                .flat_map(|account| {
+                    let mut checks = Vec::new();
+                    if account.writable {
+                        checks.push(format!("    require_writable({})?;\n", account.name));
+                    }
+                    if account.signer {
+                        checks.push(format!("    require_signer({})?;\n", account.name));
+                    }
+                    checks
+                })
+                .collect();
+            if !checks.is_empty() {
+                out.push('\n');
+                out.push_str(&checks.concat());
+            }
+
+            let loads: Vec<String> = accounts
+                .iter()
+                .filter(|account| account.loads())
+                .filter_map(|account| {
+                    let state = account.state?;
+                    Some(format!(
+                        "    let {}{}_data = {}::load({}, program_id)?;\n",
+                        if account.writable { "" } else { "_" },
+                        account.name,
+                        to_pascal_case(&state.name),
+                        account.name
+                    ))
+                })
+                .collect();
+            if !loads.is_empty() {
+                out.push('\n');
+                out.push_str(&loads.concat());
+            }
+            out.push('\n');
+        }
+
+        if instruction.args.is_empty() {
+            out.push_str(&format!("    msg!(\"Instruction: {}\");\n", variant));
+        } else {
+            out.push_str(&format!(
+                "    msg!(\"Instruction: {} {{:?}}\", args);\n",
+                variant
+            ));
+        }
+        for account in accounts.iter().filter(|account| account.created) {
+            if let Some(state) = account.state {
+                out.push_str(&format!(
+                    "    // `{}` is new: allocate `{}::SPACE` bytes owned by the program, then\n    // `{}::save` its initial state\n",
+                    account.name,
+                    to_pascal_case(&state.name),
+                    to_pascal_case(&state.name)
+                ));
+            }
+        }
+
+        let saves: Vec<String> = accounts
+            .iter()
+            .filter(|account| account.writable && account.loads())
+            .map(|account| format!("    {}_data.save({})?;\n", account.name, account.name))
+            .collect();
+        if !saves.is_empty() {
+            out.push('\n');
+            out.push_str(&saves.concat());
+        }
+        out.push_str("    Ok(())\n}\n");
+        out
+    }
+
+    fn state_rs(&self) -> Result<String, CodegenError> {
+        let schema = self.schema;
+        let field_types = || {
+            schema
+                .accounts
+                .iter()
+                .flat_map(|account| account.fields.iter())
+                .chain(schema.types.iter().flat_map(typedef_fields))
+                .map(|field| &field.field_type)
+        };
+        let has_accounts = !schema.accounts.is_empty();
+
+        let mut out = String::from("//! Account and type definitions\n");
+        if !has_accounts && schema.types.is_empty() {
+            return Ok(out);
+        }
+        out.push_str("\nuse borsh::{BorshDeserialize, BorshSerialize};\n");
+        if has_accounts {
+            out.push_str(
+                "use solana_program::account_info::AccountInfo;\nuse solana_program::entrypoint::ProgramResult;\nuse solana_program::program_error::ProgramError;\n",
+            );
+        }
+        if has_accounts || field_types().any(uses_pubkey) {
+            out.push_str("use solana_program::pubkey::Pubkey;\n");
+        }
+        if has_accounts {
+            out.push_str(&format!("\nuse crate::error::{};\n", self.error_enum));
+        }
+
+        for def in &schema.types {
+            out.push('\n');
+            out.push_str(&typedef(def));
+        }
+        for account in &schema.accounts {
+            out.push('\n');
+            out.push_str(&self.account(account)?);
+        }
+        Ok(out)
+    }
+
+    fn account(&self, account: &Account) -> Result<String, CodegenError> {
+        let name = to_pascal_case(&account.name);
+        let discriminator = schema_account_discriminator(account);
+        let size = account_size(account, &self.schema.types, true)?;
+
+        let mut out = format!(
+            "/// `{}` account data, stored after its discriminator\n",
+            name
+        );
+        out.push_str(DERIVE);
+        out.push_str(&format!("pub struct {} {{\n", name));
+        out.push_str(&struct_fields(&account.fields, "    ", true));
+        out.push_str("}\n\n");
+
+        out.push_str(&format!("impl {} {{\n", name));
+        out.push_str(&format!(
+            "    pub const DISCRIMINATOR: [u8; {}] = {};\n",
+            discriminator.len(),
+            byte_array(&discriminator)
+        ));
+        if size.is_bounded() {
+            out.push_str("    /// Bytes to allocate, including the discriminator\n");
+        } else {
+            out.push_str("    /// Bytes to allocate, including the discriminator; strings and vectors\n    /// without a max length are counted as empty\n");
+        }
+        out.push_str(&format!(
+            "    pub const SPACE: usize = {};\n\n",
+            size.total()
+        ));
+
+        out.push_str("    /// Deserialize the account, checking its owner and discriminator\n");
+        out.push_str(&signature(
+            "    ",
+            "pub fn load",
+            &[
+                "account: &AccountInfo".to_string(),
+                "program_id: &Pubkey".to_string(),
+            ],
+            "Result<Self, ProgramError>",
+        ));
+        out.push_str(&format!(
+            r#"        if account.owner != program_id {{
+            return Err(ProgramError::IncorrectProgramId);
+        }}
+        let data = account.try_borrow_data()?;
+        let mut rest = data
+            .strip_prefix(&Self::DISCRIMINATOR[..])
+            .ok_or({}::AccountDiscriminatorMismatch)?;
+        Self::deserialize(&mut rest).map_err(|_| ProgramError::InvalidAccountData)
+    }}
+
+    /// Serialize the account after its discriminator
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {{
+        let mut data = account.try_borrow_mut_data()?;
+        if data.len() < Self::DISCRIMINATOR.len() {{
+            return Err(ProgramError::AccountDataTooSmall);
+        }}
+        let (discriminator, mut rest) = data.split_at_mut(Self::DISCRIMINATOR.len());
+        discriminator.copy_from_slice(&Self::DISCRIMINATOR);
+        self.serialize(&mut rest)
+            .map_err(|_| ProgramError::AccountDataTooSmall)
+    }}
+}}
+"#,
+            self.error_enum
+        ));
+        Ok(out)
+    }
+}
+
+pub(crate) const DERIVE: &str =
+    "#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]\n";
+
+fn flags(account: &AccountRef) -> &'static str {
+    match (account.writable, account.signer) {
+        (true, true) => "writable, signer",
+        (true, false) => "writable",
+        (false, true) => "signer",
+        (false, false) => "",
+    }
+}
+
+pub(crate) fn use_list(module: &str, items: &BTreeSet<String>) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => format!("use {}::{};\n", module, items.iter().next().unwrap()),
+        _ => {
+            let list: Vec<&str> = items.iter().map(String::as_str).collect();
+            let single = format!("use {}::{{{}}};", module, list.join(", "));
+            if fits(&single) {
+                single + "\n"
+            } else {
+                format!("use {}::{{\n    {},\n}};\n", module, list.join(", "))
+            }
+        }
+    }
+}
+
+pub(crate) fn typedef_fields(def: &TypeDef) -> Box<dyn Iterator<Item = &Field> + '_> {
+    match &def.kind {
+        TypeDefKind::Struct { fields } => Box::new(fields.iter()),
+        TypeDefKind::Enum { variants } => {
+            Box::new(variants.iter().flat_map(|variant| variant.fields.iter()))
+        }
+    }
+}
+
+pub(crate) fn struct_fields(fields: &[Field], indent: &str, public: bool) -> String {
+    let mut out = String::new();
+    for field in fields {
+        if !field.max_len.is_empty() {
+            let lens: Vec<String> = field.max_len.iter().map(usize::to_string).collect();
+            out.push_str(&format!("{}/// Max length: {}\n", indent, lens.join(", ")));
+        }
+        out.push_str(&format!(
+            "{}{}{}: {},\n",
+            indent,
+            if public { "pub " } else { "" },
+            to_snake_case(&field.name),
+            rust_type(&field.field_type)
+        ));
+    }
+    out
+}
+
+/// Fields of a short struct variant on one line, as rustfmt lays them out
+fn inline_fields(fields: &[Field]) -> Option<String> {
+    if fields.iter().any(|field| !field.max_len.is_empty()) {
+        return None;
+    }
+    let body = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{}: {}",
+                to_snake_case(&field.name),
+                rust_type(&field.field_type)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    (body.chars().count() <= 35).then_some(body)
+}
+
+pub(crate) fn typedef(def: &TypeDef) -> String {
+    let name = to_pascal_case(&def.name);
+    let mut out = String::from(DERIVE);
+    match &def.kind {
+        TypeDefKind::Struct { fields } => {
+            out.push_str(&format!("pub struct {} {{\n", name));
+            out.push_str(&struct_fields(fields, "    ", true));
+        }
+        TypeDefKind::Enum { variants } => {
+            out.push_str(&format!("pub enum {} {{\n", name));
+            for variant in variants {
+                let variant_name = to_pascal_case(&variant.name);
+                if variant.fields.is_empty() {
+                    out.push_str(&format!("    {},\n", variant_name));
+                } else if let Some(body) = inline_fields(&variant.fields) {
+                    out.push_str(&format!("    {} {{ {} }},\n", variant_name, body));
+                } else {
+                    out.push_str(&format!("    {} {{\n", variant_name));
+                    out.push_str(&struct_fields(&variant.fields, "        ", false));
+                    out.push_str("    },\n");
+                }
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::program_builder::{InstructionAccount, InstructionArg};
+    use crate::utils::fixtures::{counter_schema, field};
+    use crate::utils::golden::assert_golden;
+
+    fn instruction(name: &str, accounts: &[&str], args: Vec<InstructionArg>) -> Instruction {
+        Instruction {
+            name: name.to_string(),
+            accounts: accounts
+                .iter()
+                .map(|name| InstructionAccount::inferred(name.to_string()))
+                .collect(),
+            args,
+            discriminator: None,
+        }
+    }
+
+    #[test]
+    fn test_native_counter_snapshot() {
+        let project = native_program(&counter_schema()).unwrap();
+        assert_eq!(project.name, "counter");
+        for file in &project.files {
+            assert_golden(&format!("native_counter/{}", file.path), &file.contents);
+        }
+        assert_eq!(
+            project
+                .files
+                .iter()
+                .map(|file| file.path.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                ".gitignore",
+                "Cargo.toml",
+                "src/lib.rs",
+                "src/error.rs",
+                "src/instruction.rs",
+                "src/processor.rs",
+                "src/state.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stateless_program() {
+        let schema = ProgramSchema {
+            name: "Memo Log".to_string(),
+            version: "draft".to_string(),
+            instructions: vec![instruction("Ping", &[], vec![])],
+            ..Default::default()
+        };
+
+        let project = native_program(&schema).unwrap();
+        assert_eq!(project.name, "memo_log");
+        assert!(project
+            .file("Cargo.toml")
+            .unwrap()
+            .contains("version = \"0.1.0\""));
+        assert_eq!(
+            project.file("src/state.rs"),
+            Some("//! Account and type definitions\n")
+        );
+        let processor = project.file("src/processor.rs").unwrap();
+        assert!(
+            processor.contains("fn process_ping(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {\n    msg!(\"Instruction: Ping\");\n")
+        );
+        assert!(!processor.contains("next_account_info"));
+        assert!(!processor.contains("require_signer"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_schemas() {
+        let mut schema = counter_schema();
+        schema.accounts[0].fields[0].name = "type".to_string();
+        assert!(matches!(
+            native_program(&schema),
+            Err(CodegenError::InvalidSchema(errors)) if errors[0].path == "$.accounts[0].fields[0].name"
+        ));
+
+        let mut schema = counter_schema();
+        schema.instructions.clear();
+        assert!(matches!(
+            native_program(&schema),
+            Err(CodegenError::NoInstructions)
+        ));
+
+        let mut schema = counter_schema();
+        schema.errors[0].code = 2000;
+        assert!(matches!(
+            native_program(&schema),
+            Err(CodegenError::ReservedError { code: 2000, .. })
+        ));
+
+        let mut schema = counter_schema();
+        schema.types.push(TypeDef {
+            name: "Node".to_string(),
+            kind: TypeDefKind::Struct {
+                fields: vec![field(
+                    "next",
+                    FieldType::Option(Box::new(FieldType::Defined("Node".to_string()))),
+                )],
+            },
+        });
+        schema.accounts[0]
+            .fields
+            .push(field("head", FieldType::Defined("Node".to_string())));
+        assert!(matches!(
+            native_program(&schema),
+            Err(CodegenError::Size(SizeError::RecursiveType { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_write_to() {
+        let project = native_program(&counter_schema()).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let written = project.write_to(dir.path()).unwrap();
+        assert_eq!(written.len(), project.files.len());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+            project.file("src/lib.rs").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rust_type() {
+        assert_eq!(rust_type(&FieldType::U128), "u128");
+        assert_eq!(rust_type(&FieldType::Bool), "bool");
+        assert_eq!(
+            rust_type(&FieldType::Vec(Box::new(FieldType::Option(Box::new(
+                FieldType::Defined("stake mode".to_string())
+            ))))),
+            "Vec<Option<StakeMode>>"
+        );
+        assert_eq!(
+            rust_type(&FieldType::Array(Box::new(FieldType::Pubkey), 3)),
+            "[Pubkey; 3]"
+        );
+    }
+}
@@ -5,35 +5,349 @@
 //! and other cryptographic operations needed for the application.
 
 use bs58;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use sha2::{Digest, Sha256};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
-/// Generate a new Solana keypair (mock implementation)
-pub fn generate_keypair() -> (String, String) {
-    // In a real implementation, this would generate actual Ed25519 keypair
-    // For now, generate mock addresses using deterministic random
-    let mut rng = ChaCha8Rng::from_entropy();
-    let mut seed = [0u8; 32];
-    rng.fill_bytes(&mut seed);
+/// Errors produced while decoding keypair files and secret keys
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum KeypairError {
+    #[error("Secret key must be {expected} bytes, found {found}")]
+    InvalidLength {
+        expected: &'static str,
+        found: usize,
+    },
+    #[error("Public key half of the secret key does not match its private key")]
+    PublicKeyMismatch,
+    #[error("Invalid keypair format: {0}")]
+    InvalidFormat(String),
+}
+
+/// Ed25519 keypair controlling a Solana account
+#[derive(Clone)]
+pub struct Keypair {
+    signing_key: SigningKey,
+}
+
+impl Keypair {
+    /// Generate a new random keypair
+    pub fn generate() -> Self {
+        let mut seed = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut seed);
+        Self::from_seed(&seed)
+    }
+
+    /// Derive a keypair from a 32-byte secret seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Self {
+            signing_key: SigningKey::from_bytes(seed),
+        }
+    }
+
+    /// Public key bytes
+    pub fn pubkey(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    /// Public key as a Base58 Solana address
+    pub fn address(&self) -> String {
+        bs58::encode(self.pubkey()).into_string()
+    }
 
-    let public_key = generate_public_key(&seed);
-    let private_key = bs58::encode(&seed).into_string();
+    /// 32-byte secret seed
+    pub fn secret(&self) -> [u8; 32] {
+        self.signing_key.to_bytes()
+    }
+
+    /// Sign a message
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.signing_key.sign(message).to_bytes()
+    }
+
+    /// Decode a 32-byte seed or a 64-byte `seed || pubkey` secret key
+    ///
+    /// For 64-byte keys the public half is checked against the one derived
+    /// from the seed, so a corrupted or mismatched key is rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KeypairError> {
+        match bytes.len() {
+            32 => {
+                let mut seed = [0u8; 32];
+                seed.copy_from_slice(bytes);
+                Ok(Self::from_seed(&seed))
+            }
+            64 => {
+                let mut seed = [0u8; 32];
+                seed.copy_from_slice(&bytes[..32]);
+                let keypair = Self::from_seed(&seed);
+                if keypair.pubkey()[..] != bytes[32..] {
+                    return Err(KeypairError::PublicKeyMismatch);
+                }
+                Ok(keypair)
+            }
+            found => Err(KeypairError::InvalidLength {
+                expected: "32 or 64",
+                found,
+            }),
+        }
+    }
+
+    /// 64-byte `seed || pubkey` secret key used by solana-keygen and Phantom
+    pub fn to_bytes(&self) -> [u8; 64] {
+        self.signing_key.to_keypair_bytes()
+    }
+
+    /// Decode a solana-keygen `id.json` keypair (a JSON array of 64 bytes)
+    pub fn from_json(json: &str) -> Result<Self, KeypairError> {
+        let bytes: Vec<u8> = serde_json::from_str(json).map_err(|e| {
+            KeypairError::InvalidFormat(format!("expected a JSON byte array: {}", e))
+        })?;
+        if bytes.len() != 64 {
+            return Err(KeypairError::InvalidLength {
+                expected: "64",
+                found: bytes.len(),
+            });
+        }
+        Self::from_bytes(&bytes)
+    }
+
+    /// Encode as a solana-keygen `id.json` keypair
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.to_bytes().to_vec()).unwrap_or_default()
+    }
+
+    /// Decode a Base58 secret key as exported by Phantom (64 bytes) or a bare 32-byte seed
+    pub fn from_base58_string(secret: &str) -> Result<Self, KeypairError> {
+        let bytes = bs58::decode(secret.trim())
+            .into_vec()
+            .map_err(|e| KeypairError::InvalidFormat(format!("invalid Base58: {}", e)))?;
+        Self::from_bytes(&bytes)
+    }
 
-    (public_key, private_key)
+    /// Encode the 64-byte secret key as Base58, as Phantom exports it
+    pub fn to_base58_string(&self) -> String {
+        bs58::encode(self.to_bytes()).into_string()
+    }
+}
+
+impl std::fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the secret key
+        f.debug_struct("Keypair")
+            .field("address", &self.address())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Verify an ed25519 signature over `message` by `pubkey`
+pub fn verify(pubkey: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    match VerifyingKey::from_bytes(pubkey) {
+        Ok(verifying_key) => verifying_key
+            .verify(message, &ed25519_dalek::Signature::from_bytes(signature))
+            .is_ok(),
+        Err(_) => false,
+    }
 }
 
-/// Generate a public key from seed bytes
-pub fn generate_public_key(seed: &[u8]) -> String {
+/// Maximum number of seeds accepted by `create_program_address`
+pub const MAX_SEEDS: usize = 16;
+
+/// Maximum length of a single program address seed
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Domain separator appended when hashing program derived addresses
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Errors produced while deriving program addresses
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PdaError {
+    #[error("At most {} seeds are allowed", MAX_SEEDS)]
+    TooManySeeds,
+    #[error("Seeds must be at most {} bytes each", MAX_SEED_LEN)]
+    MaxSeedLengthExceeded,
+    #[error("Derived address lies on the ed25519 curve, use a different bump")]
+    InvalidSeeds,
+    #[error("Unable to find a viable program address bump seed")]
+    NoViableBump,
+}
+
+/// Whether 32 bytes decode to a point on the ed25519 curve
+///
+/// Program derived addresses must be off-curve so no private key can sign for them.
+pub fn is_on_curve(bytes: &[u8; 32]) -> bool {
+    VerifyingKey::from_bytes(bytes).is_ok()
+}
+
+/// Derive a program address from seeds that already include the bump
+///
+/// Mirrors `Pubkey::create_program_address`: `sha256(seeds || program_id ||
+/// "ProgramDerivedAddress")`, rejected if the result is on the curve.
+pub fn create_program_address(
+    seeds: &[&[u8]],
+    program_id: &[u8; 32],
+) -> Result<[u8; 32], PdaError> {
+    if seeds.len() > MAX_SEEDS {
+        return Err(PdaError::TooManySeeds);
+    }
+    if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(PdaError::MaxSeedLengthExceeded);
+    }
+
     let mut hasher = Sha256::new();
-    hasher.update(seed);
-    hasher.update(b"public_key");
-    let hash = hasher.finalize();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    hasher.update(program_id);
+    hasher.update(PDA_MARKER);
+    let address: [u8; 32] = hasher.finalize().into();
 
-    // Use first 32 bytes for address
-    bs58::encode(&hash[..32]).into_string()
+    if is_on_curve(&address) {
+        return Err(PdaError::InvalidSeeds);
+    }
+    Ok(address)
+}
+
+/// Find the canonical program address and bump for `seeds`
+///
+/// Mirrors `Pubkey::find_program_address`: bumps are tried from 255 down to 1
+/// and the first off-curve address wins.
+pub fn find_program_address(
+    seeds: &[&[u8]],
+    program_id: &[u8; 32],
+) -> Result<([u8; 32], u8), PdaError> {
+    // One seed slot is reserved for the bump
+    if seeds.len() >= MAX_SEEDS {
+        return Err(PdaError::TooManySeeds);
+    }
+
+    for bump in (1..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(&bump_seed);
+
+        match create_program_address(&seeds_with_bump, program_id) {
+            Ok(address) => return Ok((address, bump)),
+            Err(PdaError::InvalidSeeds) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(PdaError::NoViableBump)
+}
+
+/// How a PDA seed typed by the user is turned into bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedKind {
+    /// UTF-8 bytes of the text
+    String,
+    /// 32 bytes of a Base58 public key
+    Pubkey,
+    /// Raw bytes written as hex
+    Hex,
+    /// Little-endian integers, as `to_le_bytes()` in programs
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl SeedKind {
+    /// All seed kinds, in the order offered to users
+    pub fn all() -> [SeedKind; 7] {
+        [
+            SeedKind::String,
+            SeedKind::Pubkey,
+            SeedKind::Hex,
+            SeedKind::U8,
+            SeedKind::U16,
+            SeedKind::U32,
+            SeedKind::U64,
+        ]
+    }
+
+    /// Short display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SeedKind::String => "string",
+            SeedKind::Pubkey => "pubkey",
+            SeedKind::Hex => "hex",
+            SeedKind::U8 => "u8",
+            SeedKind::U16 => "u16",
+            SeedKind::U32 => "u32",
+            SeedKind::U64 => "u64",
+        }
+    }
+
+    /// Look up a seed kind by its display name
+    pub fn from_name(name: &str) -> Option<SeedKind> {
+        SeedKind::all().into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Encode a user-entered value as seed bytes
+    pub fn encode(&self, value: &str) -> Result<Vec<u8>, String> {
+        let trimmed = value.trim();
+        let int_error = |e: std::num::ParseIntError| {
+            format!("Invalid {} seed '{}': {}", self.name(), trimmed, e)
+        };
+
+        let bytes = match self {
+            SeedKind::String => value.as_bytes().to_vec(),
+            SeedKind::Pubkey => bs58::decode(trimmed)
+                .into_vec()
+                .ok()
+                .filter(|bytes| bytes.len() == 32)
+                .ok_or_else(|| format!("Invalid pubkey seed '{}'", trimmed))?,
+            SeedKind::Hex => hex_to_bytes(trimmed.trim_start_matches("0x"))
+                .map_err(|e| format!("Invalid hex seed '{}': {}", trimmed, e))?,
+            SeedKind::U8 => trimmed
+                .parse::<u8>()
+                .map_err(int_error)?
+                .to_le_bytes()
+                .to_vec(),
+            SeedKind::U16 => trimmed
+                .parse::<u16>()
+                .map_err(int_error)?
+                .to_le_bytes()
+                .to_vec(),
+            SeedKind::U32 => trimmed
+                .parse::<u32>()
+                .map_err(int_error)?
+                .to_le_bytes()
+                .to_vec(),
+            SeedKind::U64 => trimmed
+                .parse::<u64>()
+                .map_err(int_error)?
+                .to_le_bytes()
+                .to_vec(),
+        };
+
+        if bytes.len() > MAX_SEED_LEN {
+            return Err(format!(
+                "Seed '{}' is {} bytes, the limit is {}",
+                trimmed,
+                bytes.len(),
+                MAX_SEED_LEN
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+/// Generate a new Solana keypair
+///
+/// Returns the Base58 address and the Base58-encoded 32-byte secret seed.
+pub fn generate_keypair() -> (String, String) {
+    let keypair = Keypair::generate();
+    let private_key = bs58::encode(keypair.secret()).into_string();
+
+    (keypair.address(), private_key)
+}
+
+/// Derive the Base58 public key for a 32-byte secret seed
+pub fn generate_public_key(seed: &[u8; 32]) -> String {
+    Keypair::from_seed(seed).address()
 }
 
 /// Validate a Solana address (Base58 format)
@@ -45,7 +359,12 @@ pub fn validate_address(address: &str) -> bool {
 pub fn generate_program_id(program_data: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(program_data);
-    hasher.update(chrono::Utc::now().timestamp_nanos().to_be_bytes());
+    hasher.update(
+        chrono::Utc::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .to_be_bytes(),
+    );
     let hash = hasher.finalize();
 
     bs58::encode(&hash[..32]).into_string()
@@ -68,7 +387,7 @@ pub fn generate_deterministic_address(seed: &str) -> String {
 
     // Convert to 32-byte array for Base58 encoding
     let mut bytes = [0u8; 32];
-    bytes.copy_from_slice(&hash.to_be_bytes());
+    bytes[..8].copy_from_slice(&hash.to_be_bytes());
 
     bs58::encode(bytes).into_string()
 }
@@ -106,7 +425,7 @@ pub fn bytes_to_hex(bytes: &[u8]) -> String {
 
 /// Convert hex string to bytes
 pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, &'static str> {
-    if hex.len() % 2 != 0 {
+    if !hex.len().is_multiple_of(2) {
         return Err("Hex string must have even length");
     }
 
@@ -120,7 +439,12 @@ pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, &'static str> {
 /// Generate a unique identifier
 pub fn generate_uuid() -> String {
     let mut hasher = Sha256::new();
-    hasher.update(chrono::Utc::now().timestamp_nanos().to_be_bytes());
+    hasher.update(
+        chrono::Utc::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .to_be_bytes(),
+    );
     hasher.update(generate_random_bytes(16));
     let hash = hasher.finalize();
 
@@ -131,9 +455,7 @@ pub fn generate_uuid() -> String {
         u16::from_be_bytes([hash[4], hash[5]]),
         u16::from_be_bytes([hash[6], hash[7]]),
         u16::from_be_bytes([hash[8], hash[9]]),
-        u64::from_be_bytes([
-            hash[10], hash[11], hash[12], hash[13], hash[14], hash[15], hash[16], hash[17]
-        ])
+        u64::from_be_bytes([0, 0, hash[10], hash[11], hash[12], hash[13], hash[14], hash[15]])
     )
 }
 
@@ -149,6 +471,237 @@ mod tests {
         assert!(!private_key.is_empty());
         assert_ne!(public_key, private_key);
         assert!(validate_address(&public_key));
+
+        let seed: [u8; 32] = bs58::decode(&private_key)
+            .into_vec()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(generate_public_key(&seed), public_key);
+    }
+
+    #[test]
+    fn test_keypair_from_seed() {
+        // solana-keygen derives this address from the all-ones seed
+        let keypair = Keypair::from_seed(&[1u8; 32]);
+        assert_eq!(
+            keypair.address(),
+            "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9"
+        );
+        assert_eq!(keypair.secret(), [1u8; 32]);
+        assert_eq!(generate_public_key(&[1u8; 32]), keypair.address());
+    }
+
+    #[test]
+    fn test_sign_rfc8032_vector() {
+        // RFC 8032 section 7.1, TEST 2
+        let seed: [u8; 32] =
+            hex_to_bytes("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let keypair = Keypair::from_seed(&seed);
+
+        assert_eq!(
+            bytes_to_hex(&keypair.pubkey()),
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"
+        );
+
+        let signature = keypair.sign(&[0x72]);
+        assert_eq!(
+            bytes_to_hex(&signature),
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+        );
+        assert!(verify(&keypair.pubkey(), &[0x72], &signature));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let keypair = Keypair::generate();
+        let other = Keypair::generate();
+        let signature = keypair.sign(b"surfdesk");
+
+        assert!(verify(&keypair.pubkey(), b"surfdesk", &signature));
+        assert!(!verify(&keypair.pubkey(), b"surfdesK", &signature));
+        assert!(!verify(&other.pubkey(), b"surfdesk", &signature));
+
+        let mut tampered = signature;
+        tampered[0] ^= 1;
+        assert!(!verify(&keypair.pubkey(), b"surfdesk", &tampered));
+    }
+
+    #[test]
+    fn test_keypair_json_round_trip() {
+        // solana-keygen id.json for the [1; 32] seed
+        let id_json = "[1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,\
+                       138,136,227,221,116,9,241,149,253,82,219,45,60,186,93,114,\
+                       202,103,9,191,29,148,18,27,243,116,136,1,180,15,111,92]";
+
+        let keypair = Keypair::from_json(id_json).unwrap();
+        assert_eq!(
+            keypair.address(),
+            "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9"
+        );
+        assert_eq!(keypair.to_json(), id_json.replace(' ', ""));
+        assert_eq!(
+            Keypair::from_json(&keypair.to_json()).unwrap().pubkey(),
+            keypair.pubkey()
+        );
+    }
+
+    #[test]
+    fn test_keypair_base58_round_trip() {
+        let phantom = "2AXDGYSE4f2sz7tvMMzyHvUfcoJmxudvdhBcmiUSo6iuCXagjUCKEQF21awZnUGxmwD4m9vGXuC3qieHXJQHAcT";
+
+        let keypair = Keypair::from_base58_string(phantom).unwrap();
+        assert_eq!(
+            keypair.address(),
+            "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9"
+        );
+        assert_eq!(keypair.to_base58_string(), phantom);
+
+        // A bare 32-byte seed is accepted too
+        let seed = bs58::encode([1u8; 32]).into_string();
+        assert_eq!(
+            Keypair::from_base58_string(&seed).unwrap().pubkey(),
+            keypair.pubkey()
+        );
+    }
+
+    #[test]
+    fn test_keypair_import_rejects_bad_keys() {
+        let mut bytes = Keypair::from_seed(&[1u8; 32]).to_bytes();
+        bytes[63] ^= 1;
+        assert_eq!(
+            Keypair::from_bytes(&bytes).unwrap_err(),
+            KeypairError::PublicKeyMismatch
+        );
+
+        assert_eq!(
+            Keypair::from_bytes(&[1u8; 48]).unwrap_err().to_string(),
+            "Secret key must be 32 or 64 bytes, found 48"
+        );
+        // Keypair files always hold the 64-byte form
+        assert_eq!(
+            Keypair::from_json("[1,2,3]").unwrap_err().to_string(),
+            "Secret key must be 64 bytes, found 3"
+        );
+        let seed_only = serde_json::to_string(&[1u8; 32].to_vec()).unwrap();
+        assert_eq!(
+            Keypair::from_json(&seed_only).unwrap_err(),
+            KeypairError::InvalidLength {
+                expected: "64",
+                found: 32
+            }
+        );
+        assert!(matches!(
+            Keypair::from_json("{\"secret\": 1}"),
+            Err(KeypairError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            Keypair::from_base58_string("not-base58!"),
+            Err(KeypairError::InvalidFormat(_))
+        ));
+    }
+
+    fn decode32(address: &str) -> [u8; 32] {
+        bs58::decode(address)
+            .into_vec()
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_find_program_address_vectors() {
+        // Reference results from solana-program's Pubkey::find_program_address
+        let wallet = decode32("AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9");
+        let token_program = decode32("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        let mint = decode32("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+        let ata_program = decode32("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+        let (ata, bump) =
+            find_program_address(&[&wallet, &token_program, &mint], &ata_program).unwrap();
+        assert_eq!(
+            bs58::encode(ata).into_string(),
+            "3wvJdyFnGvaMWpbq93NU91SggiVRveULUXL6iX5VZDGP"
+        );
+        assert_eq!(bump, 254);
+
+        let program_id = [9u8; 32];
+        let (vault, bump) = find_program_address(&[b"vault", &wallet], &program_id).unwrap();
+        assert_eq!(
+            bs58::encode(vault).into_string(),
+            "FvWFG11Dpen7o5mq4z6bA3YEUt93N9qAUh1b5sCbczxP"
+        );
+        assert_eq!(bump, 254);
+
+        let (empty, bump) = find_program_address(&[b""], &[0u8; 32]).unwrap();
+        assert_eq!(
+            bs58::encode(empty).into_string(),
+            "Cu7NwqCXSmsR5vgGA3Vw9uYVViPi3kQvkbKByVQ8nPY9"
+        );
+        assert_eq!(bump, 255);
+    }
+
+    #[test]
+    fn test_create_program_address() {
+        let wallet = decode32("AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9");
+        let program_id = [9u8; 32];
+
+        // The canonical bump reproduces the found address
+        let vault = create_program_address(&[b"vault", &wallet, &[254]], &program_id).unwrap();
+        assert_eq!(
+            bs58::encode(vault).into_string(),
+            "FvWFG11Dpen7o5mq4z6bA3YEUt93N9qAUh1b5sCbczxP"
+        );
+        assert!(!is_on_curve(&vault));
+
+        // Bump 255 lands on the curve for these seeds
+        assert_eq!(
+            create_program_address(&[b"vault", &wallet, &[255]], &program_id),
+            Err(PdaError::InvalidSeeds)
+        );
+        assert!(is_on_curve(&wallet));
+    }
+
+    #[test]
+    fn test_program_address_seed_limits() {
+        let program_id = [9u8; 32];
+        let long_seed = [0u8; MAX_SEED_LEN + 1];
+        assert_eq!(
+            create_program_address(&[&long_seed], &program_id),
+            Err(PdaError::MaxSeedLengthExceeded)
+        );
+
+        let seeds = vec![&b"a"[..]; MAX_SEEDS];
+        assert_eq!(
+            find_program_address(&seeds, &program_id),
+            Err(PdaError::TooManySeeds)
+        );
+        assert!(find_program_address(&seeds[1..], &program_id).is_ok());
+    }
+
+    #[test]
+    fn test_seed_kind_encode() {
+        assert_eq!(SeedKind::String.encode("vault").unwrap(), b"vault".to_vec());
+        assert_eq!(
+            SeedKind::Pubkey
+                .encode("11111111111111111111111111111111")
+                .unwrap(),
+            vec![0u8; 32]
+        );
+        assert_eq!(SeedKind::Hex.encode("0xdead").unwrap(), vec![0xde, 0xad]);
+        assert_eq!(SeedKind::U16.encode("258").unwrap(), vec![2, 1]);
+        assert_eq!(
+            SeedKind::U64.encode("1").unwrap(),
+            vec![1, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        assert!(SeedKind::U8.encode("256").is_err());
+        assert!(SeedKind::Pubkey.encode("abc").is_err());
+        assert!(SeedKind::String.encode(&"x".repeat(33)).is_err());
+        assert_eq!(SeedKind::from_name("u32"), Some(SeedKind::U32));
     }
 
     #[test]
@@ -249,9 +802,11 @@ mod tests {
         assert_ne!(uuid1, uuid2);
 
         // Check UUID format (8-4-4-4-12 characters with hyphens)
-        assert!(uuid1
-            .matches(r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$")
-            .next()
-            .is_some());
+        let groups: Vec<&str> = uuid1.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(lengths, vec![8, 4, 4, 4, 12]);
+        assert!(groups.iter().all(|g| g
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())));
     }
 }
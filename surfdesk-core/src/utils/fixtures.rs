@@ -0,0 +1,126 @@
+//! Schemas shared by generator, Borsh and validation tests
+
+use crate::components::program_builder::{
+    Account, EnumVariant, ErrorCode, Field, FieldType, Instruction, InstructionAccount,
+    InstructionArg, ProgramSchema, Seed, TypeDef, TypeDefKind,
+};
+
+/// A field without length limits
+pub(crate) fn field(name: &str, field_type: FieldType) -> Field {
+    Field {
+        name: name.to_string(),
+        field_type,
+        max_len: vec![],
+    }
+}
+
+fn instruction(
+    name: &str,
+    accounts: Vec<InstructionAccount>,
+    args: Vec<InstructionArg>,
+) -> Instruction {
+    Instruction {
+        name: name.to_string(),
+        accounts,
+        args,
+        discriminator: None,
+    }
+}
+
+fn arg(name: &str, arg_type: FieldType) -> InstructionArg {
+    InstructionArg {
+        name: name.to_string(),
+        arg_type,
+    }
+}
+
+/// Counter program using every kind of schema element
+///
+/// Only `counter` is a schema account; the other instruction accounts are
+/// wallets and programs.
+pub(crate) fn counter_schema() -> ProgramSchema {
+    let counter = || InstructionAccount::writable("counter");
+    let authority = || InstructionAccount::signer("authority");
+    ProgramSchema {
+        name: "counter".to_string(),
+        version: "0.2.0".to_string(),
+        accounts: vec![Account {
+            name: "Counter".to_string(),
+            fields: vec![
+                field("authority", FieldType::Pubkey),
+                field("count", FieldType::U64),
+                Field {
+                    max_len: vec![32],
+                    ..field("label", FieldType::String)
+                },
+                field(
+                    "history",
+                    FieldType::Vec(Box::new(FieldType::Option(Box::new(FieldType::I32)))),
+                ),
+                field("mode", FieldType::Defined("Mode".to_string())),
+            ],
+            discriminator: None,
+            seeds: vec![
+                Seed::Const("counter".to_string()),
+                Seed::Arg {
+                    name: "authority".to_string(),
+                    arg_type: FieldType::Pubkey,
+                },
+                Seed::Arg {
+                    name: "index".to_string(),
+                    arg_type: FieldType::U16,
+                },
+            ],
+        }],
+        instructions: vec![
+            instruction(
+                "Initialize",
+                vec![
+                    counter(),
+                    authority(),
+                    InstructionAccount::new("payer", true, true),
+                    InstructionAccount::readonly("system_program"),
+                ],
+                vec![arg("label", FieldType::String)],
+            ),
+            instruction(
+                "Increment",
+                vec![counter(), authority()],
+                vec![arg("by", FieldType::U64)],
+            ),
+            instruction(
+                "Set Mode",
+                vec![counter(), authority()],
+                vec![arg("mode", FieldType::Defined("Mode".to_string()))],
+            ),
+            instruction("Reset", vec![counter(), authority()], vec![]),
+            instruction("Ping", vec![], vec![]),
+        ],
+        types: vec![TypeDef {
+            name: "Mode".to_string(),
+            kind: TypeDefKind::Enum {
+                variants: vec![
+                    EnumVariant {
+                        name: "Open".to_string(),
+                        fields: vec![],
+                    },
+                    EnumVariant {
+                        name: "Locked".to_string(),
+                        fields: vec![
+                            field("until_slot", FieldType::I64),
+                            field(
+                                "unlockers",
+                                FieldType::Array(Box::new(FieldType::Pubkey), 2),
+                            ),
+                        ],
+                    },
+                ],
+            },
+        }],
+        errors: vec![ErrorCode {
+            code: 6000,
+            name: "Overflow".to_string(),
+            msg: Some("Counter overflowed".to_string()),
+        }],
+    }
+}
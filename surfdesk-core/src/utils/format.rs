@@ -56,7 +56,7 @@ pub fn format_timestamp(timestamp: &str) -> String {
             match timestamp.parse::<i64>() {
                 Ok(seconds) => {
                     let dt = chrono::DateTime::from_timestamp(seconds, 0)
-                        .unwrap_or_else(|| chrono::Utc::now().into());
+                        .unwrap_or_else(chrono::Utc::now);
                     dt.format("%Y-%m-%d %H:%M:%S").to_string()
                 }
                 Err(_) => timestamp.to_string(),
@@ -71,7 +71,7 @@ pub fn format_relative_time(timestamp: &str) -> String {
     let past_time = match chrono::DateTime::parse_from_rfc3339(timestamp) {
         Ok(dt) => dt.with_timezone(&chrono::Utc),
         Err(_) => match timestamp.parse::<i64>() {
-            Ok(seconds) => chrono::DateTime::from_timestamp(seconds, 0).unwrap_or_else(|| now),
+            Ok(seconds) => chrono::DateTime::from_timestamp(seconds, 0).unwrap_or(now),
             Err(_) => return timestamp.to_string(),
         },
     };
@@ -199,15 +199,13 @@ pub fn format_compute_units(units: u64) -> String {
 /// Format error message for user display
 pub fn format_error_message(error: &str) -> String {
     // Clean up common error patterns
-    let cleaned = error
-        .replace("Error: ", "")
-        .replace("RPC error: ", "")
-        .trim();
+    let replaced = error.replace("Error: ", "").replace("RPC error: ", "");
+    let cleaned = replaced.trim();
 
     // Capitalize first letter
     if let Some(first_char) = cleaned.chars().next() {
         let mut result = first_char.to_uppercase().collect::<String>();
-        result.push_str(&cleaned[1..]);
+        result.push_str(&cleaned[first_char.len_utf8()..]);
         result
     } else {
         cleaned.to_string()
@@ -264,6 +262,60 @@ pub fn format_list_with_and(items: &[String]) -> String {
     }
 }
 
+/// Convert a display name or camelCase identifier to snake_case
+///
+/// `"Add Liquidity"`, `"addLiquidity"` and `"AddLiquidity"` all become
+/// `"add_liquidity"`.
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower);
+            if boundary && !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result.trim_end_matches('_').to_string()
+}
+
+/// Convert a display name or snake_case identifier to camelCase
+pub fn to_camel_case(name: &str) -> String {
+    let pascal = to_pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Convert a display name or snake_case identifier to PascalCase
+pub fn to_pascal_case(name: &str) -> String {
+    to_snake_case(name)
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -286,7 +338,7 @@ mod tests {
     #[test]
     fn test_format_lamports() {
         assert_eq!(format_lamports(1_500_000_000), "1.5000 SOL");
-        assert_eq!(format_lamports(500_000_000), "500.0 M lamports");
+        assert_eq!(format_lamports(500_000_000), "500.00 M lamports");
         assert_eq!(format_lamports(50_000), "50.0 K lamports");
         assert_eq!(format_lamports(500), "500 lamports");
     }
@@ -294,9 +346,9 @@ mod tests {
     #[test]
     fn test_format_file_size() {
         assert_eq!(format_file_size(500), "500 B");
-        assert_eq!(format_file_size(1_500), "1.5 KB");
-        assert_eq!(format_file_size(1_500_000), "1.5 MB");
-        assert_eq!(format_file_size(1_500_000_000), "1.5 GB");
+        assert_eq!(format_file_size(1_536), "1.5 KB");
+        assert_eq!(format_file_size(1_572_864), "1.5 MB");
+        assert_eq!(format_file_size(1_610_612_736), "1.5 GB");
     }
 
     #[test]
@@ -346,4 +398,23 @@ mod tests {
             "a, b, and c"
         );
     }
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("Add Liquidity"), "add_liquidity");
+        assert_eq!(to_snake_case("addLiquidity"), "add_liquidity");
+        assert_eq!(to_snake_case("AddLiquidity"), "add_liquidity");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+        assert_eq!(to_snake_case("HTTPServer v2"), "http_server_v2");
+        assert_eq!(to_snake_case("Transfer"), "transfer");
+    }
+
+    #[test]
+    fn test_to_camel_and_pascal_case() {
+        assert_eq!(to_camel_case("Add Liquidity"), "addLiquidity");
+        assert_eq!(to_camel_case("minimum_amount_out"), "minimumAmountOut");
+        assert_eq!(to_pascal_case("user_account"), "UserAccount");
+        assert_eq!(to_pascal_case("Counter"), "Counter");
+        assert_eq!(to_camel_case(""), "");
+    }
 }
@@ -0,0 +1,18 @@
+//! Golden-file assertions shared by generator tests
+
+use std::path::PathBuf;
+
+/// Compare against a file under `tests/golden`, rewriting it when
+/// `UPDATE_GOLDEN=1` is set
+pub(crate) fn assert_golden(file: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(file);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Missing golden file {}: {}", path.display(), e));
+    assert_eq!(actual, expected, "{} is out of date", file);
+}
@@ -0,0 +1,1417 @@
+//! Anchor IDL export and import
+//!
+//! Converts a program builder `ProgramSchema` into the Anchor IDL JSON that
+//! Anchor's TypeScript client and related tooling consume. The layout follows
+//! the classic IDL (`isMut`/`isSigner`, camelCase names, `publicKey`), which
+//! has no discriminators: clients derive them from the instruction and
+//! account names.
+//!
+//! The importer goes the other way for legacy Anchor IDLs, the Anchor 0.30+
+//! spec and Codama root nodes. Anything the schema can't express is reported
+//! as a warning rather than silently dropped.
+
+use crate::components::program_builder::{
+    Account, EnumVariant, ErrorCode, Field, FieldType, Instruction, InstructionAccount,
+    InstructionArg, ProgramSchema, TypeDef, TypeDefKind,
+};
+use crate::utils::borsh::account_discriminator;
+use crate::utils::format::{to_camel_case, to_pascal_case, to_snake_case};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Anchor IDL document
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnchorIdl {
+    pub version: String,
+    pub name: String,
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub accounts: Vec<IdlTypeDef>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<IdlErrorCode>,
+}
+
+/// Custom error entry of an IDL
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
+}
+
+/// Instruction entry of an IDL
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub accounts: Vec<IdlAccountItem>,
+    pub args: Vec<IdlField>,
+}
+
+/// Account passed to an instruction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlAccountItem {
+    pub name: String,
+    pub is_mut: bool,
+    pub is_signer: bool,
+}
+
+/// Named, typed member of a struct or instruction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Value,
+}
+
+/// Named type definition, used for both `accounts` and `types`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+/// Body of a type definition
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefTy {
+    Struct { fields: Vec<IdlField> },
+    Enum { variants: Vec<IdlEnumVariant> },
+}
+
+/// Enum variant of a type definition
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<IdlField>,
+}
+
+/// IDL spelling of a field type
+pub fn idl_type(field_type: &FieldType) -> Value {
+    let primitive = match field_type {
+        FieldType::U8 => "u8",
+        FieldType::U16 => "u16",
+        FieldType::U32 => "u32",
+        FieldType::U64 => "u64",
+        FieldType::U128 => "u128",
+        FieldType::I8 => "i8",
+        FieldType::I16 => "i16",
+        FieldType::I32 => "i32",
+        FieldType::I64 => "i64",
+        FieldType::I128 => "i128",
+        FieldType::String => "string",
+        FieldType::Pubkey => "publicKey",
+        FieldType::Bool => "bool",
+        FieldType::Vec(inner) => return json!({ "vec": idl_type(inner) }),
+        FieldType::Option(inner) => return json!({ "option": idl_type(inner) }),
+        FieldType::Array(inner, len) => return json!({ "array": [idl_type(inner), len] }),
+        FieldType::Defined(name) => return json!({ "defined": to_pascal_case(name) }),
+    };
+    Value::String(primitive.to_string())
+}
+
+fn idl_fields(fields: &[Field]) -> Vec<IdlField> {
+    fields
+        .iter()
+        .map(|field| IdlField {
+            name: to_camel_case(&field.name),
+            ty: idl_type(&field.field_type),
+        })
+        .collect()
+}
+
+/// Infer `(is_mut, is_signer)` for an instruction account from its name
+///
+/// Used for schemas that list instruction accounts by name only. Follows
+/// Anchor naming conventions: payers sign and pay, authorities and signers
+/// only sign, and mints, programs, sysvars and owners are read-only.
+pub fn account_constraints(name: &str) -> (bool, bool) {
+    let name = to_snake_case(name);
+    let has = |word: &str| name.split('_').any(|part| part == word);
+
+    if has("payer") {
+        (true, true)
+    } else if has("authority") || has("signer") {
+        (false, true)
+    } else if ["mint", "program", "sysvar", "rent", "clock", "owner"]
+        .iter()
+        .any(|word| has(word))
+    {
+        (false, false)
+    } else {
+        (true, false)
+    }
+}
+
+/// Convert one schema instruction to its IDL entry
+pub fn export_instruction(instruction: &Instruction) -> IdlInstruction {
+    IdlInstruction {
+        name: to_camel_case(&instruction.name),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|account| IdlAccountItem {
+                name: to_camel_case(&account.name),
+                is_mut: account.writable,
+                is_signer: account.signer,
+            })
+            .collect(),
+        args: instruction
+            .args
+            .iter()
+            .map(|arg| IdlField {
+                name: to_camel_case(&arg.name),
+                ty: idl_type(&arg.arg_type),
+            })
+            .collect(),
+    }
+}
+
+/// Convert a program schema to an Anchor IDL
+pub fn export_anchor_idl(schema: &ProgramSchema) -> AnchorIdl {
+    AnchorIdl {
+        version: if schema.version.trim().is_empty() {
+            "0.1.0".to_string()
+        } else {
+            schema.version.trim().to_string()
+        },
+        name: to_snake_case(&schema.name),
+        instructions: schema.instructions.iter().map(export_instruction).collect(),
+        accounts: schema
+            .accounts
+            .iter()
+            .map(|account| IdlTypeDef {
+                name: to_pascal_case(&account.name),
+                ty: IdlTypeDefTy::Struct {
+                    fields: idl_fields(&account.fields),
+                },
+            })
+            .collect(),
+        types: schema
+            .types
+            .iter()
+            .map(|def| IdlTypeDef {
+                name: to_pascal_case(&def.name),
+                ty: match &def.kind {
+                    TypeDefKind::Struct { fields } => IdlTypeDefTy::Struct {
+                        fields: idl_fields(fields),
+                    },
+                    TypeDefKind::Enum { variants } => IdlTypeDefTy::Enum {
+                        variants: variants
+                            .iter()
+                            .map(|variant| IdlEnumVariant {
+                                name: to_pascal_case(&variant.name),
+                                fields: idl_fields(&variant.fields),
+                            })
+                            .collect(),
+                    },
+                },
+            })
+            .collect(),
+        errors: schema
+            .errors
+            .iter()
+            .map(|error| IdlErrorCode {
+                code: error.code,
+                name: to_pascal_case(&error.name),
+                msg: error.msg.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Errors produced while importing an IDL
+#[derive(Debug, thiserror::Error)]
+pub enum IdlError {
+    #[error("Invalid IDL JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error("Unrecognized IDL: {0}")]
+    Unrecognized(String),
+    #[error("Invalid IDL: {0}")]
+    Invalid(String),
+}
+
+/// IDL dialects understood by the importer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdlFormat {
+    /// Anchor IDLs before 0.30 (`isMut`/`isSigner`, inline account types)
+    AnchorLegacy,
+    /// Anchor 0.30+ spec (`metadata.spec`, `writable`/`signer`, discriminators)
+    Anchor,
+    /// Codama `rootNode` documents
+    Codama,
+}
+
+/// Outcome of importing an IDL
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdlImport {
+    pub format: IdlFormat,
+    pub schema: ProgramSchema,
+    /// Constructs that were omitted or approximated
+    pub warnings: Vec<String>,
+}
+
+/// Detect the dialect of an IDL document
+pub fn detect_idl_format(idl: &Value) -> Option<IdlFormat> {
+    if idl["kind"] == "rootNode" {
+        Some(IdlFormat::Codama)
+    } else if idl["metadata"]["spec"].is_string() || idl["address"].is_string() {
+        Some(IdlFormat::Anchor)
+    } else if idl["instructions"].is_array() && idl["name"].is_string() {
+        Some(IdlFormat::AnchorLegacy)
+    } else {
+        None
+    }
+}
+
+/// Import an Anchor (legacy or 0.30+) or Codama IDL into a program schema
+pub fn import_idl(json: &str) -> Result<IdlImport, IdlError> {
+    let idl: Value = serde_json::from_str(json)?;
+    let format = detect_idl_format(&idl).ok_or_else(|| {
+        IdlError::Unrecognized("expected an Anchor IDL or a Codama rootNode".to_string())
+    })?;
+
+    let mut importer = Importer::default();
+    let schema = match format {
+        IdlFormat::AnchorLegacy | IdlFormat::Anchor => importer.anchor(&idl, format)?,
+        IdlFormat::Codama => importer.codama(&idl["program"])?,
+    };
+    Ok(IdlImport {
+        format,
+        schema,
+        warnings: importer.warnings,
+    })
+}
+
+fn str_of<'a>(value: &'a Value, key: &str) -> &'a str {
+    value[key].as_str().unwrap_or_default()
+}
+
+fn array_of<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value[key].as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn bytes_of(value: &Value) -> Option<Vec<u8>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+        .collect()
+}
+
+/// Convert IDL error entries, whose message sits under `msg_key`
+fn error_codes(errors: &[Value], msg_key: &str) -> Result<Vec<ErrorCode>, IdlError> {
+    errors
+        .iter()
+        .map(|error| {
+            let name = to_pascal_case(str_of(error, "name"));
+            let code = error["code"]
+                .as_u64()
+                .and_then(|code| u32::try_from(code).ok())
+                .ok_or_else(|| {
+                    IdlError::Invalid(format!(
+                        "error `{}` has code {}, expected a u32",
+                        name, error["code"]
+                    ))
+                })?;
+            Ok(ErrorCode {
+                code,
+                name,
+                msg: error[msg_key].as_str().map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Short JSON rendering of an unsupported type for warnings
+fn describe(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[derive(Default)]
+struct Importer {
+    warnings: Vec<String>,
+}
+
+impl Importer {
+    fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    fn anchor(&mut self, idl: &Value, format: IdlFormat) -> Result<ProgramSchema, IdlError> {
+        let legacy = format == IdlFormat::AnchorLegacy;
+        let (name, version) = if legacy {
+            (str_of(idl, "name"), str_of(idl, "version"))
+        } else {
+            (
+                str_of(&idl["metadata"], "name"),
+                str_of(&idl["metadata"], "version"),
+            )
+        };
+
+        let types = array_of(idl, "types");
+        let mut used_types = HashSet::new();
+        let mut accounts = Vec::new();
+        for account in array_of(idl, "accounts") {
+            let account_name = str_of(account, "name");
+            // 0.30+ accounts reference a struct in `types`; legacy ones inline it
+            let definition = if legacy {
+                Some(account)
+            } else {
+                used_types.insert(account_name);
+                types.iter().find(|ty| str_of(ty, "name") == account_name)
+            };
+            let Some(fields) = definition.and_then(|def| def["type"]["fields"].as_array()) else {
+                self.warn(format!(
+                    "Account `{}` has no struct definition and was skipped",
+                    account_name
+                ));
+                continue;
+            };
+
+            let name = to_pascal_case(account_name);
+            let discriminator = match bytes_of(&account["discriminator"]) {
+                Some(bytes) => Some(bytes),
+                // Keep Anchor's hash of the original name if normalizing changed it
+                None if name != account_name => Some(account_discriminator(account_name).to_vec()),
+                None => None,
+            };
+            accounts.push(Account {
+                fields: self.anchor_fields(fields, &format!("Account `{}`", name)),
+                name,
+                discriminator,
+                seeds: vec![],
+            });
+        }
+
+        let types = types
+            .iter()
+            .filter(|ty| !used_types.contains(str_of(ty, "name")))
+            .filter_map(|ty| self.anchor_typedef(ty))
+            .collect();
+        if let Some(address) = idl["address"]
+            .as_str()
+            .or(idl["metadata"]["address"].as_str())
+        {
+            self.warn(format!("Program address `{}` was skipped", address));
+        }
+        for section in ["events", "constants", "state"] {
+            if !idl[section].is_null() && idl[section] != Value::Array(Vec::new()) {
+                self.warn(format!("IDL `{}` section was skipped", section));
+            }
+        }
+
+        let instructions = array_of(idl, "instructions")
+            .iter()
+            .map(|instruction| self.anchor_instruction(instruction))
+            .collect();
+
+        Ok(ProgramSchema {
+            name: name.to_string(),
+            version: version.to_string(),
+            accounts,
+            instructions,
+            types,
+            errors: error_codes(array_of(idl, "errors"), "msg")?,
+        })
+    }
+
+    fn anchor_instruction(&mut self, instruction: &Value) -> Instruction {
+        let name = to_snake_case(str_of(instruction, "name"));
+        let context = format!("Instruction `{}`", name);
+
+        let mut accounts = Vec::new();
+        self.anchor_accounts(array_of(instruction, "accounts"), &context, &mut accounts);
+        if !instruction["returns"].is_null() {
+            self.warn(format!(
+                "{} return type `{}` was skipped",
+                context,
+                describe(&instruction["returns"])
+            ));
+        }
+
+        let args = array_of(instruction, "args")
+            .iter()
+            .filter_map(|arg| {
+                let arg_name = to_snake_case(str_of(arg, "name"));
+                match anchor_type(&arg["type"]) {
+                    Some(arg_type) => Some(InstructionArg {
+                        name: arg_name,
+                        arg_type,
+                    }),
+                    None => {
+                        self.warn(format!(
+                            "{} arg `{}` has unsupported type `{}` and was omitted",
+                            context,
+                            arg_name,
+                            describe(&arg["type"])
+                        ));
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Instruction {
+            name,
+            accounts,
+            args,
+            discriminator: bytes_of(&instruction["discriminator"]),
+        }
+    }
+
+    /// Flatten instruction accounts, including composite account groups
+    fn anchor_accounts(
+        &mut self,
+        items: &[Value],
+        context: &str,
+        accounts: &mut Vec<InstructionAccount>,
+    ) {
+        for item in items {
+            if let Some(nested) = item["accounts"].as_array() {
+                self.anchor_accounts(nested, context, accounts);
+                continue;
+            }
+
+            let name = to_snake_case(str_of(item, "name"));
+            let context = format!("{} account `{}`", context, name);
+            let mut flag = |legacy: &str, current: &str| {
+                let value = if item[legacy].is_null() {
+                    &item[current]
+                } else {
+                    &item[legacy]
+                };
+                self.account_flag(value, current, &context)
+            };
+            let optional = flag("isOptional", "optional");
+            let writable = flag("isMut", "writable");
+            let signer = flag("isSigner", "signer");
+            if optional {
+                self.warn(format!(
+                    "{} is optional; the schema treats it as required",
+                    context
+                ));
+            }
+            for (key, what) in [
+                ("pda", "PDA seeds were"),
+                ("address", "fixed address was"),
+                ("relations", "relations were"),
+            ] {
+                if !item[key].is_null() {
+                    self.warn(format!("{} {} skipped", context, what));
+                }
+            }
+            accounts.push(InstructionAccount {
+                writable,
+                signer,
+                name,
+            });
+        }
+    }
+
+    /// Read a boolean account flag; anything else, such as `"either"`, is
+    /// imported as `false` with a warning
+    fn account_flag(&mut self, value: &Value, flag: &str, context: &str) -> bool {
+        match value {
+            Value::Null => false,
+            Value::Bool(value) => *value,
+            other => {
+                self.warn(format!(
+                    "{} has {} `{}`, imported as false",
+                    context,
+                    flag,
+                    describe(other)
+                ));
+                false
+            }
+        }
+    }
+
+    /// Convert a `types` entry into a schema type definition
+    fn anchor_typedef(&mut self, ty: &Value) -> Option<TypeDef> {
+        let name = to_pascal_case(str_of(ty, "name"));
+        let context = format!("Type `{}`", name);
+        if !array_of(ty, "generics").is_empty() {
+            self.warn(format!("{} is generic and was skipped", context));
+            return None;
+        }
+        if let Some(serialization) = ty["serialization"].as_str().filter(|s| *s != "borsh") {
+            self.warn(format!(
+                "{} uses {} serialization; its layout is treated as Borsh",
+                context, serialization
+            ));
+        }
+
+        let kind = match str_of(&ty["type"], "kind") {
+            "struct" => TypeDefKind::Struct {
+                fields: self.anchor_fields(array_of(&ty["type"], "fields"), &context),
+            },
+            "enum" => TypeDefKind::Enum {
+                variants: array_of(&ty["type"], "variants")
+                    .iter()
+                    .map(|variant| {
+                        let variant_name = to_pascal_case(str_of(variant, "name"));
+                        let context = format!("{} variant `{}`", context, variant_name);
+                        EnumVariant {
+                            fields: self.anchor_fields(array_of(variant, "fields"), &context),
+                            name: variant_name,
+                        }
+                    })
+                    .collect(),
+            },
+            other => {
+                self.warn(format!(
+                    "{} has unsupported kind `{}` and was skipped",
+                    context, other
+                ));
+                return None;
+            }
+        };
+        Some(TypeDef { name, kind })
+    }
+
+    /// Convert struct or variant fields
+    ///
+    /// Tuple fields are plain types without names; they get positional names
+    /// `_0`, `_1`, ... which keep the Borsh layout intact.
+    fn anchor_fields(&mut self, fields: &[Value], context: &str) -> Vec<Field> {
+        if fields.iter().any(|field| field.get("name").is_none()) {
+            self.warn(format!(
+                "{} has tuple fields, imported with positional names",
+                context
+            ));
+        }
+        fields
+            .iter()
+            .enumerate()
+            .filter_map(|(index, field)| {
+                let (name, ty) = match field.get("name") {
+                    Some(name) => (
+                        to_snake_case(name.as_str().unwrap_or_default()),
+                        &field["type"],
+                    ),
+                    None => (format!("_{}", index), field),
+                };
+                match anchor_type(ty) {
+                    Some(field_type) => Some(Field {
+                        name,
+                        field_type,
+                        max_len: vec![],
+                    }),
+                    None => {
+                        self.warn(format!(
+                            "{} field `{}` has unsupported type `{}` and was omitted",
+                            context,
+                            name,
+                            describe(ty)
+                        ));
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn codama(&mut self, program: &Value) -> Result<ProgramSchema, IdlError> {
+        let accounts = array_of(program, "accounts")
+            .iter()
+            .map(|account| {
+                let name = to_pascal_case(str_of(account, "name"));
+                let context = format!("Account `{}`", name);
+                let (fields, discriminator) =
+                    self.codama_fields(array_of(&account["data"], "fields"), account, &context);
+                Account {
+                    name,
+                    fields: fields
+                        .into_iter()
+                        .map(|(name, field_type)| Field {
+                            name,
+                            field_type,
+                            max_len: vec![],
+                        })
+                        .collect(),
+                    discriminator,
+                    seeds: vec![],
+                }
+            })
+            .collect();
+
+        if !array_of(program, "pdas").is_empty() {
+            self.warn("Program `pdas` were skipped".to_string());
+        }
+
+        let instructions = array_of(program, "instructions")
+            .iter()
+            .map(|instruction| {
+                let name = to_snake_case(str_of(instruction, "name"));
+                let context = format!("Instruction `{}`", name);
+                let mut accounts = Vec::new();
+                for item in array_of(instruction, "accounts") {
+                    let account_name = to_snake_case(str_of(item, "name"));
+                    let account_context = format!("{} account `{}`", context, account_name);
+                    if self.account_flag(&item["isOptional"], "isOptional", &account_context) {
+                        self.warn(format!(
+                            "{} is optional; the schema treats it as required",
+                            account_context
+                        ));
+                    }
+                    if !item["defaultValue"].is_null() {
+                        self.warn(format!("{} default value was skipped", account_context));
+                    }
+                    accounts.push(InstructionAccount {
+                        writable: self.account_flag(
+                            &item["isWritable"],
+                            "isWritable",
+                            &account_context,
+                        ),
+                        signer: self.account_flag(&item["isSigner"], "isSigner", &account_context),
+                        name: account_name,
+                    });
+                }
+                if !array_of(instruction, "remainingAccounts").is_empty() {
+                    self.warn(format!("{} remaining accounts were skipped", context));
+                }
+                let (args, discriminator) =
+                    self.codama_fields(array_of(instruction, "arguments"), instruction, &context);
+                Instruction {
+                    name,
+                    accounts,
+                    args: args
+                        .into_iter()
+                        .map(|(name, arg_type)| InstructionArg { name, arg_type })
+                        .collect(),
+                    discriminator,
+                }
+            })
+            .collect();
+
+        let types = array_of(program, "definedTypes")
+            .iter()
+            .filter_map(|ty| self.codama_typedef(ty))
+            .collect();
+
+        Ok(ProgramSchema {
+            name: to_snake_case(str_of(program, "name")),
+            version: str_of(program, "version").to_string(),
+            accounts,
+            instructions,
+            types,
+            errors: error_codes(array_of(program, "errors"), "message")?,
+        })
+    }
+
+    /// Convert a Codama `definedTypeNode`
+    fn codama_typedef(&mut self, ty: &Value) -> Option<TypeDef> {
+        let name = to_pascal_case(str_of(ty, "name"));
+        let context = format!("Type `{}`", name);
+        let body = &ty["type"];
+
+        let kind = match str_of(body, "kind") {
+            "structTypeNode" => {
+                let (fields, _) =
+                    self.codama_fields(array_of(body, "fields"), &Value::Null, &context);
+                TypeDefKind::Struct {
+                    fields: fields
+                        .into_iter()
+                        .map(|(name, field_type)| Field {
+                            name,
+                            field_type,
+                            max_len: vec![],
+                        })
+                        .collect(),
+                }
+            }
+            "enumTypeNode" if matches!(str_of(&body["size"], "format"), "" | "u8") => {
+                let mut variants = Vec::new();
+                for variant in array_of(body, "variants") {
+                    let variant_name = to_pascal_case(str_of(variant, "name"));
+                    let context = format!("{} variant `{}`", context, variant_name);
+                    let fields = match str_of(variant, "kind") {
+                        "enumEmptyVariantTypeNode" => Vec::new(),
+                        "enumStructVariantTypeNode" => {
+                            let struct_fields = array_of(&variant["struct"], "fields");
+                            self.codama_fields(struct_fields, &Value::Null, &context).0
+                        }
+                        "enumTupleVariantTypeNode" => {
+                            self.warn(format!(
+                                "{} has tuple fields, imported with positional names",
+                                context
+                            ));
+                            let items = array_of(&variant["tuple"], "items");
+                            let mut fields = Vec::new();
+                            for (index, item) in items.iter().enumerate() {
+                                match codama_type(item) {
+                                    Some(field_type) => {
+                                        fields.push((format!("_{}", index), field_type))
+                                    }
+                                    None => self.warn(format!(
+                                        "{} field `_{}` has unsupported type `{}` and was omitted",
+                                        context,
+                                        index,
+                                        str_of(item, "kind")
+                                    )),
+                                }
+                            }
+                            fields
+                        }
+                        other => {
+                            self.warn(format!(
+                                "{} has unsupported kind `{}` and was omitted",
+                                context, other
+                            ));
+                            continue;
+                        }
+                    };
+                    variants.push(EnumVariant {
+                        name: variant_name,
+                        fields: fields
+                            .into_iter()
+                            .map(|(name, field_type)| Field {
+                                name,
+                                field_type,
+                                max_len: vec![],
+                            })
+                            .collect(),
+                    });
+                }
+                TypeDefKind::Enum { variants }
+            }
+            other => {
+                self.warn(format!(
+                    "{} has unsupported kind `{}` and was skipped",
+                    context, other
+                ));
+                return None;
+            }
+        };
+        Some(TypeDef { name, kind })
+    }
+
+    /// Convert Codama struct fields or instruction arguments
+    ///
+    /// The field named by a leading `fieldDiscriminatorNode` becomes the
+    /// discriminator instead of a regular field.
+    fn codama_fields(
+        &mut self,
+        fields: &[Value],
+        node: &Value,
+        context: &str,
+    ) -> (Vec<(String, FieldType)>, Option<Vec<u8>>) {
+        let mut discriminator_field = None;
+        for discriminator in array_of(node, "discriminators") {
+            match (
+                str_of(discriminator, "kind"),
+                discriminator["offset"].as_u64(),
+            ) {
+                ("fieldDiscriminatorNode", Some(0)) => {
+                    discriminator_field = Some(str_of(discriminator, "name"))
+                }
+                (kind, _) => self.warn(format!(
+                    "{} uses an unsupported `{}` discriminator",
+                    context, kind
+                )),
+            }
+        }
+
+        let mut discriminator = None;
+        let mut converted = Vec::new();
+        for field in fields {
+            let raw_name = str_of(field, "name");
+            let name = to_snake_case(raw_name);
+            if Some(raw_name) == discriminator_field {
+                discriminator = codama_bytes(&field["defaultValue"]);
+                if discriminator.is_none() {
+                    self.warn(format!(
+                        "{} discriminator `{}` has no readable byte value",
+                        context, name
+                    ));
+                }
+                continue;
+            }
+            match codama_type(&field["type"]) {
+                Some(field_type) => converted.push((name, field_type)),
+                None => self.warn(format!(
+                    "{} field `{}` has unsupported type `{}` and was omitted",
+                    context,
+                    name,
+                    str_of(&field["type"], "kind")
+                )),
+            }
+        }
+        (converted, discriminator)
+    }
+}
+
+/// Map an Anchor IDL type to a schema field type
+fn anchor_type(ty: &Value) -> Option<FieldType> {
+    if let Some(object) = ty.as_object() {
+        let boxed = |inner: &Value| anchor_type(inner).map(Box::new);
+        return match object.iter().next()? {
+            (key, inner) if key == "vec" => Some(FieldType::Vec(boxed(inner)?)),
+            (key, inner) if key == "option" => Some(FieldType::Option(boxed(inner)?)),
+            (key, inner) if key == "array" => {
+                let len = usize::try_from(inner[1].as_u64()?).ok()?;
+                Some(FieldType::Array(boxed(&inner[0])?, len))
+            }
+            // Legacy IDLs name the type directly, 0.30+ wraps it with generics
+            (key, Value::String(name)) if key == "defined" => {
+                Some(FieldType::Defined(to_pascal_case(name)))
+            }
+            (key, defined) if key == "defined" && array_of(defined, "generics").is_empty() => Some(
+                FieldType::Defined(to_pascal_case(defined["name"].as_str()?)),
+            ),
+            _ => None,
+        };
+    }
+
+    Some(match ty.as_str()? {
+        "u8" => FieldType::U8,
+        "u16" => FieldType::U16,
+        "u32" => FieldType::U32,
+        "u64" => FieldType::U64,
+        "u128" => FieldType::U128,
+        "i8" => FieldType::I8,
+        "i16" => FieldType::I16,
+        "i32" => FieldType::I32,
+        "i64" => FieldType::I64,
+        "i128" => FieldType::I128,
+        "bool" => FieldType::Bool,
+        "string" => FieldType::String,
+        "publicKey" | "pubkey" => FieldType::Pubkey,
+        "bytes" => FieldType::Vec(Box::new(FieldType::U8)),
+        _ => return None,
+    })
+}
+
+/// Map a Codama type node to a schema field type
+fn codama_type(ty: &Value) -> Option<FieldType> {
+    fn number_format(node: &Value) -> Option<&str> {
+        (str_of(node, "kind") == "numberTypeNode").then(|| str_of(node, "format"))
+    }
+    let boxed = |inner: &Value| codama_type(inner).map(Box::new);
+
+    match str_of(ty, "kind") {
+        "numberTypeNode" => Some(match str_of(ty, "format") {
+            "u8" => FieldType::U8,
+            "u16" => FieldType::U16,
+            "u32" => FieldType::U32,
+            "u64" => FieldType::U64,
+            "u128" => FieldType::U128,
+            "i8" => FieldType::I8,
+            "i16" => FieldType::I16,
+            "i32" => FieldType::I32,
+            "i64" => FieldType::I64,
+            "i128" => FieldType::I128,
+            _ => return None,
+        }),
+        "publicKeyTypeNode" => Some(FieldType::Pubkey),
+        "booleanTypeNode" => Some(FieldType::Bool),
+        // Borsh strings and byte vectors carry a u32 length prefix
+        "sizePrefixTypeNode" if number_format(&ty["prefix"]) == Some("u32") => {
+            match str_of(&ty["type"], "kind") {
+                "stringTypeNode" => Some(FieldType::String),
+                "bytesTypeNode" => Some(FieldType::Vec(Box::new(FieldType::U8))),
+                _ => None,
+            }
+        }
+        "fixedSizeTypeNode" if str_of(&ty["type"], "kind") == "bytesTypeNode" => Some(
+            FieldType::Array(Box::new(FieldType::U8), ty["size"].as_u64()? as usize),
+        ),
+        "arrayTypeNode" => {
+            let count = &ty["count"];
+            match str_of(count, "kind") {
+                "prefixedCountNode" if number_format(&count["prefix"]) == Some("u32") => {
+                    Some(FieldType::Vec(boxed(&ty["item"])?))
+                }
+                "fixedCountNode" => Some(FieldType::Array(
+                    boxed(&ty["item"])?,
+                    count["value"].as_u64()? as usize,
+                )),
+                _ => None,
+            }
+        }
+        "optionTypeNode"
+            if !ty["fixed"].as_bool().unwrap_or(false)
+                && matches!(number_format(&ty["prefix"]), None | Some("u8")) =>
+        {
+            Some(FieldType::Option(boxed(&ty["item"])?))
+        }
+        "definedTypeLinkNode" => Some(FieldType::Defined(to_pascal_case(str_of(ty, "name")))),
+        _ => None,
+    }
+}
+
+/// Decode a Codama `bytesValueNode`
+fn codama_bytes(value: &Value) -> Option<Vec<u8>> {
+    if str_of(value, "kind") != "bytesValueNode" {
+        return None;
+    }
+    let data = str_of(value, "data");
+    match str_of(value, "encoding") {
+        "base16" => (0..data.len())
+            .step_by(2)
+            .map(|i| {
+                data.get(i..i + 2)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            })
+            .collect(),
+        "base58" => bs58::decode(data).into_vec().ok(),
+        "base64" => base64::engine::general_purpose::STANDARD.decode(data).ok(),
+        "utf8" => Some(data.as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::program_builder::{Account, Field, InstructionTemplate};
+    use crate::utils::golden::assert_golden;
+    use serde_json::json;
+
+    fn template_schema(template: InstructionTemplate) -> ProgramSchema {
+        ProgramSchema {
+            name: format!("{} Program", template.name),
+            version: "0.1.0".to_string(),
+            accounts: Vec::new(),
+            instructions: vec![template.to_instruction()],
+            types: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn export_json(schema: &ProgramSchema) -> String {
+        serde_json::to_string_pretty(&export_anchor_idl(schema)).unwrap() + "\n"
+    }
+
+    #[test]
+    fn test_golden_transfer_idl() {
+        assert_golden(
+            "transfer_idl.json",
+            &export_json(&template_schema(InstructionTemplate::transfer())),
+        );
+    }
+
+    #[test]
+    fn test_instruction_account_flags() {
+        let instruction: Instruction = serde_json::from_value(json!({
+            "name": "transfer",
+            "accounts": [
+                "from_account",
+                "authority",
+                { "name": "destination", "writable": true },
+            ],
+            "args": [],
+            "discriminator": null,
+        }))
+        .unwrap();
+        assert_eq!(
+            instruction.accounts,
+            [
+                InstructionAccount::writable("from_account"),
+                InstructionAccount::signer("authority"),
+                InstructionAccount::writable("destination"),
+            ]
+        );
+
+        let saved = serde_json::to_value(&instruction).unwrap();
+        assert_eq!(
+            saved["accounts"][0],
+            json!({ "name": "from_account", "writable": true, "signer": false })
+        );
+        let reloaded: Instruction = serde_json::from_value(saved).unwrap();
+        assert_eq!(reloaded, instruction);
+    }
+
+    #[test]
+    fn test_golden_swap_idl() {
+        assert_golden(
+            "swap_idl.json",
+            &export_json(&template_schema(InstructionTemplate::swap())),
+        );
+    }
+
+    #[test]
+    fn test_golden_store_data_idl() {
+        let mut schema = template_schema(InstructionTemplate::store_data());
+        schema.accounts.push(Account {
+            name: "data_account".to_string(),
+            fields: vec![
+                Field {
+                    name: "owner".to_string(),
+                    field_type: FieldType::Pubkey,
+                    max_len: vec![],
+                },
+                Field {
+                    name: "stored_data".to_string(),
+                    field_type: FieldType::String,
+                    max_len: vec![],
+                },
+            ],
+            discriminator: None,
+            seeds: vec![],
+        });
+        assert_golden("store_data_idl.json", &export_json(&schema));
+    }
+
+    #[test]
+    fn test_discriminators() {
+        let schema = template_schema(InstructionTemplate::add());
+        let idl = export_anchor_idl(&schema);
+        assert_eq!(idl.name, "add_liquidity_program");
+        assert_eq!(idl.instructions[0].name, "addLiquidity");
+
+        // Classic clients hash the snake_case name, as the generated programs do
+        assert!(!export_json(&schema).contains("discriminator"));
+        assert_eq!(
+            crate::utils::borsh::discriminator("global", &to_snake_case(&idl.instructions[0].name))
+                .to_vec(),
+            crate::utils::borsh::schema_instruction_discriminator(&schema.instructions[0])
+        );
+    }
+
+    #[test]
+    fn test_account_constraints() {
+        assert_eq!(account_constraints("payer"), (true, true));
+        assert_eq!(account_constraints("authority"), (false, true));
+        assert_eq!(account_constraints("signer_account"), (false, true));
+        assert_eq!(account_constraints("mint_a"), (false, false));
+        assert_eq!(account_constraints("expected_owner"), (false, false));
+        assert_eq!(account_constraints("pool_account"), (true, false));
+    }
+
+    #[test]
+    fn test_import_legacy_idl() {
+        let idl = json!({
+            "version": "0.2.0",
+            "name": "counter",
+            "instructions": [{
+                "name": "incrementBy",
+                "accounts": [
+                    {"name": "counter", "isMut": true, "isSigner": false},
+                    {"name": "authority", "isMut": false, "isSigner": true},
+                    {"name": "systemProgram", "isMut": false, "isSigner": false, "isOptional": true}
+                ],
+                "args": [
+                    {"name": "amount", "type": "u64"},
+                    {"name": "note", "type": {"option": "string"}},
+                    {"name": "ratio", "type": "f64"}
+                ]
+            }],
+            "accounts": [{
+                "name": "Counter",
+                "type": {"kind": "struct", "fields": [
+                    {"name": "authority", "type": "publicKey"},
+                    {"name": "count", "type": "u64"}
+                ]}
+            }],
+            "types": [{"name": "Mode", "type": {"kind": "enum", "variants": [
+                {"name": "Paused"},
+                {"name": "Locked", "fields": [{"name": "until", "type": "i64"}]},
+                {"name": "Delegated", "fields": ["publicKey"]}
+            ]}}],
+            "errors": [{"code": 6000, "name": "Overflow", "msg": "Counter overflowed"}]
+        });
+
+        let import = import_idl(&idl.to_string()).unwrap();
+        assert_eq!(import.format, IdlFormat::AnchorLegacy);
+        let schema = import.schema;
+        assert_eq!(schema.name, "counter");
+        assert_eq!(schema.version, "0.2.0");
+
+        let instruction = &schema.instructions[0];
+        assert_eq!(instruction.name, "increment_by");
+        assert_eq!(
+            instruction
+                .accounts
+                .iter()
+                .map(|account| account.name.as_str())
+                .collect::<Vec<_>>(),
+            ["counter", "authority", "system_program"]
+        );
+        assert_eq!(instruction.args.len(), 2);
+        assert_eq!(
+            instruction.args[1].arg_type,
+            FieldType::Option(Box::new(FieldType::String))
+        );
+        assert_eq!(instruction.discriminator, None);
+
+        assert_eq!(schema.accounts[0].name, "Counter");
+        assert_eq!(schema.accounts[0].fields[0].field_type, FieldType::Pubkey);
+        assert_eq!(
+            schema.errors,
+            vec![ErrorCode {
+                code: 6000,
+                name: "Overflow".to_string(),
+                msg: Some("Counter overflowed".to_string()),
+            }]
+        );
+
+        let TypeDefKind::Enum { variants } = &schema.types[0].kind else {
+            panic!("Mode should be an enum");
+        };
+        assert_eq!(variants.len(), 3);
+        assert!(variants[0].fields.is_empty());
+        assert_eq!(variants[1].fields[0].field_type, FieldType::I64);
+        assert_eq!(variants[2].fields[0].name, "_0");
+
+        let warnings = import.warnings.join("\n");
+        assert_eq!(import.warnings.len(), 3, "{}", warnings);
+        assert!(warnings.contains("`ratio` has unsupported type `f64`"));
+        assert!(warnings.contains("`system_program` is optional"));
+        assert!(warnings.contains("variant `Delegated` has tuple fields"));
+    }
+
+    #[test]
+    fn test_import_anchor_idl() {
+        let idl = json!({
+            "address": "Counter111111111111111111111111111111111111",
+            "metadata": {"name": "counter", "version": "0.1.0", "spec": "0.1.0"},
+            "instructions": [{
+                "name": "initialize",
+                "discriminator": [175, 175, 109, 31, 13, 152, 155, 237],
+                "accounts": [
+                    {"name": "counter", "writable": true, "signer": true},
+                    {"name": "payer", "writable": true, "signer": true, "relations": ["counter"]},
+                    {"name": "vault", "writable": true, "pda": {"seeds": [
+                        {"kind": "const", "value": [118, 97, 117, 108, 116]}
+                    ]}},
+                    {"name": "system_program", "address": "11111111111111111111111111111111"}
+                ],
+                "args": [{"name": "start", "type": "u32"}],
+                "returns": "u64"
+            }],
+            "accounts": [{"name": "Counter", "discriminator": [1, 2, 3, 4, 5, 6, 7, 8]}],
+            "types": [{
+                "name": "Counter",
+                "type": {"kind": "struct", "fields": [
+                    {"name": "count", "type": "u32"},
+                    {"name": "owner", "type": "pubkey"}
+                ]}
+            }],
+            "events": [{"name": "Initialized", "discriminator": [0, 0, 0, 0, 0, 0, 0, 0]}]
+        });
+
+        let import = import_idl(&idl.to_string()).unwrap();
+        assert_eq!(import.format, IdlFormat::Anchor);
+        let schema = &import.schema;
+        assert_eq!(schema.name, "counter");
+        assert_eq!(
+            schema.instructions[0].discriminator,
+            Some(vec![175, 175, 109, 31, 13, 152, 155, 237])
+        );
+        assert_eq!(
+            schema.accounts[0].discriminator,
+            Some(vec![1, 2, 3, 4, 5, 6, 7, 8])
+        );
+        assert_eq!(schema.accounts[0].fields[1].field_type, FieldType::Pubkey);
+
+        // Declared flags are kept even where the name suggests otherwise
+        assert_eq!(
+            schema.instructions[0].accounts,
+            [
+                InstructionAccount::new("counter", true, true),
+                InstructionAccount::new("payer", true, true),
+                InstructionAccount::writable("vault"),
+                InstructionAccount::readonly("system_program"),
+            ]
+        );
+
+        // Addresses, seeds, relations, return types and events aren't modelled
+        assert_eq!(
+            import.warnings,
+            [
+                "Program address `Counter111111111111111111111111111111111111` was skipped",
+                "IDL `events` section was skipped",
+                "Instruction `initialize` account `payer` relations were skipped",
+                "Instruction `initialize` account `vault` PDA seeds were skipped",
+                "Instruction `initialize` account `system_program` fixed address was skipped",
+                "Instruction `initialize` return type `u64` was skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_codama_idl() {
+        let discriminator = |hex: &str| {
+            json!({
+                "kind": "structFieldTypeNode",
+                "name": "discriminator",
+                "type": {"kind": "fixedSizeTypeNode", "size": 8, "type": {"kind": "bytesTypeNode"}},
+                "defaultValue": {"kind": "bytesValueNode", "data": hex, "encoding": "base16"}
+            })
+        };
+        let idl = json!({
+            "kind": "rootNode",
+            "program": {
+                "kind": "programNode",
+                "name": "counter",
+                "version": "1.0.0",
+                "accounts": [{
+                    "kind": "accountNode",
+                    "name": "counter",
+                    "data": {"kind": "structTypeNode", "fields": [
+                        discriminator("ffb004f5bcfd7c19"),
+                        {"kind": "structFieldTypeNode", "name": "count",
+                         "type": {"kind": "numberTypeNode", "format": "u64", "endian": "le"}},
+                        {"kind": "structFieldTypeNode", "name": "label",
+                         "type": {"kind": "sizePrefixTypeNode",
+                                  "type": {"kind": "stringTypeNode", "encoding": "utf8"},
+                                  "prefix": {"kind": "numberTypeNode", "format": "u32", "endian": "le"}}},
+                        {"kind": "structFieldTypeNode", "name": "delta",
+                         "type": {"kind": "numberTypeNode", "format": "f32", "endian": "le"}}
+                    ]},
+                    "discriminators": [{"kind": "fieldDiscriminatorNode", "name": "discriminator", "offset": 0}]
+                }],
+                "instructions": [{
+                    "kind": "instructionNode",
+                    "name": "increment",
+                    "accounts": [
+                        {"kind": "instructionAccountNode", "name": "counter", "isWritable": true, "isSigner": false},
+                        {"kind": "instructionAccountNode", "name": "payer", "isWritable": true, "isSigner": "either"}
+                    ],
+                    "remainingAccounts": [{"kind": "instructionRemainingAccountsNode"}],
+                    "arguments": [{"kind": "instructionArgumentNode", "name": "maxValue",
+                                   "type": {"kind": "booleanTypeNode"}}],
+                    "discriminators": [{"kind": "sizeDiscriminatorNode", "size": 1}]
+                }],
+                "errors": [{"kind": "errorNode", "name": "overflow", "code": 6000, "message": "Overflow"}],
+                "pdas": [{"kind": "pdaNode", "name": "counter", "seeds": []}]
+            }
+        });
+
+        let import = import_idl(&idl.to_string()).unwrap();
+        assert_eq!(import.format, IdlFormat::Codama);
+        let schema = &import.schema;
+        assert_eq!(schema.accounts[0].name, "Counter");
+        assert_eq!(
+            schema.accounts[0].discriminator.as_deref(),
+            Some(&account_discriminator("Counter")[..])
+        );
+        let fields: Vec<_> = schema.accounts[0]
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.field_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            [("count", FieldType::U64), ("label", FieldType::String)]
+        );
+        assert_eq!(
+            schema.instructions[0].accounts,
+            [
+                InstructionAccount::writable("counter"),
+                InstructionAccount::writable("payer"),
+            ]
+        );
+        assert_eq!(schema.instructions[0].args[0].name, "max_value");
+        assert_eq!(schema.errors[0].name, "Overflow");
+
+        assert_eq!(import.warnings.len(), 5, "{:?}", import.warnings);
+        assert!(import.warnings[0].contains("`delta` has unsupported type `numberTypeNode`"));
+        assert_eq!(import.warnings[1], "Program `pdas` were skipped");
+        assert_eq!(
+            import.warnings[2],
+            "Instruction `increment` account `payer` has isSigner `either`, imported as false"
+        );
+        assert_eq!(
+            import.warnings[3],
+            "Instruction `increment` remaining accounts were skipped"
+        );
+        assert!(import.warnings[4].contains("`sizeDiscriminatorNode`"));
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_documents() {
+        assert!(matches!(
+            import_idl("not json"),
+            Err(IdlError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            import_idl("{\"foo\": 1}"),
+            Err(IdlError::Unrecognized(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_error_codes() {
+        let anchor = json!({
+            "name": "counter",
+            "instructions": [],
+            "errors": [{"code": 4_294_967_296u64, "name": "Overflow"}]
+        });
+        let codama = json!({
+            "kind": "rootNode",
+            "program": {"name": "counter", "errors": [{"code": -1, "name": "overflow"}]}
+        });
+        for idl in [anchor, codama] {
+            match import_idl(&idl.to_string()) {
+                Err(IdlError::Invalid(message)) => {
+                    assert!(message.contains("`Overflow`"), "{}", message)
+                }
+                other => panic!("expected an invalid error code, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_export_composite_types() {
+        let nested = FieldType::Vec(Box::new(FieldType::Option(Box::new(FieldType::Array(
+            Box::new(FieldType::Defined("staker".to_string())),
+            4,
+        )))));
+        assert_eq!(
+            idl_type(&nested),
+            json!({"vec": {"option": {"array": [{"defined": "Staker"}, 4]}}})
+        );
+
+        let schema = ProgramSchema {
+            types: vec![TypeDef {
+                name: "mode".to_string(),
+                kind: TypeDefKind::Enum {
+                    variants: vec![
+                        EnumVariant {
+                            name: "Paused".to_string(),
+                            fields: Vec::new(),
+                        },
+                        EnumVariant {
+                            name: "Locked".to_string(),
+                            fields: vec![Field {
+                                name: "until_slot".to_string(),
+                                field_type: FieldType::I64,
+                                max_len: vec![],
+                            }],
+                        },
+                    ],
+                },
+            }],
+            ..Default::default()
+        };
+        let idl = serde_json::to_value(export_anchor_idl(&schema)).unwrap();
+        assert_eq!(
+            idl["types"],
+            json!([{
+                "name": "Mode",
+                "type": {"kind": "enum", "variants": [
+                    {"name": "Paused"},
+                    {"name": "Locked", "fields": [{"name": "untilSlot", "type": "i64"}]}
+                ]}
+            }])
+        );
+
+        // Exported types come back unchanged apart from name casing
+        let import = import_idl(&idl.to_string()).unwrap();
+        assert_eq!(import.schema.types[0].kind, schema.types[0].kind);
+        assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+    }
+}
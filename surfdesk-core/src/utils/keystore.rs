@@ -0,0 +1,444 @@
+//! Versioned on-disk keystore for Surfdesk accounts
+//!
+//! A keystore file stores account metadata in the clear next to the account
+//! secret keys. Secrets are either kept as plaintext (when key encryption is
+//! disabled) or encrypted as a single blob with XChaCha20-Poly1305 under a key
+//! derived from the user's password with PBKDF2-HMAC-SHA256.
+
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Current keystore file format version
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// File name of the keystore inside the account storage directory
+pub const KEYSTORE_FILE_NAME: &str = "keystore.json";
+
+/// Password key derivation algorithm identifier
+pub const KDF_ALGORITHM: &str = "pbkdf2-hmac-sha256";
+
+/// Secret encryption algorithm identifier
+pub const CIPHER_ALGORITHM: &str = "xchacha20poly1305";
+
+/// PBKDF2 iterations for newly created keystores (OWASP recommendation for SHA-256)
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+
+/// Associated data binding the ciphertext to this keystore format
+const KEYSTORE_AAD: &[u8] = b"surfdesk-keystore-v1";
+
+/// Errors produced while reading, writing or unlocking a keystore
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error("Keystore I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed keystore: {0}")]
+    Format(String),
+    #[error("Unsupported keystore version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Incorrect keystore password")]
+    IncorrectPassword,
+}
+
+/// Password key derivation parameters stored with encrypted secrets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Algorithm identifier, currently always `pbkdf2-hmac-sha256`
+    pub algorithm: String,
+    /// PBKDF2 iteration count
+    pub iterations: u32,
+    /// Base64-encoded random salt
+    pub salt: String,
+}
+
+/// Account secret keys as stored in the keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum KeystoreSecrets {
+    /// Secret keys stored unencrypted, keyed by address
+    Plaintext { keys: BTreeMap<String, String> },
+    /// Secret keys encrypted as one JSON blob
+    Encrypted {
+        kdf: KdfParams,
+        cipher: String,
+        /// Base64-encoded 24-byte nonce
+        nonce: String,
+        /// Base64-encoded ciphertext including the Poly1305 tag
+        ciphertext: String,
+    },
+}
+
+impl KeystoreSecrets {
+    /// Whether the secrets need a password to be read
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self, Self::Encrypted { .. })
+    }
+
+    /// Key derivation parameters of encrypted secrets
+    pub fn kdf(&self) -> Option<&KdfParams> {
+        match self {
+            Self::Plaintext { .. } => None,
+            Self::Encrypted { kdf, .. } => Some(kdf),
+        }
+    }
+}
+
+/// Keystore file contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreFile<T> {
+    /// File format version
+    pub version: u32,
+    /// Account metadata (labels, tags, primary flag, ...)
+    pub accounts: Vec<T>,
+    /// Account secret keys
+    pub secrets: KeystoreSecrets,
+}
+
+/// Encryption key derived from the keystore password
+///
+/// Kept in memory while the keystore is unlocked so secrets can be re-encrypted
+/// on every save without asking for the password again.
+#[derive(Clone)]
+pub struct KeystoreKey {
+    key: [u8; 32],
+    kdf: KdfParams,
+}
+
+impl KeystoreKey {
+    /// Derive a key for a new keystore with a fresh random salt
+    pub fn new(password: &str, iterations: u32) -> Self {
+        let mut salt = [0u8; 16];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+
+        let kdf = KdfParams {
+            algorithm: KDF_ALGORITHM.to_string(),
+            iterations,
+            salt: base64::engine::general_purpose::STANDARD.encode(salt),
+        };
+        Self {
+            key: pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(
+                password.as_bytes(),
+                &salt,
+                kdf.iterations,
+            ),
+            kdf,
+        }
+    }
+
+    /// Derive the key for existing secrets from their stored parameters
+    pub fn derive(password: &str, kdf: &KdfParams) -> Result<Self, KeystoreError> {
+        if kdf.algorithm != KDF_ALGORITHM {
+            return Err(KeystoreError::Format(format!(
+                "unsupported KDF {}",
+                kdf.algorithm
+            )));
+        }
+        if kdf.iterations == 0 {
+            return Err(KeystoreError::Format(
+                "KDF iterations must be positive".to_string(),
+            ));
+        }
+        let salt = decode_base64(&kdf.salt, "salt")?;
+
+        Ok(Self {
+            key: pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(
+                password.as_bytes(),
+                &salt,
+                kdf.iterations,
+            ),
+            kdf: kdf.clone(),
+        })
+    }
+
+    /// Check whether `password` derives this key
+    pub fn verify(&self, password: &str) -> bool {
+        Self::derive(password, &self.kdf).is_ok_and(|derived| derived.key == self.key)
+    }
+
+    /// Encrypt secret keys with a fresh random nonce
+    pub fn encrypt(
+        &self,
+        keys: &BTreeMap<String, String>,
+    ) -> Result<KeystoreSecrets, KeystoreError> {
+        let plaintext =
+            serde_json::to_vec(keys).map_err(|e| KeystoreError::Format(e.to_string()))?;
+
+        let mut nonce = [0u8; 24];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: KEYSTORE_AAD,
+                },
+            )
+            .map_err(|_| KeystoreError::Format("encryption failed".to_string()))?;
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        Ok(KeystoreSecrets::Encrypted {
+            kdf: self.kdf.clone(),
+            cipher: CIPHER_ALGORITHM.to_string(),
+            nonce: engine.encode(nonce),
+            ciphertext: engine.encode(ciphertext),
+        })
+    }
+
+    /// Decrypt secret keys, failing if the password or data is wrong
+    pub fn decrypt(
+        &self,
+        secrets: &KeystoreSecrets,
+    ) -> Result<BTreeMap<String, String>, KeystoreError> {
+        let (kdf, cipher, nonce, ciphertext) = match secrets {
+            KeystoreSecrets::Plaintext { keys } => return Ok(keys.clone()),
+            KeystoreSecrets::Encrypted {
+                kdf,
+                cipher,
+                nonce,
+                ciphertext,
+            } => (kdf, cipher, nonce, ciphertext),
+        };
+
+        if cipher != CIPHER_ALGORITHM {
+            return Err(KeystoreError::Format(format!(
+                "unsupported cipher {}",
+                cipher
+            )));
+        }
+        if *kdf != self.kdf {
+            return Err(KeystoreError::IncorrectPassword);
+        }
+
+        let nonce: [u8; 24] = decode_base64(nonce, "nonce")?
+            .try_into()
+            .map_err(|_| KeystoreError::Format("nonce must be 24 bytes".to_string()))?;
+        let ciphertext = decode_base64(ciphertext, "ciphertext")?;
+
+        // Poly1305 authentication fails for both a wrong key and tampered data
+        let plaintext = self
+            .cipher()
+            .decrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: KEYSTORE_AAD,
+                },
+            )
+            .map_err(|_| KeystoreError::IncorrectPassword)?;
+
+        serde_json::from_slice(&plaintext).map_err(|e| KeystoreError::Format(e.to_string()))
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.key.into())
+    }
+}
+
+impl std::fmt::Debug for KeystoreKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the derived key
+        f.debug_struct("KeystoreKey")
+            .field("kdf", &self.kdf)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Read a keystore file, returning `None` if it does not exist yet
+pub fn read_keystore<T: DeserializeOwned>(
+    path: &Path,
+) -> Result<Option<KeystoreFile<T>>, KeystoreError> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    // Check the version before parsing the rest so future formats fail clearly
+    let header: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| KeystoreError::Format(e.to_string()))?;
+    let version = header
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| KeystoreError::Format("missing version".to_string()))?;
+    if version != KEYSTORE_VERSION as u64 {
+        return Err(KeystoreError::UnsupportedVersion(version as u32));
+    }
+
+    serde_json::from_value(header)
+        .map(Some)
+        .map_err(|e| KeystoreError::Format(e.to_string()))
+}
+
+/// Write a keystore file atomically with owner-only permissions
+pub fn write_keystore<T: Serialize>(
+    path: &Path,
+    file: &KeystoreFile<T>,
+) -> Result<(), KeystoreError> {
+    let contents =
+        serde_json::to_string_pretty(file).map_err(|e| KeystoreError::Format(e.to_string()))?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Write next to the target and rename so a crash never leaves a truncated keystore
+    let temp_path = path.with_extension("json.tmp");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut temp = options.open(&temp_path)?;
+    // `mode` only applies when the file is created, so tighten a leftover temp file too
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        temp.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::io::Write::write_all(&mut temp, contents.as_bytes())?;
+    temp.sync_all()?;
+    std::fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+fn decode_base64(value: &str, field: &str) -> Result<Vec<u8>, KeystoreError> {
+    base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .map_err(|e| KeystoreError::Format(format!("invalid {}: {}", field, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_keys() -> BTreeMap<String, String> {
+        BTreeMap::from([("address".to_string(), "secret".to_string())])
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let key = KeystoreKey::new("correct horse", 1_000);
+        let secrets = key.encrypt(&sample_keys()).unwrap();
+        assert!(secrets.is_encrypted());
+
+        // A key derived again from the stored parameters decrypts the secrets
+        let KeystoreSecrets::Encrypted { kdf, .. } = &secrets else {
+            panic!("expected encrypted secrets");
+        };
+        let derived = KeystoreKey::derive("correct horse", kdf).unwrap();
+        assert_eq!(derived.decrypt(&secrets).unwrap(), sample_keys());
+        assert!(derived.verify("correct horse"));
+        assert!(!derived.verify("battery staple"));
+
+        // Every save uses a fresh nonce
+        assert_ne!(key.encrypt(&sample_keys()).unwrap(), secrets);
+    }
+
+    #[test]
+    fn test_wrong_password_and_tampering() {
+        let key = KeystoreKey::new("correct horse", 1_000);
+        let secrets = key.encrypt(&sample_keys()).unwrap();
+        let KeystoreSecrets::Encrypted {
+            kdf,
+            cipher,
+            nonce,
+            ciphertext,
+        } = secrets
+        else {
+            panic!("expected encrypted secrets");
+        };
+
+        let wrong = KeystoreKey::derive("battery staple", &kdf).unwrap();
+        let encrypted = KeystoreSecrets::Encrypted {
+            kdf: kdf.clone(),
+            cipher: cipher.clone(),
+            nonce: nonce.clone(),
+            ciphertext: ciphertext.clone(),
+        };
+        assert!(matches!(
+            wrong.decrypt(&encrypted),
+            Err(KeystoreError::IncorrectPassword)
+        ));
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let mut bytes = engine.decode(&ciphertext).unwrap();
+        bytes[0] ^= 1;
+        let tampered = KeystoreSecrets::Encrypted {
+            kdf,
+            cipher,
+            nonce,
+            ciphertext: engine.encode(bytes),
+        };
+        assert!(matches!(
+            key.decrypt(&tampered),
+            Err(KeystoreError::IncorrectPassword)
+        ));
+    }
+
+    #[test]
+    fn test_write_and_read_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(KEYSTORE_FILE_NAME);
+        assert!(read_keystore::<String>(&path).unwrap().is_none());
+
+        let file = KeystoreFile {
+            version: KEYSTORE_VERSION,
+            accounts: vec!["metadata".to_string()],
+            secrets: KeystoreSecrets::Plaintext {
+                keys: sample_keys(),
+            },
+        };
+        write_keystore(&path, &file).unwrap();
+
+        let read = read_keystore::<String>(&path).unwrap().unwrap();
+        assert_eq!(read.accounts, file.accounts);
+        assert_eq!(read.secrets, file.secrets);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+
+            // A stale temp file from an interrupted save is tightened before reuse
+            let temp_path = path.with_extension("json.tmp");
+            std::fs::write(&temp_path, "").unwrap();
+            std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            write_keystore(&path, &file).unwrap();
+            assert_eq!(
+                std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_rejects_unknown_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(KEYSTORE_FILE_NAME);
+
+        std::fs::write(&path, r#"{"version": 99, "accounts": [], "secrets": {}}"#).unwrap();
+        assert!(matches!(
+            read_keystore::<String>(&path),
+            Err(KeystoreError::UnsupportedVersion(99))
+        ));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            read_keystore::<String>(&path),
+            Err(KeystoreError::Format(_))
+        ));
+    }
+}
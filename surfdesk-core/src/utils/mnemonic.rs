@@ -0,0 +1,456 @@
+//! BIP39 mnemonics and SLIP-0010 key derivation for Surfdesk
+//!
+//! This module turns seed phrases into Solana keypairs the same way Phantom
+//! and solana-keygen do: BIP39 mnemonic → PBKDF2-HMAC-SHA512 seed → SLIP-0010
+//! ed25519 hardened derivation along a path such as `m/44'/501'/0'/0'`.
+
+use crate::utils::crypto::Keypair;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Official BIP39 English wordlist, one word per line
+const ENGLISH_WORDLIST: &str = include_str!("bip39_english.txt");
+
+/// PBKDF2 iteration count fixed by BIP39
+const SEED_ITERATIONS: u32 = 2048;
+
+/// HMAC key for the SLIP-0010 ed25519 master key
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// Offset marking a hardened derivation index
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Errors produced while handling mnemonics and derivation paths
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MnemonicError {
+    #[error("Seed phrase must have 12, 15, 18, 21 or 24 words, got {0}")]
+    InvalidWordCount(usize),
+    #[error("Unknown seed phrase word: {0}")]
+    UnknownWord(String),
+    #[error("Seed phrase checksum does not match")]
+    InvalidChecksum,
+    #[error("Entropy must be 16, 20, 24, 28 or 32 bytes, got {0}")]
+    InvalidEntropyLength(usize),
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+}
+
+/// BIP44-style derivation path where every level is hardened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath {
+    /// Level indexes without the hardened offset
+    indexes: Vec<u32>,
+}
+
+impl DerivationPath {
+    /// Path used by Phantom and solana-keygen for the first account
+    pub fn solana_default() -> Self {
+        Self {
+            indexes: vec![44, 501, 0, 0],
+        }
+    }
+
+    /// Level indexes without the hardened offset
+    pub fn indexes(&self) -> &[u32] {
+        &self.indexes
+    }
+
+    /// Path for the `index`-th account after this one
+    ///
+    /// The BIP44 account level (the third one) is advanced by `index`, so
+    /// `m/44'/501'/0'/0'` yields `m/44'/501'/1'/0'`, `m/44'/501'/2'/0'`, ...
+    /// which is how wallets enumerate accounts of a single phrase. Paths with
+    /// fewer than three levels get the index appended as a new level.
+    pub fn for_index(&self, index: u32) -> Result<Self, MnemonicError> {
+        let mut indexes = self.indexes.clone();
+        match indexes.get_mut(2) {
+            Some(account) => {
+                *account = account
+                    .checked_add(index)
+                    .filter(|account| *account < HARDENED_OFFSET)
+                    .ok_or_else(|| {
+                        MnemonicError::InvalidDerivationPath(format!(
+                            "account index overflow at {}",
+                            index
+                        ))
+                    })?;
+            }
+            None => indexes.push(index),
+        }
+        Ok(Self { indexes })
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = MnemonicError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            |reason: &str| MnemonicError::InvalidDerivationPath(format!("{} ({})", path, reason));
+
+        let mut levels = path.trim().split('/');
+        if levels.next() != Some("m") {
+            return Err(invalid("must start with m"));
+        }
+
+        let mut indexes = Vec::new();
+        for level in levels {
+            // SLIP-0010 only defines hardened derivation for ed25519
+            let digits = level
+                .strip_suffix('\'')
+                .or_else(|| level.strip_suffix('h'))
+                .or_else(|| level.strip_suffix('H'))
+                .ok_or_else(|| invalid("ed25519 levels must be hardened"))?;
+            let index = digits
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED_OFFSET)
+                .ok_or_else(|| invalid("level is not a valid index"))?;
+            indexes.push(index);
+        }
+
+        Ok(Self { indexes })
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.indexes {
+            write!(f, "/{}'", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// BIP39 English wordlist
+pub fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| ENGLISH_WORDLIST.lines().collect())
+}
+
+/// Generate a new random mnemonic with `word_count` words
+pub fn generate_mnemonic(word_count: usize) -> Result<String, MnemonicError> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err(MnemonicError::InvalidWordCount(word_count));
+    }
+
+    // Every 3 words carry 32 bits of entropy
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    rand::rngs::OsRng.fill_bytes(&mut entropy);
+    mnemonic_from_entropy(&entropy)
+}
+
+/// Encode entropy as a mnemonic, appending the SHA-256 checksum bits
+pub fn mnemonic_from_entropy(entropy: &[u8]) -> Result<String, MnemonicError> {
+    if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+        return Err(MnemonicError::InvalidEntropyLength(entropy.len()));
+    }
+
+    let checksum = Sha256::digest(entropy);
+    let mut bytes = entropy.to_vec();
+    bytes.extend_from_slice(&checksum);
+
+    let total_bits = entropy.len() * 8 + entropy.len() / 4;
+    let words = wordlist();
+    let phrase: Vec<&str> = (0..total_bits / 11)
+        .map(|word| {
+            let index = (0..11).fold(0usize, |acc, bit| {
+                (acc << 1) | read_bit(&bytes, word * 11 + bit)
+            });
+            words[index]
+        })
+        .collect();
+
+    Ok(phrase.join(" "))
+}
+
+/// Decode a mnemonic back into its entropy, verifying words and checksum
+pub fn mnemonic_to_entropy(phrase: &str) -> Result<Vec<u8>, MnemonicError> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+        return Err(MnemonicError::InvalidWordCount(words.len()));
+    }
+
+    let list = wordlist();
+    let mut bits = Vec::with_capacity(words.len() * 11);
+    for word in &words {
+        let lowercase = word.to_lowercase();
+        let index = list
+            .binary_search(&lowercase.as_str())
+            .map_err(|_| MnemonicError::UnknownWord(word.to_string()))?;
+        bits.extend((0..11).rev().map(|bit| (index >> bit) & 1 == 1));
+    }
+
+    let checksum_bits = words.len() * 11 / 33;
+    let entropy_bits = bits.len() - checksum_bits;
+    let entropy: Vec<u8> = bits[..entropy_bits]
+        .chunks(8)
+        .map(|chunk| chunk.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8))
+        .collect();
+
+    let checksum = Sha256::digest(&entropy);
+    let expected = (0..checksum_bits).map(|bit| read_bit(&checksum, bit) == 1);
+    if !expected.eq(bits[entropy_bits..].iter().copied()) {
+        return Err(MnemonicError::InvalidChecksum);
+    }
+
+    Ok(entropy)
+}
+
+/// Check that a mnemonic uses known words and has a valid checksum
+pub fn validate_mnemonic(phrase: &str) -> Result<(), MnemonicError> {
+    mnemonic_to_entropy(phrase).map(|_| ())
+}
+
+/// Derive the 64-byte BIP39 seed from a mnemonic and optional passphrase
+///
+/// Words are re-joined with single spaces. Passphrases are used as given, so
+/// non-ASCII passphrases must already be NFKD-normalized.
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> [u8; 64] {
+    let normalized = phrase
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let salt = format!("mnemonic{}", passphrase);
+
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(
+        normalized.as_bytes(),
+        salt.as_bytes(),
+        SEED_ITERATIONS,
+        &mut seed,
+    );
+    seed
+}
+
+/// SLIP-0010 ed25519 derivation, returning the private key and chain code
+pub fn derive_ed25519_private_key(seed: &[u8], path: &DerivationPath) -> ([u8; 32], [u8; 32]) {
+    let (mut key, mut chain_code) = hmac_sha512_split(ED25519_CURVE_SEED, &[seed]);
+
+    for index in path.indexes() {
+        let hardened = (index | HARDENED_OFFSET).to_be_bytes();
+        (key, chain_code) = hmac_sha512_split(&chain_code, &[&[0u8], &key, &hardened]);
+    }
+
+    (key, chain_code)
+}
+
+/// Derive the keypair at `path` from a BIP39 seed
+pub fn derive_keypair(seed: &[u8], path: &DerivationPath) -> Keypair {
+    let (private_key, _) = derive_ed25519_private_key(seed, path);
+    Keypair::from_seed(&private_key)
+}
+
+/// Validate a mnemonic and derive the keypair at `path`
+pub fn keypair_from_mnemonic(
+    phrase: &str,
+    passphrase: &str,
+    path: &DerivationPath,
+) -> Result<Keypair, MnemonicError> {
+    validate_mnemonic(phrase)?;
+    Ok(derive_keypair(&mnemonic_to_seed(phrase, passphrase), path))
+}
+
+/// Validate a mnemonic and derive `count` consecutive accounts starting at `base_path`
+pub fn keypairs_from_mnemonic(
+    phrase: &str,
+    passphrase: &str,
+    base_path: &DerivationPath,
+    count: u32,
+) -> Result<Vec<Keypair>, MnemonicError> {
+    validate_mnemonic(phrase)?;
+    let seed = mnemonic_to_seed(phrase, passphrase);
+
+    (0..count)
+        .map(|index| Ok(derive_keypair(&seed, &base_path.for_index(index)?)))
+        .collect()
+}
+
+/// Read bit `index` of `bytes`, most significant bit first
+fn read_bit(bytes: &[u8], index: usize) -> usize {
+    ((bytes[index / 8] >> (7 - index % 8)) & 1) as usize
+}
+
+/// HMAC-SHA512 over the concatenated `parts`, split into two 32-byte halves
+fn hmac_sha512_split(key: &[u8], parts: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::crypto::{bytes_to_hex, hex_to_bytes};
+
+    const ABANDON_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_wordlist() {
+        let words = wordlist();
+        assert_eq!(words.len(), 2048);
+        assert_eq!(words[0], "abandon");
+        assert_eq!(words[2047], "zoo");
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_mnemonic_from_entropy_vectors() {
+        // Reference vectors from the BIP39 test suite
+        let cases = [
+            ("00000000000000000000000000000000", ABANDON_PHRASE),
+            (
+                "9e885d952ad362caeb4efe34a8e91bd2",
+                "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title",
+            ),
+        ];
+
+        for (entropy_hex, phrase) in cases {
+            let entropy = hex_to_bytes(entropy_hex).unwrap();
+            assert_eq!(mnemonic_from_entropy(&entropy).unwrap(), phrase);
+            assert_eq!(mnemonic_to_entropy(phrase).unwrap(), entropy);
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_to_seed() {
+        assert_eq!(
+            bytes_to_hex(&mnemonic_to_seed(ABANDON_PHRASE, "TREZOR")),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+        // Extra whitespace and capitalization do not change the seed
+        let messy = format!("  {}  ", ABANDON_PHRASE.replace(' ', "   ").to_uppercase());
+        assert_eq!(
+            mnemonic_to_seed(&messy, ""),
+            mnemonic_to_seed(ABANDON_PHRASE, "")
+        );
+    }
+
+    #[test]
+    fn test_invalid_mnemonics() {
+        let bad_checksum = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert_eq!(
+            validate_mnemonic(bad_checksum),
+            Err(MnemonicError::InvalidChecksum)
+        );
+
+        let unknown = ABANDON_PHRASE.replace("about", "solana");
+        assert_eq!(
+            validate_mnemonic(&unknown),
+            Err(MnemonicError::UnknownWord("solana".to_string()))
+        );
+
+        assert_eq!(
+            validate_mnemonic("abandon about"),
+            Err(MnemonicError::InvalidWordCount(2))
+        );
+        assert!(mnemonic_from_entropy(&[0u8; 15]).is_err());
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        for word_count in [12, 24] {
+            let phrase = generate_mnemonic(word_count).unwrap();
+            assert_eq!(phrase.split(' ').count(), word_count);
+            assert!(validate_mnemonic(&phrase).is_ok());
+        }
+        assert_ne!(
+            generate_mnemonic(12).unwrap(),
+            generate_mnemonic(12).unwrap()
+        );
+        assert_eq!(
+            generate_mnemonic(13),
+            Err(MnemonicError::InvalidWordCount(13))
+        );
+    }
+
+    #[test]
+    fn test_slip10_vector() {
+        // SLIP-0010 ed25519 test vector 1
+        let seed = hex_to_bytes("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        let (key, chain_code) = derive_ed25519_private_key(&seed, &"m".parse().unwrap());
+        assert_eq!(
+            bytes_to_hex(&key),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            bytes_to_hex(&chain_code),
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+        );
+
+        let (key, chain_code) = derive_ed25519_private_key(&seed, &"m/0'".parse().unwrap());
+        assert_eq!(
+            bytes_to_hex(&key),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert_eq!(
+            bytes_to_hex(&chain_code),
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"
+        );
+
+        let (key, _) = derive_ed25519_private_key(&seed, &"m/0'/1'".parse().unwrap());
+        assert_eq!(
+            bytes_to_hex(&key),
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"
+        );
+    }
+
+    #[test]
+    fn test_solana_wallet_addresses() {
+        // Addresses Phantom and solana-keygen show for the "abandon ... about" phrase
+        let keypairs =
+            keypairs_from_mnemonic(ABANDON_PHRASE, "", &DerivationPath::solana_default(), 3)
+                .unwrap();
+        let addresses: Vec<String> = keypairs.iter().map(Keypair::address).collect();
+        assert_eq!(
+            addresses,
+            vec![
+                "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk",
+                "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb",
+                "7WktogJEd2wQ9eH2oWusmcoFTgeYi6rS632UviTBJ2jm",
+            ]
+        );
+
+        let bip44_root =
+            keypair_from_mnemonic(ABANDON_PHRASE, "", &"m/44'/501'".parse().unwrap()).unwrap();
+        assert_eq!(
+            bip44_root.address(),
+            "D2PPQSYFe83nDzk96FqGumVU8JA7J8vj2Rhjc2oXzEi5"
+        );
+    }
+
+    #[test]
+    fn test_derivation_path() {
+        let path: DerivationPath = "m/44'/501'/0'/0'".parse().unwrap();
+        assert_eq!(path, DerivationPath::solana_default());
+        assert_eq!(path.to_string(), "m/44'/501'/0'/0'");
+        assert_eq!(path.for_index(2).unwrap().to_string(), "m/44'/501'/2'/0'");
+
+        let short: DerivationPath = "m/44h/501H".parse().unwrap();
+        assert_eq!(short.for_index(3).unwrap().to_string(), "m/44'/501'/3'");
+
+        assert!("44'/501'".parse::<DerivationPath>().is_err());
+        assert!("m/44'/501'/0".parse::<DerivationPath>().is_err());
+        assert!("m/44'/x'".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648'".parse::<DerivationPath>().is_err());
+    }
+}
@@ -4,11 +4,31 @@
 //! the application, including formatting helpers, validation functions,
 //! cryptographic utilities, and other common operations.
 
+pub mod account_size;
+pub mod borsh;
+pub mod client_codegen;
+pub mod codegen;
 pub mod crypto;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod format;
+#[cfg(test)]
+pub(crate) mod golden;
+pub mod idl;
+pub mod keystore;
+pub mod mnemonic;
+pub mod schema_validation;
 pub mod validation;
 
 // Re-export commonly used utilities
+pub use account_size::*;
+pub use borsh::*;
+pub use client_codegen::*;
+pub use codegen::*;
 pub use crypto::*;
 pub use format::*;
+pub use idl::*;
+pub use keystore::*;
+pub use mnemonic::*;
+pub use schema_validation::*;
 pub use validation::*;
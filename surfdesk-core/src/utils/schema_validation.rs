@@ -0,0 +1,659 @@
+//! Program schema validation
+//!
+//! Checks a program builder `ProgramSchema` for problems that would break
+//! IDL export, Borsh layouts or generated code: empty or duplicate names,
+//! names that can't become Rust identifiers, instruction accounts the schema
+//! doesn't define, references to undeclared types and PDA seeds that can't
+//! be derived. Each problem is reported as a diagnostic carrying a JSON path
+//! into the serialized schema.
+
+use crate::components::program_builder::{Field, FieldType, ProgramSchema, Seed, TypeDefKind};
+use crate::utils::crypto::{MAX_SEEDS, MAX_SEED_LEN};
+use crate::utils::format::{to_pascal_case, to_snake_case};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// How serious a diagnostic is
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The schema can't be exported or generated as is
+    Error,
+    /// Suspicious, but the schema is still usable
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single validation finding
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SchemaDiagnostic {
+    pub severity: Severity,
+    /// JSON path of the offending value, e.g. `$.accounts[0].fields[1].name`
+    pub path: String,
+    pub message: String,
+}
+
+impl SchemaDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Whether the diagnostic points at `path` or something nested inside it
+    pub fn is_under(&self, path: &str) -> bool {
+        match self.path.strip_prefix(path) {
+            Some(rest) => rest.is_empty() || rest.starts_with('.') || rest.starts_with('['),
+            None => false,
+        }
+    }
+}
+
+const RUST_KEYWORDS: [&str; 51] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+/// Whether `name` can be used verbatim as a Rust identifier
+pub fn is_rust_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_ok = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    starts_ok
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !RUST_KEYWORDS.contains(&name)
+}
+
+/// Validate a schema, returning errors before warnings
+pub fn validate_schema(schema: &ProgramSchema) -> Vec<SchemaDiagnostic> {
+    let mut validator = Validator::default();
+    validator.schema(schema);
+    let mut diagnostics = validator.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity);
+    diagnostics
+}
+
+/// How generated code spells a name
+#[derive(Clone, Copy)]
+enum Case {
+    Snake,
+    Pascal,
+}
+
+impl Case {
+    fn apply(self, name: &str) -> String {
+        match self {
+            Case::Snake => to_snake_case(name),
+            Case::Pascal => to_pascal_case(name),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    diagnostics: Vec<SchemaDiagnostic>,
+}
+
+impl Validator {
+    fn push(&mut self, severity: Severity, path: String, message: String) {
+        self.diagnostics.push(SchemaDiagnostic {
+            severity,
+            path,
+            message,
+        });
+    }
+
+    fn schema(&mut self, schema: &ProgramSchema) {
+        self.name("$.name", "Program", &schema.name, Case::Snake);
+
+        let type_names: HashSet<String> = schema.types.iter().map(|def| def.name.clone()).collect();
+
+        // Accounts and types share one namespace in generated code
+        let mut seen = HashMap::new();
+        for (i, account) in schema.accounts.iter().enumerate() {
+            let path = format!("$.accounts[{}]", i);
+            self.declaration(&path, "Account", &account.name, Case::Pascal, &mut seen);
+            self.fields(&format!("{}.fields", path), &account.fields, &type_names);
+            self.seeds(&format!("{}.seeds", path), &account.seeds);
+        }
+
+        for (i, def) in schema.types.iter().enumerate() {
+            let path = format!("$.types[{}]", i);
+            self.declaration(&path, "Type", &def.name, Case::Pascal, &mut seen);
+            match &def.kind {
+                TypeDefKind::Struct { fields } => {
+                    self.fields(&format!("{}.kind.Struct.fields", path), fields, &type_names);
+                }
+                TypeDefKind::Enum { variants } => {
+                    if variants.is_empty() {
+                        self.push(
+                            Severity::Warning,
+                            format!("{}.kind.Enum.variants", path),
+                            format!("Enum `{}` has no variants", def.name),
+                        );
+                    }
+                    let mut seen = HashMap::new();
+                    for (j, variant) in variants.iter().enumerate() {
+                        let path = format!("{}.kind.Enum.variants[{}]", path, j);
+                        self.declaration(&path, "Variant", &variant.name, Case::Pascal, &mut seen);
+                        self.fields(&format!("{}.fields", path), &variant.fields, &type_names);
+                    }
+                }
+            }
+        }
+
+        let account_names: HashSet<String> = schema
+            .accounts
+            .iter()
+            .map(|account| to_snake_case(&account.name))
+            .collect();
+        let mut seen = HashMap::new();
+        for (i, instruction) in schema.instructions.iter().enumerate() {
+            let path = format!("$.instructions[{}]", i);
+            self.declaration(
+                &path,
+                "Instruction",
+                &instruction.name,
+                Case::Snake,
+                &mut seen,
+            );
+
+            let mut seen_accounts = HashMap::new();
+            for (j, account) in instruction.accounts.iter().enumerate() {
+                let reference = &account.name;
+                let path = format!("{}.accounts[{}]", path, j);
+                if !self.unique(
+                    &path,
+                    "Instruction account",
+                    reference,
+                    Case::Snake,
+                    &mut seen_accounts,
+                ) {
+                    continue;
+                }
+                if !account_names.contains(&to_snake_case(reference)) {
+                    self.push(
+                        Severity::Warning,
+                        path,
+                        format!(
+                            "Account `{}` is not defined in the schema's accounts",
+                            reference
+                        ),
+                    );
+                }
+            }
+
+            let mut seen_args = HashMap::new();
+            for (j, arg) in instruction.args.iter().enumerate() {
+                let path = format!("{}.args[{}]", path, j);
+                self.declaration(&path, "Argument", &arg.name, Case::Snake, &mut seen_args);
+                self.field_type(&format!("{}.arg_type", path), &arg.arg_type, &type_names);
+            }
+        }
+
+        let mut seen = HashMap::new();
+        let mut codes = HashMap::new();
+        for (i, error) in schema.errors.iter().enumerate() {
+            let path = format!("$.errors[{}]", i);
+            self.declaration(&path, "Error", &error.name, Case::Pascal, &mut seen);
+            if let Some(first) = codes.insert(error.code, i) {
+                self.push(
+                    Severity::Error,
+                    format!("{}.code", path),
+                    format!(
+                        "Error code {} is already used by $.errors[{}]",
+                        error.code, first
+                    ),
+                );
+            }
+        }
+    }
+
+    fn fields(&mut self, path: &str, fields: &[Field], type_names: &HashSet<String>) {
+        let mut seen = HashMap::new();
+        for (i, field) in fields.iter().enumerate() {
+            let path = format!("{}[{}]", path, i);
+            self.declaration(&path, "Field", &field.name, Case::Snake, &mut seen);
+            self.field_type(
+                &format!("{}.field_type", path),
+                &field.field_type,
+                type_names,
+            );
+        }
+    }
+
+    fn field_type(&mut self, path: &str, field_type: &FieldType, type_names: &HashSet<String>) {
+        match field_type {
+            FieldType::Vec(item) | FieldType::Option(item) => {
+                self.field_type(path, item, type_names)
+            }
+            FieldType::Array(item, len) => {
+                if *len == 0 {
+                    self.push(
+                        Severity::Warning,
+                        path.to_string(),
+                        "Array has length 0".to_string(),
+                    );
+                }
+                self.field_type(path, item, type_names);
+            }
+            FieldType::Defined(name) if !type_names.contains(name) => self.push(
+                Severity::Error,
+                path.to_string(),
+                format!("Type `{}` is not defined in the schema's types", name),
+            ),
+            _ => {}
+        }
+    }
+
+    fn seeds(&mut self, path: &str, seeds: &[Seed]) {
+        // One seed slot is reserved for the bump
+        if seeds.len() >= MAX_SEEDS {
+            self.push(
+                Severity::Error,
+                path.to_string(),
+                format!(
+                    "PDA has {} seeds, the limit is {} plus the bump",
+                    seeds.len(),
+                    MAX_SEEDS - 1
+                ),
+            );
+        }
+        let mut seen = HashMap::new();
+        for (i, seed) in seeds.iter().enumerate() {
+            let path = format!("{}[{}]", path, i);
+            match seed {
+                Seed::Const(value) if value.len() > MAX_SEED_LEN => self.push(
+                    Severity::Error,
+                    format!("{}.Const", path),
+                    format!(
+                        "Seed `{}` is {} bytes, the limit is {}",
+                        value,
+                        value.len(),
+                        MAX_SEED_LEN
+                    ),
+                ),
+                Seed::Const(value) if value.is_empty() => self.push(
+                    Severity::Warning,
+                    format!("{}.Const", path),
+                    "Constant seed is empty".to_string(),
+                ),
+                Seed::Const(_) => {}
+                Seed::Arg { name, arg_type } => {
+                    self.unique(
+                        &format!("{}.Arg.name", path),
+                        "Seed",
+                        name,
+                        Case::Snake,
+                        &mut seen,
+                    );
+                    if !arg_type.is_seed_type() {
+                        self.push(
+                            Severity::Error,
+                            format!("{}.Arg.arg_type", path),
+                            format!(
+                                "Seed type `{}` can't be used as a seed, use Pubkey, String or an integer",
+                                arg_type
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check a declared name and that no earlier sibling generates the same identifier
+    fn declaration(
+        &mut self,
+        path: &str,
+        kind: &str,
+        name: &str,
+        case: Case,
+        seen: &mut HashMap<String, String>,
+    ) {
+        self.unique(&format!("{}.name", path), kind, name, case, seen);
+    }
+
+    /// Returns false when the name is empty or a duplicate
+    fn unique(
+        &mut self,
+        path: &str,
+        kind: &str,
+        name: &str,
+        case: Case,
+        seen: &mut HashMap<String, String>,
+    ) -> bool {
+        if !self.name(path, kind, name, case) {
+            return false;
+        }
+        let ident = case.apply(name);
+        if let Some(first) = seen.get(&ident) {
+            self.push(
+                Severity::Error,
+                path.to_string(),
+                format!("{} `{}` duplicates {}", kind, name, first),
+            );
+            return false;
+        }
+        seen.insert(ident, path.to_string());
+        true
+    }
+
+    /// Returns false when the name is empty
+    fn name(&mut self, path: &str, kind: &str, name: &str, case: Case) -> bool {
+        if name.trim().is_empty() {
+            self.push(
+                Severity::Error,
+                path.to_string(),
+                format!("{} name cannot be empty", kind),
+            );
+            return false;
+        }
+        let ident = case.apply(name);
+        if !is_rust_identifier(&ident) {
+            self.push(
+                Severity::Error,
+                path.to_string(),
+                format!("{} name `{}` is not a valid Rust identifier", kind, name),
+            );
+        } else if name
+            .chars()
+            .any(|c| !(c.is_ascii_alphanumeric() || c == '_' || c == ' '))
+        {
+            self.push(
+                Severity::Warning,
+                path.to_string(),
+                format!(
+                    "{} name `{}` contains characters that generated code drops; it becomes `{}`",
+                    kind, name, ident
+                ),
+            );
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::program_builder::{
+        Account, EnumVariant, ErrorCode, Instruction, InstructionAccount, TypeDef,
+    };
+    use crate::utils::fixtures::{counter_schema, field};
+
+    /// The shared counter schema, referring only to accounts it defines
+    fn valid_schema() -> ProgramSchema {
+        let mut schema = counter_schema();
+        for instruction in &mut schema.instructions {
+            instruction
+                .accounts
+                .retain(|account| account.name == "counter");
+        }
+        schema
+    }
+
+    fn paths(diagnostics: &[SchemaDiagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_schema_has_no_diagnostics() {
+        assert_eq!(validate_schema(&valid_schema()), vec![]);
+    }
+
+    #[test]
+    fn test_is_rust_identifier() {
+        assert!(is_rust_identifier("amount_in"));
+        assert!(is_rust_identifier("_bump"));
+        assert!(is_rust_identifier("Counter2"));
+        assert!(!is_rust_identifier(""));
+        assert!(!is_rust_identifier("_"));
+        assert!(!is_rust_identifier("2fast"));
+        assert!(!is_rust_identifier("my-field"));
+        assert!(!is_rust_identifier("type"));
+        assert!(!is_rust_identifier("Self"));
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let mut schema = valid_schema();
+        schema.accounts.push(Account {
+            name: "counter".to_string(),
+            fields: vec![field("count", FieldType::U8), field("count", FieldType::U8)],
+            discriminator: None,
+            seeds: vec![],
+        });
+        schema.instructions.push(Instruction {
+            name: "increment".to_string(),
+            ..Default::default()
+        });
+
+        let diagnostics = validate_schema(&schema);
+        assert_eq!(
+            paths(&diagnostics),
+            vec![
+                "$.accounts[1].name",
+                "$.accounts[1].fields[1].name",
+                "$.instructions[5].name",
+            ]
+        );
+        assert!(diagnostics.iter().all(SchemaDiagnostic::is_error));
+        assert_eq!(
+            diagnostics[0].message,
+            "Account `counter` duplicates $.accounts[0].name"
+        );
+    }
+
+    #[test]
+    fn test_account_and_type_names_clash() {
+        let mut schema = valid_schema();
+        schema.types.push(TypeDef {
+            name: "counter".to_string(),
+            kind: TypeDefKind::Struct { fields: vec![] },
+        });
+
+        let diagnostics = validate_schema(&schema);
+        assert_eq!(paths(&diagnostics), vec!["$.types[1].name"]);
+        assert_eq!(
+            diagnostics[0].message,
+            "Type `counter` duplicates $.accounts[0].name"
+        );
+    }
+
+    #[test]
+    fn test_unknown_references() {
+        let mut schema = valid_schema();
+        schema.instructions[0].accounts = vec![
+            InstructionAccount::writable("counter"),
+            InstructionAccount::writable("vault"),
+            InstructionAccount::writable("counter"),
+        ];
+        schema.accounts[0].fields.push(field(
+            "config",
+            FieldType::Vec(Box::new(FieldType::Defined("Config".to_string()))),
+        ));
+
+        let diagnostics = validate_schema(&schema);
+        assert_eq!(
+            paths(&diagnostics),
+            vec![
+                "$.accounts[0].fields[5].field_type",
+                "$.instructions[0].accounts[2]",
+                "$.instructions[0].accounts[1]",
+            ]
+        );
+        assert_eq!(diagnostics[2].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[2].message,
+            "Account `vault` is not defined in the schema's accounts"
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "Type `Config` is not defined in the schema's types"
+        );
+    }
+
+    #[test]
+    fn test_empty_and_invalid_identifiers() {
+        let mut schema = valid_schema();
+        schema.name = " ".to_string();
+        schema.accounts[0].fields[0].name = "type".to_string();
+        schema.accounts[0].fields[1].name = "count-total".to_string();
+        schema.instructions[0].args[0].name = "2x".to_string();
+        schema.instructions[0]
+            .accounts
+            .push(InstructionAccount::default());
+
+        let diagnostics = validate_schema(&schema);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.severity, d.path.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Severity::Error, "$.name"),
+                (Severity::Error, "$.accounts[0].fields[0].name"),
+                (Severity::Error, "$.instructions[0].accounts[1]"),
+                (Severity::Error, "$.instructions[0].args[0].name"),
+                (Severity::Warning, "$.accounts[0].fields[1].name"),
+            ]
+        );
+        assert_eq!(
+            diagnostics[4].message,
+            "Field name `count-total` contains characters that generated code drops; it becomes `count_total`"
+        );
+    }
+
+    #[test]
+    fn test_display_names_are_accepted() {
+        let mut schema = valid_schema();
+        schema.instructions[0].name = "Add Liquidity".to_string();
+        schema.accounts[0].name = "user account".to_string();
+        for instruction in &mut schema.instructions {
+            for account in &mut instruction.accounts {
+                account.name = "User Account".to_string();
+            }
+        }
+        assert_eq!(validate_schema(&schema), vec![]);
+    }
+
+    #[test]
+    fn test_types_and_errors() {
+        let mut schema = valid_schema();
+        schema.types = vec![
+            TypeDef {
+                name: "Mode".to_string(),
+                kind: TypeDefKind::Enum {
+                    variants: vec![
+                        EnumVariant {
+                            name: "Open".to_string(),
+                            fields: vec![],
+                        },
+                        EnumVariant {
+                            name: "open".to_string(),
+                            fields: vec![],
+                        },
+                    ],
+                },
+            },
+            TypeDef {
+                name: "Empty".to_string(),
+                kind: TypeDefKind::Enum { variants: vec![] },
+            },
+        ];
+        schema.errors = vec![
+            ErrorCode {
+                code: 6000,
+                name: "Overflow".to_string(),
+                msg: None,
+            },
+            ErrorCode {
+                code: 6000,
+                name: "Underflow".to_string(),
+                msg: None,
+            },
+        ];
+
+        assert_eq!(
+            paths(&validate_schema(&schema)),
+            vec![
+                "$.types[0].kind.Enum.variants[1].name",
+                "$.errors[1].code",
+                "$.types[1].kind.Enum.variants",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_seeds() {
+        let mut schema = valid_schema();
+        schema.accounts[0].seeds = vec![
+            Seed::Const("counter".to_string()),
+            Seed::Arg {
+                name: "authority".to_string(),
+                arg_type: FieldType::Pubkey,
+            },
+            Seed::Arg {
+                name: "index".to_string(),
+                arg_type: FieldType::U16,
+            },
+        ];
+        assert_eq!(validate_schema(&schema), vec![]);
+
+        schema.accounts[0].seeds.extend([
+            Seed::Const("x".repeat(33)),
+            Seed::Const(String::new()),
+            Seed::Arg {
+                name: "Authority".to_string(),
+                arg_type: FieldType::Bool,
+            },
+        ]);
+        let diagnostics = validate_schema(&schema);
+        assert_eq!(
+            paths(&diagnostics),
+            vec![
+                "$.accounts[0].seeds[3].Const",
+                "$.accounts[0].seeds[5].Arg.name",
+                "$.accounts[0].seeds[5].Arg.arg_type",
+                "$.accounts[0].seeds[4].Const",
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "Seed `xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx` is 33 bytes, the limit is 32"
+        );
+        assert_eq!(
+            diagnostics[2].message,
+            "Seed type `bool` can't be used as a seed, use Pubkey, String or an integer"
+        );
+
+        schema.accounts[0].seeds = vec![Seed::Const("a".to_string()); MAX_SEEDS];
+        assert_eq!(
+            paths(&validate_schema(&schema)),
+            vec!["$.accounts[0].seeds"]
+        );
+    }
+
+    #[test]
+    fn test_is_under() {
+        let diagnostic = SchemaDiagnostic {
+            severity: Severity::Error,
+            path: "$.accounts[10].fields[0].name".to_string(),
+            message: String::new(),
+        };
+        assert!(diagnostic.is_under("$.accounts[10]"));
+        assert!(diagnostic.is_under("$.accounts[10].fields[0].name"));
+        assert!(!diagnostic.is_under("$.accounts[1]"));
+        assert!(!diagnostic.is_under("$.instructions"));
+    }
+}
@@ -3,9 +3,8 @@
 //! This module provides validation functions for Solana addresses, amounts,
 //! and other data types used throughout the application.
 
-use crate::services::solana_rpc::validate_address;
 use crate::utils::crypto::validate_address as crypto_validate_address;
-use crate::utils::format::{parse_sol_amount, sol_string_to_lamports};
+use crate::utils::format::parse_sol_amount;
 
 /// Validation result with error message
 #[derive(Debug, Clone, PartialEq)]
@@ -226,7 +225,7 @@ pub fn validate_seed_phrase(seed_phrase: &str) -> ValidationResult {
         return ValidationResult::Invalid("Seed phrase cannot be empty".to_string());
     }
 
-    let words: Vec<&str> = seed_phrase.trim().split_whitespace().collect();
+    let words: Vec<&str> = seed_phrase.split_whitespace().collect();
 
     if words.len() != 12 && words.len() != 24 {
         return ValidationResult::Invalid("Seed phrase must be 12 or 24 words".to_string());
@@ -241,7 +240,11 @@ pub fn validate_seed_phrase(seed_phrase: &str) -> ValidationResult {
         }
     }
 
-    ValidationResult::Valid
+    // Words must come from the BIP39 wordlist and carry a valid checksum
+    match crate::utils::mnemonic::validate_mnemonic(seed_phrase) {
+        Ok(()) => ValidationResult::Valid,
+        Err(e) => ValidationResult::Invalid(e.to_string()),
+    }
 }
 
 /// Validate file name
@@ -286,7 +289,7 @@ pub fn validate_hex_string(hex_str: &str) -> ValidationResult {
         return ValidationResult::Invalid("Hex string cannot be empty".to_string());
     }
 
-    if hex_str.len() % 2 != 0 {
+    if !hex_str.len().is_multiple_of(2) {
         return ValidationResult::Invalid("Hex string must have even length".to_string());
     }
 
@@ -406,6 +409,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_validate_seed_phrase() {
+        assert_eq!(
+            validate_seed_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"),
+            ValidationResult::Valid
+        );
+        assert!(matches!(
+            validate_seed_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"),
+            ValidationResult::Invalid(_)
+        ));
+        assert!(matches!(
+            validate_seed_phrase("one two three"),
+            ValidationResult::Invalid(_)
+        ));
+    }
+
     #[test]
     fn test_validate_network_url() {
         assert_eq!(
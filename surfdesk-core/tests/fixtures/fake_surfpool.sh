@@ -0,0 +1,9 @@
+#!/bin/sh
+# Stands in for the surfpool binary in tests. Prints its arguments and a
+# warning on stderr, then echoes stdin until it closes; tests serve the RPC
+# port themselves.
+echo "fake surfpool $*"
+echo "WARN upstream RPC is slow" >&2
+while read -r line; do
+  echo "received $line"
+done
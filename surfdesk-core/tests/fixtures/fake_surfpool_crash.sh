@@ -0,0 +1,4 @@
+#!/bin/sh
+# Stands in for a surfpool binary that fails right after starting
+echo "error: address already in use" >&2
+exit 3
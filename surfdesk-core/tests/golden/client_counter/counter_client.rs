@@ -0,0 +1,191 @@
+//! Client for the `counter` program, generated by Surfdesk from its program schema
+//!
+//! Needs `borsh` 1.5 with the `derive` feature and `solana-program` 2.2.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum Mode {
+    Open,
+    Locked {
+        until_slot: i64,
+        unlockers: [Pubkey; 2],
+    },
+}
+
+/// `Counter` account data, stored after its discriminator
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+    /// Max length: 32
+    pub label: String,
+    pub history: Vec<Option<i32>>,
+    pub mode: Mode,
+}
+
+impl Counter {
+    pub const DISCRIMINATOR: [u8; 8] = [255, 176, 4, 245, 188, 253, 124, 25];
+
+    /// Decode account data, checking its discriminator
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut rest = data
+            .strip_prefix(&Self::DISCRIMINATOR[..])
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Self::deserialize(&mut rest).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Find the account's program derived address and bump
+    pub fn find_address(authority: &Pubkey, index: u16, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"counter", authority.as_ref(), &index.to_le_bytes()],
+            program_id,
+        )
+    }
+}
+
+/// Accounts of `Initialize`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitializeAccounts {
+    /// Writable
+    pub counter: Pubkey,
+    /// Signer
+    pub authority: Pubkey,
+    /// Writable, signer
+    pub payer: Pubkey,
+    pub system_program: Pubkey,
+}
+
+/// Arguments of `Initialize`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct InitializeArgs {
+    pub label: String,
+}
+
+pub const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+/// Build the `Initialize` instruction
+pub fn initialize(
+    program_id: &Pubkey,
+    accounts: &InitializeAccounts,
+    args: InitializeArgs,
+) -> Instruction {
+    let mut data = INITIALIZE_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)
+        .expect("writing to a Vec cannot fail");
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(accounts.counter, false),
+            AccountMeta::new_readonly(accounts.authority, true),
+            AccountMeta::new(accounts.payer, true),
+            AccountMeta::new_readonly(accounts.system_program, false),
+        ],
+        data,
+    }
+}
+
+/// Accounts of `Increment`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IncrementAccounts {
+    /// Writable
+    pub counter: Pubkey,
+    /// Signer
+    pub authority: Pubkey,
+}
+
+/// Arguments of `Increment`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct IncrementArgs {
+    pub by: u64,
+}
+
+pub const INCREMENT_DISCRIMINATOR: [u8; 8] = [11, 18, 104, 9, 104, 174, 59, 33];
+
+/// Build the `Increment` instruction
+pub fn increment(
+    program_id: &Pubkey,
+    accounts: &IncrementAccounts,
+    args: IncrementArgs,
+) -> Instruction {
+    let mut data = INCREMENT_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)
+        .expect("writing to a Vec cannot fail");
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(accounts.counter, false),
+            AccountMeta::new_readonly(accounts.authority, true),
+        ],
+        data,
+    }
+}
+
+/// Accounts of `SetMode`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetModeAccounts {
+    /// Writable
+    pub counter: Pubkey,
+    /// Signer
+    pub authority: Pubkey,
+}
+
+/// Arguments of `SetMode`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct SetModeArgs {
+    pub mode: Mode,
+}
+
+pub const SET_MODE_DISCRIMINATOR: [u8; 8] = [159, 47, 147, 247, 85, 53, 84, 230];
+
+/// Build the `SetMode` instruction
+pub fn set_mode(program_id: &Pubkey, accounts: &SetModeAccounts, args: SetModeArgs) -> Instruction {
+    let mut data = SET_MODE_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)
+        .expect("writing to a Vec cannot fail");
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(accounts.counter, false),
+            AccountMeta::new_readonly(accounts.authority, true),
+        ],
+        data,
+    }
+}
+
+/// Accounts of `Reset`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResetAccounts {
+    /// Writable
+    pub counter: Pubkey,
+    /// Signer
+    pub authority: Pubkey,
+}
+
+pub const RESET_DISCRIMINATOR: [u8; 8] = [23, 81, 251, 84, 138, 183, 240, 214];
+
+/// Build the `Reset` instruction
+pub fn reset(program_id: &Pubkey, accounts: &ResetAccounts) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(accounts.counter, false),
+            AccountMeta::new_readonly(accounts.authority, true),
+        ],
+        data: RESET_DISCRIMINATOR.to_vec(),
+    }
+}
+
+pub const PING_DISCRIMINATOR: [u8; 8] = [173, 0, 94, 236, 73, 133, 225, 153];
+
+/// Build the `Ping` instruction
+pub fn ping(program_id: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![],
+        data: PING_DISCRIMINATOR.to_vec(),
+    }
+}
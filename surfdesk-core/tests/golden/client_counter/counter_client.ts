@@ -0,0 +1,446 @@
+// Client for the `counter` program, generated by Surfdesk from its program schema
+//
+// Needs `@solana/web3.js` 1.x and an ES2020 or later target.
+
+import { Buffer } from "buffer";
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+
+/** Borsh encoder for instruction data and seeds */
+export class BorshWriter {
+  private readonly bytes: number[] = [];
+
+  raw(bytes: ArrayLike<number>): void {
+    for (let i = 0; i < bytes.length; i++) {
+      this.bytes.push(bytes[i]);
+    }
+  }
+
+  u8(value: number): void {
+    this.int(value, 1, false);
+  }
+
+  u16(value: number): void {
+    this.int(value, 2, false);
+  }
+
+  u32(value: number): void {
+    this.int(value, 4, false);
+  }
+
+  u64(value: bigint): void {
+    this.int(value, 8, false);
+  }
+
+  u128(value: bigint): void {
+    this.int(value, 16, false);
+  }
+
+  i8(value: number): void {
+    this.int(value, 1, true);
+  }
+
+  i16(value: number): void {
+    this.int(value, 2, true);
+  }
+
+  i32(value: number): void {
+    this.int(value, 4, true);
+  }
+
+  i64(value: bigint): void {
+    this.int(value, 8, true);
+  }
+
+  i128(value: bigint): void {
+    this.int(value, 16, true);
+  }
+
+  bool(value: boolean): void {
+    this.u8(value ? 1 : 0);
+  }
+
+  string(value: string): void {
+    const bytes = new TextEncoder().encode(value);
+    this.u32(bytes.length);
+    this.raw(bytes);
+  }
+
+  pubkey(value: PublicKey): void {
+    this.raw(value.toBytes());
+  }
+
+  option<T>(value: T | null, write: (item: T) => void): void {
+    if (value === null) {
+      this.u8(0);
+    } else {
+      this.u8(1);
+      write(value);
+    }
+  }
+
+  vec<T>(items: T[], write: (item: T) => void): void {
+    this.u32(items.length);
+    this.array(items, items.length, write);
+  }
+
+  array<T>(items: T[], length: number, write: (item: T) => void): void {
+    if (items.length !== length) {
+      throw new RangeError(`Expected ${length} items, got ${items.length}`);
+    }
+    for (const item of items) {
+      write(item);
+    }
+  }
+
+  toBuffer(): Buffer {
+    return Buffer.from(this.bytes);
+  }
+
+  private int(value: number | bigint, size: number, signed: boolean): void {
+    const bits = BigInt(size * 8);
+    let n = BigInt(value);
+    const min = signed ? -(1n << (bits - 1n)) : 0n;
+    const max = (signed ? 1n << (bits - 1n) : 1n << bits) - 1n;
+    if (n < min || n > max) {
+      throw new RangeError(`${value} does not fit in ${signed ? "i" : "u"}${bits}`);
+    }
+    n = BigInt.asUintN(size * 8, n);
+    for (let i = 0; i < size; i++) {
+      this.bytes.push(Number(n & 0xffn));
+      n >>= 8n;
+    }
+  }
+}
+
+/** Borsh decoder for account data */
+export class BorshReader {
+  private offset = 0;
+
+  constructor(private readonly data: Uint8Array) {}
+
+  discriminator(expected: readonly number[]): void {
+    const actual = this.take(expected.length);
+    if (!expected.every((byte, i) => actual[i] === byte)) {
+      throw new Error("Account discriminator mismatch");
+    }
+  }
+
+  u8(): number {
+    return Number(this.int(1, false));
+  }
+
+  u16(): number {
+    return Number(this.int(2, false));
+  }
+
+  u32(): number {
+    return Number(this.int(4, false));
+  }
+
+  u64(): bigint {
+    return this.int(8, false);
+  }
+
+  u128(): bigint {
+    return this.int(16, false);
+  }
+
+  i8(): number {
+    return Number(this.int(1, true));
+  }
+
+  i16(): number {
+    return Number(this.int(2, true));
+  }
+
+  i32(): number {
+    return Number(this.int(4, true));
+  }
+
+  i64(): bigint {
+    return this.int(8, true);
+  }
+
+  i128(): bigint {
+    return this.int(16, true);
+  }
+
+  bool(): boolean {
+    const value = this.u8();
+    if (value > 1) {
+      throw new Error(`Invalid bool ${value}`);
+    }
+    return value === 1;
+  }
+
+  string(): string {
+    const bytes = this.take(this.u32());
+    return new TextDecoder("utf-8", { fatal: true }).decode(bytes);
+  }
+
+  pubkey(): PublicKey {
+    return new PublicKey(this.take(32));
+  }
+
+  option<T>(read: () => T): T | null {
+    const tag = this.u8();
+    if (tag > 1) {
+      throw new Error(`Invalid option tag ${tag}`);
+    }
+    return tag === 1 ? read() : null;
+  }
+
+  vec<T>(read: () => T): T[] {
+    return this.array(this.u32(), read);
+  }
+
+  array<T>(length: number, read: () => T): T[] {
+    const items: T[] = [];
+    for (let i = 0; i < length; i++) {
+      items.push(read());
+    }
+    return items;
+  }
+
+  private take(length: number): Uint8Array {
+    if (this.offset + length > this.data.length) {
+      throw new Error("Unexpected end of data");
+    }
+    const bytes = this.data.subarray(this.offset, this.offset + length);
+    this.offset += length;
+    return bytes;
+  }
+
+  private int(size: number, signed: boolean): bigint {
+    const bytes = this.take(size);
+    let n = 0n;
+    for (let i = size - 1; i >= 0; i--) {
+      n = (n << 8n) | BigInt(bytes[i]);
+    }
+    return signed ? BigInt.asIntN(size * 8, n) : n;
+  }
+}
+
+function seedBytes(write: (w: BorshWriter) => void): Buffer {
+  const w = new BorshWriter();
+  write(w);
+  return w.toBuffer();
+}
+
+export type Mode =
+  | { __kind: "Open" }
+  | { __kind: "Locked"; untilSlot: bigint; unlockers: PublicKey[] };
+
+export function encodeMode(w: BorshWriter, value: Mode): void {
+  switch (value.__kind) {
+    case "Open":
+      w.u8(0);
+      break;
+    case "Locked":
+      w.u8(1);
+      w.i64(value.untilSlot);
+      w.array(value.unlockers, 2, (item) => w.pubkey(item));
+      break;
+  }
+}
+
+export function decodeMode(r: BorshReader): Mode {
+  const variant = r.u8();
+  switch (variant) {
+    case 0:
+      return { __kind: "Open" };
+    case 1:
+      return {
+        __kind: "Locked",
+        untilSlot: r.i64(),
+        unlockers: r.array(2, () => r.pubkey()),
+      };
+    default:
+      throw new Error(`Invalid Mode variant ${variant}`);
+  }
+}
+
+/** `Counter` account data, stored after its discriminator */
+export interface Counter {
+  authority: PublicKey;
+  count: bigint;
+  label: string;
+  history: Array<number | null>;
+  mode: Mode;
+}
+
+export const COUNTER_DISCRIMINATOR: readonly number[] = [255, 176, 4, 245, 188, 253, 124, 25];
+
+/** Decode `Counter` account data, checking its discriminator */
+export function decodeCounter(data: Uint8Array): Counter {
+  const r = new BorshReader(data);
+  r.discriminator(COUNTER_DISCRIMINATOR);
+  return {
+    authority: r.pubkey(),
+    count: r.u64(),
+    label: r.string(),
+    history: r.vec(() => r.option(() => r.i32())),
+    mode: decodeMode(r),
+  };
+}
+
+/** Seeds of the `Counter` address */
+export interface CounterSeeds {
+  authority: PublicKey;
+  index: number;
+}
+
+/** Find the `Counter` program derived address and bump */
+export function findCounterAddress(
+  seeds: CounterSeeds,
+  programId: PublicKey,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("counter"),
+      seeds.authority.toBuffer(),
+      seedBytes((w) => w.u16(seeds.index)),
+    ],
+    programId,
+  );
+}
+
+/** Accounts of `Initialize` */
+export interface InitializeAccounts {
+  counter: PublicKey;
+  authority: PublicKey;
+  payer: PublicKey;
+  systemProgram: PublicKey;
+}
+
+/** Arguments of `Initialize` */
+export interface InitializeArgs {
+  label: string;
+}
+
+export const INITIALIZE_DISCRIMINATOR: readonly number[] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+/** Build the `Initialize` instruction */
+export function createInitializeInstruction(
+  programId: PublicKey,
+  accounts: InitializeAccounts,
+  args: InitializeArgs,
+): TransactionInstruction {
+  const w = new BorshWriter();
+  w.raw(INITIALIZE_DISCRIMINATOR);
+  w.string(args.label);
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: accounts.counter, isSigner: false, isWritable: true },
+      { pubkey: accounts.authority, isSigner: true, isWritable: false },
+      { pubkey: accounts.payer, isSigner: true, isWritable: true },
+      { pubkey: accounts.systemProgram, isSigner: false, isWritable: false },
+    ],
+    data: w.toBuffer(),
+  });
+}
+
+/** Accounts of `Increment` */
+export interface IncrementAccounts {
+  counter: PublicKey;
+  authority: PublicKey;
+}
+
+/** Arguments of `Increment` */
+export interface IncrementArgs {
+  by: bigint;
+}
+
+export const INCREMENT_DISCRIMINATOR: readonly number[] = [11, 18, 104, 9, 104, 174, 59, 33];
+
+/** Build the `Increment` instruction */
+export function createIncrementInstruction(
+  programId: PublicKey,
+  accounts: IncrementAccounts,
+  args: IncrementArgs,
+): TransactionInstruction {
+  const w = new BorshWriter();
+  w.raw(INCREMENT_DISCRIMINATOR);
+  w.u64(args.by);
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: accounts.counter, isSigner: false, isWritable: true },
+      { pubkey: accounts.authority, isSigner: true, isWritable: false },
+    ],
+    data: w.toBuffer(),
+  });
+}
+
+/** Accounts of `SetMode` */
+export interface SetModeAccounts {
+  counter: PublicKey;
+  authority: PublicKey;
+}
+
+/** Arguments of `SetMode` */
+export interface SetModeArgs {
+  mode: Mode;
+}
+
+export const SET_MODE_DISCRIMINATOR: readonly number[] = [159, 47, 147, 247, 85, 53, 84, 230];
+
+/** Build the `SetMode` instruction */
+export function createSetModeInstruction(
+  programId: PublicKey,
+  accounts: SetModeAccounts,
+  args: SetModeArgs,
+): TransactionInstruction {
+  const w = new BorshWriter();
+  w.raw(SET_MODE_DISCRIMINATOR);
+  encodeMode(w, args.mode);
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: accounts.counter, isSigner: false, isWritable: true },
+      { pubkey: accounts.authority, isSigner: true, isWritable: false },
+    ],
+    data: w.toBuffer(),
+  });
+}
+
+/** Accounts of `Reset` */
+export interface ResetAccounts {
+  counter: PublicKey;
+  authority: PublicKey;
+}
+
+export const RESET_DISCRIMINATOR: readonly number[] = [23, 81, 251, 84, 138, 183, 240, 214];
+
+/** Build the `Reset` instruction */
+export function createResetInstruction(
+  programId: PublicKey,
+  accounts: ResetAccounts,
+): TransactionInstruction {
+  const w = new BorshWriter();
+  w.raw(RESET_DISCRIMINATOR);
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: accounts.counter, isSigner: false, isWritable: true },
+      { pubkey: accounts.authority, isSigner: true, isWritable: false },
+    ],
+    data: w.toBuffer(),
+  });
+}
+
+export const PING_DISCRIMINATOR: readonly number[] = [173, 0, 94, 236, 73, 133, 225, 153];
+
+/** Build the `Ping` instruction */
+export function createPingInstruction(
+  programId: PublicKey,
+): TransactionInstruction {
+  const w = new BorshWriter();
+  w.raw(PING_DISCRIMINATOR);
+  return new TransactionInstruction({
+    programId,
+    keys: [],
+    data: w.toBuffer(),
+  });
+}
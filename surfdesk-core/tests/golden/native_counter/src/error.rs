@@ -0,0 +1,23 @@
+//! Program errors
+
+use solana_program::program_error::ProgramError;
+
+/// Errors returned as `ProgramError::Custom`, numbered like Anchor's
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CounterError {
+    /// No instruction matches the discriminator
+    InstructionFallbackNotFound = 101,
+    /// An account that must be writable is read-only
+    ConstraintMut = 2000,
+    /// Account data does not start with the expected discriminator
+    AccountDiscriminatorMismatch = 3002,
+    /// Counter overflowed
+    Overflow = 6000,
+}
+
+impl From<CounterError> for ProgramError {
+    fn from(error: CounterError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
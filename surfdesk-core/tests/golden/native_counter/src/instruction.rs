@@ -0,0 +1,114 @@
+//! Instruction data
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_error::ProgramError;
+
+use crate::error::CounterError;
+use crate::state::Mode;
+
+/// Arguments of `Initialize`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct InitializeArgs {
+    pub label: String,
+}
+
+/// Arguments of `Increment`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct IncrementArgs {
+    pub by: u64,
+}
+
+/// Arguments of `SetMode`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct SetModeArgs {
+    pub mode: Mode,
+}
+
+/// Instructions, each prefixed by its discriminator
+#[derive(Clone, Debug, PartialEq)]
+pub enum CounterInstruction {
+    /// Accounts:
+    /// 0. `[writable]` counter
+    /// 1. `[signer]` authority
+    /// 2. `[writable, signer]` payer
+    /// 3. `[]` system_program
+    Initialize(InitializeArgs),
+    /// Accounts:
+    /// 0. `[writable]` counter
+    /// 1. `[signer]` authority
+    Increment(IncrementArgs),
+    /// Accounts:
+    /// 0. `[writable]` counter
+    /// 1. `[signer]` authority
+    SetMode(SetModeArgs),
+    /// Accounts:
+    /// 0. `[writable]` counter
+    /// 1. `[signer]` authority
+    Reset,
+    Ping,
+}
+
+impl CounterInstruction {
+    pub const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+    pub const INCREMENT: [u8; 8] = [11, 18, 104, 9, 104, 174, 59, 33];
+    pub const SET_MODE: [u8; 8] = [159, 47, 147, 247, 85, 53, 84, 230];
+    pub const RESET: [u8; 8] = [23, 81, 251, 84, 138, 183, 240, 214];
+    pub const PING: [u8; 8] = [173, 0, 94, 236, 73, 133, 225, 153];
+
+    /// Decode instruction data by its discriminator
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if let Some(args) = data.strip_prefix(&Self::INITIALIZE[..]) {
+            let args = InitializeArgs::try_from_slice(args)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            return Ok(Self::Initialize(args));
+        }
+        if let Some(args) = data.strip_prefix(&Self::INCREMENT[..]) {
+            let args = IncrementArgs::try_from_slice(args)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            return Ok(Self::Increment(args));
+        }
+        if let Some(args) = data.strip_prefix(&Self::SET_MODE[..]) {
+            let args = SetModeArgs::try_from_slice(args)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            return Ok(Self::SetMode(args));
+        }
+        if let Some(args) = data.strip_prefix(&Self::RESET[..]) {
+            if !args.is_empty() {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            return Ok(Self::Reset);
+        }
+        if let Some(args) = data.strip_prefix(&Self::PING[..]) {
+            if !args.is_empty() {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            return Ok(Self::Ping);
+        }
+        Err(CounterError::InstructionFallbackNotFound.into())
+    }
+
+    /// Encode the instruction with its discriminator
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        match self {
+            Self::Initialize(args) => {
+                data.extend_from_slice(&Self::INITIALIZE);
+                args.serialize(&mut data)
+                    .expect("writing to a Vec cannot fail");
+            }
+            Self::Increment(args) => {
+                data.extend_from_slice(&Self::INCREMENT);
+                args.serialize(&mut data)
+                    .expect("writing to a Vec cannot fail");
+            }
+            Self::SetMode(args) => {
+                data.extend_from_slice(&Self::SET_MODE);
+                args.serialize(&mut data)
+                    .expect("writing to a Vec cannot fail");
+            }
+            Self::Reset => data.extend_from_slice(&Self::RESET),
+            Self::Ping => data.extend_from_slice(&Self::PING),
+        }
+        data
+    }
+}
@@ -0,0 +1,13 @@
+//! `counter` program, generated by Surfdesk from its program schema
+
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint {
+    use crate::processor::process_instruction;
+
+    solana_program::entrypoint!(process_instruction);
+}
@@ -0,0 +1,125 @@
+//! Instruction processing
+
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::msg;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use crate::error::CounterError;
+use crate::instruction::{CounterInstruction, IncrementArgs, InitializeArgs, SetModeArgs};
+use crate::state::Counter;
+
+/// Program entrypoint: dispatch on the instruction discriminator
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match CounterInstruction::unpack(instruction_data)? {
+        CounterInstruction::Initialize(args) => process_initialize(program_id, accounts, args),
+        CounterInstruction::Increment(args) => process_increment(program_id, accounts, args),
+        CounterInstruction::SetMode(args) => process_set_mode(program_id, accounts, args),
+        CounterInstruction::Reset => process_reset(program_id, accounts),
+        CounterInstruction::Ping => process_ping(program_id, accounts),
+    }
+}
+
+fn process_initialize(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: InitializeArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let counter = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+
+    require_writable(counter)?;
+    require_signer(authority)?;
+    require_writable(payer)?;
+    require_signer(payer)?;
+
+    msg!("Instruction: Initialize {:?}", args);
+    // `counter` is new: allocate `Counter::SPACE` bytes owned by the program, then
+    // `Counter::save` its initial state
+    Ok(())
+}
+
+fn process_increment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: IncrementArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let counter = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+
+    require_writable(counter)?;
+    require_signer(authority)?;
+
+    let counter_data = Counter::load(counter, program_id)?;
+
+    msg!("Instruction: Increment {:?}", args);
+
+    counter_data.save(counter)?;
+    Ok(())
+}
+
+fn process_set_mode(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetModeArgs,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let counter = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+
+    require_writable(counter)?;
+    require_signer(authority)?;
+
+    let counter_data = Counter::load(counter, program_id)?;
+
+    msg!("Instruction: SetMode {:?}", args);
+
+    counter_data.save(counter)?;
+    Ok(())
+}
+
+fn process_reset(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let counter = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+
+    require_writable(counter)?;
+    require_signer(authority)?;
+
+    let counter_data = Counter::load(counter, program_id)?;
+
+    msg!("Instruction: Reset");
+
+    counter_data.save(counter)?;
+    Ok(())
+}
+
+fn process_ping(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Instruction: Ping");
+    Ok(())
+}
+
+fn require_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        msg!("{} must sign", account.key);
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+fn require_writable(account: &AccountInfo) -> ProgramResult {
+    if !account.is_writable {
+        msg!("{} must be writable", account.key);
+        return Err(CounterError::ConstraintMut.into());
+    }
+    Ok(())
+}
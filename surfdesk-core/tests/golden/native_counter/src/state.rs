@@ -0,0 +1,60 @@
+//! Account and type definitions
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use crate::error::CounterError;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum Mode {
+    Open,
+    Locked {
+        until_slot: i64,
+        unlockers: [Pubkey; 2],
+    },
+}
+
+/// `Counter` account data, stored after its discriminator
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+    /// Max length: 32
+    pub label: String,
+    pub history: Vec<Option<i32>>,
+    pub mode: Mode,
+}
+
+impl Counter {
+    pub const DISCRIMINATOR: [u8; 8] = [255, 176, 4, 245, 188, 253, 124, 25];
+    /// Bytes to allocate, including the discriminator; strings and vectors
+    /// without a max length are counted as empty
+    pub const SPACE: usize = 161;
+
+    /// Deserialize the account, checking its owner and discriminator
+    pub fn load(account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let data = account.try_borrow_data()?;
+        let mut rest = data
+            .strip_prefix(&Self::DISCRIMINATOR[..])
+            .ok_or(CounterError::AccountDiscriminatorMismatch)?;
+        Self::deserialize(&mut rest).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Serialize the account after its discriminator
+    pub fn save(&self, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        if data.len() < Self::DISCRIMINATOR.len() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        let (discriminator, mut rest) = data.split_at_mut(Self::DISCRIMINATOR.len());
+        discriminator.copy_from_slice(&Self::DISCRIMINATOR);
+        self.serialize(&mut rest)
+            .map_err(|_| ProgramError::AccountDataTooSmall)
+    }
+}